@@ -0,0 +1,131 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::super::{Air, ConstraintDivisor};
+use math::{FieldElement, StarkField};
+
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+
+// TYPE ALIASES
+// ================================================================================================
+
+type OodEvaluator<'a, E> = Box<dyn Fn(&[E], E) -> E + Send + Sync + 'a>;
+type DomainEvaluator<'a, B, E> = Box<dyn Fn(&[B], B) -> E + Send + Sync + 'a>;
+
+// CUSTOM BOUNDARY CONSTRAINT
+// ================================================================================================
+/// A boundary constraint with a numerator computed directly by the AIR.
+///
+/// Similarly to boundary constraints derived from [assertions](super::super::Assertion), a custom
+/// boundary constraint is described by a rational function $\frac{n(x)}{z(x)}$, where $z(x)$ is
+/// the constraint divisor polynomial. However, instead of being defined as $f(x) - b(x)$, the
+/// numerator $n(x)$ is computed by closures supplied by the AIR:
+///
+/// * One closure evaluates the numerator against the current row of an out-of-domain evaluation
+///   frame at the out-of-domain point (used by the verifier).
+/// * Another closure evaluates the numerator against a row of the extended execution trace at
+///   a point of the constraint evaluation domain (used by the prover).
+///
+/// The closures may borrow from the AIR which builds the constraint, and thus, the constraint
+/// cannot outlive the AIR. Both closures must compute the same polynomial. The degree of the numerator is expected to be
+/// at most the degree of trace polynomials, and thus, the numerator is usually a linear
+/// combination of values in the row.
+///
+/// Custom boundary constraints are returned from
+/// [Air::custom_boundary_constraints()](crate::Air::custom_boundary_constraints) method.
+pub struct CustomBoundaryConstraint<'a, B: StarkField, E: FieldElement<BaseField = B> + 'a> {
+    divisor: ConstraintDivisor<B>,
+    degree_adjustment: u32,
+    cc: (E, E),
+    ood_evaluator: OodEvaluator<'a, E>,
+    domain_evaluator: DomainEvaluator<'a, B, E>,
+}
+
+impl<'a, B: StarkField, E: FieldElement<BaseField = B> + 'a> CustomBoundaryConstraint<'a, B, E> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new custom boundary constraint for the specified AIR.
+    ///
+    /// * `divisor` is the divisor of the constraint.
+    /// * `cc` are composition coefficients of the constraint; these should be taken from the
+    ///   coefficients passed to
+    ///   [Air::custom_boundary_constraints()](crate::Air::custom_boundary_constraints).
+    /// * `ood_evaluator` evaluates the numerator at an out-of-domain point `z` given the current
+    ///   row of the out-of-domain frame.
+    /// * `domain_evaluator` evaluates the numerator at a point `x` of the constraint evaluation
+    ///   domain given the row of the extended execution trace at `x`.
+    pub fn new<A, F, G>(
+        air: &A,
+        divisor: ConstraintDivisor<B>,
+        cc: (E, E),
+        ood_evaluator: F,
+        domain_evaluator: G,
+    ) -> Self
+    where
+        A: Air<BaseField = B>,
+        F: Fn(&[E], E) -> E + Send + Sync + 'a,
+        G: Fn(&[B], B) -> E + Send + Sync + 'a,
+    {
+        // the numerator has the same degree as trace polynomials; so, same as for assertion-based
+        // constraints, the degree adjustment is: deg(composition) + deg(divisor) - deg(trace)
+        let target_degree = air.composition_degree() + divisor.degree();
+        let degree_adjustment = (target_degree - air.trace_poly_degree()) as u32;
+
+        CustomBoundaryConstraint {
+            divisor,
+            degree_adjustment,
+            cc,
+            ood_evaluator: Box::new(ood_evaluator),
+            domain_evaluator: Box::new(domain_evaluator),
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the divisor of this constraint.
+    pub fn divisor(&self) -> &ConstraintDivisor<B> {
+        &self.divisor
+    }
+
+    /// Returns a degree adjustment factor for this constraint.
+    pub fn degree_adjustment(&self) -> u32 {
+        self.degree_adjustment
+    }
+
+    /// Returns composition coefficients for this constraint.
+    pub fn cc(&self) -> &(E, E) {
+        &self.cc
+    }
+
+    // CONSTRAINT EVALUATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Evaluates this constraint at the specified out-of-domain point `x`, and divides the
+    /// result by the divisor of this constraint.
+    ///
+    /// `xp` is a degree adjustment multiplier which must be computed as `x^degree_adjustment`.
+    pub fn evaluate_at(&self, state: &[E], x: E, xp: E) -> E {
+        debug_assert_eq!(
+            x.exp(self.degree_adjustment.into()),
+            xp,
+            "inconsistent degree adjustment"
+        );
+        let numerator = (self.ood_evaluator)(state, x);
+        numerator * (self.cc.0 + self.cc.1 * xp) / self.divisor.evaluate_at(x)
+    }
+
+    /// Evaluates the numerator of this constraint at the specified point `x` of the constraint
+    /// evaluation domain, and multiplies it by the composition coefficients.
+    ///
+    /// Unlike [evaluate_at()](CustomBoundaryConstraint::evaluate_at), the result is not divided
+    /// by the divisor of this constraint. Since `x` is in the base field, the degree adjustment
+    /// multiplier `xp` is in the base field as well.
+    pub fn evaluate_numerator(&self, state: &[B], x: B, xp: B) -> E {
+        let numerator = (self.domain_evaluator)(state, x);
+        numerator * (self.cc.0 + self.cc.1.mul_base(xp))
+    }
+}
@@ -7,6 +7,9 @@ use super::{Assertion, ConstraintDivisor};
 use math::{fft, polynom, FieldElement, StarkField};
 use utils::collections::{BTreeMap, Vec};
 
+mod custom;
+pub use custom::CustomBoundaryConstraint;
+
 #[cfg(test)]
 mod tests;
 
@@ -25,12 +25,14 @@ use utils::collections::Vec;
 /// * $C_i(x)$ is an evaluation of the $i$th constraint at $x$.
 /// * $d_i$ is the degree adjustment factor needed to normalize all constraints to the same degree.
 ///
-/// The coefficients are separated into two lists: one for transition constraints and another one
-/// for boundary constraints. This separation is done for convenience only.
+/// The coefficients are separated into three lists: one for transition constraints, one for
+/// boundary constraints derived from assertions, and one for custom boundary constraints. This
+/// separation is done for convenience only.
 #[derive(Debug, Clone)]
 pub struct ConstraintCompositionCoefficients<E: FieldElement> {
     pub transition: Vec<(E, E)>,
     pub boundary: Vec<(E, E)>,
+    pub custom_boundary: Vec<(E, E)>,
 }
 
 // DEEP COMPOSITION COEFFICIENTS
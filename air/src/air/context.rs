@@ -3,7 +3,10 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::{air::TransitionConstraintDegree, ProofOptions, TraceInfo};
+use crate::{
+    air::{ConstraintSet, TransitionConstraintDegree},
+    ProofOptions, TraceInfo,
+};
 use math::{log2, StarkField};
 use utils::collections::Vec;
 
@@ -15,7 +18,9 @@ pub struct AirContext<B: StarkField> {
     pub(super) options: ProofOptions,
     pub(super) trace_info: TraceInfo,
     pub(super) transition_constraint_degrees: Vec<TransitionConstraintDegree>,
+    pub(super) transition_constraint_labels: Vec<&'static str>,
     pub(super) ce_blowup_factor: usize,
+    pub(super) num_custom_boundary_constraints: usize,
     pub(super) trace_domain_generator: B,
     pub(super) lde_domain_generator: B,
 }
@@ -31,7 +36,10 @@ impl<B: StarkField> AirContext<B> {
     /// in the order defined by this list.
     ///
     /// # Panics
-    /// Panics if `transition_constraint_degrees` is an empty vector.
+    /// Panics if:
+    /// * `transition_constraint_degrees` is an empty vector.
+    /// * The domain offset specified by `options` is not valid for the field `B` (see
+    ///   [ProofOptions::validate_domain_offset()]).
     pub fn new(
         trace_info: TraceInfo,
         transition_constraint_degrees: Vec<TransitionConstraintDegree>,
@@ -41,6 +49,9 @@ impl<B: StarkField> AirContext<B> {
             !transition_constraint_degrees.is_empty(),
             "at least one transition constraint degree must be specified"
         );
+        if let Err(message) = options.validate_domain_offset::<B>() {
+            panic!("{}", message);
+        }
 
         // determine minimum blowup factor needed to evaluate transition constraints by taking
         // the blowup factor of the highest degree constraint
@@ -65,9 +76,76 @@ impl<B: StarkField> AirContext<B> {
             options,
             trace_info,
             transition_constraint_degrees,
+            transition_constraint_labels: Vec::new(),
             ce_blowup_factor,
+            num_custom_boundary_constraints: 0,
             trace_domain_generator: B::get_root_of_unity(log2(trace_length)),
             lde_domain_generator: B::get_root_of_unity(log2(lde_domain_size)),
         }
     }
+
+    /// Returns a new instance of [AirContext] with transition constraint degrees and labels taken
+    /// from the specified constraint set.
+    ///
+    /// The set is expected to be created via [ConstraintSet::describe()] from the same definition
+    /// which is used to evaluate transition constraints.
+    ///
+    /// # Panics
+    /// Panics under the same conditions as [AirContext::new()].
+    pub fn from_constraints(
+        trace_info: TraceInfo,
+        constraints: &ConstraintSet<B>,
+        options: ProofOptions,
+    ) -> Self {
+        let mut context = Self::new(trace_info, constraints.degrees().to_vec(), options);
+        context.transition_constraint_labels = constraints.labels().to_vec();
+        context
+    }
+
+    /// Sets the number of columns into which the constraint composition polynomial is split,
+    /// and returns the updated context.
+    ///
+    /// By default, the number of columns is equal to the smallest constraint evaluation domain
+    /// blowup factor required by transition constraint degrees. Splitting the composition
+    /// polynomial into more columns increases the constraint evaluation blowup factor to
+    /// `num_columns`, but each column remains a polynomial of degree `trace_length - 1`.
+    ///
+    /// # Panics
+    /// Panics if `num_columns`:
+    /// * Is not a power of two.
+    /// * Is smaller than the blowup factor required by transition constraint degrees.
+    /// * Is greater than the blowup factor specified by proof options.
+    pub fn with_num_composition_columns(mut self, num_columns: usize) -> Self {
+        assert!(
+            num_columns.is_power_of_two(),
+            "number of composition columns must be a power of two, but was {}",
+            num_columns
+        );
+        assert!(
+            num_columns >= self.ce_blowup_factor,
+            "number of composition columns must be at least {}, but was {}",
+            self.ce_blowup_factor,
+            num_columns
+        );
+        assert!(
+            num_columns <= self.options.blowup_factor(),
+            "number of composition columns cannot be greater than blowup factor {}, but was {}",
+            self.options.blowup_factor(),
+            num_columns
+        );
+        self.ce_blowup_factor = num_columns;
+        self
+    }
+
+    /// Sets the number of custom boundary constraints defined for the computation, and returns
+    /// the updated context.
+    ///
+    /// Custom boundary constraints are returned from
+    /// [Air::custom_boundary_constraints()](crate::Air::custom_boundary_constraints) method, and
+    /// a pair of composition coefficients is drawn for each of them. By default, the number of
+    /// custom boundary constraints is zero.
+    pub fn with_custom_boundary_constraints(mut self, num_constraints: usize) -> Self {
+        self.num_custom_boundary_constraints = num_constraints;
+        self
+    }
 }
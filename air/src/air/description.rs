@@ -0,0 +1,125 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Air, TransitionConstraintDegree};
+use utils::{
+    collections::Vec,
+    string::{String, ToString},
+};
+
+// AIR DESCRIPTION
+// ================================================================================================
+/// A description of an instance of a computation described by an AIR.
+///
+/// An AIR description is created via [Air::describe()](crate::Air::describe) method, and lists
+/// the parameters which define the constraint system of the AIR: dimensions of the execution
+/// trace, the shape of the evaluation frame, degrees and labels of transition constraints, the
+/// transition constraint divisor, positions of assertions, and lengths of periodic columns.
+///
+/// The description does not include any values of the execution trace, nor any values of
+/// assertions or periodic columns; thus, it does not depend on the witness. This makes AIR
+/// descriptions suitable for pinning the shape of an AIR in tests, and for comparing the AIR
+/// against other implementations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AirDescription {
+    /// Number of registers in the execution trace.
+    pub trace_width: usize,
+    /// Number of steps in the execution trace.
+    pub trace_length: usize,
+    /// Offsets of the trace rows included in an evaluation frame relative to the current step;
+    /// evaluation frames always consist of the current and the next rows.
+    pub frame_offsets: Vec<usize>,
+    /// Descriptions of transition constraints in the order in which they are evaluated.
+    pub transition_constraints: Vec<TransitionConstraintDescription>,
+    /// The divisor of transition constraints, formatted as a rational function of `x`.
+    pub transition_divisor: String,
+    /// Descriptions of assertions in the order in which they are returned by the AIR.
+    pub assertions: Vec<AssertionDescription>,
+    /// Number of custom boundary constraints.
+    pub num_custom_boundary_constraints: usize,
+    /// Number of values in each of the periodic columns.
+    pub periodic_column_lengths: Vec<usize>,
+    /// Constraint evaluation domain blowup factor; the LDE blowup factor specified in proof
+    /// options must be at least as large as this factor.
+    pub ce_blowup_factor: usize,
+}
+
+impl AirDescription {
+    /// Returns a description of the specified AIR.
+    pub(super) fn new<A: Air + ?Sized>(air: &A) -> Self {
+        let transition_constraints = air
+            .transition_constraint_degrees()
+            .iter()
+            .enumerate()
+            .map(|(i, degree)| TransitionConstraintDescription {
+                label: air.transition_constraint_label(i),
+                degree: degree.clone(),
+                evaluation_degree: degree.get_evaluation_degree(air.trace_length()),
+            })
+            .collect();
+
+        let assertions = air
+            .get_assertions()
+            .iter()
+            .map(|assertion| AssertionDescription {
+                register: assertion.register(),
+                first_step: assertion.first_step(),
+                stride: assertion.stride(),
+                num_values: assertion.values().len(),
+            })
+            .collect();
+
+        let periodic_column_lengths = air
+            .get_periodic_column_values()
+            .iter()
+            .map(|column| column.len())
+            .collect();
+
+        AirDescription {
+            trace_width: air.trace_width(),
+            trace_length: air.trace_length(),
+            frame_offsets: vec![0, 1],
+            transition_constraints,
+            transition_divisor: air.transition_constraint_divisor().to_string(),
+            assertions,
+            num_custom_boundary_constraints: air.num_custom_boundary_constraints(),
+            periodic_column_lengths,
+            ce_blowup_factor: air.ce_blowup_factor(),
+        }
+    }
+}
+
+// TRANSITION CONSTRAINT DESCRIPTION
+// ================================================================================================
+/// A description of a single transition constraint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransitionConstraintDescription {
+    /// Label of the constraint, if the AIR was instantiated with constraint labels.
+    pub label: Option<&'static str>,
+    /// Declared degree of the constraint.
+    pub degree: TransitionConstraintDegree,
+    /// Degree of the constraint polynomial evaluated over the execution trace.
+    pub evaluation_degree: usize,
+}
+
+// ASSERTION DESCRIPTION
+// ================================================================================================
+/// A description of the position of an assertion.
+///
+/// The asserted values are not included. A single assertion applies to `first_step` only;
+/// periodic and sequence assertions apply to every `stride`-th step starting with `first_step`
+/// (see [Assertion](crate::Assertion) for details).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AssertionDescription {
+    /// Index of the register against which the assertion is made.
+    pub register: usize,
+    /// The first step to which the assertion applies.
+    pub first_step: usize,
+    /// Number of steps between the steps to which the assertion applies; for single assertions
+    /// this is zero.
+    pub stride: usize,
+    /// Number of asserted values; for single and periodic assertions this is one.
+    pub num_values: usize,
+}
@@ -20,12 +20,17 @@ use utils::collections::Vec;
 /// For example divisor $(x^a - 1) \cdot (x^b - 2) / (x - 3)$ can be represented as:
 /// numerator: `[(a, 1), (b, 2)]`, exclude: `[3]`.
 ///
+/// The denominator may also contain periodic exemptions, which are described in the same way as
+/// the numerator; each exemption $(x^c - d)$ excludes a whole coset of points from the divisor
+/// at once (see [from_transition_with_periodic_exemptions()](Self::from_transition_with_periodic_exemptions)).
+///
 /// A divisor cannot be instantiated directly, and instead must be created either for an
 /// [Assertion] or for a transition constraint.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ConstraintDivisor<B: StarkField> {
     pub(super) numerator: Vec<(usize, B)>,
     pub(super) exclude: Vec<B>,
+    pub(super) exemptions: Vec<(usize, B)>,
 }
 
 impl<B: StarkField> ConstraintDivisor<B> {
@@ -34,7 +39,11 @@ impl<B: StarkField> ConstraintDivisor<B> {
 
     /// Returns a new divisor instantiated from the provided parameters.
     fn new(numerator: Vec<(usize, B)>, exclude: Vec<B>) -> Self {
-        ConstraintDivisor { numerator, exclude }
+        ConstraintDivisor {
+            numerator,
+            exclude,
+            exemptions: Vec::new(),
+        }
     }
 
     /// Builds a divisor for transition constraints.
@@ -55,6 +64,188 @@ impl<B: StarkField> ConstraintDivisor<B> {
         Self::new(vec![(trace_length, B::ONE)], vec![x_at_last_step])
     }
 
+    /// Builds a divisor for transition constraints which do not need to hold on steps
+    /// `offset`, `offset + period`, `offset + 2 * period` etc.
+    ///
+    /// The divisor polynomial $z(x)$ is defined as:
+    ///
+    /// $$
+    /// z(x) = \frac{x^n - 1}{(x^k - g^{a \cdot k}) \cdot (x - g^{n-1})}
+    /// $$
+    ///
+    /// where, $n$ is the length of the execution trace, $g$ is the generator of the trace
+    /// domain, $k = n / period$ is the number of exempted steps, and $a$ is the `offset`. The
+    /// $(x - g^{n-1})$ term is omitted when the last step is already exempted (i.e., when
+    /// `offset` is `period - 1`).
+    ///
+    /// The above divisor specifies that transition constraints must hold on all steps of the
+    /// execution trace except for the exempted steps and the last step. This can be used to
+    /// describe constraints which are switched off on a periodic subset of steps without
+    /// introducing a selector column.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `period` is not a power of two, is smaller than 2, or is greater than `trace_length`.
+    /// * `offset` is greater than or equal to `period`.
+    pub fn from_transition_with_periodic_exemptions(
+        trace_length: usize,
+        period: usize,
+        offset: usize,
+    ) -> Self {
+        assert!(
+            period.is_power_of_two(),
+            "exemption period must be a power of two, but was {}",
+            period
+        );
+        assert!(
+            period >= 2,
+            "exemption period must be at least 2, but was {}",
+            period
+        );
+        assert!(
+            period <= trace_length,
+            "exemption period cannot exceed trace length of {}, but was {}",
+            trace_length,
+            period
+        );
+        assert!(
+            offset < period,
+            "exemption offset must be smaller than period {}, but was {}",
+            period,
+            offset
+        );
+
+        let num_exemptions = trace_length / period;
+        let exemption_offset =
+            get_trace_domain_value_at::<B>(trace_length, offset * num_exemptions);
+
+        // the last step needs to be excluded explicitly only if it is not one of the exempted
+        // steps
+        let exclude = if offset == period - 1 {
+            Vec::new()
+        } else {
+            vec![get_trace_domain_value_at::<B>(
+                trace_length,
+                trace_length - 1,
+            )]
+        };
+
+        ConstraintDivisor {
+            numerator: vec![(trace_length, B::ONE)],
+            exclude,
+            exemptions: vec![(num_exemptions, exemption_offset)],
+        }
+    }
+
+    /// Builds a divisor for transition constraints which need to hold only on steps `offset`,
+    /// `offset + stride`, `offset + 2 * stride` etc.
+    ///
+    /// The divisor polynomial $z(x)$ is defined as:
+    ///
+    /// $$
+    /// z(x) = x^k - g^{a \cdot k}
+    /// $$
+    ///
+    /// where, $g$ is the generator of the trace domain, $k = n / stride$ is the number of
+    /// enforced steps, $n$ is the length of the execution trace, and $a$ is the `offset`. When
+    /// `offset` is `stride - 1`, the last enforced step is the last step of the trace; a transition
+    /// at this step would wrap around to the first step, and thus, the divisor is additionally
+    /// divided by $(x - g^{n-1})$.
+    ///
+    /// The above divisor specifies that transition constraints must hold once every `stride`
+    /// steps. This can be used to describe constraints which relate consecutive blocks of a
+    /// trace (e.g., the last row of one hash cycle and the first row of the next) without
+    /// introducing a selector column.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `stride` is not a power of two, is smaller than 2, or is not smaller than
+    ///   `trace_length`.
+    /// * `offset` is greater than or equal to `stride`.
+    pub fn from_transition_with_stride(trace_length: usize, stride: usize, offset: usize) -> Self {
+        assert!(
+            stride.is_power_of_two(),
+            "transition stride must be a power of two, but was {}",
+            stride
+        );
+        assert!(
+            stride >= 2,
+            "transition stride must be at least 2, but was {}",
+            stride
+        );
+        assert!(
+            stride < trace_length,
+            "transition stride must be smaller than trace length of {}, but was {}",
+            trace_length,
+            stride
+        );
+        assert!(
+            offset < stride,
+            "transition offset must be smaller than stride {}, but was {}",
+            stride,
+            offset
+        );
+
+        let num_steps = trace_length / stride;
+        let numerator_offset = get_trace_domain_value_at::<B>(trace_length, offset * num_steps);
+
+        // the last step needs to be excluded only if it is one of the enforced steps
+        let exclude = if offset == stride - 1 {
+            vec![get_trace_domain_value_at::<B>(
+                trace_length,
+                trace_length - 1,
+            )]
+        } else {
+            Vec::new()
+        };
+
+        Self::new(vec![(num_steps, numerator_offset)], exclude)
+    }
+
+    /// Builds a divisor for transition constraints of an execution trace which was padded to a
+    /// power of two.
+    ///
+    /// The divisor polynomial $z(x)$ is defined as:
+    ///
+    /// $$
+    /// z(x) = \frac{x^n - 1}{(x - g^{m-1}) \cdot (x - g^{n-1})}
+    /// $$
+    ///
+    /// where, $n$ is the length of the padded execution trace, $m$ is the length of the trace
+    /// before padding, and $g$ is the generator of the trace domain.
+    ///
+    /// The above divisor specifies that transition constraints must hold on all steps of the
+    /// execution trace except for the last step of the unpadded trace (i.e., the transition from
+    /// the last real row into the first padding row), and the last step of the padded trace.
+    /// Thus, the padding rows must themselves satisfy the transition constraints. If the trace
+    /// was not padded (i.e., `unpadded_length` is equal to `trace_length`), this is the same as
+    /// [from_transition()](Self::from_transition).
+    ///
+    /// # Panics
+    /// Panics if `unpadded_length` is zero or is greater than `trace_length`.
+    pub fn from_transition_with_padding(trace_length: usize, unpadded_length: usize) -> Self {
+        assert!(
+            unpadded_length > 0,
+            "unpadded trace length must be greater than 0"
+        );
+        assert!(
+            unpadded_length <= trace_length,
+            "unpadded trace length cannot exceed trace length of {}, but was {}",
+            trace_length,
+            unpadded_length
+        );
+        if unpadded_length == trace_length {
+            return Self::from_transition(trace_length);
+        }
+
+        let x_at_last_real_step = get_trace_domain_value_at::<B>(trace_length, unpadded_length - 1);
+        let x_at_last_step = get_trace_domain_value_at::<B>(trace_length, trace_length - 1);
+        Self::new(
+            vec![(trace_length, B::ONE)],
+            vec![x_at_last_real_step, x_at_last_step],
+        )
+    }
+
     /// Builds a divisor for a boundary constraint described by the assertion.
     ///
     /// For boundary constraints, the divisor polynomial is defined as:
@@ -102,16 +293,50 @@ impl<B: StarkField> ConstraintDivisor<B> {
         &self.exclude
     }
 
+    /// Returns periodic exemptions of this constraint divisor; each exemption describes an
+    /// expression $(x^a - b)$ in the denominator of the divisor.
+    pub fn exemptions(&self) -> &[(usize, B)] {
+        &self.exemptions
+    }
+
     /// Returns the degree of the divisor polynomial
     pub fn degree(&self) -> usize {
         let numerator_degree = self
             .numerator
             .iter()
             .fold(0, |degree, term| degree + term.0);
-        let denominator_degree = self.exclude.len();
+        let denominator_degree = self
+            .exemptions
+            .iter()
+            .fold(self.exclude.len(), |degree, term| degree + term.0);
         numerator_degree - denominator_degree
     }
 
+    /// Returns true if the denominator of this divisor evaluates to zero at the provided `x`
+    /// coordinate; that is, if constraints with this divisor do not need to hold at `x`.
+    pub fn excludes(&self, x: B) -> bool {
+        self.exclude.contains(&x)
+            || self
+                .exemptions
+                .iter()
+                .any(|&(degree, constant)| x.exp((degree as u64).into()) == constant)
+    }
+
+    /// Returns true if constraints with this divisor must hold at the provided `x` coordinate;
+    /// that is, if the numerator of this divisor evaluates to zero at `x`, and the denominator
+    /// does not.
+    ///
+    /// For `x` in the trace domain, this is the complement of [excludes()](Self::excludes) for
+    /// all divisors except for the ones built via
+    /// [from_transition_with_stride()](Self::from_transition_with_stride), whose numerator
+    /// vanishes only on a subset of the trace domain.
+    pub fn enforces(&self, x: B) -> bool {
+        self.numerator
+            .iter()
+            .any(|&(degree, constant)| x.exp((degree as u64).into()) == constant)
+            && !self.excludes(x)
+    }
+
     // EVALUATOR
     // --------------------------------------------------------------------------------------------
     /// Evaluates the divisor polynomial at the provided `x` coordinate.
@@ -130,6 +355,11 @@ impl<B: StarkField> ConstraintDivisor<B> {
             let v = x - E::from(*exception);
             denominator *= v;
         }
+        for (degree, constant) in self.exemptions.iter() {
+            let v = x.exp((*degree as u32).into());
+            let v = v - E::from(*constant);
+            denominator *= v;
+        }
 
         numerator / denominator
     }
@@ -140,8 +370,11 @@ impl<B: StarkField> Display for ConstraintDivisor<B> {
         for (degree, offset) in self.numerator.iter() {
             write!(f, "(x^{} - {})", degree, offset)?;
         }
-        if !self.exclude.is_empty() {
+        if !self.exclude.is_empty() || !self.exemptions.is_empty() {
             write!(f, " / ")?;
+            for (degree, offset) in self.exemptions.iter() {
+                write!(f, "(x^{} - {})", degree, offset)?;
+            }
             for x in self.exclude.iter() {
                 write!(f, "(x - {})", x)?;
             }
@@ -234,6 +467,129 @@ mod tests {
         assert_eq!(expected, div.evaluate_at(BaseElement::new(4)));
     }
 
+    #[test]
+    fn periodic_exemptions_divisor() {
+        let n = 16_usize;
+        let g = BaseElement::get_root_of_unity(n.trailing_zeros());
+
+        // ----- exempted steps include the last step ---------------------------------------------
+
+        // constraints do not need to hold on steps 3, 7, 11, 15
+        let divisor = ConstraintDivisor::from_transition_with_periodic_exemptions(n, 4, 3);
+        assert_eq!(12, divisor.degree());
+        assert!(divisor.exclude().is_empty());
+        assert_eq!(&[(4, g.exp(12))], divisor.exemptions());
+
+        // z(x) = (x^16 - 1) / (x^4 - g^12) is a product of (x - g^i) for all non-exempted steps
+        let mut poly = vec![BaseElement::ONE];
+        for i in (0..n).filter(|i| i % 4 != 3) {
+            poly = polynom::mul(&poly, &[-g.exp((i as u64).into()), BaseElement::ONE]);
+        }
+        for i in 0..n {
+            let x = g.exp((i as u64).into());
+            assert_eq!(i % 4 == 3, divisor.excludes(x));
+            if i % 4 != 3 {
+                assert_eq!(BaseElement::ZERO, polynom::eval(&poly, x));
+            }
+        }
+        let x = BaseElement::new(123);
+        assert_eq!(polynom::eval(&poly, x), divisor.evaluate_at(x));
+
+        // ----- last step is excluded separately -------------------------------------------------
+
+        // constraints do not need to hold on steps 1, 9 and on the last step
+        let divisor = ConstraintDivisor::from_transition_with_periodic_exemptions(n, 8, 1);
+        assert_eq!(13, divisor.degree());
+        assert_eq!(&[g.exp(15)], divisor.exclude());
+        assert_eq!(&[(2, g.exp(2))], divisor.exemptions());
+
+        let mut poly = vec![BaseElement::ONE];
+        for i in (0..n - 1).filter(|i| i % 8 != 1) {
+            poly = polynom::mul(&poly, &[-g.exp((i as u64).into()), BaseElement::ONE]);
+        }
+        for i in 0..n {
+            assert_eq!(
+                i % 8 == 1 || i == n - 1,
+                divisor.excludes(g.exp((i as u64).into()))
+            );
+        }
+        assert_eq!(polynom::eval(&poly, x), divisor.evaluate_at(x));
+    }
+
+    #[test]
+    fn strided_transition_divisor() {
+        let n = 16_usize;
+        let g = BaseElement::get_root_of_unity(n.trailing_zeros());
+        let x = BaseElement::new(123);
+
+        // ----- enforced steps do not include the last step --------------------------------------
+
+        // constraints need to hold on steps 1, 5, 9, 13
+        let divisor = ConstraintDivisor::from_transition_with_stride(n, 4, 1);
+        assert_eq!(4, divisor.degree());
+        assert!(divisor.exclude().is_empty());
+        assert_eq!(&[(4, g.exp(4))], divisor.numerator());
+
+        // z(x) = x^4 - g^4 is a product of (x - g^i) for all enforced steps
+        let mut poly = vec![BaseElement::ONE];
+        for i in (0..n).filter(|i| i % 4 == 1) {
+            poly = polynom::mul(&poly, &[-g.exp((i as u64).into()), BaseElement::ONE]);
+        }
+        for i in 0..n {
+            let x = g.exp((i as u64).into());
+            assert_eq!(i % 4 == 1, divisor.enforces(x));
+            assert!(!divisor.excludes(x));
+        }
+        assert_eq!(polynom::eval(&poly, x), divisor.evaluate_at(x));
+
+        // ----- last step is excluded separately -------------------------------------------------
+
+        // constraints need to hold on step 7, but not on the last step
+        let divisor = ConstraintDivisor::from_transition_with_stride(n, 8, 7);
+        assert_eq!(1, divisor.degree());
+        assert_eq!(&[g.exp(15)], divisor.exclude());
+        for i in 0..n {
+            assert_eq!(i == 7, divisor.enforces(g.exp((i as u64).into())));
+        }
+        assert_eq!(x - g.exp(7), divisor.evaluate_at(x));
+
+        // ----- default divisor enforces all but the last step -----------------------------------
+
+        let divisor = ConstraintDivisor::<BaseElement>::from_transition(n);
+        for i in 0..n {
+            assert_eq!(i != n - 1, divisor.enforces(g.exp((i as u64).into())));
+        }
+    }
+
+    #[test]
+    fn padded_transition_divisor() {
+        let n = 16_usize;
+        let g = BaseElement::get_root_of_unity(n.trailing_zeros());
+        let x = BaseElement::new(123);
+
+        // the trace has 11 real rows and 5 padding rows; constraints do not need to hold on the
+        // transition from step 10 into padding, and on the last step
+        let divisor = ConstraintDivisor::from_transition_with_padding(n, 11);
+        assert_eq!(n - 2, divisor.degree());
+        assert_eq!(&[g.exp(10), g.exp(15)], divisor.exclude());
+        for i in 0..n {
+            let x = g.exp((i as u64).into());
+            assert_eq!(i != 10 && i != n - 1, divisor.enforces(x));
+        }
+
+        let mut poly = vec![BaseElement::ONE];
+        for i in (0..n).filter(|&i| i != 10 && i != n - 1) {
+            poly = polynom::mul(&poly, &[-g.exp((i as u64).into()), BaseElement::ONE]);
+        }
+        assert_eq!(polynom::eval(&poly, x), divisor.evaluate_at(x));
+
+        // an unpadded trace uses the default divisor
+        assert_eq!(
+            ConstraintDivisor::<BaseElement>::from_transition(n),
+            ConstraintDivisor::from_transition_with_padding(n, n)
+        );
+    }
+
     #[test]
     fn constraint_divisor_equivalence() {
         let n = 8_usize;
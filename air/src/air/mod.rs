@@ -4,10 +4,11 @@
 // LICENSE file in the root directory of this source tree.
 
 use crate::ProofOptions;
-use crypto::{Hasher, RandomCoin, RandomCoinError};
+use crypto::{PublicCoin, RandomCoinError};
 use math::{fft, ExtensibleField, FieldElement, StarkField};
 use utils::{
     collections::{BTreeMap, BTreeSet, Vec},
+    string::String,
     Serializable,
 };
 
@@ -21,10 +22,12 @@ mod assertions;
 pub use assertions::Assertion;
 
 mod boundary;
-pub use boundary::{BoundaryConstraint, BoundaryConstraintGroup};
+pub use boundary::{BoundaryConstraint, BoundaryConstraintGroup, CustomBoundaryConstraint};
 
 mod transition;
-pub use transition::{EvaluationFrame, TransitionConstraintDegree, TransitionConstraintGroup};
+pub use transition::{
+    ConstraintSet, EvaluationFrame, TransitionConstraintDegree, TransitionConstraintGroup,
+};
 
 mod coefficients;
 pub use coefficients::{ConstraintCompositionCoefficients, DeepCompositionCoefficients};
@@ -32,6 +35,9 @@ pub use coefficients::{ConstraintCompositionCoefficients, DeepCompositionCoeffic
 mod divisor;
 pub use divisor::ConstraintDivisor;
 
+mod description;
+pub use description::{AirDescription, AssertionDescription, TransitionConstraintDescription};
+
 #[cfg(test)]
 mod tests;
 
@@ -89,6 +95,12 @@ const MIN_CYCLE_LENGTH: usize = 2;
 /// described using only algebraic operations: additions, subtractions, and multiplications
 /// (divisions can be emulated using inverse of multiplication).
 ///
+/// Alternatively, transition constraints can be defined by a function which adds each constraint
+/// together with its label and degree to a [ConstraintSet]. In this case,
+/// [Air::evaluate_transition()] can delegate to [ConstraintSet::evaluate()], and constraint
+/// degrees passed to [AirContext] can be derived from the same definition via
+/// [AirContext::from_constraints()].
+///
 /// ### Constraint degrees
 /// One of the main factors impacting proof generation time and proof size is the maximum degree
 /// of transition constraints. The higher is this degree, the larger our blowup factor needs to be.
@@ -119,7 +131,8 @@ const MIN_CYCLE_LENGTH: usize = 2;
 ///
 /// To define assertions for your computation, you'll need to implement [Air::get_assertions()]
 /// function which should return a vector of [Assertion] structs. Every computation must have at
-/// least one assertion. Assertions can be of the following types:
+/// least one assertion, unless it defines
+/// [custom boundary constraints](Air::custom_boundary_constraints). Assertions can be of the following types:
 ///
 /// * A single assertion - such assertion specifies that a single cell of an execution trace must
 ///   be equal to a specific value. For example: *value in register 0, at step 0, must be equal
@@ -163,6 +176,11 @@ pub trait Air: Send + Sync {
     /// - `public_inputs` specifies public inputs for this instance of the computation.
     /// - `options` defines proof generation options such as blowup factor, hash function etc.
     ///   these options define security level of the proof and influence proof generation time.
+    ///
+    /// The verifier instantiates the AIR from trace info (including the `meta` field) recorded in
+    /// the proof context, and this context is bound to the proof. Thus, the set of constraints
+    /// (e.g., the number of transition constraints and their degrees) may depend on trace
+    /// metadata and public inputs, as long as it is fully determined by them.
     fn new(trace_info: TraceInfo, pub_inputs: Self::PublicInputs, options: ProofOptions) -> Self;
 
     /// Returns context for this instance of the computation.
@@ -174,6 +192,9 @@ pub trait Air: Send + Sync {
     /// the order of transition constraint degree descriptors used to instantiate [AirContext]
     /// for this AIR. Thus, the length of the `result` slice will equal to the number of
     /// transition constraints defined for this computation.
+    ///
+    /// If transition constraints are defined via a [ConstraintSet], this method can delegate to
+    /// [ConstraintSet::evaluate()].
     fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
         &self,
         frame: &EvaluationFrame<E>,
@@ -187,6 +208,38 @@ pub trait Air: Send + Sync {
     // PROVIDED METHODS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns an identifier of the computation described by this AIR.
+    ///
+    /// The identifier is used to make sure that a proof is verified against the AIR for which
+    /// it was generated (e.g., when a proof is bundled with its public inputs into a claim).
+    ///
+    /// The default implementation returns the name of the type implementing this trait. Type
+    /// names are not guaranteed to be stable across compiler versions, and so computations for
+    /// which proofs are persisted should override this method with a fixed identifier. The
+    /// identifier must be at most 2^16 - 1 bytes long to be included in a claim.
+    fn air_id() -> &'static str {
+        core::any::type_name::<Self>()
+    }
+
+    /// Checks whether an instance of this computation can be created from the specified trace
+    /// info and public inputs.
+    ///
+    /// The verifier calls this method before instantiating the AIR via [Air::new()] from the
+    /// trace info read from a proof, and rejects the proof if an error is returned. Thus, AIRs
+    /// which panic in [Air::new()] on values a malicious prover could put into a proof (e.g.,
+    /// invalid trace metadata) should reject such values here.
+    ///
+    /// The default implementation accepts any trace info and public inputs.
+    ///
+    /// # Errors
+    /// Returns an error describing why the specified values are not valid for this computation.
+    fn validate_instance(
+        _trace_info: &TraceInfo,
+        _pub_inputs: &Self::PublicInputs,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+
     /// Returns values for all periodic columns used in the computation.
     ///
     /// These values will be used to compute column values at specific states of the computation
@@ -200,6 +253,26 @@ pub trait Air: Send + Sync {
         Vec::new()
     }
 
+    /// Returns custom boundary constraints for this computation.
+    ///
+    /// Custom boundary constraints let an AIR compute boundary terms directly rather than
+    /// through [assertions](Air::get_assertions). `coefficients` contains one pair of composition
+    /// coefficients per constraint, and each returned constraint should be built using one of
+    /// these pairs. The number of constraints must be specified via
+    /// [AirContext::with_custom_boundary_constraints()] method.
+    ///
+    /// Custom boundary constraints are composed alongside boundary constraints derived from
+    /// assertions. To use custom boundary constraints instead of assertions, an AIR should
+    /// return an empty vector from [get_assertions()](Air::get_assertions) method.
+    ///
+    /// The default implementation of this method returns `None`.
+    fn custom_boundary_constraints<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        _coefficients: &[(E, E)],
+    ) -> Option<Vec<CustomBoundaryConstraint<'_, Self::BaseField, E>>> {
+        None
+    }
+
     /// Returns polynomial for all periodic columns.
     ///
     /// These polynomials are interpolated from the values returned from the
@@ -258,6 +331,7 @@ pub trait Air: Send + Sync {
         // iterate over all transition constraint degrees, and assign each constraint to the
         // appropriate group based on degree
         let context = self.context();
+        let divisor_degree = self.transition_constraint_divisor().degree();
         let mut groups = BTreeMap::new();
         for (i, degree) in context.transition_constraint_degrees.iter().enumerate() {
             let evaluation_degree = degree.get_evaluation_degree(self.trace_length());
@@ -265,6 +339,7 @@ pub trait Air: Send + Sync {
                 TransitionConstraintGroup::new(
                     degree.clone(),
                     self.trace_poly_degree(),
+                    divisor_degree,
                     self.composition_degree(),
                 )
             });
@@ -328,6 +403,35 @@ pub trait Air: Send + Sync {
         groups
     }
 
+    /// Returns custom boundary constraints defined by
+    /// [custom_boundary_constraints()](Air::custom_boundary_constraints) method, or an empty
+    /// vector if the AIR does not define any.
+    ///
+    /// # Panics
+    /// Panics if the number of returned constraints is different from the number of custom
+    /// boundary constraints specified in the context of this AIR.
+    fn get_custom_boundary_constraints<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        coefficients: &[(E, E)],
+    ) -> Vec<CustomBoundaryConstraint<'_, Self::BaseField, E>> {
+        assert_eq!(
+            self.num_custom_boundary_constraints(),
+            coefficients.len(),
+            "number of custom boundary constraints must match the number of coefficient tuples"
+        );
+        let constraints = self
+            .custom_boundary_constraints(coefficients)
+            .unwrap_or_default();
+        assert_eq!(
+            self.num_custom_boundary_constraints(),
+            constraints.len(),
+            "expected {} custom boundary constraints, but {} were defined",
+            self.num_custom_boundary_constraints(),
+            constraints.len()
+        );
+        constraints
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -378,15 +482,24 @@ pub trait Air: Send + Sync {
     /// Returns constraint evaluation domain blowup factor for the computation described by this
     /// AIR.
     ///
-    /// The blowup factor is defined as the smallest power of two greater than or equal to the
-    /// hightest transition constraint degree. For example, if the hightest transition
-    /// constraint degree = 3, `ce_blowup_factor` will be set to 4.
+    /// By default, the blowup factor is defined as the smallest power of two greater than or
+    /// equal to the hightest transition constraint degree. For example, if the hightest
+    /// transition constraint degree = 3, `ce_blowup_factor` will be set to 4. The blowup factor
+    /// can be increased via [AirContext::with_num_composition_columns()].
     ///
     /// `ce_blowup_factor` is guaranteed to be smaller than or equal to the `lde_blowup_factor`.
     fn ce_blowup_factor(&self) -> usize {
         self.context().ce_blowup_factor
     }
 
+    /// Returns the number of columns into which the constraint composition polynomial is split.
+    ///
+    /// This is the same as `ce_blowup_factor`, and each column is a polynomial of degree
+    /// `trace_length - 1`.
+    fn num_composition_columns(&self) -> usize {
+        self.ce_blowup_factor()
+    }
+
     /// Returns size of the constraint evaluation domain.
     ///
     /// This is guaranteed to be a power of two, and is equal to `trace_length * ce_blowup_factor`.
@@ -440,6 +553,17 @@ pub trait Air: Send + Sync {
         &self.context().transition_constraint_degrees
     }
 
+    /// Returns the label of the transition constraint at the specified index, or `None` if the
+    /// constraint does not have a label.
+    ///
+    /// Labels are available only for AIRs instantiated via [AirContext::from_constraints()].
+    fn transition_constraint_label(&self, index: usize) -> Option<&'static str> {
+        self.context()
+            .transition_constraint_labels
+            .get(index)
+            .copied()
+    }
+
     /// Returns the number of transition constraints for an instance of the computation described
     /// by this AIR.
     ///
@@ -449,6 +573,14 @@ pub trait Air: Send + Sync {
         self.context().transition_constraint_degrees.len()
     }
 
+    /// Returns the number of custom boundary constraints for an instance of the computation
+    /// described by this AIR.
+    ///
+    /// This is zero unless specified via [AirContext::with_custom_boundary_constraints()].
+    fn num_custom_boundary_constraints(&self) -> usize {
+        self.context().num_custom_boundary_constraints
+    }
+
     /// Returns a divisor for transition constraints.
     ///
     /// All transition constraints have the same divisor which has the form:
@@ -459,23 +591,41 @@ pub trait Air: Send + Sync {
     /// domain.
     ///
     /// This divisor specifies that transition constraints must hold on all steps of the
-    /// execution trace except for the last one.
+    /// execution trace except for the last one. An AIR can override this method to exempt
+    /// additional steps from transition constraints, e.g., via
+    /// [ConstraintDivisor::from_transition_with_periodic_exemptions()], or to enforce transition
+    /// constraints only once every few steps via
+    /// [ConstraintDivisor::from_transition_with_stride()].
     fn transition_constraint_divisor(&self) -> ConstraintDivisor<Self::BaseField> {
         ConstraintDivisor::from_transition(self.trace_length())
     }
 
+    // DESCRIPTION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a description of the constraint system of this AIR.
+    ///
+    /// The description lists trace dimensions, frame shape, transition constraint degrees and
+    /// labels, the transition constraint divisor, assertion positions, and periodic column
+    /// lengths; it does not include any values of the execution trace, assertions, or periodic
+    /// columns. Building the description requires calling [Air::get_assertions()] and
+    /// [Air::get_periodic_column_values()], which may be expensive for some AIRs.
+    fn describe(&self) -> AirDescription {
+        AirDescription::new(self)
+    }
+
     // LINEAR COMBINATION COEFFICIENTS
     // --------------------------------------------------------------------------------------------
 
     /// Returns coefficients needed for random linear combination during construction of constraint
     /// composition polynomial.
-    fn get_constraint_composition_coefficients<E, H>(
+    fn get_constraint_composition_coefficients<E, R>(
         &self,
-        public_coin: &mut RandomCoin<Self::BaseField, H>,
+        public_coin: &mut R,
     ) -> Result<ConstraintCompositionCoefficients<E>, RandomCoinError>
     where
         E: FieldElement<BaseField = Self::BaseField>,
-        H: Hasher,
+        R: PublicCoin<BaseField = Self::BaseField>,
     {
         let mut t_coefficients = Vec::new();
         for _ in 0..self.num_transition_constraints() {
@@ -489,30 +639,35 @@ pub trait Air: Send + Sync {
             b_coefficients.push(public_coin.draw_pair()?);
         }
 
+        let mut c_coefficients = Vec::new();
+        for _ in 0..self.num_custom_boundary_constraints() {
+            c_coefficients.push(public_coin.draw_pair()?);
+        }
+
         Ok(ConstraintCompositionCoefficients {
             transition: t_coefficients,
             boundary: b_coefficients,
+            custom_boundary: c_coefficients,
         })
     }
 
     /// Returns coefficients needed for random linear combinations during construction of DEEP
     /// composition polynomial.
-    fn get_deep_composition_coefficients<E, H>(
+    fn get_deep_composition_coefficients<E, R>(
         &self,
-        public_coin: &mut RandomCoin<Self::BaseField, H>,
+        public_coin: &mut R,
     ) -> Result<DeepCompositionCoefficients<E>, RandomCoinError>
     where
         E: FieldElement<BaseField = Self::BaseField>,
-        H: Hasher,
+        R: PublicCoin<BaseField = Self::BaseField>,
     {
         let mut t_coefficients = Vec::new();
         for _ in 0..self.trace_width() {
             t_coefficients.push(public_coin.draw_triple()?);
         }
 
-        // self.ce_blowup_factor() is the same as number of composition columns
         let mut c_coefficients = Vec::new();
-        for _ in 0..self.ce_blowup_factor() {
+        for _ in 0..self.num_composition_columns() {
             c_coefficients.push(public_coin.draw()?);
         }
 
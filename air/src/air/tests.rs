@@ -4,13 +4,16 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{
-    Air, AirContext, Assertion, EvaluationFrame, ProofOptions, TraceInfo,
-    TransitionConstraintDegree,
+    Air, AirContext, Assertion, AssertionDescription, ConstraintSet, EvaluationFrame, ProofOptions,
+    TraceInfo, TransitionConstraintDegree,
 };
-use crate::{FieldExtension, HashFunction};
+use crate::{FieldExtension, HashFunction, TraceInfoError};
 use crypto::{hashers::Blake3_256, RandomCoin};
-use math::{fields::f128::BaseElement, get_power_series, log2, polynom, FieldElement, StarkField};
-use rand_utils::shuffle;
+use math::{
+    fields::{f128::BaseElement, QuadExtension},
+    get_power_series, log2, polynom, FieldElement, StarkField,
+};
+use rand_utils::{rand_value, rand_vector, shuffle};
 use utils::collections::{BTreeMap, Vec};
 
 // PERIODIC COLUMNS
@@ -60,7 +63,30 @@ fn get_periodic_column_polys_num_values_not_power_of_two() {
 // TRANSITION CONSTRAINTS
 // ================================================================================================
 
-// TODO
+#[test]
+fn merge_base_evaluations() {
+    let air = ConstraintSetAir::new(
+        TraceInfo::new(2, 16),
+        (),
+        build_context::<BaseElement>(16, 2).options,
+    );
+    let coefficients = air
+        .get_constraint_composition_coefficients::<QuadExtension<BaseElement>, _>(&mut build_prng())
+        .unwrap();
+    let groups = air.get_transition_constraints(&coefficients.transition);
+    assert_eq!(3, groups.len());
+
+    // merging base field evaluations yields the same result as merging evaluations lifted into
+    // the extension field
+    let evaluations = rand_vector::<BaseElement>(air.num_transition_constraints());
+    let x = rand_value::<BaseElement>();
+    for group in groups.iter() {
+        assert_eq!(
+            group.merge_evaluations(&evaluations, x),
+            group.merge_base_evaluations(&evaluations, x)
+        );
+    }
+}
 
 // BOUNDARY CONSTRAINTS
 // ================================================================================================
@@ -293,6 +319,211 @@ fn prepare_assertions_with_invalid_trace_width() {
     let _ = super::prepare_assertions(assertions.clone(), &context);
 }
 
+// TRANSITION CONSTRAINT SETS
+// ================================================================================================
+
+#[test]
+fn transition_constraint_set() {
+    let air = ConstraintSetAir::new(
+        TraceInfo::new(2, 16),
+        (),
+        build_context::<BaseElement>(16, 2).options,
+    );
+
+    // number, degrees, and labels of constraints are derived from the constraint definitions
+    assert_eq!(3, air.num_transition_constraints());
+    let expected_degrees = vec![
+        TransitionConstraintDegree::new(1),
+        TransitionConstraintDegree::new(2),
+        TransitionConstraintDegree::with_cycles(1, vec![8]),
+    ];
+    assert_eq!(expected_degrees, air.transition_constraint_degrees());
+    assert_eq!(Some("sum"), air.transition_constraint_label(0));
+    assert_eq!(Some("product"), air.transition_constraint_label(1));
+    assert_eq!(Some("periodic"), air.transition_constraint_label(2));
+    assert_eq!(None, air.transition_constraint_label(3));
+
+    // constraints are evaluated in the order in which they were defined
+    let frame = EvaluationFrame::from_rows(
+        vec![BaseElement::new(2), BaseElement::new(3)],
+        vec![BaseElement::new(5), BaseElement::new(7)],
+    );
+    let mut result = vec![BaseElement::ZERO; 3];
+    air.evaluate_transition(&frame, &[BaseElement::new(11)], &mut result);
+    let expected = vec![
+        BaseElement::new(5) - (BaseElement::new(2) + BaseElement::new(3)),
+        BaseElement::new(7) - BaseElement::new(2) * BaseElement::new(3),
+        BaseElement::new(11) * BaseElement::new(2),
+    ];
+    assert_eq!(expected, result);
+
+    // AIRs which do not use constraint sets have no labels
+    let air = MockAir::with_periodic_columns(vec![], 16);
+    assert_eq!(None, air.transition_constraint_label(0));
+}
+
+#[test]
+#[should_panic(
+    expected = "expected 2 transition constraints, but constraint 'periodic' was evaluated in addition"
+)]
+fn transition_constraint_set_too_many_constraints() {
+    let air = ConstraintSetAir::new(
+        TraceInfo::new(2, 16),
+        (),
+        build_context::<BaseElement>(16, 2).options,
+    );
+    let frame = EvaluationFrame::<BaseElement>::new(2);
+    let mut result = vec![BaseElement::ZERO; 2];
+    air.evaluate_transition(&frame, &[BaseElement::ONE], &mut result);
+}
+
+#[test]
+#[should_panic(expected = "expected 4 transition constraints, but 3 were evaluated")]
+fn transition_constraint_set_too_few_constraints() {
+    let air = ConstraintSetAir::new(
+        TraceInfo::new(2, 16),
+        (),
+        build_context::<BaseElement>(16, 2).options,
+    );
+    let frame = EvaluationFrame::<BaseElement>::new(2);
+    let mut result = vec![BaseElement::ZERO; 4];
+    air.evaluate_transition(&frame, &[BaseElement::ONE], &mut result);
+}
+
+// TRACE INFO
+// ================================================================================================
+
+#[test]
+fn trace_info_validation() {
+    assert!(TraceInfo::try_new(1, 8).is_ok());
+    assert!(TraceInfo::try_with_meta(255, 1024, vec![0; 65535]).is_ok());
+
+    assert_eq!(
+        Err(TraceInfoError::ZeroTraceWidth),
+        TraceInfo::try_new(0, 8)
+    );
+    assert_eq!(
+        Err(TraceInfoError::TraceWidthTooLarge(256)),
+        TraceInfo::try_new(256, 8)
+    );
+    assert_eq!(
+        Err(TraceInfoError::TraceLengthTooShort(4)),
+        TraceInfo::try_new(1, 4)
+    );
+    assert_eq!(
+        Err(TraceInfoError::TraceLengthNotPowerOfTwo(1000)),
+        TraceInfo::try_new(1, 1000)
+    );
+    assert_eq!(
+        Err(TraceInfoError::MetaTooLong(65536)),
+        TraceInfo::try_with_meta(1, 8, vec![0; 65536])
+    );
+}
+
+#[test]
+#[should_panic(expected = "trace width cannot be greater than 255, but was 256")]
+fn trace_info_invalid_width() {
+    TraceInfo::new(256, 8);
+}
+
+#[test]
+fn trace_info_with_padding() {
+    // a trace of 1000 steps is padded to 1024 steps, and the unpadded length is recorded in meta
+    let info = TraceInfo::with_padding(2, 1000);
+    assert_eq!(1024, info.length());
+    assert_eq!(1000u64.to_le_bytes().to_vec(), info.meta());
+    assert_eq!(Some(1000), info.unpadded_length());
+
+    // short traces are padded to the minimum trace length
+    let info = TraceInfo::with_padding(2, 3);
+    assert_eq!(TraceInfo::MIN_TRACE_LENGTH, info.length());
+    assert_eq!(Some(3), info.unpadded_length());
+
+    // traces which do not need to be padded have empty metadata
+    let info = TraceInfo::with_padding(2, 1024);
+    assert_eq!(TraceInfo::new(2, 1024), info);
+    assert_eq!(Some(1024), info.unpadded_length());
+
+    // metadata which is inconsistent with the trace length is rejected
+    let meta = 100u64.to_le_bytes().to_vec();
+    assert_eq!(None, TraceInfo::with_meta(2, 1024, meta).unpadded_length());
+    let meta = 1024u64.to_le_bytes().to_vec();
+    assert_eq!(None, TraceInfo::with_meta(2, 1024, meta).unpadded_length());
+    assert_eq!(
+        None,
+        TraceInfo::with_meta(2, 1024, vec![1]).unpadded_length()
+    );
+}
+
+// AIR DESCRIPTION
+// ================================================================================================
+
+#[test]
+fn describe_air() {
+    let assertions = vec![
+        Assertion::single(1, 3, BaseElement::new(5)),
+        Assertion::periodic(0, 1, 4, BaseElement::new(7)),
+        Assertion::sequence(2, 0, 8, vec![BaseElement::ONE, BaseElement::new(2)]),
+    ];
+    let mut air = MockAir::with_assertions(assertions, 16);
+    air.periodic_columns = vec![vec![BaseElement::ONE; 4], vec![BaseElement::ONE; 8]];
+    let description = air.describe();
+
+    assert_eq!(4, description.trace_width);
+    assert_eq!(16, description.trace_length);
+    assert_eq!(vec![0, 1], description.frame_offsets);
+    assert_eq!(1, description.transition_constraints.len());
+    assert_eq!(None, description.transition_constraints[0].label);
+    assert_eq!(2, description.transition_constraints[0].degree.base());
+    assert_eq!(30, description.transition_constraints[0].evaluation_degree);
+    assert_eq!(
+        air.transition_constraint_divisor().to_string(),
+        description.transition_divisor
+    );
+    let expected_assertions = vec![
+        AssertionDescription {
+            register: 1,
+            first_step: 3,
+            stride: 0,
+            num_values: 1,
+        },
+        AssertionDescription {
+            register: 0,
+            first_step: 1,
+            stride: 4,
+            num_values: 1,
+        },
+        AssertionDescription {
+            register: 2,
+            first_step: 0,
+            stride: 8,
+            num_values: 2,
+        },
+    ];
+    assert_eq!(expected_assertions, description.assertions);
+    assert_eq!(0, description.num_custom_boundary_constraints);
+    assert_eq!(vec![4, 8], description.periodic_column_lengths);
+    assert_eq!(2, description.ce_blowup_factor);
+
+    // labels and cycles are taken from constraint definitions
+    let air = ConstraintSetAir::new(
+        TraceInfo::new(2, 16),
+        (),
+        build_context::<BaseElement>(16, 2).options,
+    );
+    let labels = air
+        .describe()
+        .transition_constraints
+        .iter()
+        .map(|constraint| constraint.label)
+        .collect::<Vec<_>>();
+    assert_eq!(vec![Some("sum"), Some("product"), Some("periodic")], labels);
+    assert_eq!(
+        &[8],
+        air.describe().transition_constraints[2].degree.cycles()
+    );
+}
+
 // MOCK AIR
 // ================================================================================================
 
@@ -377,6 +608,60 @@ impl Air for MockAir {
     }
 }
 
+// CONSTRAINT SET AIR
+// ================================================================================================
+
+/// An AIR which defines its transition constraints via a constraint set.
+struct ConstraintSetAir {
+    context: AirContext<BaseElement>,
+}
+
+impl ConstraintSetAir {
+    fn define_constraints<E: FieldElement>(constraints: &mut ConstraintSet<E>) {
+        constraints
+            .add("sum", TransitionConstraintDegree::new(1), |frame, _| {
+                frame.next()[0] - (frame.current()[0] + frame.current()[1])
+            })
+            .add("product", TransitionConstraintDegree::new(2), |frame, _| {
+                frame.next()[1] - frame.current()[0] * frame.current()[1]
+            })
+            .add(
+                "periodic",
+                TransitionConstraintDegree::with_cycles(1, vec![8]),
+                |frame, periodic_values| periodic_values[0] * frame.current()[0],
+            );
+    }
+}
+
+impl Air for ConstraintSetAir {
+    type BaseField = BaseElement;
+    type PublicInputs = ();
+
+    fn new(trace_info: TraceInfo, _pub_inputs: (), options: ProofOptions) -> Self {
+        let constraints = ConstraintSet::describe(Self::define_constraints);
+        ConstraintSetAir {
+            context: AirContext::from_constraints(trace_info, &constraints, options),
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        ConstraintSet::evaluate(frame, periodic_values, result, Self::define_constraints);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        vec![Assertion::single(0, 0, BaseElement::ONE)]
+    }
+}
+
 // UTILITY FUNCTIONS
 // ================================================================================================
 
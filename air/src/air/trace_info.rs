@@ -3,6 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use crate::TraceInfoError;
 use utils::collections::Vec;
 
 // TRACE INFO
@@ -47,35 +48,79 @@ impl TraceInfo {
     /// * `length` is smaller than 8 or is not a power of two.
     /// * Length of `meta` is greater than 65535;
     pub fn with_meta(width: usize, length: usize, meta: Vec<u8>) -> Self {
-        assert!(width > 0, "trace width must be greater than 0");
-        assert!(
-            width <= Self::MAX_TRACE_WIDTH,
-            "trace width cannot be greater than {}, but was {}",
-            Self::MAX_TRACE_WIDTH,
-            width
-        );
-        assert!(
-            length >= Self::MIN_TRACE_LENGTH,
-            "trace length must be at least {}, but was {}",
-            Self::MIN_TRACE_LENGTH,
-            length
-        );
-        assert!(
-            length.is_power_of_two(),
-            "trace length must be a power of two, but was {}",
-            length
-        );
-        assert!(
-            meta.len() <= Self::MAX_META_LENGTH,
-            "number of metadata bytes cannot be greater than {}, but was {}",
-            Self::MAX_META_LENGTH,
-            meta.len()
-        );
-        TraceInfo {
+        Self::try_with_meta(width, length, meta).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Creates a new trace info from the specified length, or returns an error if the parameters
+    /// do not describe a valid execution trace.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * `width` is zero or greater than 255.
+    /// * `length` is smaller than 8 or is not a power of two.
+    pub fn try_new(width: usize, length: usize) -> Result<Self, TraceInfoError> {
+        Self::try_with_meta(width, length, vec![])
+    }
+
+    /// Creates a new trace info from the specified length and metadata, or returns an error if
+    /// the parameters do not describe a valid execution trace.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * `width` is zero or greater than 255.
+    /// * `length` is smaller than 8 or is not a power of two.
+    /// * Length of `meta` is greater than 65535;
+    pub fn try_with_meta(
+        width: usize,
+        length: usize,
+        meta: Vec<u8>,
+    ) -> Result<Self, TraceInfoError> {
+        if width == 0 {
+            return Err(TraceInfoError::ZeroTraceWidth);
+        }
+        if width > Self::MAX_TRACE_WIDTH {
+            return Err(TraceInfoError::TraceWidthTooLarge(width));
+        }
+        if length < Self::MIN_TRACE_LENGTH {
+            return Err(TraceInfoError::TraceLengthTooShort(length));
+        }
+        if !length.is_power_of_two() {
+            return Err(TraceInfoError::TraceLengthNotPowerOfTwo(length));
+        }
+        if meta.len() > Self::MAX_META_LENGTH {
+            return Err(TraceInfoError::MetaTooLong(meta.len()));
+        }
+        Ok(TraceInfo {
             width,
             length,
             meta,
-        }
+        })
+    }
+
+    /// Creates a new trace info for an execution trace of `unpadded_length` steps padded to the
+    /// next power of two.
+    ///
+    /// The length of the padded trace is the smallest power of two which is at least 8 and is not
+    /// smaller than `unpadded_length`. If the trace needs to be padded, `unpadded_length` is
+    /// recorded in trace metadata as an 8-byte little-endian integer, and can be retrieved via
+    /// [unpadded_length()](TraceInfo::unpadded_length) method; otherwise, metadata is empty.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `width` is zero or greater than 255.
+    /// * `unpadded_length` is zero.
+    pub fn with_padding(width: usize, unpadded_length: usize) -> Self {
+        assert!(
+            unpadded_length > 0,
+            "unpadded trace length must be greater than 0"
+        );
+        let length = Self::padded_length(unpadded_length);
+        let meta = if length == unpadded_length {
+            vec![]
+        } else {
+            (unpadded_length as u64).to_le_bytes().to_vec()
+        };
+        Self::with_meta(width, length, meta)
     }
 
     // PUBLIC ACCESSORS
@@ -99,4 +144,38 @@ impl TraceInfo {
     pub fn meta(&self) -> &[u8] {
         &self.meta
     }
+
+    /// Returns the length of the execution trace before it was padded to a power of two.
+    ///
+    /// This method interprets trace metadata as described in
+    /// [with_padding()](TraceInfo::with_padding): if metadata is empty, the trace was not padded
+    /// and the returned value is equal to the trace length. Thus, this method should be used only
+    /// by AIRs which do not store any other values in trace metadata.
+    ///
+    /// Returns `None` if metadata does not encode a valid unpadded length for this trace.
+    pub fn unpadded_length(&self) -> Option<usize> {
+        if self.meta.is_empty() {
+            return Some(self.length);
+        }
+        let bytes: [u8; 8] = self.meta.as_slice().try_into().ok()?;
+        let unpadded_length = u64::from_le_bytes(bytes);
+        if unpadded_length == 0 || unpadded_length >= self.length as u64 {
+            return None;
+        }
+        let unpadded_length = unpadded_length as usize;
+        if Self::padded_length(unpadded_length) != self.length {
+            return None;
+        }
+        Some(unpadded_length)
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the length to which a trace of the specified length is padded.
+    fn padded_length(unpadded_length: usize) -> usize {
+        unpadded_length
+            .next_power_of_two()
+            .max(Self::MIN_TRACE_LENGTH)
+    }
 }
@@ -0,0 +1,171 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{EvaluationFrame, TransitionConstraintDegree};
+use math::FieldElement;
+use utils::collections::Vec;
+
+// CONSTRAINT SET
+// ================================================================================================
+/// A list of transition constraints, each paired with its label and degree.
+///
+/// A constraint set allows defining transition constraints of an AIR in a single place, such that
+/// the number of constraints, their degrees, and their evaluations cannot get out of sync. The
+/// constraints are defined by a function which adds them to the set via
+/// [add()](ConstraintSet::add) method, for example:
+///
+/// ```ignore
+/// fn define_constraints<E: FieldElement>(constraints: &mut ConstraintSet<E>) {
+///     constraints
+///         .add("s0", TransitionConstraintDegree::new(2), |frame, _| {
+///             frame.next()[0] - frame.current()[0] * frame.current()[1]
+///         })
+///         .add("s1", TransitionConstraintDegree::new(2), |frame, _| {
+///             frame.next()[1] - frame.current()[1] * frame.next()[0]
+///         });
+/// }
+/// ```
+///
+/// The same function is then executed in two modes:
+/// * When an AIR is instantiated, a set created via [describe()](ConstraintSet::describe)
+///   records labels and degrees of the constraints without evaluating them. Such a set can be
+///   passed to [AirContext::from_constraints()](crate::AirContext::from_constraints).
+/// * When constraints are evaluated, a set created via [evaluate()](ConstraintSet::evaluate)
+///   evaluates each constraint closure against an evaluation frame and writes the results into
+///   the result slice in the order in which the constraints were added. An implementation of
+///   [Air::evaluate_transition()](crate::Air::evaluate_transition) can simply delegate to this
+///   function, e.g., `ConstraintSet::evaluate(frame, periodic_values, result, define_constraints)`.
+///
+/// In the evaluation mode, constraint closures are monomorphized and nothing is allocated; thus,
+/// the overhead compared to a hand-written `evaluate_transition()` is small.
+pub struct ConstraintSet<'a, E: FieldElement> {
+    frame: Option<(&'a EvaluationFrame<E>, &'a [E])>,
+    result: &'a mut [E],
+    num_constraints: usize,
+    labels: Vec<&'static str>,
+    degrees: Vec<TransitionConstraintDegree>,
+}
+
+impl<'a, E: FieldElement> ConstraintSet<'a, E> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a set describing the constraints added by the `define` function.
+    ///
+    /// Constraint closures are not evaluated; only labels and degrees of the constraints are
+    /// recorded.
+    ///
+    /// # Panics
+    /// Panics if `define` does not add any constraints to the set.
+    pub fn describe<F>(define: F) -> Self
+    where
+        F: FnOnce(&mut ConstraintSet<E>),
+    {
+        let mut constraints = ConstraintSet {
+            frame: None,
+            result: &mut [],
+            num_constraints: 0,
+            labels: Vec::new(),
+            degrees: Vec::new(),
+        };
+        define(&mut constraints);
+        assert!(
+            constraints.num_constraints > 0,
+            "at least one transition constraint must be defined"
+        );
+        constraints
+    }
+
+    /// Evaluates the constraints added by the `define` function against the specified evaluation
+    /// frame and periodic values, and writes the results into `result`.
+    ///
+    /// # Panics
+    /// Panics if the number of constraints added by `define` is not equal to the length of
+    /// `result`.
+    pub fn evaluate<F>(
+        frame: &'a EvaluationFrame<E>,
+        periodic_values: &'a [E],
+        result: &'a mut [E],
+        define: F,
+    ) where
+        F: FnOnce(&mut ConstraintSet<E>),
+    {
+        let num_results = result.len();
+        let mut constraints = ConstraintSet {
+            frame: Some((frame, periodic_values)),
+            result,
+            num_constraints: 0,
+            labels: Vec::new(),
+            degrees: Vec::new(),
+        };
+        define(&mut constraints);
+        assert_eq!(
+            constraints.num_constraints, num_results,
+            "expected {} transition constraints, but {} were evaluated",
+            num_results, constraints.num_constraints
+        );
+    }
+
+    // CONSTRAINT DEFINITION
+    // --------------------------------------------------------------------------------------------
+
+    /// Adds a transition constraint with the specified label and degree to this set.
+    ///
+    /// The `constraint` closure receives an evaluation frame and values of periodic columns, and
+    /// must return the evaluation of the constraint. The closure is executed only if this set is
+    /// used to evaluate constraints.
+    ///
+    /// # Panics
+    /// Panics if more constraints are added than the number of results this set evaluates.
+    pub fn add<F>(
+        &mut self,
+        label: &'static str,
+        degree: TransitionConstraintDegree,
+        constraint: F,
+    ) -> &mut Self
+    where
+        F: FnOnce(&EvaluationFrame<E>, &[E]) -> E,
+    {
+        match self.frame {
+            Some((frame, periodic_values)) => {
+                assert!(
+                    self.num_constraints < self.result.len(),
+                    "expected {} transition constraints, but constraint '{}' was evaluated in addition",
+                    self.result.len(),
+                    label
+                );
+                self.result[self.num_constraints] = constraint(frame, periodic_values);
+            }
+            None => {
+                self.labels.push(label);
+                self.degrees.push(degree);
+            }
+        }
+        self.num_constraints += 1;
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of constraints in this set.
+    pub fn num_constraints(&self) -> usize {
+        self.num_constraints
+    }
+
+    /// Returns labels of constraints in this set in the order in which they were added.
+    ///
+    /// Labels are recorded only for sets created via [describe()](ConstraintSet::describe).
+    pub fn labels(&self) -> &[&'static str] {
+        &self.labels
+    }
+
+    /// Returns degrees of constraints in this set in the order in which they were added.
+    ///
+    /// Degrees are recorded only for sets created via [describe()](ConstraintSet::describe).
+    pub fn degrees(&self) -> &[TransitionConstraintDegree] {
+        &self.degrees
+    }
+}
@@ -7,6 +7,9 @@ use core::cmp;
 use math::FieldElement;
 use utils::collections::Vec;
 
+mod constraint_set;
+pub use constraint_set::ConstraintSet;
+
 // CONSTANTS
 // ================================================================================================
 
@@ -46,13 +49,15 @@ impl<E: FieldElement> TransitionConstraintGroup<E> {
     pub(super) fn new(
         degree: TransitionConstraintDegree,
         trace_poly_degree: usize,
+        divisor_degree: usize,
         composition_degree: usize,
     ) -> Self {
         // We want to make sure that once we divide a constraint polynomial by its divisor, the
         // degree of the resulting polynomial will be exactly equal to the composition_degree.
-        // For transition constraints, divisor degree = deg(trace). So, target degree for all
-        // transitions constraints is simply: deg(composition) + deg(trace)
-        let target_degree = composition_degree + trace_poly_degree;
+        // For the default transition divisor, divisor degree = deg(trace), but divisors with
+        // periodic exemptions have smaller degrees. So, target degree for all transitions
+        // constraints is: deg(composition) + deg(divisor)
+        let target_degree = composition_degree + divisor_degree;
         let evaluation_degree = degree.get_evaluation_degree(trace_poly_degree + 1);
         let degree_adjustment = (target_degree - evaluation_degree) as u32;
         TransitionConstraintGroup {
@@ -95,18 +100,18 @@ impl<E: FieldElement> TransitionConstraintGroup<E> {
     /// * $C_i(x)$ is the evaluation of the $i$th constraint at `x` (same as `evaluations[i]`).
     /// * $\alpha$ and $\beta$ are random field elements. In the interactive version of the
     ///   protocol, these are provided by the verifier.
-    /// * $d$ is the degree adjustment factor computed as $D + (n - 1) - deg(C_i(x))$, where
-    ///   $D$ is the degree of the composition polynomial, $n$ is the length of the execution
-    ///   trace, and $deg(C_i(x))$ is the evaluation degree of the $i$th constraint.
+    /// * $d$ is the degree adjustment factor computed as $D + deg(z(x)) - deg(C_i(x))$, where
+    ///   $D$ is the degree of the composition polynomial, $z(x)$ is the transition constraint
+    ///   divisor (for the default divisor, its degree is $n - 1$, where $n$ is the length of the
+    ///   execution trace), and $deg(C_i(x))$ is the evaluation degree of the $i$th constraint.
     ///
     /// There are two things to note here. First, the degree adjustment factor $d$ is the same
     /// for all constraints in the group (since all constraints have the same degree). Second,
-    /// the merged evaluations represent a polynomial of degree $D + n - 1$, which is higher
+    /// the merged evaluations represent a polynomial of degree $D + deg(z(x))$, which is higher
     /// then the target degree of the composition polynomial. This is because at this stage,
     /// we are merging only the numerators of transition constraints, and we will need to divide
-    /// them by the divisor later on. The degree of the divisor for transition constraints is
-    /// always $n - 1$. Thus, once we divide out the divisor, the evaluations will represent a
-    /// polynomial of degree $D$.
+    /// them by the divisor later on. Thus, once we divide out the divisor, the evaluations will
+    /// represent a polynomial of degree $D$.
     pub fn merge_evaluations<B>(&self, evaluations: &[B], x: B) -> E
     where
         B: FieldElement,
@@ -124,6 +129,26 @@ impl<E: FieldElement> TransitionConstraintGroup<E> {
         }
         result
     }
+
+    /// Computes the same linear combination as
+    /// [merge_evaluations()](TransitionConstraintGroup::merge_evaluations), but for evaluations
+    /// in the base field of `E`.
+    ///
+    /// Since `x` and all evaluations are in the base field, multiplications by them are performed
+    /// as multiplications by base field elements, which is cheaper when `E` is an extension
+    /// field. This is used by the prover which evaluates constraints over the constraint
+    /// evaluation domain.
+    pub fn merge_base_evaluations(&self, evaluations: &[E::BaseField], x: E::BaseField) -> E {
+        // compute degree adjustment factor for this group
+        let xp = x.exp(self.degree_adjustment.into());
+
+        let mut result = E::ZERO;
+        for (&constraint_idx, coefficients) in self.indexes.iter().zip(self.coefficients.iter()) {
+            let evaluation = evaluations[constraint_idx];
+            result += (coefficients.0 + coefficients.1.mul_base(xp)).mul_base(evaluation);
+        }
+        result
+    }
 }
 
 // TRANSITION CONSTRAINT DEGREE
@@ -227,6 +252,17 @@ impl TransitionConstraintDegree {
         result
     }
 
+    /// Returns the number of trace registers multiplied together in a constraint of this degree.
+    pub fn base(&self) -> usize {
+        self.base
+    }
+
+    /// Returns the lengths of periodic columns multiplied with trace registers in a constraint of
+    /// this degree.
+    pub fn cycles(&self) -> &[usize] {
+        &self.cycles
+    }
+
     /// Returns a minimum blowup factor needed to evaluate constraint of this degree.
     ///
     /// This is guaranteed to be a power of two, greater than one.
@@ -312,4 +348,14 @@ impl<E: FieldElement> EvaluationFrame<E> {
     pub fn next_mut(&mut self) -> &mut [E] {
         &mut self.next
     }
+    // CONVERSIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Consumes this evaluation frame and returns its current and next rows.
+    ///
+    /// This is the inverse of [from_rows()](EvaluationFrame::from_rows); the rows are moved out
+    /// of the frame without being copied.
+    pub fn into_rows(self) -> (Vec<E>, Vec<E>) {
+        (self.current, self.next)
+    }
 }
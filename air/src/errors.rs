@@ -3,6 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use crate::TraceInfo;
 use core::fmt;
 
 // ASSERTION ERROR
@@ -43,3 +44,47 @@ impl fmt::Display for AssertionError {
         }
     }
 }
+
+// TRACE INFO ERROR
+// ================================================================================================
+/// Represents an error returned when a [TraceInfo] cannot be instantiated from the specified
+/// parameters.
+#[derive(Debug, PartialEq, Eq)]
+pub enum TraceInfoError {
+    /// This error occurs when trace width is zero.
+    ZeroTraceWidth,
+    /// This error occurs when trace width is greater than
+    /// [TraceInfo::MAX_TRACE_WIDTH].
+    TraceWidthTooLarge(usize),
+    /// This error occurs when trace length is smaller than
+    /// [TraceInfo::MIN_TRACE_LENGTH].
+    TraceLengthTooShort(usize),
+    /// This error occurs when trace length is not a power of two.
+    TraceLengthNotPowerOfTwo(usize),
+    /// This error occurs when trace metadata contains more than
+    /// [TraceInfo::MAX_META_LENGTH] bytes.
+    MetaTooLong(usize),
+}
+
+impl fmt::Display for TraceInfoError {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ZeroTraceWidth => {
+                write!(f, "trace width must be greater than 0")
+            }
+            Self::TraceWidthTooLarge(actual) => {
+                write!(f, "trace width cannot be greater than {}, but was {}", TraceInfo::MAX_TRACE_WIDTH, actual)
+            }
+            Self::TraceLengthTooShort(actual) => {
+                write!(f, "trace length must be at least {}, but was {}", TraceInfo::MIN_TRACE_LENGTH, actual)
+            }
+            Self::TraceLengthNotPowerOfTwo(actual) => {
+                write!(f, "trace length must be a power of two, but was {}", actual)
+            }
+            Self::MetaTooLong(actual) => {
+                write!(f, "number of metadata bytes cannot be greater than {}, but was {}", TraceInfo::MAX_META_LENGTH, actual)
+            }
+        }
+    }
+}
@@ -36,14 +36,15 @@ extern crate alloc;
 pub mod proof;
 
 mod errors;
-pub use errors::AssertionError;
+pub use errors::{AssertionError, TraceInfoError};
 
 mod options;
 pub use options::{FieldExtension, HashFunction, ProofOptions};
 
 mod air;
 pub use air::{
-    Air, AirContext, Assertion, BoundaryConstraint, BoundaryConstraintGroup,
-    ConstraintCompositionCoefficients, ConstraintDivisor, DeepCompositionCoefficients,
-    EvaluationFrame, TraceInfo, TransitionConstraintDegree, TransitionConstraintGroup,
+    Air, AirContext, AirDescription, Assertion, AssertionDescription, BoundaryConstraint,
+    BoundaryConstraintGroup, ConstraintCompositionCoefficients, ConstraintDivisor, ConstraintSet,
+    CustomBoundaryConstraint, DeepCompositionCoefficients, EvaluationFrame, TraceInfo,
+    TransitionConstraintDegree, TransitionConstraintDescription, TransitionConstraintGroup,
 };
@@ -5,7 +5,42 @@
 
 use fri::FriOptions;
 use math::StarkField;
-use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+use utils::{
+    collections::Vec,
+    string::{String, ToString},
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Smallest and largest number of queries which can be specified in proof options.
+const MIN_NUM_QUERIES: usize = 1;
+const MAX_NUM_QUERIES: usize = 128;
+
+/// Smallest and largest blowup factor which can be specified in proof options.
+const MIN_BLOWUP_FACTOR: usize = 4;
+const MAX_BLOWUP_FACTOR: usize = 128;
+
+/// Largest grinding factor which can be specified in proof options.
+const MAX_GRINDING_FACTOR: u32 = 32;
+
+/// Smallest and largest FRI folding factor which can be specified in proof options.
+const MIN_FRI_FOLDING_FACTOR: usize = 4;
+const MAX_FRI_FOLDING_FACTOR: usize = 16;
+
+/// Smallest and largest FRI max remainder size which can be specified in proof options.
+const MIN_FRI_REMAINDER_SIZE: usize = 32;
+const MAX_FRI_REMAINDER_SIZE: usize = 1024;
+
+/// Bit of the serialized grinding factor which indicates that a transcript domain separation tag
+/// follows serialized proof options; grinding factor never exceeds [MAX_GRINDING_FACTOR] and so
+/// this bit is free.
+const TRANSCRIPT_DOMAIN_FLAG: u8 = 0x80;
+
+/// Flag set in the serialized grinding factor byte when a custom domain offset follows the
+/// serialized options.
+const DOMAIN_OFFSET_FLAG: u8 = 0x40;
 
 // TYPES AND INTERFACES
 // ================================================================================================
@@ -16,7 +51,7 @@ use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serial
 /// soundness. In general, sounds of the proof is bounded by the collision resistance of the hash
 /// function used by the protocol.
 #[repr(u8)]
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum HashFunction {
     /// BLAKE3 hash function with 192 bit output.
     ///
@@ -48,7 +83,7 @@ pub enum HashFunction {
 /// However, increasing extension degree will increase proof generation time and proof size by
 /// as much as 50%.
 #[repr(u8)]
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum FieldExtension {
     /// Composition polynomial is constructed in the base field.
     None = 1,
@@ -78,7 +113,15 @@ pub enum FieldExtension {
 /// 5. Grinding factor - higher values increase proof soundness, but also may increase proof
 ///    generation time. More precisely, proof soundness is bounded by
 ///    `num_queries * log2(blowup_factor) + grinding_factor`.
-#[derive(Debug, Clone, Eq, PartialEq)]
+///
+/// Additionally, proof options may specify a transcript domain separation tag (see
+/// [with_transcript_domain()](ProofOptions::with_transcript_domain)); this does not affect proof
+/// soundness, but binds proofs to a specific protocol.
+///
+/// Proof options may also specify a custom offset of the evaluation domain (see
+/// [with_domain_offset()](ProofOptions::with_domain_offset)); this allows matching the domain
+/// used by another proving system.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct ProofOptions {
     num_queries: u8,
     blowup_factor: u8,
@@ -87,6 +130,8 @@ pub struct ProofOptions {
     field_extension: FieldExtension,
     fri_folding_factor: u8,
     fri_max_remainder_size: u8, // stored as power of 2
+    transcript_domain: Vec<u8>,
+    domain_offset: Option<u64>,
 }
 
 // PROOF OPTIONS IMPLEMENTATION
@@ -99,11 +144,10 @@ impl ProofOptions {
     /// # Panics
     /// Panics if:
     /// * `num_queries` is zero or greater than 128.
-    /// * `blowup_factor` is smaller than 4, greater than 256, or is not a power of two.
+    /// * `blowup_factor` is smaller than 4, greater than 128, or is not a power of two.
     /// * `grinding_factor` is greater than 32.
     /// * `fri_folding_factor` is not 4, 8, or 16.
     /// * `fri_max_remainder_size` is smaller than 32, greater than 1024, or is not a power of two.
-    #[rustfmt::skip]
     pub fn new(
         num_queries: usize,
         blowup_factor: usize,
@@ -114,22 +158,15 @@ impl ProofOptions {
         fri_max_remainder_size: usize,
     ) -> ProofOptions {
         // TODO: return errors instead of panicking
-        assert!(num_queries > 0, "number of queries must be greater than 0");
-        assert!(num_queries <= 128, "number of queries cannot be greater than 128");
-
-        assert!(blowup_factor.is_power_of_two(), "blowup factor must be a power of 2");
-        assert!(blowup_factor >= 4, "blowup factor cannot be smaller than 4");
-        assert!(blowup_factor <= 128, "blowup factor cannot be greater than 128");
-
-        assert!(grinding_factor <= 32, "grinding factor cannot be greater than 32");
-
-        assert!(fri_folding_factor.is_power_of_two(), "FRI folding factor must be a power of 2");
-        assert!(fri_folding_factor >= 4, "FRI folding factor cannot be smaller than 4");
-        assert!(fri_folding_factor <= 16, "FRI folding factor cannot be greater than 16");
-
-        assert!(fri_max_remainder_size.is_power_of_two(), "FRI max remainder size must be a power of 2");
-        assert!(fri_max_remainder_size >= 32, "FRI max remainder size cannot be smaller than 32");
-        assert!(fri_max_remainder_size <= 1024, "FRI max remainder size cannot be greater than 1024");
+        if let Err(msg) = validate_params(
+            num_queries,
+            blowup_factor,
+            grinding_factor,
+            fri_folding_factor,
+            fri_max_remainder_size,
+        ) {
+            panic!("{}", msg);
+        }
 
         ProofOptions {
             num_queries: num_queries as u8,
@@ -139,9 +176,52 @@ impl ProofOptions {
             field_extension,
             fri_folding_factor: fri_folding_factor as u8,
             fri_max_remainder_size: fri_max_remainder_size.trailing_zeros() as u8,
+            transcript_domain: Vec::new(),
+            domain_offset: None,
         }
     }
 
+    /// Returns these proof options with the transcript domain separation tag set to `tag`.
+    ///
+    /// The tag is absorbed into the seed of the public coin before public inputs, and is
+    /// serialized as a part of the proof context. Thus, a proof generated with one tag cannot be
+    /// verified as a proof generated with a different tag, which prevents proofs from being
+    /// replayed across protocols. An empty tag (the default) leaves the transcript unchanged.
+    ///
+    /// # Panics
+    /// Panics if `tag` is longer than 255 bytes.
+    pub fn with_transcript_domain(mut self, tag: &[u8]) -> Self {
+        assert!(
+            tag.len() <= u8::MAX as usize,
+            "transcript domain tag cannot be longer than {} bytes, but was {} bytes",
+            u8::MAX,
+            tag.len()
+        );
+        self.transcript_domain = tag.to_vec();
+        self
+    }
+
+    /// Returns these proof options with the offset of the evaluation domain set to `offset`.
+    ///
+    /// The offset is interpreted as an element of the base field of the protocol. The low-degree
+    /// extension of the execution trace, constraint evaluations, and FRI layers are all computed
+    /// over cosets shifted by this offset, and the offset is serialized as a part of the proof
+    /// context. By default (i.e., if this method is not called), the offset is the primitive
+    /// element of the base field.
+    ///
+    /// The offset must be a canonical field element which is not in the multiplicative subgroup
+    /// of order 2^`TWO_ADICITY` of the field; since this depends on the field, the offset is
+    /// validated via [validate_domain_offset()](ProofOptions::validate_domain_offset) when an AIR
+    /// is instantiated with these options, and when a proof is verified.
+    ///
+    /// # Panics
+    /// Panics if `offset` is zero.
+    pub fn with_domain_offset(mut self, offset: u64) -> Self {
+        assert!(offset != 0, "domain offset cannot be zero");
+        self.domain_offset = Some(offset);
+        self
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -194,32 +274,98 @@ impl ProofOptions {
         self.field_extension
     }
 
+    /// Returns the transcript domain separation tag for a STARK proof.
+    ///
+    /// The tag is empty unless it was set via
+    /// [with_transcript_domain()](ProofOptions::with_transcript_domain).
+    pub fn transcript_domain(&self) -> &[u8] {
+        &self.transcript_domain
+    }
+
     /// Returns the offset by which the low-degree extension domain is shifted in relation to the
     /// trace domain.
     ///
     /// Currently, this is hard-coded to the primitive element of the underlying base field.
     pub fn domain_offset<B: StarkField>(&self) -> B {
-        B::GENERATOR
+        match self.domain_offset {
+            Some(offset) => B::from(offset),
+            None => B::GENERATOR,
+        }
+    }
+
+    /// Checks whether the domain offset specified by these options is valid for the field `B`.
+    ///
+    /// The default offset is always valid. A custom offset is valid if it is a canonical element
+    /// of `B` and is not in the multiplicative subgroup of order 2^`TWO_ADICITY`; this ensures
+    /// that the shifted evaluation domains are disjoint from the trace domain and from each other.
+    ///
+    /// # Errors
+    /// Returns an error describing the problem if the offset is not valid.
+    pub fn validate_domain_offset<B: StarkField>(&self) -> Result<(), String> {
+        let offset = match self.domain_offset {
+            Some(offset) => offset,
+            None => return Ok(()),
+        };
+        if B::PositiveInteger::from(offset) >= B::MODULUS {
+            return Err(format!(
+                "domain offset {} is not a canonical field element",
+                offset
+            ));
+        }
+        let mut value = B::from(offset);
+        for _ in 0..B::TWO_ADICITY {
+            value = value.square();
+        }
+        if value == B::ONE {
+            return Err(format!(
+                "domain offset {} is in the multiplicative subgroup of order 2^{}",
+                offset,
+                B::TWO_ADICITY
+            ));
+        }
+        Ok(())
     }
 
     /// Returns options for FRI protocol instantiated with parameters from this proof options.
     pub fn to_fri_options(&self) -> FriOptions {
         let folding_factor = self.fri_folding_factor as usize;
         let max_remainder_size = 2usize.pow(self.fri_max_remainder_size as u32);
-        FriOptions::new(self.blowup_factor(), folding_factor, max_remainder_size)
+        let options = FriOptions::new(self.blowup_factor(), folding_factor, max_remainder_size);
+        match self.domain_offset {
+            Some(offset) => options.with_domain_offset(offset),
+            None => options,
+        }
     }
 }
 
 impl Serializable for ProofOptions {
     /// Serializes `self` and writes the resulting bytes into the `target`.
+    ///
+    /// The transcript domain tag is written only if it is not empty, and its presence is signaled
+    /// via a flag in the grinding factor byte. Thus, options without a tag are serialized in the
+    /// same way as before tags were introduced.
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
         target.write_u8(self.num_queries);
         target.write_u8(self.blowup_factor);
-        target.write_u8(self.grinding_factor);
+        let mut grinding_factor = self.grinding_factor;
+        if !self.transcript_domain.is_empty() {
+            grinding_factor |= TRANSCRIPT_DOMAIN_FLAG;
+        }
+        if self.domain_offset.is_some() {
+            grinding_factor |= DOMAIN_OFFSET_FLAG;
+        }
+        target.write_u8(grinding_factor);
         target.write(self.hash_fn);
         target.write(self.field_extension);
         target.write_u8(self.fri_folding_factor);
         target.write_u8(self.fri_max_remainder_size);
+        if !self.transcript_domain.is_empty() {
+            target.write_u8(self.transcript_domain.len() as u8);
+            target.write_u8_slice(&self.transcript_domain);
+        }
+        if let Some(offset) = self.domain_offset {
+            target.write_u64(offset);
+        }
     }
 }
 
@@ -229,16 +375,117 @@ impl Deserializable for ProofOptions {
     /// # Errors
     /// Returns an error of a valid proof options could not be read from the specified `source`.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
-        Ok(ProofOptions::new(
-            source.read_u8()? as usize,
-            source.read_u8()? as usize,
-            source.read_u8()? as u32,
-            HashFunction::read_from(source)?,
-            FieldExtension::read_from(source)?,
-            source.read_u8()? as usize,
-            2usize.pow(source.read_u8()? as u32),
-        ))
+        // read and validate all values before building the options, since the constructor
+        // panics on invalid values
+        let num_queries = source.read_u8()? as usize;
+        let blowup_factor = source.read_u8()? as usize;
+        let flags = source.read_u8()?;
+        let grinding_factor = (flags & !(TRANSCRIPT_DOMAIN_FLAG | DOMAIN_OFFSET_FLAG)) as u32;
+        let hash_fn = HashFunction::read_from(source)?;
+        let field_extension = FieldExtension::read_from(source)?;
+        let fri_folding_factor = source.read_u8()? as usize;
+        let fri_max_remainder_size_log2 = source.read_u8()?;
+        let fri_max_remainder_size = 2usize
+            .checked_pow(fri_max_remainder_size_log2 as u32)
+            .ok_or_else(|| {
+                DeserializationError::InvalidValue(format!(
+                    "FRI max remainder size of 2^{} is too large",
+                    fri_max_remainder_size_log2
+                ))
+            })?;
+        validate_params(
+            num_queries,
+            blowup_factor,
+            grinding_factor,
+            fri_folding_factor,
+            fri_max_remainder_size,
+        )
+        .map_err(DeserializationError::InvalidValue)?;
+
+        let mut options = ProofOptions::new(
+            num_queries,
+            blowup_factor,
+            grinding_factor,
+            hash_fn,
+            field_extension,
+            fri_folding_factor,
+            fri_max_remainder_size,
+        );
+
+        if flags & TRANSCRIPT_DOMAIN_FLAG != 0 {
+            let tag_len = source.read_u8()? as usize;
+            if tag_len == 0 {
+                return Err(DeserializationError::InvalidValue(
+                    "transcript domain tag cannot be empty".to_string(),
+                ));
+            }
+            let tag = source.read_u8_vec(tag_len)?;
+            options = options.with_transcript_domain(&tag);
+        }
+
+        if flags & DOMAIN_OFFSET_FLAG != 0 {
+            let offset = source.read_u64()?;
+            if offset == 0 {
+                return Err(DeserializationError::InvalidValue(
+                    "domain offset cannot be zero".to_string(),
+                ));
+            }
+            options = options.with_domain_offset(offset);
+        }
+
+        Ok(options)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Checks whether the specified values are valid parameters of proof options, and returns a
+/// description of the first invalid parameter if they are not.
+fn validate_params(
+    num_queries: usize,
+    blowup_factor: usize,
+    grinding_factor: u32,
+    fri_folding_factor: usize,
+    fri_max_remainder_size: usize,
+) -> Result<(), String> {
+    if !(MIN_NUM_QUERIES..=MAX_NUM_QUERIES).contains(&num_queries) {
+        return Err(format!(
+            "number of queries must be between {} and {}, but was {}",
+            MIN_NUM_QUERIES, MAX_NUM_QUERIES, num_queries
+        ));
+    }
+    if !blowup_factor.is_power_of_two()
+        || !(MIN_BLOWUP_FACTOR..=MAX_BLOWUP_FACTOR).contains(&blowup_factor)
+    {
+        return Err(format!(
+            "blowup factor must be a power of two between {} and {}, but was {}",
+            MIN_BLOWUP_FACTOR, MAX_BLOWUP_FACTOR, blowup_factor
+        ));
+    }
+    if grinding_factor > MAX_GRINDING_FACTOR {
+        return Err(format!(
+            "grinding factor cannot be greater than {}, but was {}",
+            MAX_GRINDING_FACTOR, grinding_factor
+        ));
+    }
+    if !fri_folding_factor.is_power_of_two()
+        || !(MIN_FRI_FOLDING_FACTOR..=MAX_FRI_FOLDING_FACTOR).contains(&fri_folding_factor)
+    {
+        return Err(format!(
+            "FRI folding factor must be a power of two between {} and {}, but was {}",
+            MIN_FRI_FOLDING_FACTOR, MAX_FRI_FOLDING_FACTOR, fri_folding_factor
+        ));
+    }
+    if !fri_max_remainder_size.is_power_of_two()
+        || !(MIN_FRI_REMAINDER_SIZE..=MAX_FRI_REMAINDER_SIZE).contains(&fri_max_remainder_size)
+    {
+        return Err(format!(
+            "FRI max remainder size must be a power of two between {} and {}, but was {}",
+            MIN_FRI_REMAINDER_SIZE, MAX_FRI_REMAINDER_SIZE, fri_max_remainder_size
+        ));
     }
+    Ok(())
 }
 
 // FIELD EXTENSION IMPLEMENTATION
@@ -317,3 +564,174 @@ impl Deserializable for HashFunction {
         }
     }
 }
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{FieldExtension, HashFunction, ProofOptions};
+    use math::{
+        fields::{f128::BaseElement, f62},
+        StarkField,
+    };
+    use utils::{Deserializable, Serializable, SliceReader};
+
+    #[test]
+    fn proof_options_serialization() {
+        // options without a transcript domain tag are serialized without any extra bytes
+        let options = build_options();
+        let bytes = options.to_bytes();
+        assert_eq!(vec![28, 8, 16, 2, 2, 4, 8], bytes);
+        let result = ProofOptions::read_from(&mut SliceReader::new(&bytes)).unwrap();
+        assert_eq!(options, result);
+
+        // the tag is appended after other options
+        let options = build_options().with_transcript_domain(b"test");
+        let bytes = options.to_bytes();
+        assert_eq!(
+            vec![28, 8, 0x90, 2, 2, 4, 8, 4, b't', b'e', b's', b't'],
+            bytes
+        );
+        let result = ProofOptions::read_from(&mut SliceReader::new(&bytes)).unwrap();
+        assert_eq!(options, result);
+        assert_eq!(b"test", result.transcript_domain());
+
+        // a set flag with an empty tag is rejected
+        let bytes = vec![28, 8, 0x90, 2, 2, 4, 8, 0];
+        assert!(ProofOptions::read_from(&mut SliceReader::new(&bytes)).is_err());
+    }
+
+    #[test]
+    fn proof_options_domain_offset_serialization() {
+        // the offset is appended after other options and the tag
+        let options = build_options().with_domain_offset(5);
+        let bytes = options.to_bytes();
+        assert_eq!(vec![28, 8, 0x50, 2, 2, 4, 8, 5, 0, 0, 0, 0, 0, 0, 0], bytes);
+        let result = ProofOptions::read_from(&mut SliceReader::new(&bytes)).unwrap();
+        assert_eq!(options, result);
+        assert_eq!(BaseElement::new(5), result.domain_offset());
+        assert_eq!(
+            5,
+            result
+                .to_fri_options()
+                .domain_offset::<BaseElement>()
+                .as_int()
+        );
+
+        let options = options.with_transcript_domain(b"a");
+        let bytes = options.to_bytes();
+        assert_eq!(
+            vec![28, 8, 0xd0, 2, 2, 4, 8, 1, b'a', 5, 0, 0, 0, 0, 0, 0, 0],
+            bytes
+        );
+        let result = ProofOptions::read_from(&mut SliceReader::new(&bytes)).unwrap();
+        assert_eq!(options, result);
+
+        // a zero offset is rejected
+        let bytes = vec![28, 8, 0x50, 2, 2, 4, 8, 0, 0, 0, 0, 0, 0, 0, 0];
+        assert!(ProofOptions::read_from(&mut SliceReader::new(&bytes)).is_err());
+    }
+
+    #[test]
+    fn proof_options_invalid_serialization() {
+        // values outside of the ranges accepted by the constructor are rejected
+        let bytes = build_options().to_bytes();
+        for (i, value) in [
+            (0, 0),
+            (0, 129),
+            (1, 2),
+            (1, 12),
+            (2, 33),
+            (5, 2),
+            (5, 32),
+            (6, 4),
+        ] {
+            let mut bytes = bytes.clone();
+            bytes[i] = value;
+            assert!(ProofOptions::read_from(&mut SliceReader::new(&bytes)).is_err());
+        }
+
+        // the remainder size is checked before it is computed
+        let mut bytes = bytes;
+        bytes[6] = 200;
+        assert!(ProofOptions::read_from(&mut SliceReader::new(&bytes)).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "blowup factor must be a power of two between 4 and 128, but was 2")]
+    fn proof_options_invalid_blowup_factor() {
+        ProofOptions::new(
+            28,
+            2,
+            0,
+            HashFunction::Blake3_256,
+            FieldExtension::None,
+            4,
+            256,
+        );
+    }
+
+    #[test]
+    fn proof_options_validate_domain_offset() {
+        assert!(build_options()
+            .validate_domain_offset::<BaseElement>()
+            .is_ok());
+        let options = build_options().with_domain_offset(7);
+        assert!(options.validate_domain_offset::<BaseElement>().is_ok());
+
+        // elements of the 2-adic subgroup are rejected
+        let options = build_options().with_domain_offset(1);
+        assert_eq!(
+            Err("domain offset 1 is in the multiplicative subgroup of order 2^40".to_string()),
+            options.validate_domain_offset::<BaseElement>()
+        );
+        let root = f62::BaseElement::get_root_of_unity(4).as_int();
+        let options = build_options().with_domain_offset(root);
+        assert!(options
+            .validate_domain_offset::<f62::BaseElement>()
+            .is_err());
+
+        // non-canonical elements are rejected
+        let modulus = f62::BaseElement::MODULUS;
+        let options = build_options().with_domain_offset(modulus + 7);
+        assert_eq!(
+            Err(format!(
+                "domain offset {} is not a canonical field element",
+                modulus + 7
+            )),
+            options.validate_domain_offset::<f62::BaseElement>()
+        );
+    }
+
+    #[test]
+    fn proof_options_deserialization_invalid() {
+        // values which would make the constructor panic are rejected with an error
+        let invalid = [
+            vec![0, 8, 16, 2, 2, 4, 8],    // zero queries
+            vec![129, 8, 16, 2, 2, 4, 8],  // too many queries
+            vec![28, 6, 16, 2, 2, 4, 8],   // blowup factor is not a power of two
+            vec![28, 2, 16, 2, 2, 4, 8],   // blowup factor is too small
+            vec![28, 8, 33, 2, 2, 4, 8],   // grinding factor is too large
+            vec![28, 8, 16, 2, 2, 2, 8],   // folding factor is too small
+            vec![28, 8, 16, 2, 2, 12, 8],  // folding factor is not a power of two
+            vec![28, 8, 16, 2, 2, 4, 4],   // max remainder size is too small
+            vec![28, 8, 16, 2, 2, 4, 255], // max remainder size is too large
+        ];
+        for bytes in invalid.iter() {
+            assert!(ProofOptions::read_from(&mut SliceReader::new(bytes)).is_err());
+        }
+    }
+
+    fn build_options() -> ProofOptions {
+        ProofOptions::new(
+            28,
+            8,
+            16,
+            HashFunction::Blake3_256,
+            FieldExtension::Quadratic,
+            4,
+            256,
+        )
+    }
+}
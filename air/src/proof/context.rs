@@ -13,7 +13,7 @@ use utils::{
 // PROOF CONTEXT
 // ================================================================================================
 /// Basic metadata about a specific execution of a computation.
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct Context {
     trace_width: u8,
     trace_length: u8, // stored as power of two
@@ -129,6 +129,13 @@ impl Deserializable for Context {
 
         // read and validate trace length
         let trace_length = source.read_u8()?;
+        if trace_length as u32 >= usize::BITS {
+            return Err(DeserializationError::InvalidValue(format!(
+                "Trace length cannot be greater than 2^{}, but had 2^{}",
+                usize::BITS - 1,
+                trace_length
+            )));
+        }
         if 2_usize.pow(trace_length as u32) < TraceInfo::MIN_TRACE_LENGTH {
             return Err(DeserializationError::InvalidValue(format!(
                 "Trace length cannot be smaller than {}, but had {}",
@@ -30,6 +30,13 @@ pub use ood_frame::OodFrame;
 
 const GRINDING_CONTRIBUTION_FLOOR: u32 = 80;
 
+/// Smallest value of the proximity parameter `m` for which the soundness bound of the
+/// ethSTARK paper holds.
+const MIN_PROXIMITY_PARAMETER: u32 = 3;
+
+/// Number of fractional bits computed by [log2_f64()].
+const LOG2_PRECISION_BITS: u32 = 32;
+
 // STARK PROOF
 // ================================================================================================
 /// A proof generated by Winterfell prover.
@@ -44,7 +51,13 @@ const GRINDING_CONTRIBUTION_FLOOR: u32 = 80;
 ///
 /// To estimate soundness of a proof (in bits), [security_level()](StarkProof::security_level)
 /// function can be used.
-#[derive(Debug, Clone, Eq, PartialEq)]
+///
+/// Every proof has a single serialized form, returned by [to_bytes()](StarkProof::to_bytes): all
+/// components of a proof are stored as length-prefixed byte vectors with fixed-width lengths, and
+/// proofs with trailing bytes are rejected by [from_bytes()](StarkProof::from_bytes). Thus, two
+/// proofs are equal (and have the same hash) if and only if their serialized bytes are equal, and
+/// proofs can be deduplicated by either.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct StarkProof {
     /// Basic metadata about the execution of the computation described by this proof.
     pub context: Context,
@@ -105,8 +118,11 @@ impl StarkProof {
                 self.lde_domain_size() as u64,
             )
         } else {
-            // TODO: implement provable security estimation
-            unimplemented!("proven security estimation has not been implement yet")
+            get_proven_security(
+                self.context.options(),
+                self.context.num_modulus_bits(),
+                self.lde_domain_size() as u64,
+            )
         }
     }
 
@@ -114,6 +130,11 @@ impl StarkProof {
     // --------------------------------------------------------------------------------------------
 
     /// Serializes this proof into a vector of bytes.
+    ///
+    /// The encoding is canonical: equal proofs always serialize into identical bytes, and reading
+    /// the bytes via [from_bytes()](StarkProof::from_bytes) and serializing the result again
+    /// yields identical bytes. Thus, the serialized proof (or its hash) can be used as a key for
+    /// storing and deduplicating proofs.
     pub fn to_bytes(&self) -> Vec<u8> {
         let mut result = Vec::new();
         self.context.write_into(&mut result);
@@ -178,3 +199,183 @@ fn get_conjectured_security(
         hash_fn_security,
     )
 }
+
+/// Computes proven security level for the specified proof parameters.
+///
+/// The estimate is based on the soundness bound from Theorem 2 of the ethSTARK paper
+/// (<https://eprint.iacr.org/2021/582>). The proximity parameter `m` trades the security of
+/// the commit phase for the security of the query phase; the value of `m` which maximizes the
+/// overall security is selected, and the result is capped by collision resistance of the hash
+/// function.
+fn get_proven_security(options: &ProofOptions, base_field_bits: u32, lde_domain_size: u64) -> u32 {
+    // compute max security we can get for a given hash function
+    let hash_fn_security = options.hash_fn().collision_resistance();
+
+    // the security is a minimum of terms which either increase or decrease with m, and thus,
+    // once it stops improving, larger values of m cannot improve it either
+    let mut m = MIN_PROXIMITY_PARAMETER;
+    let mut security = get_proven_security_for_m(options, base_field_bits, lde_domain_size, m);
+    loop {
+        let next = get_proven_security_for_m(options, base_field_bits, lde_domain_size, m + 1);
+        if next <= security {
+            break;
+        }
+        security = next;
+        m += 1;
+    }
+
+    if security < 0.0 {
+        return 0;
+    }
+    cmp::min(security as u32, hash_fn_security)
+}
+
+/// Computes proven security level (in bits, not rounded) for the specified proof parameters and
+/// the proximity parameter `m`.
+///
+/// The soundness error is bounded by the sum of four terms, and each term is computed below as
+/// the number of bits of security it provides:
+/// * ALI error: $(m + 1/2) / (\sqrt{\rho} \cdot |F|)$.
+/// * FRI commit phase error: $(m + 1/2)^7 \cdot |D|^2 / (3 \cdot \rho^{3/2} \cdot |F|)$.
+/// * FRI folding error: $(2m + 1) \cdot (|D| + 1) \cdot \sum a_i / (\sqrt{\rho} \cdot |F|)$,
+///   where $a_i$ are the folding factors of FRI layers; layers are folded until the remainder
+///   fits into the max remainder size, and the remainder itself is checked directly.
+/// * FRI query phase error: $((1 + 1/2m) \cdot \sqrt{\rho})^s \cdot 2^{-g}$, where $s$ is the
+///   number of queries and $g$ is the grinding factor.
+///
+/// Here, $\rho$ is the inverse of the blowup factor, $|F|$ is the size of the field from which
+/// random coefficients are drawn, and $|D|$ is the size of the LDE domain. Since the sum of four
+/// terms is at most 4x the largest term, 2 bits are subtracted from the weakest term.
+fn get_proven_security_for_m(
+    options: &ProofOptions,
+    base_field_bits: u32,
+    lde_domain_size: u64,
+    m: u32,
+) -> f64 {
+    let field_bits = (base_field_bits * options.field_extension().degree()) as f64;
+    let blowup_bits = log2(options.blowup_factor()) as f64;
+    let lde_domain_bits = lde_domain_size.trailing_zeros() as f64;
+    let m = m as f64;
+
+    let ali_security = field_bits - log2_f64(m + 0.5) - blowup_bits / 2.0;
+
+    let commit_security = field_bits + log2_f64(3.0)
+        - 7.0 * log2_f64(m + 0.5)
+        - 1.5 * blowup_bits
+        - 2.0 * lde_domain_bits;
+
+    let fri_options = options.to_fri_options();
+    let num_fri_layers = fri_options.num_fri_layers(lde_domain_size as usize);
+    let folding_sum = cmp::max(num_fri_layers * fri_options.folding_factor(), 1) as f64;
+    let folding_security = field_bits
+        - log2_f64(2.0 * m + 1.0)
+        - log2_f64(lde_domain_size as f64 + 1.0)
+        - log2_f64(folding_sum)
+        - blowup_bits / 2.0;
+
+    let security_per_query = blowup_bits / 2.0 - log2_f64(1.0 + 1.0 / (2.0 * m));
+    let query_security =
+        security_per_query * options.num_queries() as f64 + options.grinding_factor() as f64;
+
+    let security = [commit_security, folding_security, query_security]
+        .iter()
+        .fold(ali_security, |a, &b| if b < a { b } else { a });
+    security - 2.0
+}
+
+/// Returns binary logarithm of the specified positive value.
+///
+/// The integer part of the result is read from the exponent of `x`, and fractional bits are
+/// computed by repeatedly squaring the mantissa; this does not rely on `std`, and is accurate
+/// to [LOG2_PRECISION_BITS] fractional bits.
+fn log2_f64(x: f64) -> f64 {
+    debug_assert!(x.is_normal() && x > 0.0, "cannot take logarithm of {}", x);
+    let bits = x.to_bits();
+    let exponent = ((bits >> 52) & 0x7ff) as i64 - 1023;
+
+    // set the exponent to 0 so that the mantissa is in [1, 2)
+    let mut mantissa = f64::from_bits((bits & 0x000f_ffff_ffff_ffff) | 0x3ff0_0000_0000_0000);
+    let mut result = exponent as f64;
+    let mut bit = 0.5;
+    for _ in 0..LOG2_PRECISION_BITS {
+        mantissa *= mantissa;
+        if mantissa >= 2.0 {
+            mantissa /= 2.0;
+            result += bit;
+        }
+        bit /= 2.0;
+    }
+    result
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{get_proven_security, log2_f64};
+    use crate::{FieldExtension, HashFunction, ProofOptions};
+
+    #[test]
+    fn log2_f64_values() {
+        assert_eq!(0.0, log2_f64(1.0));
+        assert_eq!(10.0, log2_f64(1024.0));
+        assert_eq!(-1.0, log2_f64(0.5));
+        assert!((log2_f64(3.0) - 1.584_962_500_721_156).abs() < 1e-9);
+        assert!((log2_f64(3.5) - 1.807_354_922_057_604).abs() < 1e-9);
+    }
+
+    #[test]
+    fn proven_security_known_configurations() {
+        // 128-bit field, no grinding; the selected m balances commit and query phase security
+        let options = ProofOptions::new(
+            27,
+            8,
+            0,
+            HashFunction::Blake3_256,
+            FieldExtension::None,
+            8,
+            256,
+        );
+        assert_eq!(38, get_proven_security(&options, 128, 1 << 20));
+
+        // 62-bit field with quadratic extension; limited by the FRI commit phase
+        let options = ProofOptions::new(
+            42,
+            16,
+            16,
+            HashFunction::Blake3_256,
+            FieldExtension::Quadratic,
+            4,
+            256,
+        );
+        assert_eq!(60, get_proven_security(&options, 62, 1 << 22));
+
+        // 64-bit field with cubic extension; the result is below collision resistance of the
+        // hash function, and so it is not capped
+        let options = ProofOptions::new(
+            48,
+            8,
+            20,
+            HashFunction::Blake3_192,
+            FieldExtension::Cubic,
+            16,
+            64,
+        );
+        assert_eq!(89, get_proven_security(&options, 64, 1 << 18));
+    }
+
+    #[test]
+    fn proven_security_capped_by_hash_function() {
+        let options = ProofOptions::new(
+            80,
+            32,
+            0,
+            HashFunction::Blake3_192,
+            FieldExtension::Cubic,
+            16,
+            64,
+        );
+        assert_eq!(96, get_proven_security(&options, 64, 1 << 15));
+    }
+}
@@ -23,7 +23,7 @@ use utils::{
 ///
 /// Internally, the evaluations are stored as a sequence of bytes. Thus, to retrieve the
 /// evaluations, [parse()](OodFrame::parse) function should be used.
-#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
 pub struct OodFrame {
     trace_at_z1: Vec<u8>,
     trace_at_z2: Vec<u8>,
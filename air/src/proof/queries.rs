@@ -26,7 +26,7 @@ use utils::{
 /// Internally, all Merkle paths and query values are stored as a sequence of bytes. Thus, to
 /// retrieve query values and the corresponding Merkle authentication paths,
 /// [parse()](Queries::parse) function should be used.
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct Queries {
     paths: Vec<u8>,
     values: Vec<u8>,
@@ -41,7 +41,10 @@ pub use merkle::{build_merkle_nodes, BatchMerkleProof, MerkleTree};
 pub use merkle::concurrent;
 
 mod random;
-pub use random::RandomCoin;
+pub use random::{PublicCoin, RandomCoin};
+
+#[cfg(feature = "transcript-capture")]
+pub use random::transcript;
 
 mod errors;
 pub use errors::{MerkleTreeError, RandomCoinError};
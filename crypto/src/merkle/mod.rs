@@ -3,9 +3,12 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::{errors::MerkleTreeError, hash::Hasher};
+use crate::{
+    errors::MerkleTreeError,
+    hash::{ElementHasher, Hasher},
+};
 use core::slice;
-use math::log2;
+use math::{log2, FieldElement};
 use utils::collections::{BTreeMap, BTreeSet, Vec};
 
 mod proofs;
@@ -58,6 +61,12 @@ mod tests;
 /// To verify proofs, [MerkleTree::verify()] and [MerkleTree::verify_batch()] functions can be
 /// used respectively.
 ///
+/// A tree can also be built directly from rows of field elements via
+/// [MerkleTree::build_from_rows()]; each row is hashed into a leaf in the same way the prover
+/// hashes rows of an extended execution trace. Rows of such a tree are opened and verified via
+/// the batch methods above; the leaves opened by a batch proof can be compared against the hashed
+/// rows via [BatchMerkleProof::leaves()].
+///
 /// # Examples
 /// ```
 /// # use winter_crypto::{MerkleTree, Hasher, hashers::Blake3_256};
@@ -126,6 +135,56 @@ impl<H: Hasher> MerkleTree<H> {
         Ok(MerkleTree { nodes, leaves })
     }
 
+    /// Returns new Merkle tree with leaves computed by hashing each of the provided `rows` using
+    /// hash function specified by the `H` generic parameter.
+    ///
+    /// Rows are hashed via [ElementHasher::hash_elements()], which is the same way the prover
+    /// hashes rows of an extended execution trace; thus, a tree built from the rows of an extended
+    /// trace has the same root as the trace commitment in a proof.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * Fewer than two rows were provided.
+    /// * Number of rows is not a power of two.
+    ///
+    /// # Examples
+    /// ```
+    /// # use winter_crypto::{MerkleTree, ElementHasher, hashers::Blake3_256};
+    /// # use math::fields::f128::BaseElement;
+    /// type Blake3 = Blake3_256<BaseElement>;
+    ///
+    /// // commit to 8 rows of 4 elements each
+    /// let rows = (0..8u128)
+    ///     .map(|i| (0..4).map(|j| BaseElement::new(i * 4 + j)).collect::<Vec<_>>())
+    ///     .collect::<Vec<_>>();
+    /// let tree = MerkleTree::<Blake3>::build_from_rows(&rows).unwrap();
+    ///
+    /// // open rows 5 and 2
+    /// let indexes = [5, 2];
+    /// let proof = tree.prove_batch(&indexes).unwrap();
+    ///
+    /// // verify the opening against the hashed rows
+    /// let leaves = indexes
+    ///     .iter()
+    ///     .map(|&i| Blake3::hash_elements(&rows[i]))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(leaves, proof.leaves());
+    /// assert!(MerkleTree::verify_batch(tree.root(), &indexes, &proof).is_ok());
+    /// assert!(MerkleTree::verify_batch(tree.root(), &[2, 5], &proof).is_err());
+    /// ```
+    pub fn build_from_rows<E, R>(rows: &[R]) -> Result<Self, MerkleTreeError>
+    where
+        E: FieldElement,
+        H: ElementHasher<BaseField = E::BaseField>,
+        R: AsRef<[E]>,
+    {
+        let leaves = rows
+            .iter()
+            .map(|row| H::hash_elements(row.as_ref()))
+            .collect();
+        Self::new(leaves)
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -7,7 +7,7 @@ use crate::{errors::MerkleTreeError, Hasher};
 use utils::{
     collections::{BTreeMap, Vec},
     string::ToString,
-    ByteReader, Deserializable, DeserializationError, Serializable,
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
 };
 
 // CONSTANTS
@@ -27,6 +27,12 @@ pub(super) const MAX_PATHS: usize = 255;
 ///
 /// Currently, at most 255 paths can be aggregated into a single proof. This limitation is
 /// imposed primarily for serialization purposes.
+///
+/// A batch proof is serialized in the same way as it is encoded within a STARK proof: only the
+/// internal nodes are written (see [BatchMerkleProof::serialize_nodes()]), while the leaves and
+/// the depth of the tree are expected to be known to the party reading the proof, and are passed
+/// to [BatchMerkleProof::deserialize()]. For this reason, batch proofs implement [Serializable]
+/// but not [Deserializable].
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BatchMerkleProof<H: Hasher> {
     pub(super) leaves: Vec<H::Digest>,
@@ -115,6 +121,13 @@ impl<H: Hasher> BatchMerkleProof<H> {
         }
     }
 
+    /// Returns the leaves opened by this proof.
+    ///
+    /// Leaves are listed in the same order as the indexes for which the proof was generated.
+    pub fn leaves(&self) -> &[H::Digest] {
+        &self.leaves
+    }
+
     /// Computes a node to which all Merkle paths aggregated in this proof resolve.
     ///
     /// # Errors
@@ -335,6 +348,17 @@ impl<H: Hasher> BatchMerkleProof<H> {
     }
 }
 
+impl<H: Hasher> Serializable for BatchMerkleProof<H> {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    ///
+    /// The encoding is the same as the one produced by [BatchMerkleProof::serialize_nodes()]. The
+    /// leaves and the depth of the tree are not written, and thus, the proof can be read back only
+    /// via [BatchMerkleProof::deserialize()].
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8_slice(&self.serialize_nodes());
+    }
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -6,6 +6,7 @@
 use super::*;
 use math::fields::f128::BaseElement;
 use proptest::prelude::*;
+use utils::{Serializable, SliceReader};
 
 type Digest256 = crate::hash::ByteDigest<32>;
 type Blake3_256 = crate::hash::Blake3_256<BaseElement>;
@@ -232,6 +233,70 @@ fn verify_batch() {
     assert!(MerkleTree::verify_batch(tree.root(), &[0, 1, 2, 3, 4, 5, 6, 7], &proof).is_ok());
 }
 
+#[test]
+fn build_from_rows() {
+    let rows = build_rows(8, 3);
+    let tree = MerkleTree::<Blake3_256>::build_from_rows(&rows).unwrap();
+    let leaves = rows
+        .iter()
+        .map(|row| Blake3_256::hash_elements(row))
+        .collect::<Vec<_>>();
+    assert_eq!(leaves, tree.leaves());
+
+    let result = MerkleTree::<Blake3_256>::build_from_rows(&rows[..6]);
+    assert_eq!(
+        Err(MerkleTreeError::NumberOfLeavesNotPowerOfTwo(6)),
+        result.map(|_| ())
+    );
+}
+
+#[test]
+fn prove_batch_from_rows_n_verify() {
+    let rows = build_rows(16, 4);
+    let tree = MerkleTree::<Blake3_256>::build_from_rows(&rows).unwrap();
+    let indexes = [11, 2, 3, 14];
+    let leaves = indexes
+        .iter()
+        .map(|&i| Blake3_256::hash_elements(&rows[i]))
+        .collect::<Vec<_>>();
+
+    let proof = tree.prove_batch(&indexes).unwrap();
+    assert_eq!(leaves, proof.leaves());
+    assert!(MerkleTree::verify_batch(tree.root(), &indexes, &proof).is_ok());
+
+    // serialized proof is the same as the encoding of the proof within a STARK proof
+    let bytes = proof.to_bytes();
+    assert_eq!(proof.serialize_nodes(), bytes);
+    let mut reader = SliceReader::new(&bytes);
+    let parsed = BatchMerkleProof::<Blake3_256>::deserialize(&mut reader, leaves.clone(), 4);
+    let parsed = parsed.unwrap();
+    assert_eq!(proof, parsed);
+    assert!(MerkleTree::verify_batch(tree.root(), &indexes, &parsed).is_ok());
+
+    // wrong leaf
+    let mut wrong_leaves = leaves.clone();
+    wrong_leaves[1] = tree.leaves()[5];
+    let mut reader = SliceReader::new(&bytes);
+    let parsed = BatchMerkleProof::<Blake3_256>::deserialize(&mut reader, wrong_leaves, 4);
+    assert_eq!(
+        Err(MerkleTreeError::InvalidProof),
+        MerkleTree::verify_batch(tree.root(), &indexes, &parsed.unwrap())
+    );
+
+    // wrong index order
+    assert_eq!(
+        Err(MerkleTreeError::InvalidProof),
+        MerkleTree::verify_batch(tree.root(), &[2, 11, 3, 14], &proof)
+    );
+
+    // wrong root
+    let other_tree = MerkleTree::<Blake3_256>::build_from_rows(&build_rows(16, 5)).unwrap();
+    assert_eq!(
+        Err(MerkleTreeError::InvalidProof),
+        MerkleTree::verify_batch(other_tree.root(), &indexes, &proof)
+    );
+}
+
 proptest! {
     #[test]
     fn prove_n_verify(tree in random_blake3_merkle_tree(128),
@@ -277,6 +342,16 @@ fn hash_2x1(v1: Digest256, v2: Digest256) -> Digest256 {
     Blake3_256::merge(&[v1, v2])
 }
 
+fn build_rows(num_rows: usize, num_cols: usize) -> Vec<Vec<BaseElement>> {
+    (0..num_rows)
+        .map(|i| {
+            (0..num_cols)
+                .map(|j| BaseElement::new((i * num_cols + j) as u128))
+                .collect()
+        })
+        .collect()
+}
+
 pub fn random_blake3_merkle_tree(
     leave_count: usize,
 ) -> impl Strategy<Value = MerkleTree<Blake3_256>> {
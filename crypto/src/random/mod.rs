@@ -8,6 +8,9 @@ use core::{convert::TryInto, marker::PhantomData};
 use math::{FieldElement, StarkField};
 use utils::collections::Vec;
 
+#[cfg(feature = "transcript-capture")]
+pub mod transcript;
+
 // RANDOM COIN
 // ================================================================================================
 
@@ -63,6 +66,8 @@ where
 {
     seed: H::Digest,
     counter: u64,
+    #[cfg(feature = "transcript-capture")]
+    label: &'static str,
     _base_field: PhantomData<B>,
 }
 
@@ -71,14 +76,41 @@ impl<B: StarkField, H: Hasher> RandomCoin<B, H> {
     // --------------------------------------------------------------------------------------------
     /// Returns a new random coin instantiated with the provided `seed`.
     pub fn new(seed: &[u8]) -> Self {
+        #[cfg(feature = "transcript-capture")]
+        transcript::record(
+            transcript::TranscriptEventKind::Absorb,
+            "initial seed",
+            seed,
+        );
+
         let seed = H::hash(seed);
         RandomCoin {
             seed,
             counter: 0,
+            #[cfg(feature = "transcript-capture")]
+            label: "",
             _base_field: PhantomData,
         }
     }
 
+    // LABELING
+    // --------------------------------------------------------------------------------------------
+
+    /// Sets the label attached to all subsequent reseeding and draw operations of this coin, and
+    /// returns the coin.
+    ///
+    /// Labels do not affect values drawn from the coin; they only name the events recorded into
+    /// captured transcripts when `transcript-capture` feature is enabled (see
+    /// `transcript::capture_transcript()`). Without this feature, this method has no effect.
+    #[allow(unused_variables)]
+    pub fn label(&mut self, label: &'static str) -> &mut Self {
+        #[cfg(feature = "transcript-capture")]
+        {
+            self.label = label;
+        }
+        self
+    }
+
     // RESEEDING
     // --------------------------------------------------------------------------------------------
 
@@ -103,6 +135,9 @@ impl<B: StarkField, H: Hasher> RandomCoin<B, H> {
     /// assert_ne!(e1, e2);
     /// ```
     pub fn reseed(&mut self, data: H::Digest) {
+        #[cfg(feature = "transcript-capture")]
+        self.record(transcript::TranscriptEventKind::Absorb, &data.as_bytes());
+
         self.seed = H::merge(&[self.seed, data]);
         self.counter = 0;
     }
@@ -129,6 +164,12 @@ impl<B: StarkField, H: Hasher> RandomCoin<B, H> {
     /// assert_ne!(e1, e2);
     /// ```
     pub fn reseed_with_int(&mut self, value: u64) {
+        #[cfg(feature = "transcript-capture")]
+        self.record(
+            transcript::TranscriptEventKind::Absorb,
+            &value.to_le_bytes(),
+        );
+
         self.seed = H::merge_with_int(self.seed, value);
         self.counter = 0;
     }
@@ -188,6 +229,11 @@ impl<B: StarkField, H: Hasher> RandomCoin<B, H> {
             // check if the bytes can be converted into a valid field element; if they can,
             // return; otherwise try again
             if let Some(element) = E::from_random_bytes(bytes) {
+                #[cfg(feature = "transcript-capture")]
+                self.record(
+                    transcript::TranscriptEventKind::Draw,
+                    E::elements_as_bytes(&[element]),
+                );
                 return Ok(element);
             }
         }
@@ -293,6 +339,15 @@ impl<B: StarkField, H: Hasher> RandomCoin<B, H> {
             ));
         }
 
+        #[cfg(feature = "transcript-capture")]
+        self.record(
+            transcript::TranscriptEventKind::Draw,
+            &values
+                .iter()
+                .flat_map(|&value| (value as u64).to_le_bytes())
+                .collect::<Vec<_>>(),
+        );
+
         Ok(values)
     }
 
@@ -304,4 +359,124 @@ impl<B: StarkField, H: Hasher> RandomCoin<B, H> {
         self.counter += 1;
         H::merge_with_int(self.seed, self.counter)
     }
+
+    /// Records an event with the current label into the transcript being captured, if any.
+    #[cfg(feature = "transcript-capture")]
+    fn record(&self, kind: transcript::TranscriptEventKind, data: &[u8]) {
+        transcript::record(kind, self.label, data);
+    }
+}
+
+// PUBLIC COIN
+// ================================================================================================
+
+/// Defines an interface for pseudo-random generators used to implement the Fiat-Shamir transform
+/// in STARK and FRI protocols.
+///
+/// A public coin is instantiated from a seed, can be reseeded with commitments sent by the
+/// prover, and is used to draw field elements and query positions. [RandomCoin] is the default
+/// implementation of this trait; alternative constructions (e.g., sponge-based coins) can be
+/// provided by implementing it.
+pub trait PublicCoin: Sized {
+    /// Base field for elements drawn from this coin.
+    type BaseField: StarkField;
+    /// Hash function used to reseed this coin.
+    type Hasher: Hasher;
+
+    /// Returns a new public coin instantiated with the provided `seed`.
+    fn new(seed: &[u8]) -> Self;
+
+    /// Sets the label attached to all subsequent reseeding and draw operations of this coin, and
+    /// returns the coin.
+    ///
+    /// Labels must not affect values drawn from the coin. By default, this method has no effect.
+    #[allow(unused_variables)]
+    fn label(&mut self, label: &'static str) -> &mut Self {
+        self
+    }
+
+    /// Reseeds the coin with the specified data.
+    fn reseed(&mut self, data: <Self::Hasher as Hasher>::Digest);
+
+    /// Reseeds the coin with the specified integer value.
+    fn reseed_with_int(&mut self, value: u64);
+
+    /// Returns the number of leading zeros in the current seed of the coin.
+    fn leading_zeros(&self) -> u32;
+
+    /// Returns the number of leading zeros the seed of the coin would have after reseeding it
+    /// with the specified integer value.
+    fn check_leading_zeros(&self, value: u64) -> u32;
+
+    /// Returns the next pseudo-random field element.
+    fn draw<E>(&mut self) -> Result<E, RandomCoinError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>;
+
+    /// Returns a vector of unique integers selected from the range [0, domain_size).
+    fn draw_integers(
+        &mut self,
+        num_values: usize,
+        domain_size: usize,
+    ) -> Result<Vec<usize>, RandomCoinError>;
+
+    /// Returns the next pair of pseudo-random field elements.
+    fn draw_pair<E>(&mut self) -> Result<(E, E), RandomCoinError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        Ok((self.draw()?, self.draw()?))
+    }
+
+    /// Returns the next triplet of pseudo-random field elements.
+    fn draw_triple<E>(&mut self) -> Result<(E, E, E), RandomCoinError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        Ok((self.draw()?, self.draw()?, self.draw()?))
+    }
+}
+
+impl<B: StarkField, H: Hasher> PublicCoin for RandomCoin<B, H> {
+    type BaseField = B;
+    type Hasher = H;
+
+    fn new(seed: &[u8]) -> Self {
+        RandomCoin::new(seed)
+    }
+
+    fn label(&mut self, label: &'static str) -> &mut Self {
+        RandomCoin::label(self, label)
+    }
+
+    fn reseed(&mut self, data: H::Digest) {
+        RandomCoin::reseed(self, data)
+    }
+
+    fn reseed_with_int(&mut self, value: u64) {
+        RandomCoin::reseed_with_int(self, value)
+    }
+
+    fn leading_zeros(&self) -> u32 {
+        RandomCoin::leading_zeros(self)
+    }
+
+    fn check_leading_zeros(&self, value: u64) -> u32 {
+        RandomCoin::check_leading_zeros(self, value)
+    }
+
+    fn draw<E>(&mut self) -> Result<E, RandomCoinError>
+    where
+        E: FieldElement<BaseField = B>,
+    {
+        RandomCoin::draw(self)
+    }
+
+    fn draw_integers(
+        &mut self,
+        num_values: usize,
+        domain_size: usize,
+    ) -> Result<Vec<usize>, RandomCoinError> {
+        RandomCoin::draw_integers(self, num_values, domain_size)
+    }
 }
@@ -0,0 +1,209 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Contains utilities for capturing the Fiat-Shamir transcript of a protocol execution.
+//!
+//! When `transcript-capture` feature is enabled, every [RandomCoin](super::RandomCoin) records
+//! the data it absorbs and the values it draws, together with the label set via
+//! [RandomCoin::label()](super::RandomCoin::label), into the transcript captured on the current
+//! thread (if any). Transcripts captured on the prover and the verifier side of the same proof
+//! can then be compared event-by-event via [Transcript::diff()] to pinpoint the first place where
+//! the two sides diverge.
+
+use core::fmt;
+use std::cell::RefCell;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Maximum number of data bytes displayed for a single event.
+const MAX_DISPLAYED_BYTES: usize = 16;
+
+/// Number of events preceding the first difference displayed by [Transcript::diff()].
+const DIFF_CONTEXT: usize = 2;
+
+thread_local! {
+    static CAPTURED_EVENTS: RefCell<Option<Vec<TranscriptEvent>>> = RefCell::new(None);
+}
+
+// CAPTURE
+// ================================================================================================
+
+/// Executes `f` and returns its result together with the transcript of all public coin events
+/// recorded on the current thread during its execution.
+///
+/// Captures can be nested; events recorded within an inner capture are not included in the
+/// transcript of the outer capture.
+pub fn capture_transcript<R, F: FnOnce() -> R>(f: F) -> (R, Transcript) {
+    let outer = CAPTURED_EVENTS.with(|events| events.replace(Some(Vec::new())));
+    let result = f();
+    let events = CAPTURED_EVENTS.with(|events| events.replace(outer));
+    (
+        result,
+        Transcript {
+            events: events.unwrap_or_default(),
+        },
+    )
+}
+
+/// Records an event into the transcript being captured on the current thread, if any.
+pub(super) fn record(kind: TranscriptEventKind, label: &'static str, data: &[u8]) {
+    CAPTURED_EVENTS.with(|events| {
+        if let Some(events) = events.borrow_mut().as_mut() {
+            events.push(TranscriptEvent {
+                kind,
+                label,
+                data: data.to_vec(),
+            });
+        }
+    });
+}
+
+// TRANSCRIPT
+// ================================================================================================
+
+/// A sequence of events recorded by public coins during a protocol execution.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Transcript {
+    events: Vec<TranscriptEvent>,
+}
+
+impl Transcript {
+    /// Returns the events of this transcript in the order in which they were recorded.
+    pub fn events(&self) -> &[TranscriptEvent] {
+        &self.events
+    }
+
+    /// Returns a human-readable description of the first difference between this transcript
+    /// (on the left) and the `other` transcript (on the right), or `None` if the transcripts are
+    /// identical.
+    ///
+    /// The description lists a few events preceding the difference, followed by the differing
+    /// events of both transcripts.
+    pub fn diff(&self, other: &Transcript) -> Option<String> {
+        let index = self
+            .events
+            .iter()
+            .zip(other.events.iter())
+            .position(|(left, right)| left != right)
+            .unwrap_or_else(|| self.events.len().min(other.events.len()));
+        if index == self.events.len() && index == other.events.len() {
+            return None;
+        }
+
+        let mut result = format!("transcripts diverge at event {}:\n", index);
+        for event in self.events[index.saturating_sub(DIFF_CONTEXT)..index].iter() {
+            result.push_str(&format!("  {}\n", event));
+        }
+        let describe = |event: Option<&TranscriptEvent>| match event {
+            Some(event) => event.to_string(),
+            None => "<end of transcript>".into(),
+        };
+        result.push_str(&format!("- {}\n", describe(self.events.get(index))));
+        result.push_str(&format!("+ {}\n", describe(other.events.get(index))));
+        Some(result)
+    }
+}
+
+impl fmt::Display for Transcript {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, event) in self.events.iter().enumerate() {
+            writeln!(f, "{:>4}: {}", i, event)?;
+        }
+        Ok(())
+    }
+}
+
+// TRANSCRIPT EVENT
+// ================================================================================================
+
+/// Kind of an event recorded by a public coin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranscriptEventKind {
+    /// Data was absorbed into the coin (either as its initial seed or via reseeding).
+    Absorb,
+    /// A value was drawn from the coin.
+    Draw,
+}
+
+/// An event recorded by a public coin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranscriptEvent {
+    /// Kind of the event.
+    pub kind: TranscriptEventKind,
+    /// Label of the coin at the time the event was recorded.
+    pub label: &'static str,
+    /// Absorbed or drawn data.
+    pub data: Vec<u8>,
+}
+
+impl fmt::Display for TranscriptEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self.kind {
+            TranscriptEventKind::Absorb => "absorb",
+            TranscriptEventKind::Draw => "draw",
+        };
+        write!(f, "{:<6} {}: ", kind, self.label)?;
+        for byte in self.data.iter().take(MAX_DISPLAYED_BYTES) {
+            write!(f, "{:02x}", byte)?;
+        }
+        if self.data.len() > MAX_DISPLAYED_BYTES {
+            write!(f, "... ({} bytes)", self.data.len())?;
+        }
+        Ok(())
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{capture_transcript, TranscriptEventKind};
+    use crate::{hashers::Blake3_256, Hasher, RandomCoin};
+    use math::fields::f128::BaseElement;
+
+    type Coin = RandomCoin<BaseElement, Blake3_256<BaseElement>>;
+
+    #[test]
+    fn capture_and_diff_transcripts() {
+        let run = |value: u8| {
+            let mut coin = Coin::new(&[1, 2, 3, 4]);
+            coin.label("commitment")
+                .reseed(Blake3_256::<BaseElement>::hash(&[value]));
+            coin.label("challenge").draw::<BaseElement>().unwrap()
+        };
+
+        let (_, transcript) = capture_transcript(|| run(1));
+        let (_, same) = capture_transcript(|| run(1));
+        let (_, other) = capture_transcript(|| run(2));
+
+        let events = transcript.events();
+        assert_eq!(3, events.len());
+        assert_eq!(TranscriptEventKind::Absorb, events[0].kind);
+        assert_eq!("initial seed", events[0].label);
+        assert_eq!("commitment", events[1].label);
+        assert_eq!(TranscriptEventKind::Draw, events[2].kind);
+        assert_eq!("challenge", events[2].label);
+
+        assert_eq!(None, transcript.diff(&same));
+        let diff = transcript.diff(&other).unwrap();
+        assert!(diff.starts_with("transcripts diverge at event 1:\n"));
+        assert!(diff.contains("- absorb commitment: "));
+        assert!(diff.contains("+ absorb commitment: "));
+    }
+
+    #[test]
+    fn nested_transcript_captures() {
+        let ((_, inner), outer) = capture_transcript(|| {
+            Coin::new(&[1]);
+            capture_transcript(|| Coin::new(&[2]))
+        });
+        assert_eq!(vec![1], outer.events()[0].data);
+        assert_eq!(1, outer.events().len());
+        assert_eq!(vec![2], inner.events()[0].data);
+        assert_eq!(1, inner.events().len());
+    }
+}
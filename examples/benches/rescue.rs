@@ -6,9 +6,10 @@
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
 use examples::{rescue, Example};
 use std::time::Duration;
-use winterfell::{FieldExtension, HashFunction, ProofOptions};
+use winterfell::{FieldExtension, HashFunction, ProofOptions, VerifierCache};
 
 const SIZES: [usize; 2] = [256, 512];
+const NUM_VERIFICATIONS: usize = 1000;
 
 fn rescue(c: &mut Criterion) {
     let mut group = c.benchmark_group("rescue");
@@ -28,11 +29,108 @@ fn rescue(c: &mut Criterion) {
     for &size in SIZES.iter() {
         let resc = rescue::RescueExample::new(size, options.clone());
         group.bench_function(BenchmarkId::from_parameter(size), |bench| {
-            bench.iter(|| resc.prove());
+            bench.iter(|| resc.prove().unwrap());
         });
     }
     group.finish();
 }
 
-criterion_group!(rescue_group, rescue);
+fn rescue_extension(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rescue_extension");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(25));
+
+    let options = ProofOptions::new(
+        32,
+        32,
+        0,
+        HashFunction::Blake3_256,
+        FieldExtension::Quadratic,
+        4,
+        256,
+    );
+
+    for &size in SIZES.iter() {
+        let resc = rescue::RescueExample::new(size, options.clone());
+        group.bench_function(BenchmarkId::from_parameter(size), |bench| {
+            bench.iter(|| resc.prove().unwrap());
+        });
+    }
+    group.finish();
+}
+
+fn rescue_verify(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rescue_verify");
+    group.sample_size(10);
+
+    let options = ProofOptions::new(
+        32,
+        32,
+        0,
+        HashFunction::Blake3_256,
+        FieldExtension::None,
+        4,
+        256,
+    );
+
+    for &size in SIZES.iter() {
+        let resc = rescue::RescueExample::new(size, options.clone());
+        let proof = resc.prove().unwrap();
+        group.bench_function(BenchmarkId::new("uncached", size), |bench| {
+            bench.iter(|| resc.verify(proof.clone()).unwrap());
+        });
+
+        let cache = VerifierCache::new();
+        group.bench_function(BenchmarkId::new("cached", size), |bench| {
+            bench.iter(|| resc.verify_cached(proof.clone(), &cache).unwrap());
+        });
+    }
+    group.finish();
+}
+
+fn rescue_verify_stream(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rescue_verify_stream");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(60));
+
+    let options = ProofOptions::new(
+        32,
+        32,
+        0,
+        HashFunction::Blake3_256,
+        FieldExtension::None,
+        4,
+        256,
+    );
+
+    // each iteration verifies a stream of proofs for the same computation; with a cache, the
+    // cost of building AIR-derived data is paid only once per stream
+    let size = SIZES[0];
+    let resc = rescue::RescueExample::new(size, options);
+    let proof = resc.prove().unwrap();
+    group.bench_function(BenchmarkId::new("uncached", NUM_VERIFICATIONS), |bench| {
+        bench.iter(|| {
+            for _ in 0..NUM_VERIFICATIONS {
+                resc.verify(proof.clone()).unwrap();
+            }
+        });
+    });
+    group.bench_function(BenchmarkId::new("cached", NUM_VERIFICATIONS), |bench| {
+        bench.iter(|| {
+            let cache = VerifierCache::new();
+            for _ in 0..NUM_VERIFICATIONS {
+                resc.verify_cached(proof.clone(), &cache).unwrap();
+            }
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(
+    rescue_group,
+    rescue,
+    rescue_extension,
+    rescue_verify,
+    rescue_verify_stream
+);
 criterion_main!(rescue_group);
@@ -0,0 +1,55 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use examples::{fibonacci, rescue, Example};
+use winterfell::{FieldExtension, HashFunction, ProofOptions, StarkProof};
+
+const FIB_SIZE: usize = 65_536;
+const RESCUE_SIZE: usize = 512;
+
+fn verifier(c: &mut Criterion) {
+    let mut group = c.benchmark_group("verifier");
+    group.sample_size(20);
+
+    let options = ProofOptions::new(
+        32,
+        8,
+        0,
+        HashFunction::Blake3_256,
+        FieldExtension::None,
+        4,
+        256,
+    );
+
+    // proofs are generated once and kept in serialized form; each iteration deserializes and
+    // verifies a proof, same as a client receiving the proof would
+    let examples: Vec<(&str, usize, Box<dyn Example>)> = vec![
+        (
+            "fibonacci",
+            FIB_SIZE,
+            Box::new(fibonacci::fib2::FibExample::new(FIB_SIZE, options.clone())),
+        ),
+        (
+            "rescue",
+            RESCUE_SIZE,
+            Box::new(rescue::RescueExample::new(RESCUE_SIZE, options)),
+        ),
+    ];
+
+    for (name, size, example) in examples.iter() {
+        let proof_bytes = example.prove().unwrap().to_bytes();
+        group.bench_function(BenchmarkId::new(*name, size), |bench| {
+            bench.iter(|| {
+                let proof = StarkProof::from_bytes(&proof_bytes).unwrap();
+                example.verify(proof).unwrap()
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(verifier_group, verifier);
+criterion_main!(verifier_group);
@@ -0,0 +1,114 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{BaseElement, FieldElement, ProofOptions, RESET_PERIOD, TRACE_WIDTH};
+use crate::utils::{are_equal, validate_trace_width};
+use winterfell::{
+    Air, AirContext, Assertion, ConstraintDivisor, Deserializable, EvaluationFrame, Serializable,
+    TraceInfo, TransitionConstraintDegree,
+};
+
+// TRANSITION CONSTRAINT DEGREES
+// ================================================================================================
+
+/// Returns degrees of all transition constraints of this AIR.
+pub(super) fn transition_constraint_degrees() -> Vec<TransitionConstraintDegree> {
+    vec![TransitionConstraintDegree::new(1)]
+}
+
+// COUNTER AIR
+// ================================================================================================
+
+#[derive(Serializable, Deserializable)]
+#[winter_utils(crate = "winterfell")]
+pub struct PublicInputs {
+    pub reset_values: Vec<BaseElement>,
+}
+
+/// AIR for a counter which is incremented by one on every step, and is reset to the next of the
+/// public reset values every [RESET_PERIOD] steps.
+///
+/// The AIR does not use a selector column to switch off the increment constraint on the reset
+/// steps. Instead, the reset steps are exempted from the transition constraint by the divisor.
+pub struct CounterAir {
+    context: AirContext<BaseElement>,
+    reset_values: Vec<BaseElement>,
+}
+
+impl Air for CounterAir {
+    type BaseField = BaseElement;
+    type PublicInputs = PublicInputs;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
+        let degrees = transition_constraint_degrees();
+        assert_eq!(TRACE_WIDTH, trace_info.width());
+        assert_eq!(
+            trace_info.length() / RESET_PERIOD,
+            pub_inputs.reset_values.len(),
+            "number of reset values must be equal to the number of reset periods"
+        );
+        CounterAir {
+            context: AirContext::new(trace_info, degrees, options),
+            reset_values: pub_inputs.reset_values,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn air_id() -> &'static str {
+        "winterfell-examples/counter"
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        // expected state width is 1 field element
+        debug_assert_eq!(TRACE_WIDTH, current.len());
+        debug_assert_eq!(TRACE_WIDTH, next.len());
+
+        // the counter is incremented by one; on the last step of every reset period this
+        // constraint does not hold, but these steps are excluded by the divisor
+        result[0] = are_equal(next[0], current[0] + E::ONE);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        // at the beginning of every reset period, the counter is equal to the next reset value
+        vec![Assertion::sequence(
+            0,
+            0,
+            RESET_PERIOD,
+            self.reset_values.clone(),
+        )]
+    }
+
+    fn transition_constraint_divisor(&self) -> ConstraintDivisor<Self::BaseField> {
+        ConstraintDivisor::from_transition_with_periodic_exemptions(
+            self.trace_length(),
+            RESET_PERIOD,
+            RESET_PERIOD - 1,
+        )
+    }
+
+    fn validate_instance(trace_info: &TraceInfo, pub_inputs: &PublicInputs) -> Result<(), String> {
+        validate_trace_width(trace_info, TRACE_WIDTH)?;
+        if trace_info.length() / RESET_PERIOD != pub_inputs.reset_values.len() {
+            return Err(format!(
+                "expected {} reset values, but was {}",
+                trace_info.length() / RESET_PERIOD,
+                pub_inputs.reset_values.len()
+            ));
+        }
+        Ok(())
+    }
+}
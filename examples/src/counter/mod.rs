@@ -0,0 +1,182 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::utils::{checkpoint::load_or_build_trace, max_trace_length, SizeRange};
+use crate::{Example, ExampleOptions, DEFAULT_GRINDING_FACTOR};
+use log::debug;
+use std::{path::Path, time::Instant};
+use winterfell::{
+    math::{fields::f128::BaseElement, log2, FieldElement},
+    Air, AirDescription, Claim, ClaimError, ProofOptions, Prover, ProverError, ProverOptions,
+    Serializable, StarkProof, Trace, TraceInfo, TraceTable, TransitionConstraintDegree,
+    VerifierError,
+};
+
+mod air;
+use air::{transition_constraint_degrees, CounterAir, PublicInputs};
+
+mod prover;
+use prover::CounterProver;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+const TRACE_WIDTH: usize = 1;
+
+/// Number of steps after which the counter is reset.
+const RESET_PERIOD: usize = 8;
+
+/// Blowup factor used by this example unless a different one is specified via `-b` option.
+pub const DEFAULT_BLOWUP_FACTOR: usize = 8;
+
+/// Valid trace lengths; the trace must contain at least one full reset period.
+///
+/// The largest valid value depends on the blowup factor.
+pub const fn size_range(blowup_factor: usize) -> SizeRange {
+    SizeRange::power_of_two(
+        "trace length",
+        RESET_PERIOD,
+        max_trace_length(TRACE_WIDTH, blowup_factor),
+    )
+}
+
+/// Valid sizes of this example with the default blowup factor.
+pub const SIZE_RANGE: SizeRange = size_range(DEFAULT_BLOWUP_FACTOR);
+
+// COUNTER EXAMPLE
+// ================================================================================================
+
+pub fn get_example(options: ExampleOptions, trace_length: usize) -> Box<dyn Example> {
+    Box::new(CounterExample::new(
+        trace_length,
+        options.to_proof_options(28, DEFAULT_BLOWUP_FACTOR, DEFAULT_GRINDING_FACTOR),
+    ))
+}
+
+/// Verifies a claim created by this example.
+pub fn verify_claim(claim: Claim) -> Result<(), ClaimError> {
+    claim.verify::<CounterAir>()
+}
+
+pub struct CounterExample {
+    options: ProofOptions,
+    trace_length: usize,
+    reset_values: Vec<BaseElement>,
+}
+
+impl CounterExample {
+    pub fn new(trace_length: usize, options: ProofOptions) -> CounterExample {
+        assert!(
+            trace_length.is_power_of_two(),
+            "trace length must be a power of 2"
+        );
+        assert!(
+            trace_length >= RESET_PERIOD,
+            "trace length must be at least {}",
+            RESET_PERIOD
+        );
+
+        // the counter is reset to k^3 + 1 at the start of k-th period
+        let reset_values = (0..trace_length / RESET_PERIOD)
+            .map(|k| BaseElement::new(k as u128).exp(3) + BaseElement::ONE)
+            .collect();
+
+        CounterExample {
+            options,
+            trace_length,
+            reset_values,
+        }
+    }
+
+    /// Returns public inputs of this example.
+    fn pub_inputs(&self) -> PublicInputs {
+        PublicInputs {
+            reset_values: self.reset_values.clone(),
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl Example for CounterExample {
+    fn prove_with(
+        &self,
+        prover_options: &ProverOptions,
+        checkpoint_dir: Option<&Path>,
+    ) -> Result<StarkProof, ProverError> {
+        debug!(
+            "Generating proof for a counter which is reset every {} steps over {} steps\n\
+            ---------------------",
+            RESET_PERIOD, self.trace_length
+        );
+
+        // create a prover
+        let prover = CounterProver::new(self.options.clone());
+
+        // generate execution trace
+        let now = Instant::now();
+        let trace = load_or_build_trace(checkpoint_dir, self, || {
+            prover.build_trace(&self.reset_values)
+        });
+
+        let trace_width = trace.width();
+        let trace_length = trace.length();
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace_width,
+            log2(trace_length),
+            now.elapsed().as_millis()
+        );
+
+        // generate the proof
+        prover.prove_with(trace, prover_options)
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = PublicInputs {
+            reset_values: self.reset_values.clone(),
+        };
+        winterfell::verify::<CounterAir>(proof, pub_inputs)
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let mut reset_values = self.reset_values.clone();
+        reset_values[1] += BaseElement::ONE;
+        let pub_inputs = PublicInputs { reset_values };
+        winterfell::verify::<CounterAir>(proof, pub_inputs)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn trace_info(&self) -> TraceInfo {
+        TraceInfo::new(TRACE_WIDTH, self.trace_length)
+    }
+
+    fn transition_constraint_degrees(&self) -> Vec<TransitionConstraintDegree> {
+        transition_constraint_degrees()
+    }
+
+    fn air_id(&self) -> &'static str {
+        CounterAir::air_id()
+    }
+
+    fn describe_air(&self) -> AirDescription {
+        crate::describe_example_air::<CounterAir>(self)
+    }
+
+    fn pub_inputs_bytes(&self) -> Vec<u8> {
+        self.pub_inputs().to_bytes()
+    }
+
+    fn claim(&self, proof: StarkProof) -> Claim {
+        Claim::new::<CounterAir>(proof, &self.pub_inputs())
+    }
+}
@@ -0,0 +1,62 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    BaseElement, CounterAir, FieldElement, ProofOptions, Prover, PublicInputs, Trace, TraceTable,
+    RESET_PERIOD, TRACE_WIDTH,
+};
+
+// COUNTER PROVER
+// ================================================================================================
+
+pub struct CounterProver {
+    options: ProofOptions,
+}
+
+impl CounterProver {
+    pub fn new(options: ProofOptions) -> Self {
+        Self { options }
+    }
+
+    /// Builds an execution trace for a counter which is reset to the next of the specified values
+    /// every [RESET_PERIOD] steps; the length of the trace is the number of reset values times
+    /// [RESET_PERIOD].
+    pub fn build_trace(&self, reset_values: &[BaseElement]) -> TraceTable<BaseElement> {
+        let trace_length = reset_values.len() * RESET_PERIOD;
+        let mut trace = TraceTable::new(TRACE_WIDTH, trace_length);
+        trace.fill(
+            |state| {
+                state[0] = reset_values[0];
+            },
+            |step, state| {
+                if (step + 1) % RESET_PERIOD == 0 {
+                    state[0] = reset_values[(step + 1) / RESET_PERIOD];
+                } else {
+                    state[0] += BaseElement::ONE;
+                }
+            },
+        );
+
+        trace
+    }
+}
+
+impl Prover for CounterProver {
+    type BaseField = BaseElement;
+    type Air = CounterAir;
+    type Trace = TraceTable<BaseElement>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> PublicInputs {
+        let reset_values = (0..trace.length())
+            .step_by(RESET_PERIOD)
+            .map(|step| trace.get(0, step))
+            .collect();
+        PublicInputs { reset_values }
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
@@ -0,0 +1,85 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{CounterAir, CounterExample, CounterProver, PublicInputs, RESET_PERIOD};
+use winterfell::{
+    math::{fields::f128::BaseElement, FieldElement},
+    Air, FieldExtension, HashFunction, ProofOptions, Prover, Trace, VerifierError,
+};
+
+#[test]
+fn counter_test_basic_proof_verification() {
+    let counter = Box::new(CounterExample::new(64, build_options(false)));
+    crate::tests::test_basic_proof_verification(counter);
+}
+
+#[test]
+fn counter_test_transcript_consistency() {
+    let counter = Box::new(CounterExample::new(64, build_options(false)));
+    crate::tests::test_transcript_consistency(counter);
+}
+
+#[test]
+fn counter_test_basic_proof_verification_extension() {
+    let counter = Box::new(CounterExample::new(64, build_options(true)));
+    crate::tests::test_basic_proof_verification(counter);
+}
+
+#[test]
+fn counter_test_basic_proof_verification_fail() {
+    let counter = Box::new(CounterExample::new(64, build_options(false)));
+    crate::tests::test_basic_proof_verification_fail(counter);
+}
+
+#[test]
+fn counter_test_divisor_degree() {
+    let reset_values = (1..9).map(BaseElement::new).collect::<Vec<_>>();
+    let prover = CounterProver::new(build_options(false));
+    let trace = prover.build_trace(&reset_values);
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    assert_eq!(reset_values, pub_inputs.reset_values);
+
+    // one out of every RESET_PERIOD steps is exempted from the transition constraint, and the
+    // last step of the trace is among the exempted steps
+    let air = CounterAir::new(trace.get_info(), pub_inputs, build_options(false));
+    let divisor = air.transition_constraint_divisor();
+    assert_eq!(64 - 64 / RESET_PERIOD, divisor.degree());
+    assert!(divisor.exclude().is_empty());
+
+    // the increment constraint does not hold on the exempted steps
+    assert_ne!(trace.get(0, 7) + BaseElement::ONE, trace.get(0, 8));
+}
+
+#[test]
+fn counter_test_wrong_number_of_reset_values() {
+    let reset_values = (1..9).map(BaseElement::new).collect::<Vec<_>>();
+    let prover = CounterProver::new(build_options(false));
+    let trace = prover.build_trace(&reset_values);
+    let proof = prover.prove(trace).unwrap();
+
+    // a malformed claim must be rejected by the verifier rather than panic in CounterAir::new()
+    let pub_inputs = PublicInputs {
+        reset_values: reset_values[..4].to_vec(),
+    };
+    assert!(matches!(
+        winterfell::verify::<CounterAir>(proof, pub_inputs),
+        Err(VerifierError::InvalidAirInstance(_))
+    ));
+}
+
+#[test]
+fn counter_test_pub_inputs_serialization() {
+    let reset_values = (1..9).map(BaseElement::new).collect();
+    crate::tests::test_pub_inputs_serialization(PublicInputs { reset_values });
+}
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(28, 8, 0, HashFunction::Blake3_256, extension, 4, 256)
+}
@@ -0,0 +1,234 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Runs sets of examples described by JSON config files.
+//!
+//! A config file contains a JSON array of [run specs](RunSpec); each spec names an example, its
+//! size argument, and proof options. Runs are executed sequentially, and a [RunResult] is
+//! recorded for each run regardless of whether the run succeeded.
+
+use crate::{get_example, parse_field_extension, parse_hash_fn, ExampleOptions, ExampleType};
+use serde::{Deserialize, Serialize};
+use std::{
+    panic::{self, AssertUnwindSafe},
+    time::Instant,
+};
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Columns of the CSV representation of run results.
+const CSV_HEADER: &str = "example,size,success,error,prove_time_ms,verify_time_ms,\
+    proof_size_bytes,conjectured_security,proven_security";
+
+// RUN SPEC
+// ================================================================================================
+
+/// Describes a single run of an example.
+///
+/// Options which are not specified take the same default values as the corresponding
+/// command-line options. Unknown fields are rejected.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct RunSpec {
+    /// Name of the example (e.g., `fib` or `lamport-a`).
+    pub example: String,
+    /// Size argument of the example (same as `-n` command-line option).
+    pub size: usize,
+    /// Hash function used in the protocol (same as `--hash_fn` command-line option).
+    pub hash_fn: Option<String>,
+    /// Number of queries to include in a proof (same as `--queries` command-line option).
+    pub queries: Option<usize>,
+    /// Blowup factor for low degree extension (same as `--blowup` command-line option).
+    pub blowup: Option<usize>,
+    /// Grinding factor for query seed (same as `--grinding` command-line option).
+    pub grinding: Option<u32>,
+    /// Field extension degree for composition polynomial (same as `--field_extension`
+    /// command-line option).
+    pub field_extension: Option<u32>,
+    /// Folding factor for FRI protocol (same as `--folding` command-line option).
+    pub folding: Option<usize>,
+}
+
+impl RunSpec {
+    /// Converts this spec into options with which the example can be instantiated.
+    ///
+    /// Returns an error if the example is not known, if the size is not valid for the example,
+    /// or if the hash function or field extension are not valid.
+    pub fn to_example_options(&self) -> Result<ExampleOptions, String> {
+        let example = ExampleType::from_name(&self.example, self.size)?;
+        example.accepts_size(self.size, self.blowup)?;
+
+        if let Some(hash_fn) = self.hash_fn.as_deref() {
+            parse_hash_fn(hash_fn)?;
+        }
+        if let Some(field_extension) = self.field_extension {
+            parse_field_extension(field_extension)?;
+        }
+
+        Ok(ExampleOptions {
+            example,
+            hash_fn: self.hash_fn.clone(),
+            num_queries: self.queries,
+            blowup_factor: self.blowup,
+            grinding_factor: self.grinding,
+            field_extension: self.field_extension,
+            folding_factor: self.folding,
+            proof_out: None,
+            checkpoint_dir: None,
+            seed: None,
+            min_proven_security: None,
+            #[cfg(feature = "tracing")]
+            tracing_json: None,
+            describe_air: false,
+        })
+    }
+}
+
+// RUN RESULT
+// ================================================================================================
+
+/// Describes the outcome of a single run of an example.
+///
+/// Metrics of a failed run are set to `None` starting from the stage at which the run failed.
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct RunResult {
+    pub example: String,
+    pub size: usize,
+    pub success: bool,
+    pub error: Option<String>,
+    pub prove_time_ms: Option<f64>,
+    pub verify_time_ms: Option<f64>,
+    pub proof_size_bytes: Option<usize>,
+    pub conjectured_security: Option<u32>,
+    pub proven_security: Option<u32>,
+}
+
+impl RunResult {
+    fn new(spec: &RunSpec) -> Self {
+        RunResult {
+            example: spec.example.clone(),
+            size: spec.size,
+            success: false,
+            error: None,
+            prove_time_ms: None,
+            verify_time_ms: None,
+            proof_size_bytes: None,
+            conjectured_security: None,
+            proven_security: None,
+        }
+    }
+
+    /// Returns this result as a single CSV row with columns in the order of [CSV_HEADER].
+    fn to_csv_row(&self) -> String {
+        fn cell<T: ToString>(value: &Option<T>) -> String {
+            value.as_ref().map_or(String::new(), |v| v.to_string())
+        }
+        let error = self.error.as_ref().map_or(String::new(), |err| {
+            format!("\"{}\"", err.replace('"', "\"\""))
+        });
+        format!(
+            "{},{},{},{},{},{},{},{},{}",
+            self.example,
+            self.size,
+            self.success,
+            error,
+            cell(&self.prove_time_ms),
+            cell(&self.verify_time_ms),
+            cell(&self.proof_size_bytes),
+            cell(&self.conjectured_security),
+            cell(&self.proven_security)
+        )
+    }
+}
+
+// PUBLIC FUNCTIONS
+// ================================================================================================
+
+/// Parses a JSON array of run specs.
+///
+/// Returns an error naming the offending key if a spec contains unknown fields.
+pub fn parse_config(config: &str) -> Result<Vec<RunSpec>, String> {
+    serde_json::from_str(config).map_err(|err| err.to_string())
+}
+
+/// Executes all specified runs sequentially and returns their results in the same order.
+pub fn run_all(specs: &[RunSpec]) -> Vec<RunResult> {
+    specs.iter().map(run).collect()
+}
+
+/// Executes a single run: instantiates the example, generates a proof, and verifies it.
+pub fn run(spec: &RunSpec) -> RunResult {
+    let mut result = RunResult::new(spec);
+    match execute(spec, &mut result) {
+        Ok(()) => result.success = true,
+        Err(err) => result.error = Some(err),
+    }
+    result
+}
+
+/// Serializes the specified results into a JSON array.
+pub fn to_json(results: &[RunResult]) -> String {
+    serde_json::to_string_pretty(results).expect("failed to serialize run results")
+}
+
+/// Serializes the specified results into CSV with a header row.
+pub fn to_csv(results: &[RunResult]) -> String {
+    let mut output = String::from(CSV_HEADER);
+    output.push('\n');
+    for result in results {
+        output.push_str(&result.to_csv_row());
+        output.push('\n');
+    }
+    output
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn execute(spec: &RunSpec, result: &mut RunResult) -> Result<(), String> {
+    let options = spec.to_example_options()?;
+
+    // proof options and examples validate their parameters via assertions; panics are caught so
+    // that a single invalid spec does not abort the remaining runs
+    let example = catch_panic(|| get_example(options).expect("run spec must describe an example"))?;
+    example.validate_options(example.options())?;
+
+    let now = Instant::now();
+    let proof = catch_panic(|| example.prove())?
+        .map_err(|err| format!("failed to generate proof: {}", err))?;
+    result.prove_time_ms = Some(elapsed_ms(now));
+    result.proof_size_bytes = Some(proof.to_bytes().len());
+    result.conjectured_security = Some(proof.security_level(true));
+    result.proven_security = Some(proof.security_level(false));
+
+    let now = Instant::now();
+    example
+        .verify(proof)
+        .map_err(|err| format!("failed to verify proof: {}", err))?;
+    result.verify_time_ms = Some(elapsed_ms(now));
+
+    Ok(())
+}
+
+fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|err| {
+        let msg = match err.downcast_ref::<String>() {
+            Some(msg) => msg.clone(),
+            None => err
+                .downcast_ref::<&str>()
+                .map_or("unknown error", |msg| msg)
+                .to_string(),
+        };
+        format!("run panicked: {}", msg)
+    })
+}
+
+fn elapsed_ms(start: Instant) -> f64 {
+    start.elapsed().as_micros() as f64 / 1000f64
+}
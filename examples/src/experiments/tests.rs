@@ -0,0 +1,113 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{parse_config, run_all, to_csv, to_json, RunSpec, CSV_HEADER};
+
+const TEST_CONFIG: &str = include_str!("test_config.json");
+
+#[test]
+fn experiments_parse_config() {
+    let specs = parse_config(TEST_CONFIG).unwrap();
+    assert_eq!(3, specs.len());
+    assert_eq!(
+        RunSpec {
+            example: "rescue".to_string(),
+            size: 2,
+            hash_fn: Some("sha3_256".to_string()),
+            queries: None,
+            blowup: None,
+            grinding: Some(0),
+            field_extension: Some(2),
+            folding: None,
+        },
+        specs[1]
+    );
+}
+
+#[test]
+fn experiments_reject_unknown_fields() {
+    let config = r#"[{ "example": "fib", "size": 16, "blowup_factor": 8 }]"#;
+    let err = parse_config(config).unwrap_err();
+    assert!(err.starts_with("unknown field `blowup_factor`"), "{}", err);
+
+    // missing required fields are rejected as well
+    let err = parse_config(r#"[{ "example": "fib" }]"#).unwrap_err();
+    assert!(err.starts_with("missing field `size`"), "{}", err);
+}
+
+#[test]
+fn experiments_run_config() {
+    let specs = parse_config(TEST_CONFIG).unwrap();
+    let results = run_all(&specs);
+    assert_eq!(3, results.len());
+
+    // the first two runs succeed and record all metrics
+    for result in results[..2].iter() {
+        assert!(result.success);
+        assert_eq!(None, result.error);
+        assert!(result.prove_time_ms.is_some());
+        assert!(result.verify_time_ms.is_some());
+        assert!(result.proof_size_bytes.unwrap() > 0);
+        assert!(result.conjectured_security.unwrap() > 0);
+        assert!(result.proven_security.unwrap() <= result.conjectured_security.unwrap());
+    }
+
+    // the last run fails because the sequence length is not a power of two
+    assert!(!results[2].success);
+    assert!(results[2]
+        .error
+        .as_ref()
+        .unwrap()
+        .contains("nearest valid value is 1024"));
+    assert_eq!(None, results[2].prove_time_ms);
+    assert_eq!(None, results[2].proof_size_bytes);
+
+    // JSON output contains one object per run with the same set of keys
+    let json: serde_json::Value = serde_json::from_str(&to_json(&results)).unwrap();
+    let runs = json.as_array().unwrap();
+    assert_eq!(3, runs.len());
+    let expected_keys = CSV_HEADER.split(',').collect::<Vec<_>>();
+    for (run, result) in runs.iter().zip(results.iter()) {
+        let keys = run.as_object().unwrap().keys().collect::<Vec<_>>();
+        assert_eq!(expected_keys.len(), keys.len());
+        for key in expected_keys.iter() {
+            assert!(run.get(key).is_some(), "missing key {}", key);
+        }
+        assert_eq!(result.success, run["success"].as_bool().unwrap());
+    }
+
+    // CSV output contains a header and one row per run
+    let csv = to_csv(&results);
+    let lines = csv.lines().collect::<Vec<_>>();
+    assert_eq!(4, lines.len());
+    assert_eq!(CSV_HEADER, lines[0]);
+    assert!(lines[1].starts_with("fib,16,true,,"));
+    assert!(lines[3].starts_with("fib8,1000,false,\"invalid sequence length 1000"));
+}
+
+#[test]
+fn experiments_invalid_options() {
+    // invalid hash functions and proof options fail the run instead of aborting all runs
+    let config = r#"[
+        { "example": "fib", "size": 16, "hash_fn": "md5" },
+        { "example": "fib", "size": 16, "blowup": 3 },
+        { "example": "unknown", "size": 16 }
+    ]"#;
+    let results = run_all(&parse_config(config).unwrap());
+    assert!(results.iter().all(|result| !result.success));
+    assert_eq!(
+        Some("'md5' is not a valid hash function".to_string()),
+        results[0].error
+    );
+    assert!(results[1]
+        .error
+        .as_ref()
+        .unwrap()
+        .starts_with("run panicked:"));
+    assert_eq!(
+        Some("'unknown' is not a known example".to_string()),
+        results[2].error
+    );
+}
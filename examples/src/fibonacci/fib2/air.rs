@@ -4,17 +4,41 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{BaseElement, FieldElement, ProofOptions, TRACE_WIDTH};
-use crate::utils::are_equal;
+use crate::utils::{are_equal, validate_trace_width};
 use winterfell::{
-    Air, AirContext, Assertion, EvaluationFrame, TraceInfo, TransitionConstraintDegree,
+    Air, AirContext, Assertion, ConstraintDivisor, EvaluationFrame, TraceInfo,
+    TransitionConstraintDegree,
 };
 
+// TRANSITION CONSTRAINT DEGREES
+// ================================================================================================
+
+/// Returns degrees of all transition constraints of this AIR.
+pub(super) fn transition_constraint_degrees() -> Vec<TransitionConstraintDegree> {
+    vec![
+        TransitionConstraintDegree::new(1),
+        TransitionConstraintDegree::new(1),
+    ]
+}
+
+/// Returns the number of rows in the execution trace before it was padded to a power of two.
+///
+/// # Panics
+/// Panics if trace metadata does not encode a valid unpadded length; the verifier rejects such
+/// trace info via [FibAir::validate_instance()] before the AIR is instantiated.
+pub(super) fn unpadded_length(trace_info: &TraceInfo) -> usize {
+    trace_info
+        .unpadded_length()
+        .expect("trace metadata must encode a valid unpadded trace length")
+}
+
 // FIBONACCI AIR
 // ================================================================================================
 
 pub struct FibAir {
     context: AirContext<BaseElement>,
     result: BaseElement,
+    unpadded_length: usize,
 }
 
 impl Air for FibAir {
@@ -24,14 +48,13 @@ impl Air for FibAir {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     fn new(trace_info: TraceInfo, pub_inputs: Self::BaseField, options: ProofOptions) -> Self {
-        let degrees = vec![
-            TransitionConstraintDegree::new(1),
-            TransitionConstraintDegree::new(1),
-        ];
+        let degrees = transition_constraint_degrees();
         assert_eq!(TRACE_WIDTH, trace_info.width());
+        let unpadded_length = unpadded_length(&trace_info);
         FibAir {
             context: AirContext::new(trace_info, degrees, options),
             result: pub_inputs,
+            unpadded_length,
         }
     }
 
@@ -39,6 +62,10 @@ impl Air for FibAir {
         &self.context
     }
 
+    fn air_id() -> &'static str {
+        "winterfell-examples/fib"
+    }
+
     fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
         &self,
         frame: &EvaluationFrame<E>,
@@ -58,14 +85,28 @@ impl Air for FibAir {
         result[1] = are_equal(next[1], current[1] + next[0]);
     }
 
+    fn transition_constraint_divisor(&self) -> ConstraintDivisor<Self::BaseField> {
+        // padding rows are all zeros and satisfy the transition constraints; thus, only the
+        // transition from the last unpadded row into padding needs to be excluded
+        ConstraintDivisor::from_transition_with_padding(self.trace_length(), self.unpadded_length)
+    }
+
     fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
         // a valid Fibonacci sequence should start with two ones and terminate with
-        // the expected result
-        let last_step = self.trace_length() - 1;
+        // the expected result at the last unpadded step
+        let last_step = self.unpadded_length - 1;
         vec![
             Assertion::single(0, 0, Self::BaseField::ONE),
             Assertion::single(1, 0, Self::BaseField::ONE),
             Assertion::single(1, last_step, self.result),
         ]
     }
+
+    fn validate_instance(trace_info: &TraceInfo, _pub_inputs: &BaseElement) -> Result<(), String> {
+        validate_trace_width(trace_info, TRACE_WIDTH)?;
+        if trace_info.unpadded_length().is_none() {
+            return Err("trace metadata does not encode a valid unpadded trace length".to_string());
+        }
+        Ok(())
+    }
 }
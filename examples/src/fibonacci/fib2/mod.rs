@@ -4,16 +4,19 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::utils::compute_fib_term;
-use crate::{Example, ExampleOptions};
+use crate::utils::{checkpoint::load_or_build_trace, max_trace_length, SizeRange};
+use crate::{Example, ExampleOptions, DEFAULT_GRINDING_FACTOR};
 use log::debug;
-use std::time::Instant;
+use std::{path::Path, time::Instant};
 use winterfell::{
     math::{fields::f128::BaseElement, log2, FieldElement},
-    ProofOptions, Prover, StarkProof, Trace, TraceTable, VerifierError,
+    Air, AirDescription, Claim, ClaimError, ProofOptions, Prover, ProverError, ProverOptions,
+    Serializable, StarkProof, Trace, TraceInfo, TraceTable, TransitionConstraintDegree,
+    VerifierError,
 };
 
 mod air;
-use air::FibAir;
+use air::{transition_constraint_degrees, FibAir};
 
 mod prover;
 use prover::FibProver;
@@ -26,16 +29,40 @@ mod tests;
 
 const TRACE_WIDTH: usize = 2;
 
+/// Blowup factor used by this example unless a different one is specified via `-b` option.
+pub const DEFAULT_BLOWUP_FACTOR: usize = 8;
+
+/// Valid lengths of the Fibonacci sequence; two terms of the sequence are computed per step, and
+/// the trace must have at least 8 steps. Traces with a number of steps which is not a power of two
+/// are padded with zeros.
+///
+/// The largest valid value depends on the blowup factor.
+pub const fn size_range(blowup_factor: usize) -> SizeRange {
+    SizeRange::even(
+        "sequence length",
+        16,
+        max_trace_length(TRACE_WIDTH, blowup_factor) * 2,
+    )
+}
+
+/// Valid sizes of this example with the default blowup factor.
+pub const SIZE_RANGE: SizeRange = size_range(DEFAULT_BLOWUP_FACTOR);
+
 // FIBONACCI EXAMPLE
 // ================================================================================================
 
 pub fn get_example(options: ExampleOptions, sequence_length: usize) -> Box<dyn Example> {
     Box::new(FibExample::new(
         sequence_length,
-        options.to_proof_options(28, 8),
+        options.to_proof_options(28, DEFAULT_BLOWUP_FACTOR, DEFAULT_GRINDING_FACTOR),
     ))
 }
 
+/// Verifies a claim created by this example.
+pub fn verify_claim(claim: Claim) -> Result<(), ClaimError> {
+    claim.verify::<FibAir>()
+}
+
 pub struct FibExample {
     options: ProofOptions,
     sequence_length: usize,
@@ -45,8 +72,8 @@ pub struct FibExample {
 impl FibExample {
     pub fn new(sequence_length: usize, options: ProofOptions) -> FibExample {
         assert!(
-            sequence_length.is_power_of_two(),
-            "sequence length must be a power of 2"
+            sequence_length % 2 == 0,
+            "sequence length must be an even number"
         );
 
         // compute Fibonacci sequence
@@ -66,11 +93,25 @@ impl FibExample {
     }
 }
 
+impl FibExample {
+    /// Generates a proof in the same way as [Example::prove()], but without materializing the
+    /// execution trace.
+    pub fn prove_streaming(&self) -> Result<StarkProof, ProverError> {
+        let prover = FibProver::new(self.options.clone());
+        let trace = prover.build_streaming_trace(self.sequence_length);
+        prover.prove_streaming(&trace, self.result)
+    }
+}
+
 // EXAMPLE IMPLEMENTATION
 // ================================================================================================
 
 impl Example for FibExample {
-    fn prove(&self) -> StarkProof {
+    fn prove_with(
+        &self,
+        prover_options: &ProverOptions,
+        checkpoint_dir: Option<&Path>,
+    ) -> Result<StarkProof, ProverError> {
         debug!(
             "Generating proof for computing Fibonacci sequence (2 terms per step) up to {}th term\n\
             ---------------------",
@@ -82,7 +123,9 @@ impl Example for FibExample {
 
         // generate execution trace
         let now = Instant::now();
-        let trace = prover.build_trace(self.sequence_length);
+        let trace = load_or_build_trace(checkpoint_dir, self, || {
+            prover.build_trace(self.sequence_length)
+        });
 
         let trace_width = trace.width();
         let trace_length = trace.length();
@@ -94,7 +137,7 @@ impl Example for FibExample {
         );
 
         // generate the proof
-        prover.prove(trace).unwrap()
+        prover.prove_with(trace, prover_options)
     }
 
     fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
@@ -104,4 +147,32 @@ impl Example for FibExample {
     fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
         winterfell::verify::<FibAir>(proof, self.result + BaseElement::ONE)
     }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn trace_info(&self) -> TraceInfo {
+        TraceInfo::with_padding(TRACE_WIDTH, self.sequence_length / 2)
+    }
+
+    fn transition_constraint_degrees(&self) -> Vec<TransitionConstraintDegree> {
+        transition_constraint_degrees()
+    }
+
+    fn air_id(&self) -> &'static str {
+        FibAir::air_id()
+    }
+
+    fn describe_air(&self) -> AirDescription {
+        crate::describe_example_air::<FibAir>(self)
+    }
+
+    fn pub_inputs_bytes(&self) -> Vec<u8> {
+        self.result.to_bytes()
+    }
+
+    fn claim(&self, proof: StarkProof) -> Claim {
+        Claim::new::<FibAir>(proof, &self.result)
+    }
 }
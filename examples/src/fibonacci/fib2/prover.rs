@@ -4,8 +4,10 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{
-    BaseElement, FibAir, FieldElement, ProofOptions, Prover, Trace, TraceTable, TRACE_WIDTH,
+    air::unpadded_length, BaseElement, FibAir, FieldElement, ProofOptions, Prover, Trace,
+    TraceTable, TRACE_WIDTH,
 };
+use winterfell::PaddingStrategy;
 
 // FIBONACCI PROVER
 // ================================================================================================
@@ -21,13 +23,18 @@ impl FibProver {
 
     /// Builds an execution trace for computing a Fibonacci sequence of the specified length such
     /// that each row advances the sequence by 2 terms.
+    ///
+    /// If the number of rows is not a power of two, the trace is padded with zeros; the zero rows
+    /// satisfy the transition constraints of [FibAir], and thus, only the transition into the
+    /// first padding row needs to be excluded from the constraints.
     pub fn build_trace(&self, sequence_length: usize) -> TraceTable<BaseElement> {
         assert!(
-            sequence_length.is_power_of_two(),
-            "sequence length must be a power of 2"
+            sequence_length % 2 == 0,
+            "sequence length must be an even number"
         );
 
-        let mut trace = TraceTable::new(TRACE_WIDTH, sequence_length / 2);
+        let num_rows = sequence_length / 2;
+        let mut trace = TraceTable::new(TRACE_WIDTH, num_rows.next_power_of_two());
         trace.fill(
             |state| {
                 state[0] = BaseElement::ONE;
@@ -38,8 +45,21 @@ impl FibProver {
                 state[1] += state[0];
             },
         );
+        trace.truncate(num_rows);
+
+        trace.pad(PaddingStrategy::Zeros)
+    }
 
-        trace
+    /// Builds the same execution trace as [build_trace()](FibProver::build_trace), but the rows
+    /// of the returned trace are generated on demand rather than stored in memory.
+    pub fn build_streaming_trace(&self, sequence_length: usize) -> FibStreamingTrace {
+        assert!(
+            sequence_length.is_power_of_two(),
+            "sequence length must be a power of 2"
+        );
+        FibStreamingTrace {
+            length: sequence_length / 2,
+        }
     }
 }
 
@@ -49,7 +69,7 @@ impl Prover for FibProver {
     type Trace = TraceTable<BaseElement>;
 
     fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
-        let last_step = trace.length() - 1;
+        let last_step = unpadded_length(&trace.get_info()) - 1;
         trace.get(1, last_step)
     }
 
@@ -57,3 +77,38 @@ impl Prover for FibProver {
         &self.options
     }
 }
+
+// STREAMING TRACE
+// ================================================================================================
+
+/// Execution trace for computing a Fibonacci sequence which re-computes the sequence every time
+/// a column of the trace is requested.
+pub struct FibStreamingTrace {
+    length: usize,
+}
+
+impl winterfell::StreamingTrace for FibStreamingTrace {
+    type BaseField = BaseElement;
+
+    fn stream_width(&self) -> usize {
+        TRACE_WIDTH
+    }
+
+    fn stream_length(&self) -> usize {
+        self.length
+    }
+
+    fn stream_meta(&self) -> &[u8] {
+        &[]
+    }
+
+    fn column(&self, col_idx: usize) -> Box<dyn Iterator<Item = BaseElement> + '_> {
+        let mut state = [BaseElement::ONE, BaseElement::ONE];
+        Box::new((0..self.length).map(move |_| {
+            let value = state[col_idx];
+            state[0] += state[1];
+            state[1] += state[0];
+            value
+        }))
+    }
+}
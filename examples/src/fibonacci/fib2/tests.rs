@@ -3,7 +3,17 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::super::utils::build_proof_options;
+use super::{
+    super::utils::{build_proof_options, build_proof_options_with_grinding},
+    BaseElement, FibAir, FibProver,
+};
+use crate::{Example, ExampleOptions, DEFAULT_GRINDING_FACTOR};
+use structopt::StructOpt;
+use winterfell::{
+    crypto::transcript::{capture_transcript, TranscriptEventKind},
+    math::FieldElement,
+    Prover, ProverOptions, Serializable, StarkProof, Trace, VerifierError,
+};
 
 #[test]
 fn fib2_test_basic_proof_verification() {
@@ -11,6 +21,56 @@ fn fib2_test_basic_proof_verification() {
     crate::tests::test_basic_proof_verification(fib);
 }
 
+#[test]
+fn fib2_test_padded_proof_verification() {
+    // 500 rows are padded to 512 rows, and the result is asserted at the last unpadded step
+    let trace = FibProver::new(build_proof_options(false)).build_trace(1000);
+    assert_eq!(512, trace.length());
+    assert_eq!(Some(500), trace.get_info().unpadded_length());
+    assert_eq!(super::compute_fib_term(1000), trace.get(1, 499));
+    assert_eq!(BaseElement::ZERO, trace.get(1, 500));
+
+    let fib = Box::new(super::FibExample::new(1000, build_proof_options(false)));
+    assert_eq!(trace.get_info(), fib.trace_info());
+    crate::tests::test_basic_proof_verification(fib);
+
+    let fib = Box::new(super::FibExample::new(1000, build_proof_options(false)));
+    crate::tests::test_basic_proof_verification_fail(fib);
+}
+
+#[test]
+fn fib2_test_invalid_unpadded_length() {
+    // trace metadata follows trace width (1 byte), trace length (1 byte), and the number of
+    // metadata bytes (2 bytes) in a serialized proof
+    const META_POSITION: usize = 4;
+
+    let prover = FibProver::new(build_proof_options(false));
+    let trace = prover.build_trace(1000);
+    let result = trace.get(1, 499);
+    let proof = prover.prove(trace).unwrap();
+
+    // a proof with trace metadata which does not encode a valid unpadded length is rejected
+    // before the AIR is instantiated
+    let mut proof_bytes = proof.to_bytes();
+    assert_eq!(
+        500u64.to_le_bytes(),
+        proof_bytes[META_POSITION..META_POSITION + 8]
+    );
+    proof_bytes[META_POSITION..META_POSITION + 8].copy_from_slice(&600u64.to_le_bytes());
+    let proof = StarkProof::from_bytes(&proof_bytes).unwrap();
+    assert_eq!(None, proof.get_trace_info().unpadded_length());
+    assert!(matches!(
+        winterfell::verify::<FibAir>(proof, result),
+        Err(VerifierError::InvalidAirInstance(_))
+    ));
+}
+
+#[test]
+fn fib2_test_air_description() {
+    let fib = Box::new(super::FibExample::new(64, build_proof_options(false)));
+    crate::tests::test_air_description(fib, include_str!("air_description.json"));
+}
+
 #[test]
 fn fib2_test_basic_proof_verification_extension() {
     let fib = Box::new(super::FibExample::new(16, build_proof_options(true)));
@@ -22,3 +82,208 @@ fn fib2_test_basic_proof_verification_fail() {
     let fib = Box::new(super::FibExample::new(16, build_proof_options(false)));
     crate::tests::test_basic_proof_verification_fail(fib);
 }
+
+#[test]
+fn fib2_test_claim_verification() {
+    let fib = Box::new(super::FibExample::new(16, build_proof_options(false)));
+    crate::tests::test_claim_verification("fib", "fib8", fib);
+}
+
+#[test]
+#[should_panic(expected = "AIR identifier cannot be longer than 65535 bytes, but was 65536 bytes")]
+fn fib2_test_claim_air_id_too_long() {
+    let fib = super::FibExample::new(16, build_proof_options(false));
+    let mut claim = fib.claim(fib.prove().unwrap());
+    claim.air_id = "x".repeat(u16::MAX as usize + 1);
+    claim.to_bytes();
+}
+
+#[test]
+fn fib2_test_checkpoint_resume() {
+    let fib = Box::new(super::FibExample::new(16, build_proof_options(false)));
+    crate::tests::test_checkpoint_resume("fib", fib);
+}
+
+#[test]
+fn fib2_test_streaming_proof() {
+    // a proof generated from a streaming trace is the same as a proof from a materialized trace
+    let fib = super::FibExample::new(1024, build_proof_options(false));
+    let proof = fib.prove_streaming().unwrap();
+    assert_eq!(fib.prove().unwrap().to_bytes(), proof.to_bytes());
+    assert!(fib.verify(proof).is_ok());
+}
+
+#[test]
+fn fib2_test_proof_fingerprint() {
+    // proofs generated with the default number of composition columns must not change
+    let fib = super::FibExample::new(16, build_proof_options(false));
+    let proof = fib.prove().unwrap().to_bytes();
+    assert_eq!(
+        "e06dcdf1ab266b66366c1619fbf665a98dfecb0d57ec3ebe584e4bde5d9c6194",
+        hex::encode(blake3::hash(&proof).as_bytes())
+    );
+}
+
+#[test]
+fn fib2_test_proof_artifacts() {
+    let fib = super::FibExample::new(16, build_proof_options(false));
+    let prover = FibProver::new(build_proof_options(false));
+    let (proof, artifacts) = prover
+        .prove_with_artifacts(prover.build_trace(16), &ProverOptions::new())
+        .unwrap();
+
+    // generating artifacts must not change the proof
+    let proof_bytes = proof.to_bytes();
+    assert_eq!(fib.prove().unwrap().to_bytes(), proof_bytes);
+    assert_eq!(
+        "e06dcdf1ab266b66366c1619fbf665a98dfecb0d57ec3ebe584e4bde5d9c6194",
+        hex::encode(blake3::hash(&proof_bytes).as_bytes())
+    );
+
+    // the composition root is the second commitment in the serialized proof (after the trace
+    // root and the 2-byte length prefix), and the OOD frame is the one in the proof
+    let parsed = StarkProof::from_bytes(&proof_bytes).unwrap();
+    let commitments = parsed.commitments.to_bytes();
+    let digest_size = artifacts.composition_roots[0].len();
+    assert_eq!(1, artifacts.composition_roots.len());
+    assert_eq!(
+        &commitments[2 + digest_size..2 + 2 * digest_size],
+        &artifacts.composition_roots[0][..]
+    );
+    assert_eq!(vec![parsed.ood_frame.clone()], artifacts.ood_frames);
+
+    // query positions are the same as the ones drawn by the verifier
+    let (result, transcript) = capture_transcript(|| fib.verify(parsed));
+    assert!(result.is_ok());
+    let event = transcript
+        .events()
+        .iter()
+        .find(|event| event.kind == TranscriptEventKind::Draw && event.label == "query positions")
+        .unwrap();
+    let positions = event
+        .data
+        .chunks(8)
+        .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()) as usize)
+        .collect::<Vec<_>>();
+    assert_eq!(positions, artifacts.query_positions);
+    assert_eq!(
+        proof.options().num_queries(),
+        artifacts.query_positions.len()
+    );
+}
+
+#[test]
+#[cfg(debug_assertions)]
+fn fib2_test_default_grinding_factor() {
+    // grinding is disabled for proofs generated in tests, unless explicitly requested
+    assert_eq!(0, DEFAULT_GRINDING_FACTOR);
+    assert_eq!(0, build_proof_options(false).grinding_factor());
+
+    let options = ExampleOptions::from_iter(&["winterfell", "fib"]);
+    let proof_options = options.to_proof_options(28, 8, DEFAULT_GRINDING_FACTOR);
+    assert_eq!(0, proof_options.grinding_factor());
+
+    // the grinding factor passed by the example is overridden by the command-line option
+    let options = ExampleOptions::from_iter(&["winterfell", "--grinding", "12", "fib"]);
+    let proof_options = options.to_proof_options(28, 8, DEFAULT_GRINDING_FACTOR);
+    assert_eq!(12, proof_options.grinding_factor());
+}
+
+#[test]
+#[cfg(not(debug_assertions))]
+fn fib2_test_default_grinding_factor() {
+    // release builds grind by default, unless overridden via the command-line option
+    assert_eq!(16, DEFAULT_GRINDING_FACTOR);
+
+    let options = ExampleOptions::from_iter(&["winterfell", "fib"]);
+    let proof_options = options.to_proof_options(28, 8, DEFAULT_GRINDING_FACTOR);
+    assert_eq!(16, proof_options.grinding_factor());
+
+    let options = ExampleOptions::from_iter(&["winterfell", "--grinding", "0", "fib"]);
+    let proof_options = options.to_proof_options(28, 8, DEFAULT_GRINDING_FACTOR);
+    assert_eq!(0, proof_options.grinding_factor());
+}
+
+#[test]
+fn fib2_test_grinding_proof() {
+    let options = build_proof_options_with_grinding(false, 16);
+    let fib = super::FibExample::new(1024, options);
+    let proof = fib.prove().unwrap();
+    assert_eq!(16, proof.options().grinding_factor());
+    assert!(fib.verify(proof).is_ok());
+}
+
+#[test]
+fn fib2_test_custom_domain_offset() {
+    let fib = super::FibExample::new(1024, build_proof_options(false));
+    let default_proof = fib.prove().unwrap();
+
+    // proofs can be generated and verified over a coset with a non-default offset, both with and
+    // without field extension
+    for use_extension_field in [false, true] {
+        let options = build_proof_options(use_extension_field).with_domain_offset(7);
+        let proof = super::FibExample::new(1024, options).prove().unwrap();
+        assert_eq!(
+            BaseElement::new(7),
+            proof.options().domain_offset::<BaseElement>()
+        );
+        assert!(fib.verify(proof).is_ok());
+    }
+
+    // the offset is bound to the proof: a proof generated with one offset is rejected if its
+    // context declares a different offset
+    let options = build_proof_options(false).with_domain_offset(7);
+    let proof = super::FibExample::new(1024, options).prove().unwrap();
+    assert_ne!(default_proof.to_bytes(), proof.to_bytes());
+    let mut wrong_proof = proof.clone();
+    wrong_proof.context = default_proof.context.clone();
+    assert!(fib.verify(wrong_proof).is_err());
+    let mut wrong_proof = default_proof;
+    wrong_proof.context = proof.context.clone();
+    assert!(fib.verify(wrong_proof).is_err());
+
+    // a proof whose context declares an offset in the 2-adic subgroup is rejected; the offset is
+    // serialized as the last 8 bytes of the context
+    let context_len = proof.context.to_bytes().len();
+    let mut bytes = proof.to_bytes();
+    assert_eq!(7, bytes[context_len - 8]);
+    bytes[context_len - 8] = 1;
+    let proof = StarkProof::from_bytes(&bytes).unwrap();
+    assert!(matches!(
+        fib.verify(proof),
+        Err(VerifierError::ProofDeserializationError(_))
+    ));
+}
+
+#[test]
+fn fib2_test_size_suggestions() {
+    assert!(super::SIZE_RANGE.check(1024).is_ok());
+    assert!(super::SIZE_RANGE.check(1000).is_ok());
+    assert_eq!(
+        Err(
+            "invalid sequence length 1001: must be an even number between 16 and 134217728; \
+            nearest valid value is 1002"
+                .to_string()
+        ),
+        super::SIZE_RANGE.check(1001)
+    );
+    assert_eq!(16, super::SIZE_RANGE.nearest(8));
+    assert_eq!(3000, super::SIZE_RANGE.nearest(3000));
+    assert_eq!(134217728, super::SIZE_RANGE.nearest(usize::MAX));
+}
+
+#[test]
+fn fib2_test_size_range_blowup() {
+    // the largest sequence length depends on the blowup factor with which proofs are generated
+    let fib = crate::ExampleType::Fib {
+        sequence_length: 1 << 27,
+    };
+    assert!(fib.accepts_size(1 << 27, None).is_ok());
+    assert!(fib.accepts_size(1 << 27, Some(16)).is_err());
+    assert!(fib.accepts_size(1 << 28, Some(4)).is_ok());
+
+    assert_eq!(
+        "Sequence length; must be an even number between 16 and 2^27",
+        super::SIZE_RANGE.help()
+    );
+}
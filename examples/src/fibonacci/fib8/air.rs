@@ -4,12 +4,23 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{BaseElement, FieldElement, TRACE_WIDTH};
-use crate::utils::are_equal;
+use crate::utils::{are_equal, validate_trace_width};
 use winterfell::{
     Air, AirContext, Assertion, EvaluationFrame, ProofOptions, TraceInfo,
     TransitionConstraintDegree,
 };
 
+// TRANSITION CONSTRAINT DEGREES
+// ================================================================================================
+
+/// Returns degrees of all transition constraints of this AIR.
+pub(super) fn transition_constraint_degrees() -> Vec<TransitionConstraintDegree> {
+    vec![
+        TransitionConstraintDegree::new(1),
+        TransitionConstraintDegree::new(1),
+    ]
+}
+
 // FIBONACCI AIR
 // ================================================================================================
 
@@ -25,10 +36,7 @@ impl Air for Fib8Air {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     fn new(trace_info: TraceInfo, pub_inputs: Self::BaseField, options: ProofOptions) -> Self {
-        let degrees = vec![
-            TransitionConstraintDegree::new(1),
-            TransitionConstraintDegree::new(1),
-        ];
+        let degrees = transition_constraint_degrees();
         assert_eq!(TRACE_WIDTH, trace_info.width());
         Fib8Air {
             context: AirContext::new(trace_info, degrees, options),
@@ -40,6 +48,10 @@ impl Air for Fib8Air {
         &self.context
     }
 
+    fn air_id() -> &'static str {
+        "winterfell-examples/fib8"
+    }
+
     fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
         &self,
         frame: &EvaluationFrame<E>,
@@ -76,4 +88,8 @@ impl Air for Fib8Air {
             Assertion::single(1, last_step, self.result),
         ]
     }
+
+    fn validate_instance(trace_info: &TraceInfo, _pub_inputs: &BaseElement) -> Result<(), String> {
+        validate_trace_width(trace_info, TRACE_WIDTH)
+    }
 }
@@ -4,16 +4,19 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::utils::compute_fib_term;
-use crate::{Example, ExampleOptions};
+use crate::utils::{checkpoint::load_or_build_trace, max_trace_length, SizeRange};
+use crate::{Example, ExampleOptions, DEFAULT_GRINDING_FACTOR};
 use log::debug;
-use std::time::Instant;
+use std::{path::Path, time::Instant};
 use winterfell::{
     math::{fields::f128::BaseElement, log2, FieldElement},
-    ProofOptions, Prover, StarkProof, Trace, TraceTable, VerifierError,
+    Air, AirDescription, Claim, ClaimError, ProofOptions, Prover, ProverError, ProverOptions,
+    Serializable, StarkProof, Trace, TraceInfo, TraceTable, TransitionConstraintDegree,
+    VerifierError,
 };
 
 mod air;
-use air::Fib8Air;
+use air::{transition_constraint_degrees, Fib8Air};
 
 mod prover;
 use prover::Fib8Prover;
@@ -26,16 +29,39 @@ mod tests;
 
 const TRACE_WIDTH: usize = 2;
 
+/// Blowup factor used by this example unless a different one is specified via `-b` option.
+pub const DEFAULT_BLOWUP_FACTOR: usize = 8;
+
+/// Valid lengths of the Fibonacci sequence; eight terms of the sequence are computed per step,
+/// and the trace must have at least 8 steps.
+///
+/// The largest valid value depends on the blowup factor.
+pub const fn size_range(blowup_factor: usize) -> SizeRange {
+    SizeRange::power_of_two(
+        "sequence length",
+        64,
+        max_trace_length(TRACE_WIDTH, blowup_factor) * 8,
+    )
+}
+
+/// Valid sizes of this example with the default blowup factor.
+pub const SIZE_RANGE: SizeRange = size_range(DEFAULT_BLOWUP_FACTOR);
+
 // FIBONACCI EXAMPLE
 // ================================================================================================
 
 pub fn get_example(options: ExampleOptions, sequence_length: usize) -> Box<dyn Example> {
     Box::new(Fib8Example::new(
         sequence_length,
-        options.to_proof_options(28, 8),
+        options.to_proof_options(28, DEFAULT_BLOWUP_FACTOR, DEFAULT_GRINDING_FACTOR),
     ))
 }
 
+/// Verifies a claim created by this example.
+pub fn verify_claim(claim: Claim) -> Result<(), ClaimError> {
+    claim.verify::<Fib8Air>()
+}
+
 pub struct Fib8Example {
     options: ProofOptions,
     sequence_length: usize,
@@ -70,7 +96,11 @@ impl Fib8Example {
 // ================================================================================================
 
 impl Example for Fib8Example {
-    fn prove(&self) -> StarkProof {
+    fn prove_with(
+        &self,
+        prover_options: &ProverOptions,
+        checkpoint_dir: Option<&Path>,
+    ) -> Result<StarkProof, ProverError> {
         debug!(
             "Generating proof for computing Fibonacci sequence (8 terms per step) up to {}th term\n\
             ---------------------",
@@ -82,7 +112,9 @@ impl Example for Fib8Example {
 
         // generate execution trace
         let now = Instant::now();
-        let trace = prover.build_trace(self.sequence_length);
+        let trace = load_or_build_trace(checkpoint_dir, self, || {
+            prover.build_trace(self.sequence_length)
+        });
         let trace_width = trace.width();
         let trace_length = trace.length();
         debug!(
@@ -93,7 +125,7 @@ impl Example for Fib8Example {
         );
 
         // generate the proof
-        prover.prove(trace).unwrap()
+        prover.prove_with(trace, prover_options)
     }
 
     fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
@@ -103,4 +135,32 @@ impl Example for Fib8Example {
     fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
         winterfell::verify::<Fib8Air>(proof, self.result + BaseElement::ONE)
     }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn trace_info(&self) -> TraceInfo {
+        TraceInfo::new(TRACE_WIDTH, self.sequence_length / 8)
+    }
+
+    fn transition_constraint_degrees(&self) -> Vec<TransitionConstraintDegree> {
+        transition_constraint_degrees()
+    }
+
+    fn air_id(&self) -> &'static str {
+        Fib8Air::air_id()
+    }
+
+    fn describe_air(&self) -> AirDescription {
+        crate::describe_example_air::<Fib8Air>(self)
+    }
+
+    fn pub_inputs_bytes(&self) -> Vec<u8> {
+        self.result.to_bytes()
+    }
+
+    fn claim(&self, proof: StarkProof) -> Claim {
+        Claim::new::<Fib8Air>(proof, &self.result)
+    }
 }
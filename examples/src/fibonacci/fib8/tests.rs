@@ -11,6 +11,12 @@ fn fib8_test_basic_proof_verification() {
     crate::tests::test_basic_proof_verification(fib);
 }
 
+#[test]
+fn fib8_test_transcript_consistency() {
+    let fib = Box::new(super::Fib8Example::new(64, build_proof_options(false)));
+    crate::tests::test_transcript_consistency(fib);
+}
+
 #[test]
 fn fib8_test_basic_proof_verification_extension() {
     let fib = Box::new(super::Fib8Example::new(64, build_proof_options(true)));
@@ -22,3 +28,20 @@ fn fib8_test_basic_proof_verification_fail() {
     let fib = Box::new(super::Fib8Example::new(64, build_proof_options(false)));
     crate::tests::test_basic_proof_verification_fail(fib);
 }
+
+#[test]
+fn fib8_test_size_suggestions() {
+    assert!(super::SIZE_RANGE.check(1024).is_ok());
+    assert_eq!(
+        Err(
+            "invalid sequence length 1000: must be a power of two between 64 and 536870912; \
+            nearest valid value is 1024"
+                .to_string()
+        ),
+        super::SIZE_RANGE.check(1000)
+    );
+    assert_eq!(64, super::SIZE_RANGE.nearest(8));
+    assert_eq!(2048, super::SIZE_RANGE.nearest(3000));
+    assert_eq!(4096, super::SIZE_RANGE.nearest(3072));
+    assert_eq!(536870912, super::SIZE_RANGE.nearest(usize::MAX));
+}
@@ -3,17 +3,45 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::utils::are_equal;
+use crate::utils::{are_equal, validate_trace_width};
 use winterfell::{
     math::{fields::f128::BaseElement, FieldElement},
-    Air, AirContext, Assertion, EvaluationFrame, ProofOptions, TraceInfo,
+    Air, AirContext, Assertion, ConstraintSet, EvaluationFrame, ProofOptions, TraceInfo,
     TransitionConstraintDegree,
 };
 
+// TRANSITION CONSTRAINTS
+// ================================================================================================
+
+/// Adds all transition constraints of this AIR to the specified constraint set.
+///
+/// Unlike other Fibonacci examples, which evaluate their constraints in a hand-written
+/// `evaluate_transition()`, this AIR defines constraints together with their degrees, and thus,
+/// the degrees cannot get out of sync with the constraints.
+pub(super) fn define_constraints<E: FieldElement>(constraints: &mut ConstraintSet<E>) {
+    // constraints of multiplicative Fibonacci (with 2 registers) which state that:
+    // s_{0, i+1} = s_{0, i} * s_{1, i}
+    // s_{1, i+1} = s_{1, i} * s_{0, i+1}
+    constraints
+        .add("s0", TransitionConstraintDegree::new(2), |frame, _| {
+            are_equal(frame.next()[0], frame.current()[0] * frame.current()[1])
+        })
+        .add("s1", TransitionConstraintDegree::new(2), |frame, _| {
+            are_equal(frame.next()[1], frame.current()[1] * frame.next()[0])
+        });
+}
+
+/// Returns degrees of all transition constraints of this AIR.
+pub(super) fn transition_constraint_degrees() -> Vec<TransitionConstraintDegree> {
+    ConstraintSet::<BaseElement>::describe(define_constraints)
+        .degrees()
+        .to_vec()
+}
+
 // FIBONACCI AIR
 // ================================================================================================
 
-const TRACE_WIDTH: usize = 2;
+pub const TRACE_WIDTH: usize = 2;
 
 pub struct MulFib2Air {
     context: AirContext<BaseElement>,
@@ -27,13 +55,10 @@ impl Air for MulFib2Air {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     fn new(trace_info: TraceInfo, pub_inputs: Self::BaseField, options: ProofOptions) -> Self {
-        let degrees = vec![
-            TransitionConstraintDegree::new(2),
-            TransitionConstraintDegree::new(2),
-        ];
+        let constraints = ConstraintSet::describe(define_constraints);
         assert_eq!(TRACE_WIDTH, trace_info.width());
         MulFib2Air {
-            context: AirContext::new(trace_info, degrees, options),
+            context: AirContext::from_constraints(trace_info, &constraints, options),
             result: pub_inputs,
         }
     }
@@ -42,23 +67,17 @@ impl Air for MulFib2Air {
         &self.context
     }
 
+    fn air_id() -> &'static str {
+        "winterfell-examples/mulfib"
+    }
+
     fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
         &self,
         frame: &EvaluationFrame<E>,
-        _periodic_values: &[E],
+        periodic_values: &[E],
         result: &mut [E],
     ) {
-        let current = frame.current();
-        let next = frame.next();
-        // expected state width is 2 field elements
-        debug_assert_eq!(TRACE_WIDTH, current.len());
-        debug_assert_eq!(TRACE_WIDTH, next.len());
-
-        // constraints of multiplicative Fibonacci (with 2 registers) which state that:
-        // s_{0, i+1} = s_{0, i} * s_{1, i}
-        // s_{1, i+1} = s_{1, i} * s_{0, i+1}
-        result[0] = are_equal(next[0], current[0] * current[1]);
-        result[1] = are_equal(next[1], current[1] * next[0]);
+        ConstraintSet::evaluate(frame, periodic_values, result, define_constraints);
     }
 
     fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
@@ -71,4 +90,8 @@ impl Air for MulFib2Air {
             Assertion::single(0, last_step, self.result),
         ]
     }
+
+    fn validate_instance(trace_info: &TraceInfo, _pub_inputs: &BaseElement) -> Result<(), String> {
+        validate_trace_width(trace_info, TRACE_WIDTH)
+    }
 }
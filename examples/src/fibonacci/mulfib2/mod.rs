@@ -4,16 +4,19 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::utils::compute_mulfib_term;
-use crate::{Example, ExampleOptions};
+use crate::utils::{checkpoint::load_or_build_trace, max_trace_length, SizeRange};
+use crate::{Example, ExampleOptions, DEFAULT_GRINDING_FACTOR};
 use log::debug;
-use std::time::Instant;
+use std::{path::Path, time::Instant};
 use winterfell::{
     math::{fields::f128::BaseElement, log2, FieldElement},
-    ProofOptions, Prover, StarkProof, Trace, TraceTable, VerifierError,
+    Air, AirDescription, Claim, ClaimError, ProofOptions, Prover, ProverError, ProverOptions,
+    Serializable, StarkProof, Trace, TraceInfo, TraceTable, TransitionConstraintDegree,
+    VerifierError,
 };
 
 mod air;
-use air::MulFib2Air;
+use air::{transition_constraint_degrees, MulFib2Air, TRACE_WIDTH};
 
 mod prover;
 use prover::MulFib2Prover;
@@ -21,15 +24,41 @@ use prover::MulFib2Prover;
 #[cfg(test)]
 mod tests;
 
+// CONSTANTS
+// ================================================================================================
+
+/// Blowup factor used by this example unless a different one is specified via `-b` option.
+pub const DEFAULT_BLOWUP_FACTOR: usize = 8;
+
+/// Valid lengths of the multiplicative Fibonacci sequence; two terms of the sequence are computed
+/// per step, and the trace must have at least 8 steps.
+///
+/// The largest valid value depends on the blowup factor.
+pub const fn size_range(blowup_factor: usize) -> SizeRange {
+    SizeRange::power_of_two(
+        "sequence length",
+        16,
+        max_trace_length(TRACE_WIDTH, blowup_factor) * 2,
+    )
+}
+
+/// Valid sizes of this example with the default blowup factor.
+pub const SIZE_RANGE: SizeRange = size_range(DEFAULT_BLOWUP_FACTOR);
+
 // FIBONACCI EXAMPLE
 // ================================================================================================
 
 pub fn get_example(options: ExampleOptions, sequence_length: usize) -> Box<dyn Example> {
     Box::new(MulFib2Example::new(
         sequence_length,
-        options.to_proof_options(28, 8),
+        options.to_proof_options(28, DEFAULT_BLOWUP_FACTOR, DEFAULT_GRINDING_FACTOR),
     ))
 }
+
+/// Verifies a claim created by this example.
+pub fn verify_claim(claim: Claim) -> Result<(), ClaimError> {
+    claim.verify::<MulFib2Air>()
+}
 pub struct MulFib2Example {
     options: ProofOptions,
     sequence_length: usize,
@@ -64,7 +93,11 @@ impl MulFib2Example {
 // ================================================================================================
 
 impl Example for MulFib2Example {
-    fn prove(&self) -> StarkProof {
+    fn prove_with(
+        &self,
+        prover_options: &ProverOptions,
+        checkpoint_dir: Option<&Path>,
+    ) -> Result<StarkProof, ProverError> {
         let sequence_length = self.sequence_length;
         debug!(
             "Generating proof for computing multiplicative Fibonacci sequence (2 terms per step) up to {}th term\n\
@@ -77,7 +110,8 @@ impl Example for MulFib2Example {
 
         // generate execution trace
         let now = Instant::now();
-        let trace = prover.build_trace(sequence_length);
+        let trace =
+            load_or_build_trace(checkpoint_dir, self, || prover.build_trace(sequence_length));
         let trace_width = trace.width();
         let trace_length = trace.length();
         debug!(
@@ -88,7 +122,7 @@ impl Example for MulFib2Example {
         );
 
         // generate the proof
-        prover.prove(trace).unwrap()
+        prover.prove_with(trace, prover_options)
     }
 
     fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
@@ -98,4 +132,32 @@ impl Example for MulFib2Example {
     fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
         winterfell::verify::<MulFib2Air>(proof, self.result + BaseElement::ONE)
     }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn trace_info(&self) -> TraceInfo {
+        TraceInfo::new(TRACE_WIDTH, self.sequence_length / 2)
+    }
+
+    fn transition_constraint_degrees(&self) -> Vec<TransitionConstraintDegree> {
+        transition_constraint_degrees()
+    }
+
+    fn air_id(&self) -> &'static str {
+        MulFib2Air::air_id()
+    }
+
+    fn describe_air(&self) -> AirDescription {
+        crate::describe_example_air::<MulFib2Air>(self)
+    }
+
+    fn pub_inputs_bytes(&self) -> Vec<u8> {
+        self.result.to_bytes()
+    }
+
+    fn claim(&self, proof: StarkProof) -> Claim {
+        Claim::new::<MulFib2Air>(proof, &self.result)
+    }
 }
@@ -11,6 +11,12 @@ fn mulfib2_test_basic_proof_verification() {
     crate::tests::test_basic_proof_verification(fib);
 }
 
+#[test]
+fn mulfib2_test_transcript_consistency() {
+    let fib = Box::new(super::MulFib2Example::new(16, build_proof_options(false)));
+    crate::tests::test_transcript_consistency(fib);
+}
+
 #[test]
 fn mulfib2_test_basic_proof_verification_extension() {
     let fib = Box::new(super::MulFib2Example::new(16, build_proof_options(true)));
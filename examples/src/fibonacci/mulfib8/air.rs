@@ -3,17 +3,34 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::utils::are_equal;
+use crate::utils::{are_equal, validate_trace_width};
 use winterfell::{
     math::{fields::f128::BaseElement, FieldElement},
     Air, AirContext, Assertion, EvaluationFrame, ProofOptions, TraceInfo,
     TransitionConstraintDegree,
 };
 
+// TRANSITION CONSTRAINT DEGREES
+// ================================================================================================
+
+/// Returns degrees of all transition constraints of this AIR.
+pub(super) fn transition_constraint_degrees() -> Vec<TransitionConstraintDegree> {
+    vec![
+        TransitionConstraintDegree::new(2),
+        TransitionConstraintDegree::new(2),
+        TransitionConstraintDegree::new(2),
+        TransitionConstraintDegree::new(2),
+        TransitionConstraintDegree::new(2),
+        TransitionConstraintDegree::new(2),
+        TransitionConstraintDegree::new(2),
+        TransitionConstraintDegree::new(2),
+    ]
+}
+
 // FIBONACCI AIR
 // ================================================================================================
 
-const TRACE_WIDTH: usize = 8;
+pub const TRACE_WIDTH: usize = 8;
 
 pub struct MulFib8Air {
     context: AirContext<BaseElement>,
@@ -27,16 +44,7 @@ impl Air for MulFib8Air {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     fn new(trace_info: TraceInfo, pub_inputs: Self::BaseField, options: ProofOptions) -> Self {
-        let degrees = vec![
-            TransitionConstraintDegree::new(2),
-            TransitionConstraintDegree::new(2),
-            TransitionConstraintDegree::new(2),
-            TransitionConstraintDegree::new(2),
-            TransitionConstraintDegree::new(2),
-            TransitionConstraintDegree::new(2),
-            TransitionConstraintDegree::new(2),
-            TransitionConstraintDegree::new(2),
-        ];
+        let degrees = transition_constraint_degrees();
         assert_eq!(TRACE_WIDTH, trace_info.width());
         MulFib8Air {
             context: AirContext::new(trace_info, degrees, options),
@@ -48,6 +56,10 @@ impl Air for MulFib8Air {
         &self.context
     }
 
+    fn air_id() -> &'static str {
+        "winterfell-examples/mulfib8"
+    }
+
     fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
         &self,
         frame: &EvaluationFrame<E>,
@@ -89,4 +101,8 @@ impl Air for MulFib8Air {
             Assertion::single(6, last_step, self.result),
         ]
     }
+
+    fn validate_instance(trace_info: &TraceInfo, _pub_inputs: &BaseElement) -> Result<(), String> {
+        validate_trace_width(trace_info, TRACE_WIDTH)
+    }
 }
@@ -31,6 +31,14 @@ pub fn compute_mulfib_term(n: usize) -> BaseElement {
 
 #[cfg(test)]
 pub fn build_proof_options(use_extension_field: bool) -> winterfell::ProofOptions {
+    build_proof_options_with_grinding(use_extension_field, 0)
+}
+
+#[cfg(test)]
+pub fn build_proof_options_with_grinding(
+    use_extension_field: bool,
+    grinding_factor: u32,
+) -> winterfell::ProofOptions {
     use winterfell::{FieldExtension, HashFunction, ProofOptions};
 
     let extension = if use_extension_field {
@@ -38,5 +46,13 @@ pub fn build_proof_options(use_extension_field: bool) -> winterfell::ProofOption
     } else {
         FieldExtension::None
     };
-    ProofOptions::new(28, 8, 0, HashFunction::Blake3_256, extension, 4, 256)
+    ProofOptions::new(
+        28,
+        8,
+        grinding_factor,
+        HashFunction::Blake3_256,
+        extension,
+        4,
+        256,
+    )
 }
@@ -6,35 +6,65 @@
 use super::{
     message_to_elements, rescue, Example, PrivateKey, Signature, CYCLE_LENGTH, NUM_HASH_ROUNDS,
 };
-use crate::ExampleOptions;
+use crate::utils::{checkpoint::load_or_build_trace, max_trace_length, SizeRange};
+use crate::{ExampleOptions, DEFAULT_GRINDING_FACTOR};
 use log::debug;
-use std::time::Instant;
+use rand_utils::SeededRng;
+use std::{path::Path, time::Instant};
 use winterfell::{
     math::{fields::f128::BaseElement, get_power_series, log2, FieldElement, StarkField},
-    ProofOptions, Prover, StarkProof, Trace, TraceTable, VerifierError,
+    Air, AirDescription, Claim, ClaimError, ProofOptions, Prover, ProverError, ProverOptions,
+    Serializable, StarkProof, Trace, TraceInfo, TraceTable, TransitionConstraintDegree,
+    VerifierError,
 };
 
 mod air;
-use air::{LamportAggregateAir, PublicInputs};
+use air::{transition_constraint_degrees, LamportAggregateAir, PublicInputs};
 
 mod prover;
 use prover::LamportAggregateProver;
 
+#[cfg(test)]
+mod tests;
+
 // CONSTANTS
 // ================================================================================================
 
 const TRACE_WIDTH: usize = 22;
 const SIG_CYCLE_LENGTH: usize = 128 * CYCLE_LENGTH; // 1024 steps
 
+/// Blowup factor used by this example unless a different one is specified via `-b` option.
+pub const DEFAULT_BLOWUP_FACTOR: usize = 8;
+
+/// Valid numbers of signatures; each signature takes up one signature cycle of the trace.
+///
+/// The largest valid value depends on the blowup factor.
+pub const fn size_range(blowup_factor: usize) -> SizeRange {
+    SizeRange::power_of_two(
+        "number of signatures",
+        1,
+        max_trace_length(TRACE_WIDTH, blowup_factor) / SIG_CYCLE_LENGTH,
+    )
+}
+
+/// Valid sizes of this example with the default blowup factor.
+pub const SIZE_RANGE: SizeRange = size_range(DEFAULT_BLOWUP_FACTOR);
+
 // LAMPORT MULTI-MESSAGE, MULTI-KEY, SIGNATURE EXAMPLE
 // ================================================================================================
 pub fn get_example(options: ExampleOptions, num_signatures: usize) -> Box<dyn Example> {
     Box::new(LamportAggregateExample::new(
         num_signatures,
-        options.to_proof_options(28, 8),
+        options.get_seed(),
+        options.to_proof_options(28, DEFAULT_BLOWUP_FACTOR, DEFAULT_GRINDING_FACTOR),
     ))
 }
 
+/// Verifies a claim created by this example.
+pub fn verify_claim(claim: Claim) -> Result<(), ClaimError> {
+    claim.verify::<LamportAggregateAir>()
+}
+
 pub struct LamportAggregateExample {
     options: ProofOptions,
     pub_keys: Vec<[BaseElement; 2]>,
@@ -43,17 +73,20 @@ pub struct LamportAggregateExample {
 }
 
 impl LamportAggregateExample {
-    pub fn new(num_signatures: usize, options: ProofOptions) -> Self {
+    /// Returns a new example for the specified number of signatures; private keys of the signers
+    /// are generated pseudo-randomly from `seed`.
+    pub fn new(num_signatures: usize, seed: u64, options: ProofOptions) -> Self {
         assert!(
             num_signatures.is_power_of_two(),
             "number of signatures must be a power of 2"
         );
         // generate private/public key pairs for the specified number of signatures
+        let mut rng = SeededRng::new(seed);
         let mut private_keys = Vec::with_capacity(num_signatures);
         let mut public_keys = Vec::with_capacity(num_signatures);
         let now = Instant::now();
         for i in 0..num_signatures {
-            private_keys.push(PrivateKey::from_seed([i as u8; 32]));
+            private_keys.push(PrivateKey::from_seed(rng.seed()));
             public_keys.push(private_keys[i].pub_key().to_elements());
         }
         debug!(
@@ -99,13 +132,25 @@ impl LamportAggregateExample {
             signatures,
         }
     }
+
+    /// Returns public inputs of this example.
+    fn pub_inputs(&self) -> PublicInputs {
+        PublicInputs {
+            pub_keys: self.pub_keys.clone(),
+            messages: self.messages.clone(),
+        }
+    }
 }
 
 // EXAMPLE IMPLEMENTATION
 // ================================================================================================
 
 impl Example for LamportAggregateExample {
-    fn prove(&self) -> StarkProof {
+    fn prove_with(
+        &self,
+        prover_options: &ProverOptions,
+        checkpoint_dir: Option<&Path>,
+    ) -> Result<StarkProof, ProverError> {
         // generate the execution trace
         debug!(
             "Generating proof for verifying {} Lamport+ signatures \n\
@@ -118,7 +163,9 @@ impl Example for LamportAggregateExample {
             LamportAggregateProver::new(&self.pub_keys, &self.messages, self.options.clone());
 
         let now = Instant::now();
-        let trace = prover.build_trace(&self.messages, &self.signatures);
+        let trace = load_or_build_trace(checkpoint_dir, self, || {
+            prover.build_trace(&self.messages, &self.signatures)
+        });
         let trace_length = trace.length();
         debug!(
             "Generated execution trace of {} registers and 2^{} steps in {} ms",
@@ -128,7 +175,7 @@ impl Example for LamportAggregateExample {
         );
 
         // generate the proof
-        prover.prove(trace).unwrap()
+        prover.prove_with(trace, prover_options)
     }
 
     fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
@@ -148,4 +195,32 @@ impl Example for LamportAggregateExample {
         };
         winterfell::verify::<LamportAggregateAir>(proof, pub_inputs)
     }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn trace_info(&self) -> TraceInfo {
+        TraceInfo::new(TRACE_WIDTH, self.messages.len() * SIG_CYCLE_LENGTH)
+    }
+
+    fn transition_constraint_degrees(&self) -> Vec<TransitionConstraintDegree> {
+        transition_constraint_degrees()
+    }
+
+    fn air_id(&self) -> &'static str {
+        LamportAggregateAir::air_id()
+    }
+
+    fn describe_air(&self) -> AirDescription {
+        crate::describe_example_air::<LamportAggregateAir>(self)
+    }
+
+    fn pub_inputs_bytes(&self) -> Vec<u8> {
+        self.pub_inputs().to_bytes()
+    }
+
+    fn claim(&self, proof: StarkProof) -> Claim {
+        Claim::new::<LamportAggregateAir>(proof, &self.pub_inputs())
+    }
 }
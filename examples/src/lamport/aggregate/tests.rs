@@ -0,0 +1,61 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{super::build_proof_options, BaseElement, PublicInputs};
+use crate::Example;
+use winterfell::{FieldExtension, HashFunction, ProofOptions};
+
+#[test]
+fn lamport_aggregate_test_pub_inputs_serialization() {
+    let pub_inputs = PublicInputs {
+        pub_keys: vec![
+            [BaseElement::new(1), BaseElement::new(2)],
+            [BaseElement::new(3), BaseElement::new(4)],
+        ],
+        messages: vec![
+            [BaseElement::new(5), BaseElement::new(6)],
+            [BaseElement::new(7), BaseElement::new(8)],
+        ],
+    };
+    crate::tests::test_pub_inputs_serialization(pub_inputs);
+}
+
+#[test]
+fn lamport_aggregate_test_seeded_inputs() {
+    let build = |seed| super::LamportAggregateExample::new(4, seed, build_proof_options());
+    assert_eq!(build(7).pub_inputs_bytes(), build(7).pub_inputs_bytes());
+    assert_ne!(build(7).pub_inputs_bytes(), build(8).pub_inputs_bytes());
+}
+
+#[test]
+fn lamport_aggregate_test_validate_options() {
+    let lamport = super::LamportAggregateExample::new(1, 7, build_proof_options());
+    assert_eq!(Ok(()), lamport.validate_options(lamport.options()));
+
+    // hash constraints have degree 5 and are applied once per hash cycle
+    let options = ProofOptions::new(
+        28,
+        4,
+        0,
+        HashFunction::Blake3_256,
+        FieldExtension::None,
+        4,
+        256,
+    );
+    assert_eq!(
+        Err("lamport-a example requires blowup factor of at least 8, but was 4".to_string()),
+        lamport.validate_options(&options)
+    );
+}
+
+#[test]
+fn lamport_aggregate_test_transcript_consistency() {
+    let lamport = Box::new(super::LamportAggregateExample::new(
+        1,
+        7,
+        build_proof_options(),
+    ));
+    crate::tests::test_transcript_consistency(lamport);
+}
@@ -12,3 +12,18 @@ mod signature;
 use signature::{message_to_elements, PrivateKey, Signature};
 
 use crate::utils::rescue::{self, CYCLE_LENGTH, NUM_ROUNDS as NUM_HASH_ROUNDS};
+
+#[cfg(test)]
+pub fn build_proof_options() -> winterfell::ProofOptions {
+    use winterfell::{FieldExtension, HashFunction, ProofOptions};
+
+    ProofOptions::new(
+        28,
+        8,
+        0,
+        HashFunction::Blake3_256,
+        FieldExtension::None,
+        4,
+        256,
+    )
+}
@@ -7,34 +7,69 @@ use super::{
     message_to_elements, rescue, Example, PrivateKey, Signature, CYCLE_LENGTH as HASH_CYCLE_LENGTH,
     NUM_HASH_ROUNDS,
 };
-use crate::ExampleOptions;
+use crate::utils::{checkpoint::load_or_build_trace, max_trace_length, SizeRange};
+use crate::{ExampleOptions, DEFAULT_GRINDING_FACTOR};
 use log::debug;
-use std::time::Instant;
+use rand_utils::SeededRng;
+use std::{path::Path, time::Instant};
 use winterfell::{
     math::{fields::f128::BaseElement, get_power_series, log2, FieldElement, StarkField},
-    ProofOptions, Prover, StarkProof, Trace, TraceTable, VerifierError,
+    Air, AirDescription, Claim, ClaimError, ProofOptions, Prover, ProverError, ProverOptions,
+    Serializable, StarkProof, Trace, TraceInfo, TraceTable, TransitionConstraintDegree,
+    VerifierError,
 };
 
 mod signature;
 use signature::AggPublicKey;
 
 mod air;
-use air::{LamportThresholdAir, PublicInputs};
+use air::{transition_constraint_degrees, LamportThresholdAir, PublicInputs};
 
 mod prover;
 use prover::LamportThresholdProver;
 
+#[cfg(test)]
+mod tests;
+
 // CONSTANTS
 // ================================================================================================
 
 const TRACE_WIDTH: usize = 28;
 const SIG_CYCLE_LENGTH: usize = 128 * HASH_CYCLE_LENGTH; // 1024 steps
 
+/// Blowup factor used by this example unless a different one is specified via `-b` option.
+pub const DEFAULT_BLOWUP_FACTOR: usize = 8;
+
+/// Valid numbers of signers; each signer takes up one signature cycle of the trace, and the
+/// number of cycles is padded to the next power of two. With a single signer, the public key
+/// tree is too shallow for the trace to satisfy the AIR, and so at least 3 signers are required.
+///
+/// The largest valid value depends on the blowup factor.
+pub const fn size_range(blowup_factor: usize) -> SizeRange {
+    SizeRange::power_of_two_minus_one(
+        "number of signers",
+        3,
+        (max_trace_length(TRACE_WIDTH, blowup_factor) / SIG_CYCLE_LENGTH).saturating_sub(1),
+    )
+}
+
+/// Valid sizes of this example with the default blowup factor.
+pub const SIZE_RANGE: SizeRange = size_range(DEFAULT_BLOWUP_FACTOR);
+
 // LAMPORT THRESHOLD SIGNATURE EXAMPLE
 // ================================================================================================
 
 pub fn get_example(options: ExampleOptions, num_signers: usize) -> Box<dyn Example> {
-    Box::new(LamportThresholdExample::new(num_signers, options))
+    Box::new(LamportThresholdExample::new(
+        num_signers,
+        options.get_seed(),
+        options.to_proof_options(28, DEFAULT_BLOWUP_FACTOR, DEFAULT_GRINDING_FACTOR),
+    ))
+}
+
+/// Verifies a claim created by this example.
+pub fn verify_claim(claim: Claim) -> Result<(), ClaimError> {
+    claim.verify::<LamportThresholdAir>()
 }
 
 pub struct LamportThresholdExample {
@@ -45,14 +80,17 @@ pub struct LamportThresholdExample {
 }
 
 impl LamportThresholdExample {
-    pub fn new(num_signers: usize, options: ExampleOptions) -> Self {
+    /// Returns a new example for the specified number of signers; private keys of the signers,
+    /// and the subset of signers which sign the message are chosen pseudo-randomly from `seed`.
+    pub fn new(num_signers: usize, seed: u64, options: ProofOptions) -> Self {
         assert!(
             (num_signers + 1).is_power_of_two(),
             "number of signers must be one less than a power of 2"
         );
         // generate private/public key pairs for the specified number of signatures
+        let mut rng = SeededRng::new(seed);
         let now = Instant::now();
-        let private_keys = build_keys(num_signers);
+        let private_keys = build_keys(num_signers, &mut rng);
         debug!(
             "Generated {} private-public key pairs in {} ms",
             num_signers,
@@ -62,7 +100,7 @@ impl LamportThresholdExample {
 
         // sign the message with the subset of previously generated keys
         let message = "test message";
-        let selected_indexes = pick_random_indexes(num_signers);
+        let selected_indexes = pick_random_indexes(num_signers, &mut rng);
         let mut signatures = Vec::new();
         for &key_index in selected_indexes.iter() {
             let signature = private_keys[key_index].sign(message.as_bytes());
@@ -78,19 +116,33 @@ impl LamportThresholdExample {
         );
 
         LamportThresholdExample {
-            options: options.to_proof_options(28, 8),
+            options,
             pub_key,
             signatures,
             message: message_to_elements(message.as_bytes()),
         }
     }
+
+    /// Returns public inputs of this example.
+    fn pub_inputs(&self) -> PublicInputs {
+        PublicInputs {
+            pub_key_root: self.pub_key.root().to_elements(),
+            num_pub_keys: self.pub_key.num_keys(),
+            num_signatures: self.signatures.len(),
+            message: self.message,
+        }
+    }
 }
 
 // EXAMPLE IMPLEMENTATION
 // ================================================================================================
 
 impl Example for LamportThresholdExample {
-    fn prove(&self) -> StarkProof {
+    fn prove_with(
+        &self,
+        prover_options: &ProverOptions,
+        checkpoint_dir: Option<&Path>,
+    ) -> Result<StarkProof, ProverError> {
         // generate the execution trace
         debug!(
             "Generating proof for verifying {}-of-{} signature \n\
@@ -109,7 +161,9 @@ impl Example for LamportThresholdExample {
 
         // generate execution trace
         let now = Instant::now();
-        let trace = prover.build_trace(&self.pub_key, self.message, &self.signatures);
+        let trace = load_or_build_trace(checkpoint_dir, self, || {
+            prover.build_trace(&self.pub_key, self.message, &self.signatures)
+        });
         let trace_length = trace.length();
         debug!(
             "Generated execution trace of {} registers and 2^{} steps in {} ms",
@@ -119,7 +173,7 @@ impl Example for LamportThresholdExample {
         );
 
         // generate the proof
-        prover.prove(trace).unwrap()
+        prover.prove_with(trace, prover_options)
     }
 
     fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
@@ -141,25 +195,56 @@ impl Example for LamportThresholdExample {
         };
         winterfell::verify::<LamportThresholdAir>(proof, pub_inputs)
     }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn trace_info(&self) -> TraceInfo {
+        TraceInfo::new(
+            TRACE_WIDTH,
+            self.pub_key.num_keys().next_power_of_two() * SIG_CYCLE_LENGTH,
+        )
+    }
+
+    fn transition_constraint_degrees(&self) -> Vec<TransitionConstraintDegree> {
+        transition_constraint_degrees()
+    }
+
+    fn air_id(&self) -> &'static str {
+        LamportThresholdAir::air_id()
+    }
+
+    fn describe_air(&self) -> AirDescription {
+        crate::describe_example_air::<LamportThresholdAir>(self)
+    }
+
+    fn pub_inputs_bytes(&self) -> Vec<u8> {
+        self.pub_inputs().to_bytes()
+    }
+
+    fn claim(&self, proof: StarkProof) -> Claim {
+        Claim::new::<LamportThresholdAir>(proof, &self.pub_inputs())
+    }
 }
 
 // HELPER FUNCTIONS
 // ================================================================================================
-fn build_keys(num_keys: usize) -> Vec<PrivateKey> {
+fn build_keys(num_keys: usize, rng: &mut SeededRng) -> Vec<PrivateKey> {
     let mut result = Vec::with_capacity(num_keys);
-    for i in 0..num_keys {
-        result.push(PrivateKey::from_seed([i as u8; 32]));
+    for _ in 0..num_keys {
+        result.push(PrivateKey::from_seed(rng.seed()));
     }
     result.sort_by_key(|k| k.pub_key());
     result
 }
 
-fn pick_random_indexes(num_keys: usize) -> Vec<usize> {
+/// Returns a sorted list of indexes of 2/3 of the keys, selected pseudo-randomly.
+fn pick_random_indexes(num_keys: usize, rng: &mut SeededRng) -> Vec<usize> {
     let num_selected_keys = num_keys * 2 / 3;
-    let mut result = Vec::with_capacity(num_selected_keys);
-    // TODO: change to actual random selection
-    for i in 0..num_selected_keys {
-        result.push(i);
-    }
+    let mut result = (0..num_keys).collect::<Vec<_>>();
+    rng.shuffle(&mut result);
+    result.truncate(num_selected_keys);
+    result.sort_unstable();
     result
 }
@@ -0,0 +1,35 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{super::build_proof_options, BaseElement, PublicInputs};
+use crate::Example;
+
+#[test]
+fn lamport_threshold_test_pub_inputs_serialization() {
+    let pub_inputs = PublicInputs {
+        pub_key_root: [BaseElement::new(1), BaseElement::new(2)],
+        num_pub_keys: 8,
+        num_signatures: 5,
+        message: [BaseElement::new(3), BaseElement::new(4)],
+    };
+    crate::tests::test_pub_inputs_serialization(pub_inputs);
+}
+
+#[test]
+fn lamport_threshold_test_seeded_inputs() {
+    let build = |seed| super::LamportThresholdExample::new(3, seed, build_proof_options());
+    assert_eq!(build(7).pub_inputs_bytes(), build(7).pub_inputs_bytes());
+    assert_ne!(build(7).pub_inputs_bytes(), build(8).pub_inputs_bytes());
+}
+
+#[test]
+fn lamport_threshold_test_transcript_consistency() {
+    let lamport = Box::new(super::LamportThresholdExample::new(
+        3,
+        7,
+        build_proof_options(),
+    ));
+    crate::tests::test_transcript_consistency(lamport);
+}
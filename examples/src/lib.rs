@@ -3,9 +3,21 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use std::path::{Path, PathBuf};
 use structopt::StructOpt;
-use winterfell::{FieldExtension, HashFunction, ProofOptions, StarkProof, VerifierError};
+use winterfell::{
+    math::{
+        fields::{f128::BaseElement, CubeExtension, QuadExtension},
+        ExtensibleField, StarkField,
+    },
+    Air, AirDescription, Claim, Deserializable, FieldExtension, HashFunction, ProofOptions,
+    ProverError, ProverOptions, SliceReader, StarkProof, TraceInfo, TransitionConstraintDegree,
+    VerifierError,
+};
 
+pub mod counter;
+#[cfg(feature = "std")]
+pub mod experiments;
 pub mod fibonacci;
 #[cfg(feature = "std")]
 pub mod lamport;
@@ -13,6 +25,7 @@ pub mod lamport;
 pub mod merkle;
 pub mod rescue;
 pub mod utils;
+use utils::SizeRange;
 
 #[cfg(test)]
 mod tests;
@@ -21,23 +34,291 @@ mod tests;
 // ================================================================================================
 
 pub trait Example {
-    fn prove(&self) -> StarkProof;
+    /// Generates a proof for this example using the specified prover options.
+    ///
+    /// If `checkpoint_dir` is provided, the execution trace is loaded from a checkpoint in this
+    /// directory if one exists; otherwise, the trace is built and saved into the directory
+    /// before the proof is generated.
+    ///
+    /// # Errors
+    /// Returns an error if the prover fails to generate the proof (e.g., if the execution trace
+    /// is too large, or if grinding does not succeed within the allowed number of attempts).
+    fn prove_with(
+        &self,
+        prover_options: &ProverOptions,
+        checkpoint_dir: Option<&Path>,
+    ) -> Result<StarkProof, ProverError>;
+
+    /// Generates a proof for this example using default prover options.
+    ///
+    /// See [prove_with()](Example::prove_with) for the description of `checkpoint_dir`.
+    fn prove_with_checkpoint(
+        &self,
+        checkpoint_dir: Option<&Path>,
+    ) -> Result<StarkProof, ProverError> {
+        self.prove_with(&ProverOptions::default(), checkpoint_dir)
+    }
+
+    /// Generates a proof for this example without saving or loading the execution trace.
+    fn prove(&self) -> Result<StarkProof, ProverError> {
+        self.prove_with_checkpoint(None)
+    }
+
     fn verify(&self, proof: StarkProof) -> Result<(), VerifierError>;
     fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError>;
+
+    /// Returns proof options with which this example was instantiated.
+    fn options(&self) -> &ProofOptions;
+
+    /// Returns a description of the execution trace of this example.
+    ///
+    /// The description is computed without building the trace, and thus, can be used to check
+    /// whether the trace is small enough to be proven before any memory is allocated for it.
+    fn trace_info(&self) -> TraceInfo;
+
+    /// Returns degrees of transition constraints of the AIR used by this example.
+    fn transition_constraint_degrees(&self) -> Vec<TransitionConstraintDegree>;
+
+    /// Checks whether proofs for this example can be generated using the specified options, and
+    /// returns a description of the problem if they cannot.
+    ///
+    /// The options are checked via [validate_proof_options()] against the 128-bit field used by
+    /// all examples; errors name the example by its AIR identifier without the
+    /// `winterfell-examples/` prefix.
+    fn validate_options(&self, options: &ProofOptions) -> Result<(), String> {
+        let name = self.air_id().trim_start_matches("winterfell-examples/");
+        validate_proof_options::<BaseElement>(name, options, &self.transition_constraint_degrees())
+    }
+
+    /// Returns public inputs of this example serialized into bytes.
+    fn pub_inputs_bytes(&self) -> Vec<u8>;
+
+    /// Returns an identifier of the AIR used by this example.
+    fn air_id(&self) -> &'static str;
+
+    /// Returns a description of the AIR used by this example.
+    ///
+    /// The AIR is instantiated without building the execution trace; see
+    /// [describe_example_air()].
+    fn describe_air(&self) -> AirDescription;
+
+    /// Bundles the specified proof together with public inputs of this example into a claim.
+    ///
+    /// The claim can be verified via [verify_claim()] without instantiating the example.
+    fn claim(&self, proof: StarkProof) -> Claim;
+}
+
+/// Instantiates the example described by the specified options.
+///
+/// Returns `None` if the options describe a command which does not instantiate an example (e.g.,
+/// `verify`).
+pub fn get_example(options: ExampleOptions) -> Option<Box<dyn Example>> {
+    let example = match options.example {
+        ExampleType::Fib { sequence_length } => {
+            fibonacci::fib2::get_example(options, sequence_length)
+        }
+        ExampleType::Fib8 { sequence_length } => {
+            fibonacci::fib8::get_example(options, sequence_length)
+        }
+        ExampleType::Mulfib { sequence_length } => {
+            fibonacci::mulfib2::get_example(options, sequence_length)
+        }
+        ExampleType::Mulfib8 { sequence_length } => {
+            fibonacci::mulfib8::get_example(options, sequence_length)
+        }
+        ExampleType::Counter { trace_length } => counter::get_example(options, trace_length),
+        ExampleType::Rescue { chain_length } => rescue::get_example(options, chain_length),
+        #[cfg(feature = "std")]
+        ExampleType::Merkle { tree_depth } => merkle::get_example(options, tree_depth),
+        #[cfg(feature = "std")]
+        ExampleType::LamportA { num_signatures } => {
+            lamport::aggregate::get_example(options, num_signatures)
+        }
+        #[cfg(feature = "std")]
+        ExampleType::LamportT { num_signers } => {
+            lamport::threshold::get_example(options, num_signers)
+        }
+        ExampleType::Verify { .. } => return None,
+        ExampleType::Diff { .. } => return None,
+        #[cfg(feature = "std")]
+        ExampleType::Experiments { .. } => return None,
+    };
+    Some(example)
+}
+
+/// Verifies a serialized claim for the example with the specified name (e.g., `fib` or
+/// `lamport-a`).
+///
+/// Returns an error if the name does not refer to a known example, if the claim could not be
+/// deserialized, if the claim was created by a different example, or if the proof of the claim
+/// could not be verified.
+pub fn verify_claim(example: &str, claim: &[u8]) -> Result<(), String> {
+    let claim =
+        Claim::from_bytes(claim).map_err(|err| format!("claim deserialization failed: {}", err))?;
+    let result = match example {
+        "fib" => fibonacci::fib2::verify_claim(claim),
+        "fib8" => fibonacci::fib8::verify_claim(claim),
+        "mulfib" => fibonacci::mulfib2::verify_claim(claim),
+        "mulfib8" => fibonacci::mulfib8::verify_claim(claim),
+        "counter" => counter::verify_claim(claim),
+        "rescue" => rescue::verify_claim(claim),
+        #[cfg(feature = "std")]
+        "merkle" => merkle::verify_claim(claim),
+        #[cfg(feature = "std")]
+        "lamport-a" => lamport::aggregate::verify_claim(claim),
+        #[cfg(feature = "std")]
+        "lamport-t" => lamport::threshold::verify_claim(claim),
+        _ => return Err(format!("'{}' is not a known example", example)),
+    };
+    result.map_err(|err| err.to_string())
+}
+
+/// Returns a description of the AIR `A` instantiated for the specified example.
+///
+/// The AIR is instantiated from the trace info, proof options, and serialized public inputs of
+/// the example; neither the execution trace nor a proof is generated.
+///
+/// # Panics
+/// Panics if public inputs of the example cannot be deserialized into public inputs of `A`.
+pub fn describe_example_air<A>(example: &dyn Example) -> AirDescription
+where
+    A: Air,
+    A::PublicInputs: Deserializable,
+{
+    let pub_inputs = A::PublicInputs::read_from(&mut SliceReader::new(&example.pub_inputs_bytes()))
+        .expect("failed to deserialize public inputs of the example");
+    A::new(example.trace_info(), pub_inputs, example.options().clone()).describe()
+}
+
+/// Formats the specified AIR description as a pretty-printed JSON object.
+///
+/// The output is deterministic: object keys are sorted and all values are derived from the
+/// description only.
+#[cfg(feature = "std")]
+pub fn air_description_to_json(description: &AirDescription) -> String {
+    let transition_constraints = description
+        .transition_constraints
+        .iter()
+        .map(|constraint| {
+            serde_json::json!({
+                "label": constraint.label,
+                "base_degree": constraint.degree.base(),
+                "cycles": constraint.degree.cycles(),
+                "evaluation_degree": constraint.evaluation_degree,
+            })
+        })
+        .collect::<Vec<_>>();
+    let assertions = description
+        .assertions
+        .iter()
+        .map(|assertion| {
+            serde_json::json!({
+                "register": assertion.register,
+                "first_step": assertion.first_step,
+                "stride": assertion.stride,
+                "num_values": assertion.num_values,
+            })
+        })
+        .collect::<Vec<_>>();
+    let json = serde_json::json!({
+        "trace_width": description.trace_width,
+        "trace_length": description.trace_length,
+        "frame_offsets": description.frame_offsets,
+        "transition_constraints": transition_constraints,
+        "transition_divisor": description.transition_divisor,
+        "assertions": assertions,
+        "num_custom_boundary_constraints": description.num_custom_boundary_constraints,
+        "periodic_column_lengths": description.periodic_column_lengths,
+        "ce_blowup_factor": description.ce_blowup_factor,
+    });
+    serde_json::to_string_pretty(&json).expect("failed to serialize AIR description")
+}
+
+/// Checks whether the specified proof options can be used with an AIR which has transition
+/// constraints of the specified degrees and is defined over the field `B`.
+///
+/// Returns an error naming the `example` if the blowup factor is too small for the constraint
+/// degrees, or if the requested field extension is not supported for the field `B`.
+pub fn validate_proof_options<B>(
+    example: &str,
+    options: &ProofOptions,
+    degrees: &[TransitionConstraintDegree],
+) -> Result<(), String>
+where
+    B: StarkField + ExtensibleField<2> + ExtensibleField<3>,
+{
+    let min_blowup_factor = degrees
+        .iter()
+        .map(|degree| degree.min_blowup_factor())
+        .max()
+        .unwrap_or(0);
+    if options.blowup_factor() < min_blowup_factor {
+        return Err(format!(
+            "{} example requires blowup factor of at least {}, but was {}",
+            example,
+            min_blowup_factor,
+            options.blowup_factor()
+        ));
+    }
+
+    let extension_supported = match options.field_extension() {
+        FieldExtension::None => true,
+        FieldExtension::Quadratic => QuadExtension::<B>::is_supported(),
+        FieldExtension::Cubic => CubeExtension::<B>::is_supported(),
+    };
+    if !extension_supported {
+        return Err(format!(
+            "{} example does not support field extension of degree {}",
+            example,
+            options.field_extension().degree()
+        ));
+    }
+
+    Ok(())
 }
 
 // EXAMPLE OPTIONS
 // ================================================================================================
 
+/// Default grinding factor of the examples.
+///
+/// Grinding is disabled in debug builds so that tests and CI runs stay fast, and is set to 16 bits
+/// in release builds so that benchmarks are comparable.
+#[cfg(debug_assertions)]
+pub const DEFAULT_GRINDING_FACTOR: u32 = 0;
+
+/// Default grinding factor of the examples.
+///
+/// Grinding is disabled in debug builds so that tests and CI runs stay fast, and is set to 16 bits
+/// in release builds so that benchmarks are comparable.
+#[cfg(not(debug_assertions))]
+pub const DEFAULT_GRINDING_FACTOR: u32 = 16;
+
+/// Default hash function of the examples.
+pub const DEFAULT_HASH_FN: &str = "blake3_256";
+
+/// Default degree of the field extension used for composition polynomials of the examples.
+pub const DEFAULT_FIELD_EXTENSION: u32 = 1;
+
+/// Default folding factor of the FRI protocol used by the examples.
+pub const DEFAULT_FOLDING_FACTOR: usize = 8;
+
+/// Hash functions which can be used by the examples, together with their names.
+const HASH_FUNCTIONS: [(&str, HashFunction); 3] = [
+    ("blake3_192", HashFunction::Blake3_192),
+    ("blake3_256", HashFunction::Blake3_256),
+    ("sha3_256", HashFunction::Sha3_256),
+];
+
 #[derive(StructOpt, Debug)]
 #[structopt(name = "winterfell", about = "Winterfell examples")]
 pub struct ExampleOptions {
     #[structopt(subcommand)]
     pub example: ExampleType,
 
-    /// Hash function used in the protocol
-    #[structopt(short = "h", long = "hash_fn", default_value = "blake3_256")]
-    hash_fn: String,
+    /// Hash function used in the protocol; defaults to blake3_256
+    #[structopt(short = "h", long = "hash_fn")]
+    hash_fn: Option<String>,
 
     /// Number of queries to include in a proof
     #[structopt(short = "q", long = "queries")]
@@ -47,100 +328,296 @@ pub struct ExampleOptions {
     #[structopt(short = "b", long = "blowup")]
     blowup_factor: Option<usize>,
 
-    /// Grinding factor for query seed
-    #[structopt(short = "g", long = "grinding", default_value = "16")]
-    grinding_factor: u32,
+    /// Grinding factor for query seed; defaults to 16
+    #[structopt(short = "g", long = "grinding")]
+    grinding_factor: Option<u32>,
+
+    /// Field extension degree for composition polynomial; defaults to 1
+    #[structopt(short = "e", long = "field_extension")]
+    field_extension: Option<u32>,
+
+    /// Folding factor for FRI protocol; defaults to 8
+    #[structopt(short = "f", long = "folding")]
+    folding_factor: Option<usize>,
+
+    /// File into which the generated proof is written together with its public inputs
+    #[structopt(long = "proof-out", parse(from_os_str))]
+    pub proof_out: Option<PathBuf>,
+
+    /// Directory in which the execution trace is saved before proving, and from which it is
+    /// loaded if it was saved by an earlier run
+    #[structopt(long = "checkpoint-dir", parse(from_os_str))]
+    pub checkpoint_dir: Option<PathBuf>,
 
-    /// Field extension degree for composition polynomial
-    #[structopt(short = "e", long = "field_extension", default_value = "1")]
-    field_extension: u32,
+    /// Seed from which random inputs of the example are generated; if not specified, a random
+    /// seed is chosen and printed
+    #[structopt(long = "seed")]
+    pub seed: Option<u64>,
 
-    /// Folding factor for FRI protocol
-    #[structopt(short = "f", long = "folding", default_value = "8")]
-    folding_factor: usize,
+    /// Proven security level (in bits) below which a warning is printed
+    #[structopt(long = "min-proven-security")]
+    pub min_proven_security: Option<u32>,
+
+    /// File into which prover and verifier spans are written in Chrome trace format
+    #[cfg(feature = "tracing")]
+    #[structopt(long = "tracing-json", parse(from_os_str))]
+    pub tracing_json: Option<PathBuf>,
+
+    /// Print a JSON description of the AIR of the example instead of generating a proof
+    #[structopt(long = "describe-air")]
+    pub describe_air: bool,
 }
 
 impl ExampleOptions {
-    pub fn to_proof_options(&self, q: usize, b: usize) -> ProofOptions {
+    /// Returns the seed from which random inputs of the example should be generated; this is
+    /// the seed specified via `--seed` option, or a random seed if the option was not specified.
+    ///
+    /// The seed is printed so that the run can be reproduced.
+    #[cfg(feature = "std")]
+    pub fn get_seed(&self) -> u64 {
+        let seed = self.seed.unwrap_or_else(rand_utils::rand_value);
+        log::debug!("Generating example inputs from seed {}", seed);
+        seed
+    }
+
+    /// Checks whether the size argument (`-n`) of the example is valid for the blowup factor with
+    /// which proofs of the example will be generated.
+    pub fn check_size(&self) -> Result<(), String> {
+        match self.example.size() {
+            Some(n) => self.example.accepts_size(n, self.blowup_factor),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns proof options described by these example options.
+    ///
+    /// The number of queries `q`, the blowup factor `b`, and the grinding factor `g` are the
+    /// defaults of the example; they are used unless overridden via the corresponding
+    /// command-line options. Examples are expected to use [DEFAULT_GRINDING_FACTOR] for `g`.
+    pub fn to_proof_options(&self, q: usize, b: usize, g: u32) -> ProofOptions {
         let num_queries = self.num_queries.unwrap_or(q);
         let blowup_factor = self.blowup_factor.unwrap_or(b);
-        let field_extension = match self.field_extension {
-            1 => FieldExtension::None,
-            2 => FieldExtension::Quadratic,
-            3 => FieldExtension::Cubic,
-            val => panic!("'{}' is not a valid field extension option", val),
-        };
-        let hash_fn = match self.hash_fn.as_str() {
-            "blake3_192" => HashFunction::Blake3_192,
-            "blake3_256" => HashFunction::Blake3_256,
-            "sha3_256" => HashFunction::Sha3_256,
-            val => panic!("'{}' is not a valid hash function option", val),
-        };
+        let grinding_factor = self.grinding_factor.unwrap_or(g);
+        let field_extension =
+            parse_field_extension(self.field_extension.unwrap_or(DEFAULT_FIELD_EXTENSION))
+                .unwrap_or_else(|err| panic!("{}", err));
+        let hash_fn = parse_hash_fn(self.hash_fn.as_deref().unwrap_or(DEFAULT_HASH_FN))
+            .unwrap_or_else(|err| panic!("{}", err));
 
         ProofOptions::new(
             num_queries,
             blowup_factor,
-            self.grinding_factor,
+            grinding_factor,
             hash_fn,
             field_extension,
-            self.folding_factor,
+            self.folding_factor.unwrap_or(DEFAULT_FOLDING_FACTOR),
             256,
         )
     }
 }
 
+/// Returns the hash function with the specified name, or an error if no such hash function can
+/// be used by the examples.
+pub(crate) fn parse_hash_fn(name: &str) -> Result<HashFunction, String> {
+    HASH_FUNCTIONS
+        .iter()
+        .find(|(hash_fn_name, _)| *hash_fn_name == name)
+        .map(|&(_, hash_fn)| hash_fn)
+        .ok_or_else(|| format!("'{}' is not a valid hash function", name))
+}
+
+/// Returns the field extension of the specified degree, or an error if the degree is not 1, 2,
+/// or 3.
+pub(crate) fn parse_field_extension(degree: u32) -> Result<FieldExtension, String> {
+    match degree {
+        1 => Ok(FieldExtension::None),
+        2 => Ok(FieldExtension::Quadratic),
+        3 => Ok(FieldExtension::Cubic),
+        _ => Err(format!(
+            "'{}' is not a valid field extension degree",
+            degree
+        )),
+    }
+}
+
 #[derive(StructOpt, Debug)]
 //#[structopt(about = "available examples")]
 pub enum ExampleType {
     /// Compute a Fibonacci sequence using trace table with 2 registers
     Fib {
-        /// Length of Fibonacci sequence; must be a power of two
-        #[structopt(short = "n", default_value = "1048576")]
+        #[structopt(short = "n", default_value = "1048576", help = fibonacci::fib2::SIZE_RANGE.help())]
         sequence_length: usize,
     },
     /// Compute a Fibonacci sequence using trace table with 8 registers
     Fib8 {
-        /// Length of Fibonacci sequence; must be a power of two
-        #[structopt(short = "n", default_value = "1048576")]
+        #[structopt(short = "n", default_value = "1048576", help = fibonacci::fib8::SIZE_RANGE.help())]
         sequence_length: usize,
     },
     /// Compute a multiplicative Fibonacci sequence using trace table with 2 registers
     Mulfib {
-        /// Length of Fibonacci sequence; must be a power of two
-        #[structopt(short = "n", default_value = "1048576")]
+        #[structopt(short = "n", default_value = "1048576", help = fibonacci::mulfib2::SIZE_RANGE.help())]
         sequence_length: usize,
     },
     /// Compute a multiplicative Fibonacci sequence using trace table with 8 registers
     Mulfib8 {
-        /// Length of Fibonacci sequence; must be a power of two
-        #[structopt(short = "n", default_value = "1048576")]
+        #[structopt(short = "n", default_value = "1048576", help = fibonacci::mulfib8::SIZE_RANGE.help())]
         sequence_length: usize,
     },
+    /// Compute a counter which resets every 8 steps without a selector column
+    Counter {
+        #[structopt(short = "n", default_value = "1048576", help = counter::SIZE_RANGE.help())]
+        trace_length: usize,
+    },
     /// Compute a hash chain using Rescue hash function
     Rescue {
-        /// Length of the hash chain; must be a power of two
-        #[structopt(short = "n", default_value = "1024")]
+        #[structopt(short = "n", default_value = "1024", help = rescue::SIZE_RANGE.help())]
         chain_length: usize,
     },
     /// Compute a root of a Merkle path using Rescue hash function
     #[cfg(feature = "std")]
     Merkle {
-        /// Depth of the Merkle tree; must be one less than a power of two
-        #[structopt(short = "n", default_value = "7")]
+        #[structopt(short = "n", default_value = "7", help = merkle::SIZE_RANGE.help())]
         tree_depth: usize,
     },
     /// Compute an aggregate Lamport+ signature
     #[cfg(feature = "std")]
     LamportA {
-        /// Number of signatures to aggregate; must be a power of two
-        #[structopt(short = "n", default_value = "4")]
+        #[structopt(short = "n", default_value = "4", help = lamport::aggregate::SIZE_RANGE.help())]
         num_signatures: usize,
     },
     /// Compute a threshold Lamport+ signature
     #[cfg(feature = "std")]
     LamportT {
-        /// Number of signers; must be one less than a power of two
-        #[structopt(short = "n", default_value = "3")]
+        #[structopt(short = "n", default_value = "3", help = lamport::threshold::SIZE_RANGE.help())]
         num_signers: usize,
     },
+    /// Verify a previously generated proof for one of the examples
+    Verify {
+        /// Name of the example for which the proof was generated (e.g., fib)
+        example: String,
+        /// File containing the proof together with its public inputs
+        #[structopt(long = "proof-in", parse(from_os_str))]
+        proof_in: PathBuf,
+    },
+    /// Compare two execution traces saved into checkpoint files and print their differences
+    Diff {
+        /// File containing the first trace
+        #[structopt(parse(from_os_str))]
+        left: PathBuf,
+        /// File containing the second trace
+        #[structopt(parse(from_os_str))]
+        right: PathBuf,
+    },
+    /// Run a set of examples described by a JSON config file and record the results
+    #[cfg(feature = "std")]
+    Experiments {
+        /// JSON file containing an array of run specs
+        #[structopt(long = "config", parse(from_os_str))]
+        config: PathBuf,
+        /// File into which results are written; CSV is used for files with .csv extension, and
+        /// JSON otherwise
+        #[structopt(long = "results-out", parse(from_os_str))]
+        results_out: PathBuf,
+    },
+}
+
+impl ExampleType {
+    /// Returns a description of the example with the specified name (e.g., `fib` or `lamport-a`)
+    /// and size argument, or an error if the name does not refer to a known example.
+    pub fn from_name(name: &str, size: usize) -> Result<Self, String> {
+        let example = match name {
+            "fib" => ExampleType::Fib {
+                sequence_length: size,
+            },
+            "fib8" => ExampleType::Fib8 {
+                sequence_length: size,
+            },
+            "mulfib" => ExampleType::Mulfib {
+                sequence_length: size,
+            },
+            "mulfib8" => ExampleType::Mulfib8 {
+                sequence_length: size,
+            },
+            "counter" => ExampleType::Counter { trace_length: size },
+            "rescue" => ExampleType::Rescue { chain_length: size },
+            #[cfg(feature = "std")]
+            "merkle" => ExampleType::Merkle { tree_depth: size },
+            #[cfg(feature = "std")]
+            "lamport-a" => ExampleType::LamportA {
+                num_signatures: size,
+            },
+            #[cfg(feature = "std")]
+            "lamport-t" => ExampleType::LamportT { num_signers: size },
+            _ => return Err(format!("'{}' is not a known example", name)),
+        };
+        Ok(example)
+    }
+
+    /// Returns the value of the size argument (`-n`) of this example, or `None` if the command
+    /// does not instantiate an example.
+    pub fn size(&self) -> Option<usize> {
+        match *self {
+            ExampleType::Fib { sequence_length } => Some(sequence_length),
+            ExampleType::Fib8 { sequence_length } => Some(sequence_length),
+            ExampleType::Mulfib { sequence_length } => Some(sequence_length),
+            ExampleType::Mulfib8 { sequence_length } => Some(sequence_length),
+            ExampleType::Counter { trace_length } => Some(trace_length),
+            ExampleType::Rescue { chain_length } => Some(chain_length),
+            #[cfg(feature = "std")]
+            ExampleType::Merkle { tree_depth } => Some(tree_depth),
+            #[cfg(feature = "std")]
+            ExampleType::LamportA { num_signatures } => Some(num_signatures),
+            #[cfg(feature = "std")]
+            ExampleType::LamportT { num_signers } => Some(num_signers),
+            ExampleType::Verify { .. } => None,
+            ExampleType::Diff { .. } => None,
+            #[cfg(feature = "std")]
+            ExampleType::Experiments { .. } => None,
+        }
+    }
+
+    /// Returns the range of valid values of the size argument (`-n`) of this example when proofs
+    /// are generated with the specified blowup factor, or `None` if the command does not
+    /// instantiate an example.
+    ///
+    /// If `blowup_factor` is `None`, the default blowup factor of the example is assumed.
+    pub fn size_range(&self, blowup_factor: Option<usize>) -> Option<SizeRange> {
+        macro_rules! range {
+            ($example:path) => {{
+                use $example as example;
+                example::size_range(blowup_factor.unwrap_or(example::DEFAULT_BLOWUP_FACTOR))
+            }};
+        }
+
+        match self {
+            ExampleType::Fib { .. } => Some(range!(fibonacci::fib2)),
+            ExampleType::Fib8 { .. } => Some(range!(fibonacci::fib8)),
+            ExampleType::Mulfib { .. } => Some(range!(fibonacci::mulfib2)),
+            ExampleType::Mulfib8 { .. } => Some(range!(fibonacci::mulfib8)),
+            ExampleType::Counter { .. } => Some(range!(counter)),
+            ExampleType::Rescue { .. } => Some(range!(rescue)),
+            #[cfg(feature = "std")]
+            ExampleType::Merkle { .. } => Some(merkle::SIZE_RANGE),
+            #[cfg(feature = "std")]
+            ExampleType::LamportA { .. } => Some(range!(lamport::aggregate)),
+            #[cfg(feature = "std")]
+            ExampleType::LamportT { .. } => Some(range!(lamport::threshold)),
+            ExampleType::Verify { .. } => None,
+            ExampleType::Diff { .. } => None,
+            #[cfg(feature = "std")]
+            ExampleType::Experiments { .. } => None,
+        }
+    }
+
+    /// Checks whether `n` is a valid size argument for this example when proofs are generated
+    /// with the specified blowup factor, and returns a description of the valid sizes together
+    /// with the nearest valid value if it is not.
+    ///
+    /// If `blowup_factor` is `None`, the default blowup factor of the example is assumed.
+    pub fn accepts_size(&self, n: usize, blowup_factor: Option<usize>) -> Result<(), String> {
+        match self.size_range(blowup_factor) {
+            Some(range) => range.check(n),
+            None => Ok(()),
+        }
+    }
 }
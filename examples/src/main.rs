@@ -3,15 +3,18 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use log::debug;
+use log::{debug, warn};
 use std::io::Write;
-use std::time::Instant;
+use std::path::Path;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use std::{fs, process};
 use structopt::StructOpt;
-use winterfell::StarkProof;
+use winterfell::{math::fields::f128::BaseElement, Claim, ProverOptions, StarkProof};
 
-use examples::{fibonacci, rescue, ExampleOptions, ExampleType};
-#[cfg(feature = "std")]
-use examples::{lamport, merkle};
+use examples::{
+    utils::{self, checkpoint},
+    ExampleOptions, ExampleType,
+};
 
 // EXAMPLE RUNNER
 // ================================================================================================
@@ -26,38 +29,92 @@ fn main() {
     // read command-line args
     let options = ExampleOptions::from_args();
 
+    // write prover and verifier spans into a Chrome trace file, if requested; the file is
+    // flushed when the guard is dropped
+    #[cfg(feature = "tracing")]
+    let _tracing_guard = options.tracing_json.as_deref().map(init_chrome_tracing);
+
     debug!("============================================================");
 
+    // verify a previously generated proof, if requested
+    if let ExampleType::Verify { example, proof_in } = &options.example {
+        verify_saved_proof(example, proof_in);
+        debug!("============================================================");
+        return;
+    }
+
+    // compare two saved execution traces, if requested
+    if let ExampleType::Diff { left, right } = &options.example {
+        diff_saved_traces(left, right);
+        debug!("============================================================");
+        return;
+    }
+
+    // run a set of experiments, if requested
+    #[cfg(feature = "std")]
+    if let ExampleType::Experiments {
+        config,
+        results_out,
+    } = &options.example
+    {
+        run_experiments(config, results_out);
+        debug!("============================================================");
+        return;
+    }
+
+    // make sure the example can be instantiated with the specified size
+    if let Err(err) = options.check_size() {
+        eprintln!("Invalid example size: {}", err);
+        process::exit(1);
+    }
+
     // instantiate and prepare the example
-    let example = match options.example {
-        ExampleType::Fib { sequence_length } => {
-            fibonacci::fib2::get_example(options, sequence_length)
-        }
-        ExampleType::Fib8 { sequence_length } => {
-            fibonacci::fib8::get_example(options, sequence_length)
-        }
-        ExampleType::Mulfib { sequence_length } => {
-            fibonacci::mulfib2::get_example(options, sequence_length)
-        }
-        ExampleType::Mulfib8 { sequence_length } => {
-            fibonacci::mulfib8::get_example(options, sequence_length)
-        }
-        ExampleType::Rescue { chain_length } => rescue::get_example(options, chain_length),
-        #[cfg(feature = "std")]
-        ExampleType::Merkle { tree_depth } => merkle::get_example(options, tree_depth),
-        #[cfg(feature = "std")]
-        ExampleType::LamportA { num_signatures } => {
-            lamport::aggregate::get_example(options, num_signatures)
-        }
-        #[cfg(feature = "std")]
-        ExampleType::LamportT { num_signers } => {
-            lamport::threshold::get_example(options, num_signers)
-        }
-    };
+    let proof_out = options.proof_out.clone();
+    let min_proven_security = options.min_proven_security;
+    let checkpoint_dir = options.checkpoint_dir.clone();
+    let describe_air = options.describe_air;
+    let example = examples::get_example(options).expect("command has already been handled");
+
+    // print the description of the AIR instead of generating a proof, if requested
+    #[cfg(feature = "std")]
+    if describe_air {
+        println!(
+            "{}",
+            examples::air_description_to_json(&example.describe_air())
+        );
+        return;
+    }
+
+    // make sure proofs for the example can be generated with the specified options
+    if let Err(err) = example.validate_options(example.options()) {
+        eprintln!("Invalid proof options: {}", err);
+        std::process::exit(1);
+    }
+
+    // make sure the execution trace of the example is not too large to be proven; this is checked
+    // before the trace is built so that oversized traces do not exhaust memory
+    let prover_options = ProverOptions::default();
+    if let Err(err) = utils::check_trace_size(example.as_ref(), &prover_options) {
+        eprintln!("Execution trace is too large: {}", err);
+        process::exit(1);
+    }
+
+    // make sure the checkpoint directory exists, if checkpointing was requested
+    if let Some(dir) = &checkpoint_dir {
+        fs::create_dir_all(dir).unwrap_or_else(|err| {
+            eprintln!("Failed to create {}: {}", dir.display(), err);
+            process::exit(1);
+        });
+    }
 
     // generate proof
     let now = Instant::now();
-    let proof = example.prove();
+    let proof = example
+        .prove_with(&prover_options, checkpoint_dir.as_deref())
+        .unwrap_or_else(|err| {
+            eprintln!("Failed to generate proof: {}", err);
+            process::exit(1);
+        });
     debug!(
         "---------------------\nProof generated in {} ms",
         now.elapsed().as_millis()
@@ -65,13 +122,23 @@ fn main() {
 
     let proof_bytes = proof.to_bytes();
     debug!("Proof size: {:.1} KB", proof_bytes.len() as f64 / 1024f64);
-    debug!("Proof security: {} bits", proof.security_level(true));
+    print_security_level(&proof, min_proven_security);
     #[cfg(feature = "std")]
     debug!(
         "Proof hash: {}",
         hex::encode(blake3::hash(&proof_bytes).as_bytes())
     );
 
+    // save the proof together with public inputs as a claim, if requested
+    if let Some(path) = proof_out {
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_secs());
+        let claim = example.claim(proof.clone()).with_created_at(created_at);
+        write_file(&path, &claim.to_bytes());
+        debug!("Proof and public inputs written to {}", path.display());
+    }
+
     // verify the proof
     debug!("---------------------");
     let parsed_proof = StarkProof::from_bytes(&proof_bytes).unwrap();
@@ -86,3 +153,134 @@ fn main() {
     }
     debug!("============================================================");
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Installs a global tracing subscriber which writes spans into the specified file in Chrome
+/// trace format, and returns a guard which flushes the file when dropped.
+#[cfg(feature = "tracing")]
+fn init_chrome_tracing(path: &Path) -> tracing_chrome::FlushGuard {
+    use tracing_subscriber::prelude::*;
+
+    let (layer, guard) = tracing_chrome::ChromeLayerBuilder::new()
+        .file(path)
+        .include_args(true)
+        .build();
+    tracing::subscriber::set_global_default(tracing_subscriber::registry().with(layer))
+        .expect("failed to set global tracing subscriber");
+    debug!("Writing spans to {}", path.display());
+    guard
+}
+
+/// Prints conjectured and proven security levels of the specified proof, and warns if the proven
+/// security level is below `min_proven_security`.
+fn print_security_level(proof: &StarkProof, min_proven_security: Option<u32>) {
+    let conjectured_security = proof.security_level(true);
+    let proven_security = proof.security_level(false);
+    debug!(
+        "Proof security: {} bits (conjectured), {} bits (proven)",
+        conjectured_security, proven_security
+    );
+    if let Some(min_security) = min_proven_security {
+        if proven_security < min_security {
+            warn!(
+                "Proven security of {} bits is below the minimum of {} bits; conjectured security \
+                is {} bits",
+                proven_security, min_security, conjectured_security
+            );
+        }
+    }
+}
+
+/// Reads a claim from the specified file, verifies the claim for the example with the specified
+/// name, and prints the result.
+fn verify_saved_proof(example: &str, proof_in: &Path) {
+    let claim_bytes = read_file(proof_in);
+
+    debug!("Verifying proof for {} example", example);
+    if let Ok(claim) = Claim::from_bytes(&claim_bytes) {
+        let proof_bytes = claim.proof.to_bytes();
+        debug!("Proof size: {:.1} KB", proof_bytes.len() as f64 / 1024f64);
+        print_security_level(&claim.proof, None);
+        #[cfg(feature = "std")]
+        debug!(
+            "Proof hash: {}",
+            hex::encode(blake3::hash(&proof_bytes).as_bytes())
+        );
+    }
+
+    debug!("---------------------");
+    let now = Instant::now();
+    match examples::verify_claim(example, &claim_bytes) {
+        Ok(_) => debug!(
+            "Proof verified in {:.1} ms",
+            now.elapsed().as_micros() as f64 / 1000f64
+        ),
+        Err(msg) => {
+            debug!("Failed to verify proof: {}", msg);
+            process::exit(1);
+        }
+    }
+}
+
+/// Loads execution traces from the specified checkpoint files, and prints their differences.
+///
+/// Exits with a non-zero status if the traces differ or could not be loaded.
+fn diff_saved_traces(left: &Path, right: &Path) {
+    let load = |path: &Path| {
+        checkpoint::load_trace::<BaseElement>(path).unwrap_or_else(|err| {
+            eprintln!("Failed to load trace from {}: {}", path.display(), err);
+            process::exit(1);
+        })
+    };
+    let diff = load(left).diff(&load(right));
+    println!("{}", diff);
+    if !diff.is_empty() {
+        process::exit(1);
+    }
+}
+
+/// Runs the experiments described by the config file at `config` and writes the results into the
+/// file at `results_out`.
+#[cfg(feature = "std")]
+fn run_experiments(config: &Path, results_out: &Path) {
+    let config = String::from_utf8(read_file(config)).unwrap_or_else(|err| {
+        eprintln!("Failed to read {}: {}", config.display(), err);
+        process::exit(1);
+    });
+    let specs = examples::experiments::parse_config(&config).unwrap_or_else(|err| {
+        eprintln!("Invalid experiments config: {}", err);
+        process::exit(1);
+    });
+
+    let results = examples::experiments::run_all(&specs);
+    let num_failed = results.iter().filter(|result| !result.success).count();
+    let is_csv = results_out.extension().map_or(false, |ext| ext == "csv");
+    let output = if is_csv {
+        examples::experiments::to_csv(&results)
+    } else {
+        examples::experiments::to_json(&results)
+    };
+    write_file(results_out, output.as_bytes());
+    debug!(
+        "Completed {} runs ({} failed); results written to {}",
+        results.len(),
+        num_failed,
+        results_out.display()
+    );
+}
+
+fn read_file(path: &Path) -> Vec<u8> {
+    fs::read(path).unwrap_or_else(|err| {
+        eprintln!("Failed to read {}: {}", path.display(), err);
+        process::exit(1);
+    })
+}
+
+fn write_file(path: &Path, bytes: &[u8]) {
+    fs::write(path, bytes).unwrap_or_else(|err| {
+        eprintln!("Failed to write {}: {}", path.display(), err);
+        process::exit(1);
+    })
+}
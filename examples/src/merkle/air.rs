@@ -4,25 +4,37 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{rescue, BaseElement, FieldElement, HASH_CYCLE_LEN, HASH_STATE_WIDTH, TRACE_WIDTH};
-use crate::utils::{are_equal, is_binary, is_zero, not, EvaluationResult};
+use crate::utils::{are_equal, is_binary, is_zero, not, validate_trace_width, EvaluationResult};
 use winterfell::{
-    Air, AirContext, Assertion, ByteWriter, EvaluationFrame, ProofOptions, Serializable, TraceInfo,
-    TransitionConstraintDegree,
+    Air, AirContext, Assertion, Deserializable, EvaluationFrame, ProofOptions, Serializable,
+    TraceInfo, TransitionConstraintDegree,
 };
 
+// TRANSITION CONSTRAINT DEGREES
+// ================================================================================================
+
+/// Returns degrees of all transition constraints of this AIR.
+pub(super) fn transition_constraint_degrees() -> Vec<TransitionConstraintDegree> {
+    vec![
+        TransitionConstraintDegree::with_cycles(5, vec![HASH_CYCLE_LEN]),
+        TransitionConstraintDegree::with_cycles(5, vec![HASH_CYCLE_LEN]),
+        TransitionConstraintDegree::with_cycles(5, vec![HASH_CYCLE_LEN]),
+        TransitionConstraintDegree::with_cycles(5, vec![HASH_CYCLE_LEN]),
+        TransitionConstraintDegree::with_cycles(5, vec![HASH_CYCLE_LEN]),
+        TransitionConstraintDegree::with_cycles(5, vec![HASH_CYCLE_LEN]),
+        TransitionConstraintDegree::new(2),
+    ]
+}
+
 // MERKLE PATH VERIFICATION AIR
 // ================================================================================================
 
+#[derive(Serializable, Deserializable)]
+#[winter_utils(crate = "winterfell")]
 pub struct PublicInputs {
     pub tree_root: [BaseElement; 2],
 }
 
-impl Serializable for PublicInputs {
-    fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        target.write(&self.tree_root[..]);
-    }
-}
-
 pub struct MerkleAir {
     context: AirContext<BaseElement>,
     tree_root: [BaseElement; 2],
@@ -35,15 +47,7 @@ impl Air for MerkleAir {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
-        let degrees = vec![
-            TransitionConstraintDegree::with_cycles(5, vec![HASH_CYCLE_LEN]),
-            TransitionConstraintDegree::with_cycles(5, vec![HASH_CYCLE_LEN]),
-            TransitionConstraintDegree::with_cycles(5, vec![HASH_CYCLE_LEN]),
-            TransitionConstraintDegree::with_cycles(5, vec![HASH_CYCLE_LEN]),
-            TransitionConstraintDegree::with_cycles(5, vec![HASH_CYCLE_LEN]),
-            TransitionConstraintDegree::with_cycles(5, vec![HASH_CYCLE_LEN]),
-            TransitionConstraintDegree::new(2),
-        ];
+        let degrees = transition_constraint_degrees();
         assert_eq!(TRACE_WIDTH, trace_info.width());
         MerkleAir {
             context: AirContext::new(trace_info, degrees, options),
@@ -55,6 +59,10 @@ impl Air for MerkleAir {
         &self.context
     }
 
+    fn air_id() -> &'static str {
+        "winterfell-examples/merkle"
+    }
+
     fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
         &self,
         frame: &EvaluationFrame<E>,
@@ -116,6 +124,9 @@ impl Air for MerkleAir {
         result.append(&mut rescue::get_round_constants());
         result
     }
+    fn validate_instance(trace_info: &TraceInfo, _pub_inputs: &PublicInputs) -> Result<(), String> {
+        validate_trace_width(trace_info, TRACE_WIDTH)
+    }
 }
 
 // MASKS
@@ -8,20 +8,26 @@ use crate::utils::rescue::{
     STATE_WIDTH as HASH_STATE_WIDTH,
 };
 use crate::{
-    utils::rescue::{Hash, Rescue128},
-    Example, ExampleOptions,
+    utils::{
+        checkpoint::load_or_build_trace,
+        rescue::{Hash, Rescue128},
+        SizeRange,
+    },
+    Example, ExampleOptions, DEFAULT_GRINDING_FACTOR,
 };
 use log::debug;
-use rand_utils::{rand_value, rand_vector};
-use std::time::Instant;
+use rand_utils::SeededRng;
+use std::{path::Path, time::Instant};
 use winterfell::{
     crypto::{Digest, MerkleTree},
-    math::{fields::f128::BaseElement, log2, FieldElement, StarkField},
-    ProofOptions, Prover, StarkProof, Trace, TraceTable, VerifierError,
+    math::{fields::f128::BaseElement, log2, FieldElement},
+    Air, AirDescription, Claim, ClaimError, ProofOptions, Prover, ProverError, ProverOptions,
+    Serializable, StarkProof, Trace, TraceInfo, TraceTable, TransitionConstraintDegree,
+    VerifierError,
 };
 
 mod air;
-use air::{MerkleAir, PublicInputs};
+use air::{transition_constraint_degrees, MerkleAir, PublicInputs};
 
 mod prover;
 use prover::MerkleProver;
@@ -34,15 +40,25 @@ mod tests;
 
 const TRACE_WIDTH: usize = 7;
 
+/// Valid depths of the Merkle tree; the full tree is built in memory, and so the number of leaves
+/// rather than the trace length limits the depth.
+pub const SIZE_RANGE: SizeRange = SizeRange::power_of_two_minus_one("tree depth", 1, 15);
+
 // MERKLE AUTHENTICATION PATH EXAMPLE
 // ================================================================================================
 pub fn get_example(options: ExampleOptions, tree_depth: usize) -> Box<dyn Example> {
     Box::new(MerkleExample::new(
         tree_depth,
-        options.to_proof_options(28, 8),
+        options.get_seed(),
+        options.to_proof_options(28, 8, DEFAULT_GRINDING_FACTOR),
     ))
 }
 
+/// Verifies a claim created by this example.
+pub fn verify_claim(claim: Claim) -> Result<(), ClaimError> {
+    claim.verify::<MerkleAir>()
+}
+
 pub struct MerkleExample {
     options: ProofOptions,
     tree_root: Hash,
@@ -52,18 +68,20 @@ pub struct MerkleExample {
 }
 
 impl MerkleExample {
-    pub fn new(tree_depth: usize, options: ProofOptions) -> MerkleExample {
+    /// Returns a new example for a Merkle tree of the specified depth; leaves of the tree and
+    /// the index of the leaf to authenticate are generated pseudo-randomly from `seed`.
+    pub fn new(tree_depth: usize, seed: u64, options: ProofOptions) -> MerkleExample {
         assert!(
             (tree_depth + 1).is_power_of_two(),
             "tree depth must be one less than a power of 2"
         );
+        let mut rng = SeededRng::new(seed);
         let value = [BaseElement::new(42), BaseElement::new(43)];
-        let index =
-            (rand_value::<BaseElement>().as_int() % u128::pow(2, tree_depth as u32)) as usize;
+        let index = rng.index(usize::pow(2, tree_depth as u32));
 
         // build Merkle tree of the specified depth
         let now = Instant::now();
-        let tree = build_merkle_tree(tree_depth, value, index);
+        let tree = build_merkle_tree(tree_depth, value, index, &mut rng);
         debug!(
             "Built Merkle tree of depth {} in {} ms",
             tree_depth,
@@ -88,13 +106,24 @@ impl MerkleExample {
             path,
         }
     }
+
+    /// Returns public inputs of this example.
+    fn pub_inputs(&self) -> PublicInputs {
+        PublicInputs {
+            tree_root: self.tree_root.to_elements(),
+        }
+    }
 }
 
 // EXAMPLE IMPLEMENTATION
 // ================================================================================================
 
 impl Example for MerkleExample {
-    fn prove(&self) -> StarkProof {
+    fn prove_with(
+        &self,
+        prover_options: &ProverOptions,
+        checkpoint_dir: Option<&Path>,
+    ) -> Result<StarkProof, ProverError> {
         // generate the execution trace
         debug!(
             "Generating proof for proving membership in a Merkle tree of depth {}\n\
@@ -106,7 +135,9 @@ impl Example for MerkleExample {
 
         // generate the execution trace
         let now = Instant::now();
-        let trace = prover.build_trace(self.value, &self.path, self.index);
+        let trace = load_or_build_trace(checkpoint_dir, self, || {
+            prover.build_trace(self.value, &self.path, self.index)
+        });
         let trace_length = trace.length();
         debug!(
             "Generated execution trace of {} registers and 2^{} steps in {} ms",
@@ -116,7 +147,7 @@ impl Example for MerkleExample {
         );
 
         // generate the proof
-        prover.prove(trace).unwrap()
+        prover.prove_with(trace, prover_options)
     }
 
     fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
@@ -133,13 +164,46 @@ impl Example for MerkleExample {
         };
         winterfell::verify::<MerkleAir>(proof, pub_inputs)
     }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn trace_info(&self) -> TraceInfo {
+        TraceInfo::new(TRACE_WIDTH, self.path.len() * HASH_CYCLE_LEN)
+    }
+
+    fn transition_constraint_degrees(&self) -> Vec<TransitionConstraintDegree> {
+        transition_constraint_degrees()
+    }
+
+    fn air_id(&self) -> &'static str {
+        MerkleAir::air_id()
+    }
+
+    fn describe_air(&self) -> AirDescription {
+        crate::describe_example_air::<MerkleAir>(self)
+    }
+
+    fn pub_inputs_bytes(&self) -> Vec<u8> {
+        self.pub_inputs().to_bytes()
+    }
+
+    fn claim(&self, proof: StarkProof) -> Claim {
+        Claim::new::<MerkleAir>(proof, &self.pub_inputs())
+    }
 }
 
 // HELPER FUNCTIONS
 // ================================================================================================
-fn build_merkle_tree(depth: usize, value: [BaseElement; 2], index: usize) -> MerkleTree<Rescue128> {
+fn build_merkle_tree(
+    depth: usize,
+    value: [BaseElement; 2],
+    index: usize,
+    rng: &mut SeededRng,
+) -> MerkleTree<Rescue128> {
     let num_leaves = usize::pow(2, depth as u32);
-    let leaf_elements: Vec<BaseElement> = rand_vector(num_leaves * 2);
+    let leaf_elements: Vec<BaseElement> = rng.vector(num_leaves * 2);
     let mut leaves = Vec::new();
     for i in (0..leaf_elements.len()).step_by(2) {
         leaves.push(Hash::new(leaf_elements[i], leaf_elements[i + 1]));
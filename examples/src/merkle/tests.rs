@@ -3,26 +3,86 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use super::{BaseElement, PublicInputs};
+use crate::Example;
 use winterfell::{FieldExtension, HashFunction, ProofOptions};
 
 #[test]
 fn merkle_test_basic_proof_verification() {
-    let merkle = Box::new(super::MerkleExample::new(7, build_options(false)));
+    let merkle = Box::new(super::MerkleExample::new(7, 1, build_options(false)));
     crate::tests::test_basic_proof_verification(merkle);
 }
 
+#[test]
+fn merkle_test_transcript_consistency() {
+    let merkle = Box::new(super::MerkleExample::new(7, 1, build_options(false)));
+    crate::tests::test_transcript_consistency(merkle);
+}
+
 #[test]
 fn merkle_test_basic_proof_verification_extension() {
-    let merkle = Box::new(super::MerkleExample::new(7, build_options(true)));
+    let merkle = Box::new(super::MerkleExample::new(7, 1, build_options(true)));
     crate::tests::test_basic_proof_verification(merkle);
 }
 
 #[test]
 fn merkle_test_basic_proof_verification_fail() {
-    let merkle = Box::new(super::MerkleExample::new(7, build_options(false)));
+    let merkle = Box::new(super::MerkleExample::new(7, 1, build_options(false)));
     crate::tests::test_basic_proof_verification_fail(merkle);
 }
 
+#[test]
+fn merkle_test_seeded_inputs() {
+    crate::tests::test_seeded_inputs("merkle", |seed| {
+        Box::new(super::MerkleExample::new(7, seed, build_options(false)))
+    });
+}
+
+#[test]
+fn merkle_test_pub_inputs_serialization() {
+    let pub_inputs = PublicInputs {
+        tree_root: [BaseElement::new(1), BaseElement::new(2)],
+    };
+    crate::tests::test_pub_inputs_serialization(pub_inputs);
+}
+
+#[test]
+fn merkle_test_validate_options() {
+    let merkle = super::MerkleExample::new(7, 1, build_options(false));
+    assert_eq!(Ok(()), merkle.validate_options(merkle.options()));
+
+    // hash constraints have degree 5 and are applied once per hash cycle
+    let options = ProofOptions::new(
+        28,
+        4,
+        0,
+        HashFunction::Blake3_256,
+        FieldExtension::None,
+        4,
+        256,
+    );
+    assert_eq!(
+        Err("merkle example requires blowup factor of at least 8, but was 4".to_string()),
+        merkle.validate_options(&options)
+    );
+}
+
+#[test]
+fn merkle_test_size_suggestions() {
+    assert!(super::SIZE_RANGE.check(7).is_ok());
+    assert_eq!(
+        Err(
+            "invalid tree depth 8: must be one less than a power of two between 1 and 15; \
+            nearest valid value is 7"
+                .to_string()
+        ),
+        super::SIZE_RANGE.check(8)
+    );
+    assert_eq!(1, super::SIZE_RANGE.nearest(0));
+    assert_eq!(15, super::SIZE_RANGE.nearest(11));
+    assert_eq!(15, super::SIZE_RANGE.nearest(usize::MAX));
+}
+
 fn build_options(use_extension_field: bool) -> ProofOptions {
     let extension = if use_extension_field {
         FieldExtension::Quadratic
@@ -4,9 +4,9 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{rescue, BaseElement, FieldElement, ProofOptions, CYCLE_LENGTH, TRACE_WIDTH};
-use crate::utils::{are_equal, is_zero, not, EvaluationResult};
+use crate::utils::{are_equal, is_zero, not, validate_trace_width, EvaluationResult};
 use winterfell::{
-    Air, AirContext, Assertion, ByteWriter, EvaluationFrame, Serializable, TraceInfo,
+    Air, AirContext, Assertion, Deserializable, EvaluationFrame, Serializable, TraceInfo,
     TransitionConstraintDegree,
 };
 
@@ -33,21 +33,29 @@ const CYCLE_MASK: [BaseElement; CYCLE_LENGTH] = [
     BaseElement::ZERO,
 ];
 
+// TRANSITION CONSTRAINT DEGREES
+// ================================================================================================
+
+/// Returns degrees of all transition constraints of this AIR.
+pub(super) fn transition_constraint_degrees() -> Vec<TransitionConstraintDegree> {
+    vec![
+        TransitionConstraintDegree::with_cycles(3, vec![CYCLE_LENGTH]),
+        TransitionConstraintDegree::with_cycles(3, vec![CYCLE_LENGTH]),
+        TransitionConstraintDegree::with_cycles(3, vec![CYCLE_LENGTH]),
+        TransitionConstraintDegree::with_cycles(3, vec![CYCLE_LENGTH]),
+    ]
+}
+
 // RESCUE AIR
 // ================================================================================================
 
+#[derive(Serializable, Deserializable)]
+#[winter_utils(crate = "winterfell")]
 pub struct PublicInputs {
     pub seed: [BaseElement; 2],
     pub result: [BaseElement; 2],
 }
 
-impl Serializable for PublicInputs {
-    fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        target.write(&self.seed[..]);
-        target.write(&self.result[..]);
-    }
-}
-
 pub struct RescueAir {
     context: AirContext<BaseElement>,
     seed: [BaseElement; 2],
@@ -61,12 +69,7 @@ impl Air for RescueAir {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
-        let degrees = vec![
-            TransitionConstraintDegree::with_cycles(3, vec![CYCLE_LENGTH]),
-            TransitionConstraintDegree::with_cycles(3, vec![CYCLE_LENGTH]),
-            TransitionConstraintDegree::with_cycles(3, vec![CYCLE_LENGTH]),
-            TransitionConstraintDegree::with_cycles(3, vec![CYCLE_LENGTH]),
-        ];
+        let degrees = transition_constraint_degrees();
         assert_eq!(TRACE_WIDTH, trace_info.width());
         RescueAir {
             context: AirContext::new(trace_info, degrees, options),
@@ -79,6 +82,10 @@ impl Air for RescueAir {
         &self.context
     }
 
+    fn air_id() -> &'static str {
+        "winterfell-examples/rescue"
+    }
+
     fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
         &self,
         frame: &EvaluationFrame<E>,
@@ -120,6 +127,17 @@ impl Air for RescueAir {
         result.append(&mut rescue::get_round_constants());
         result
     }
+    fn validate_instance(trace_info: &TraceInfo, _pub_inputs: &PublicInputs) -> Result<(), String> {
+        validate_trace_width(trace_info, TRACE_WIDTH)?;
+        if trace_info.length() < CYCLE_LENGTH {
+            return Err(format!(
+                "trace length must be at least {}, but was {}",
+                CYCLE_LENGTH,
+                trace_info.length()
+            ));
+        }
+        Ok(())
+    }
 }
 
 // HELPER EVALUATORS
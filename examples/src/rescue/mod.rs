@@ -3,19 +3,22 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::{Example, ExampleOptions};
+use crate::utils::{checkpoint::load_or_build_trace, max_trace_length, SizeRange};
+use crate::{Example, ExampleOptions, DEFAULT_GRINDING_FACTOR};
 use log::debug;
-use std::time::Instant;
+use std::{path::Path, time::Instant};
 use winterfell::{
     math::{fields::f128::BaseElement, log2, FieldElement},
-    ProofOptions, Prover, StarkProof, Trace, TraceTable, VerifierError,
+    Air, AirDescription, Claim, ClaimError, ProofOptions, Prover, ProverError, ProverOptions,
+    Serializable, StarkProof, Trace, TraceInfo, TraceTable, TransitionConstraintDegree,
+    VerifierCache, VerifierError,
 };
 
 #[allow(clippy::module_inception)]
 mod rescue;
 
 mod air;
-use air::{PublicInputs, RescueAir};
+use air::{transition_constraint_degrees, PublicInputs, RescueAir};
 
 mod prover;
 use prover::RescueProver;
@@ -30,16 +33,38 @@ const CYCLE_LENGTH: usize = 16;
 const NUM_HASH_ROUNDS: usize = 14;
 const TRACE_WIDTH: usize = 4;
 
+/// Blowup factor used by this example unless a different one is specified via `-b` option.
+pub const DEFAULT_BLOWUP_FACTOR: usize = 4;
+
+/// Valid lengths of the hash chain; each hash in the chain takes up one cycle of the trace.
+///
+/// The largest valid value depends on the blowup factor.
+pub const fn size_range(blowup_factor: usize) -> SizeRange {
+    SizeRange::power_of_two(
+        "chain length",
+        1,
+        max_trace_length(TRACE_WIDTH, blowup_factor) / CYCLE_LENGTH,
+    )
+}
+
+/// Valid sizes of this example with the default blowup factor.
+pub const SIZE_RANGE: SizeRange = size_range(DEFAULT_BLOWUP_FACTOR);
+
 // RESCUE HASH CHAIN EXAMPLE
 // ================================================================================================
 
 pub fn get_example(options: ExampleOptions, chain_length: usize) -> Box<dyn Example> {
     Box::new(RescueExample::new(
         chain_length,
-        options.to_proof_options(42, 4),
+        options.to_proof_options(42, DEFAULT_BLOWUP_FACTOR, DEFAULT_GRINDING_FACTOR),
     ))
 }
 
+/// Verifies a claim created by this example.
+pub fn verify_claim(claim: Claim) -> Result<(), ClaimError> {
+    claim.verify::<RescueAir>()
+}
+
 pub struct RescueExample {
     options: ProofOptions,
     chain_length: usize,
@@ -71,13 +96,39 @@ impl RescueExample {
             result,
         }
     }
+
+    /// Verifies the proof in the same way as [Example::verify()], but re-uses AIR-derived data
+    /// stored in the provided cache.
+    pub fn verify_cached(
+        &self,
+        proof: StarkProof,
+        cache: &VerifierCache,
+    ) -> Result<(), VerifierError> {
+        let pub_inputs = PublicInputs {
+            seed: self.seed,
+            result: self.result,
+        };
+        winterfell::verify_cached::<RescueAir>(proof, pub_inputs, cache)
+    }
+
+    /// Returns public inputs of this example.
+    fn pub_inputs(&self) -> PublicInputs {
+        PublicInputs {
+            seed: self.seed,
+            result: self.result,
+        }
+    }
 }
 
 // EXAMPLE IMPLEMENTATION
 // ================================================================================================
 
 impl Example for RescueExample {
-    fn prove(&self) -> StarkProof {
+    fn prove_with(
+        &self,
+        prover_options: &ProverOptions,
+        checkpoint_dir: Option<&Path>,
+    ) -> Result<StarkProof, ProverError> {
         // generate the execution trace
         debug!(
             "Generating proof for computing a chain of {} Rescue hashes\n\
@@ -90,7 +141,9 @@ impl Example for RescueExample {
 
         // generate the execution trace
         let now = Instant::now();
-        let trace = prover.build_trace(self.seed, self.chain_length);
+        let trace = load_or_build_trace(checkpoint_dir, self, || {
+            prover.build_trace(self.seed, self.chain_length)
+        });
         let trace_length = trace.length();
         debug!(
             "Generated execution trace of {} registers and 2^{} steps in {} ms",
@@ -100,7 +153,7 @@ impl Example for RescueExample {
         );
 
         // generate the proof
-        prover.prove(trace).unwrap()
+        prover.prove_with(trace, prover_options)
     }
 
     fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
@@ -118,6 +171,34 @@ impl Example for RescueExample {
         };
         winterfell::verify::<RescueAir>(proof, pub_inputs)
     }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn trace_info(&self) -> TraceInfo {
+        TraceInfo::new(TRACE_WIDTH, self.chain_length * CYCLE_LENGTH)
+    }
+
+    fn transition_constraint_degrees(&self) -> Vec<TransitionConstraintDegree> {
+        transition_constraint_degrees()
+    }
+
+    fn air_id(&self) -> &'static str {
+        RescueAir::air_id()
+    }
+
+    fn describe_air(&self) -> AirDescription {
+        crate::describe_example_air::<RescueAir>(self)
+    }
+
+    fn pub_inputs_bytes(&self) -> Vec<u8> {
+        self.pub_inputs().to_bytes()
+    }
+
+    fn claim(&self, proof: StarkProof) -> Claim {
+        Claim::new::<RescueAir>(proof, &self.pub_inputs())
+    }
 }
 
 // HELPER FUNCTIONS
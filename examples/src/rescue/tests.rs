@@ -3,26 +3,253 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use winterfell::{FieldExtension, HashFunction, ProofOptions};
+use super::{PublicInputs, RescueAir, RescueExample};
+use crate::Example;
+use winterfell::{
+    math::{fields::f128::BaseElement, FieldElement},
+    verify_cached, verify_with_policy, FieldExtension, HashFunction, ProofOptions,
+    VerificationPolicy, VerifierCache, VerifierError,
+};
 
 #[test]
 fn rescue_test_basic_proof_verification() {
-    let rescue_eg = Box::new(super::RescueExample::new(128, build_options(false)));
+    let rescue_eg = Box::new(RescueExample::new(128, build_options(false)));
     crate::tests::test_basic_proof_verification(rescue_eg);
 }
 
+#[test]
+fn rescue_test_transcript_consistency() {
+    let rescue_eg = Box::new(RescueExample::new(128, build_options(true)));
+    crate::tests::test_transcript_consistency(rescue_eg);
+}
+
+#[test]
+fn rescue_test_air_description() {
+    let rescue_eg = Box::new(RescueExample::new(128, build_options(false)));
+    crate::tests::test_air_description(rescue_eg, include_str!("air_description.json"));
+}
+
 #[test]
 fn rescue_test_basic_proof_verification_extension() {
-    let rescue_eg = Box::new(super::RescueExample::new(128, build_options(true)));
+    let rescue_eg = Box::new(RescueExample::new(128, build_options(true)));
     crate::tests::test_basic_proof_verification(rescue_eg);
 }
 
 #[test]
 fn rescue_test_basic_proof_verification_fail() {
-    let rescue_eg = Box::new(super::RescueExample::new(128, build_options(false)));
+    let rescue_eg = Box::new(RescueExample::new(128, build_options(false)));
     crate::tests::test_basic_proof_verification_fail(rescue_eg);
 }
 
+#[test]
+fn rescue_test_tampered_queries_verification() {
+    let rescue_eg = Box::new(RescueExample::new(128, build_options(true)));
+    crate::tests::test_tampered_queries_verification(rescue_eg);
+}
+
+#[test]
+fn rescue_test_proof_canonical_form() {
+    let rescue_eg = Box::new(RescueExample::new(128, build_options(true)));
+    crate::tests::test_proof_canonical_form(rescue_eg);
+}
+
+#[test]
+fn rescue_test_claim_verification() {
+    let rescue_eg = Box::new(RescueExample::new(32, build_options(false)));
+    crate::tests::test_claim_verification("rescue", "merkle", rescue_eg);
+}
+
+#[test]
+fn rescue_test_checkpoint_resume() {
+    let rescue_eg = Box::new(RescueExample::new(32, build_options(false)));
+    crate::tests::test_checkpoint_resume("rescue", rescue_eg);
+}
+
+#[test]
+fn rescue_test_pub_inputs_serialization() {
+    let rescue_eg = RescueExample::new(128, build_options(false));
+    crate::tests::test_pub_inputs_serialization(build_pub_inputs(&rescue_eg, false));
+}
+
+#[test]
+fn rescue_test_cached_proof_verification() {
+    let cache = VerifierCache::new();
+    let rescue_eg = RescueExample::new(128, build_options(false));
+    let proof = rescue_eg.prove().unwrap();
+
+    // the first verification populates the cache, the second one re-uses the cached entry
+    let pub_inputs = build_pub_inputs(&rescue_eg, false);
+    assert!(verify_cached::<RescueAir>(proof.clone(), pub_inputs, &cache).is_ok());
+    assert_eq!(1, cache.len());
+    let pub_inputs = build_pub_inputs(&rescue_eg, false);
+    assert!(verify_cached::<RescueAir>(proof.clone(), pub_inputs, &cache).is_ok());
+    assert_eq!(1, cache.len());
+
+    // verification against wrong inputs fails the same way as without the cache; public inputs
+    // are not a part of the cache key, and so the cached entry is re-used
+    let pub_inputs = build_pub_inputs(&rescue_eg, true);
+    assert!(verify_cached::<RescueAir>(proof, pub_inputs, &cache).is_err());
+    assert_eq!(1, cache.len());
+
+    cache.clear();
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn rescue_test_cached_proof_verification_different_contexts() {
+    let cache = VerifierCache::new();
+
+    // proofs for different chain lengths have different trace lengths and so must not share
+    // a cache entry
+    for (i, &chain_length) in [64, 128].iter().enumerate() {
+        let rescue_eg = RescueExample::new(chain_length, build_options(false));
+        let proof = rescue_eg.prove().unwrap();
+        let pub_inputs = build_pub_inputs(&rescue_eg, false);
+        assert!(verify_cached::<RescueAir>(proof, pub_inputs, &cache).is_ok());
+        assert_eq!(i + 1, cache.len());
+    }
+
+    // proofs generated with different options must not share a cache entry either
+    let rescue_eg = RescueExample::new(128, build_options(true));
+    let proof = rescue_eg.prove().unwrap();
+    let pub_inputs = build_pub_inputs(&rescue_eg, false);
+    assert!(verify_cached::<RescueAir>(proof, pub_inputs, &cache).is_ok());
+    assert_eq!(3, cache.len());
+}
+
+#[test]
+fn rescue_test_cached_proof_verification_eviction() {
+    let cache = VerifierCache::with_capacity(2);
+    assert_eq!(2, cache.capacity());
+
+    // once the cache is full, the oldest entry is evicted to make room for a new one
+    let examples = [32, 64, 128]
+        .iter()
+        .map(|&chain_length| {
+            let rescue_eg = RescueExample::new(chain_length, build_options(false));
+            let proof = rescue_eg.prove().unwrap();
+            (rescue_eg, proof)
+        })
+        .collect::<Vec<_>>();
+    for (i, (rescue_eg, proof)) in examples.iter().enumerate() {
+        let pub_inputs = build_pub_inputs(rescue_eg, false);
+        assert!(verify_cached::<RescueAir>(proof.clone(), pub_inputs, &cache).is_ok());
+        assert_eq!((i + 1).min(2), cache.len());
+    }
+
+    // verification against an evicted entry rebuilds it and still succeeds
+    let (rescue_eg, proof) = &examples[0];
+    let pub_inputs = build_pub_inputs(rescue_eg, false);
+    assert!(verify_cached::<RescueAir>(proof.clone(), pub_inputs, &cache).is_ok());
+    assert_eq!(2, cache.len());
+}
+
+#[test]
+fn rescue_test_proof_fingerprint() {
+    // constraint evaluations depend on values of periodic columns; proofs must not change when
+    // the way in which these values are computed changes
+    let rescue_eg = RescueExample::new(32, build_options(false));
+    let proof = rescue_eg.prove().unwrap().to_bytes();
+    assert_eq!(
+        "067aec91cfa50956f1eaa12c8c99faf0ffb0dbd3dbdd2246be179d31e91dd7e6",
+        hex::encode(blake3::hash(&proof).as_bytes())
+    );
+}
+
+#[test]
+fn rescue_test_proof_fingerprint_extension() {
+    // constraint evaluations are merged in the base field and only the composition coefficients
+    // are in the extension field, but the merging must not change the generated proofs
+    let rescue_eg = RescueExample::new(32, build_options(true));
+    let proof = rescue_eg.prove().unwrap().to_bytes();
+    assert_eq!(
+        "f597b31a84c46b29fc853d362858b04d8babe59dacf6305e5bb3598487ac782c",
+        hex::encode(blake3::hash(&proof).as_bytes())
+    );
+}
+
+fn build_pub_inputs(example: &RescueExample, wrong_result: bool) -> PublicInputs {
+    let mut result = example.result;
+    if wrong_result {
+        result[1] += BaseElement::ONE;
+    }
+    PublicInputs {
+        seed: example.seed,
+        result,
+    }
+}
+
+#[test]
+fn rescue_test_transcript_domain() {
+    let rescue_eg = RescueExample::new(128, build_options(false));
+    let options_a = build_options(false).with_transcript_domain(b"protocol-a");
+    let options_b = build_options(false).with_transcript_domain(b"protocol-b");
+    let proof_a = RescueExample::new(128, options_a).prove().unwrap();
+    let proof_b = RescueExample::new(128, options_b).prove().unwrap();
+
+    // the verifier reads the tag from the proof context
+    assert!(rescue_eg.verify(proof_a.clone()).is_ok());
+    assert!(rescue_eg.verify(proof_b.clone()).is_ok());
+
+    // a proof generated under one tag is not valid under a different tag
+    let mut proof = proof_a.clone();
+    proof.context = proof_b.context.clone();
+    assert!(rescue_eg.verify(proof).is_err());
+
+    // a verification policy can require a specific tag
+    let policy = VerificationPolicy::new().with_transcript_domain(b"protocol-a");
+    let pub_inputs = build_pub_inputs(&rescue_eg, false);
+    assert!(verify_with_policy::<RescueAir>(proof_a, pub_inputs, &policy).is_ok());
+    let pub_inputs = build_pub_inputs(&rescue_eg, false);
+    assert_eq!(
+        Err(VerifierError::TranscriptDomainMismatch),
+        verify_with_policy::<RescueAir>(proof_b, pub_inputs, &policy)
+    );
+    let pub_inputs = build_pub_inputs(&rescue_eg, false);
+    assert_eq!(
+        Err(VerifierError::TranscriptDomainMismatch),
+        verify_with_policy::<RescueAir>(rescue_eg.prove().unwrap(), pub_inputs, &policy)
+    );
+
+    // an empty tag does not change the proof
+    let proof = rescue_eg.prove().unwrap();
+    let options = build_options(false).with_transcript_domain(&[]);
+    let proof_empty_tag = RescueExample::new(128, options).prove().unwrap();
+    assert_eq!(proof.to_bytes(), proof_empty_tag.to_bytes());
+}
+
+#[test]
+fn rescue_test_validate_options() {
+    let rescue_eg = RescueExample::new(128, build_options(true));
+    assert_eq!(Ok(()), rescue_eg.validate_options(rescue_eg.options()));
+
+    // cubic extension is not supported for the 128-bit field
+    let options = ProofOptions::new(
+        32,
+        8,
+        0,
+        HashFunction::Blake3_256,
+        FieldExtension::Cubic,
+        4,
+        256,
+    );
+    assert_eq!(
+        Err("rescue example does not support field extension of degree 3".to_string()),
+        rescue_eg.validate_options(&options)
+    );
+}
+
+#[test]
+fn rescue_test_size_range() {
+    // the size range is computed for the default blowup factor of the example, which is 4
+    assert_eq!(1 << 22, super::SIZE_RANGE.max());
+    assert_eq!(1 << 21, super::size_range(8).max());
+    assert_eq!(
+        "Chain length; must be a power of two between 1 and 2^22",
+        super::SIZE_RANGE.help()
+    );
+}
+
 fn build_options(use_extension_field: bool) -> ProofOptions {
     let extension = if use_extension_field {
         FieldExtension::Quadratic
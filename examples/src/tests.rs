@@ -3,15 +3,266 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::Example;
+use crate::{
+    fibonacci,
+    utils::{check_trace_size, checkpoint},
+    Example, ExampleOptions,
+};
+use structopt::StructOpt;
+use winterfell::{
+    crypto::transcript::capture_transcript, math::fields::f128::BaseElement, ByteReader, Claim,
+    Deserializable, ProverOptions, Serializable, SliceReader, StarkProof, VerifierError,
+};
 
 pub fn test_basic_proof_verification(e: Box<dyn Example>) {
-    let proof = e.prove();
+    e.validate_options(e.options()).unwrap();
+    let proof = e.prove().unwrap();
+    assert_eq!(e.trace_info().width(), proof.trace_width());
+    assert_eq!(e.trace_info().length(), proof.trace_length());
     assert!(e.verify(proof).is_ok());
 }
 
 pub fn test_basic_proof_verification_fail(e: Box<dyn Example>) {
-    let proof = e.prove();
+    e.validate_options(e.options()).unwrap();
+    let proof = e.prove().unwrap();
     let verified = e.verify_with_wrong_inputs(proof);
     assert!(verified.is_err());
 }
+
+/// Makes sure that proofs with tampered trace queries, constraint queries, or FRI remainder are
+/// rejected with the expected errors.
+///
+/// Each tampered proof is verified several times to make sure that the returned error does not
+/// depend on the order in which queries are processed when `concurrent-verify` feature is enabled.
+pub fn test_tampered_queries_verification(e: Box<dyn Example>) {
+    let proof = e.prove().unwrap();
+    let proof_bytes = proof.to_bytes();
+
+    // value bytes of trace and constraint queries come right after their length prefixes, and
+    // the FRI remainder is followed only by the number of partitions and the proof-of-work nonce
+    let trace_offset = proof.context.to_bytes().len() + proof.commitments.to_bytes().len();
+    let constraint_offset = trace_offset + proof.trace_queries.to_bytes().len();
+    let positions = [
+        trace_offset + 4,
+        constraint_offset + 4,
+        proof_bytes.len() - 10,
+    ];
+
+    let mut errors = Vec::new();
+    for &position in positions.iter() {
+        let mut bytes = proof_bytes.clone();
+        bytes[position] ^= 1;
+        let error = e
+            .verify(StarkProof::from_bytes(&bytes).unwrap())
+            .unwrap_err();
+        for _ in 0..4 {
+            let proof = StarkProof::from_bytes(&bytes).unwrap();
+            assert_eq!(error, e.verify(proof).unwrap_err());
+        }
+        errors.push(error);
+    }
+
+    assert_eq!(VerifierError::TraceQueryDoesNotMatchCommitment, errors[0]);
+    assert_eq!(
+        VerifierError::ConstraintQueryDoesNotMatchCommitment,
+        errors[1]
+    );
+    assert!(matches!(errors[2], VerifierError::FriVerificationFailed(_)));
+}
+
+/// Makes sure that the prover and the verifier follow the same Fiat-Shamir schedule, i.e., that
+/// the public coin events recorded while generating a proof are identical (event-for-event) to
+/// the events recorded while verifying it.
+pub fn test_transcript_consistency(e: Box<dyn Example>) {
+    let (proof, prover_transcript) = capture_transcript(|| e.prove().unwrap());
+    let (result, verifier_transcript) = capture_transcript(|| e.verify(proof));
+    assert!(result.is_ok());
+
+    if let Some(diff) = prover_transcript.diff(&verifier_transcript) {
+        panic!("prover and verifier transcripts differ; {}", diff);
+    }
+    assert!(!prover_transcript.events().is_empty());
+    assert!(prover_transcript
+        .events()
+        .iter()
+        .all(|event| !event.label.is_empty()));
+}
+
+/// Makes sure that proofs have a single canonical serialization: deserializing and re-serializing
+/// a proof yields identical bytes, and two proofs are equal if and only if their serialized bytes
+/// and hashes match.
+pub fn test_proof_canonical_form(e: Box<dyn Example>) {
+    let proof = e.prove().unwrap();
+    let proof_bytes = proof.to_bytes();
+
+    let parsed = StarkProof::from_bytes(&proof_bytes).unwrap();
+    assert_eq!(proof_bytes, parsed.to_bytes());
+    assert_eq!(proof, parsed);
+    assert_eq!(fingerprint(&proof), fingerprint(&parsed));
+
+    // proofs generated independently for the same trace and options are identical
+    let other = e.prove().unwrap();
+    assert_eq!(proof_bytes, other.to_bytes());
+    assert_eq!(proof, other);
+    assert_eq!(fingerprint(&proof), fingerprint(&other));
+
+    // proofs which differ in a single byte are different
+    let mut other = proof.clone();
+    other.pow_nonce += 1;
+    assert_ne!(proof_bytes, other.to_bytes());
+    assert_ne!(proof, other);
+    assert_ne!(fingerprint(&proof), fingerprint(&other));
+
+    // trailing bytes are rejected
+    let mut bytes = proof_bytes;
+    bytes.push(0);
+    assert!(StarkProof::from_bytes(&bytes).is_err());
+}
+
+/// Returns a hash of the specified proof computed via its [Hash](std::hash::Hash) implementation.
+fn fingerprint(proof: &StarkProof) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    proof.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Makes sure that a proof generated from an execution trace saved into a checkpoint and later
+/// loaded from it is identical to a proof generated without checkpointing.
+pub fn test_checkpoint_resume(example: &str, e: Box<dyn Example>) {
+    let dir = std::env::temp_dir().join(format!(
+        "winterfell-checkpoint-{}-{}",
+        example,
+        std::process::id()
+    ));
+    std::fs::create_dir_all(&dir).unwrap();
+    let expected = e.prove().unwrap().to_bytes();
+
+    // the first run builds the trace and saves it; the second run loads the saved trace
+    let path = checkpoint::checkpoint_path(&dir, e.as_ref());
+    assert_eq!(
+        expected,
+        e.prove_with_checkpoint(Some(&dir)).unwrap().to_bytes()
+    );
+    assert!(path.exists());
+    assert_eq!(
+        expected,
+        e.prove_with_checkpoint(Some(&dir)).unwrap().to_bytes()
+    );
+
+    // a corrupted checkpoint is detected and the trace is rebuilt
+    let mut bytes = std::fs::read(&path).unwrap();
+    let last = bytes.len() - 1;
+    bytes[last] ^= 1;
+    std::fs::write(&path, bytes).unwrap();
+    assert!(checkpoint::load_trace::<BaseElement>(&path).is_err());
+    assert_eq!(
+        expected,
+        e.prove_with_checkpoint(Some(&dir)).unwrap().to_bytes()
+    );
+    assert!(checkpoint::load_trace::<BaseElement>(&path).is_ok());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Makes sure that examples built from the same seed have identical inputs, execution traces, and
+/// proofs, and that examples built from different seeds have different inputs.
+///
+/// Execution traces are compared via trace hashes of checkpoints saved while proving.
+pub fn test_seeded_inputs<F>(example: &str, build: F)
+where
+    F: Fn(u64) -> Box<dyn Example>,
+{
+    let (e1, e2, e3) = (build(7), build(7), build(8));
+    assert_eq!(e1.pub_inputs_bytes(), e2.pub_inputs_bytes());
+    assert_ne!(e1.pub_inputs_bytes(), e3.pub_inputs_bytes());
+
+    let dir = std::env::temp_dir().join(format!(
+        "winterfell-seed-{}-{}",
+        example,
+        std::process::id()
+    ));
+    let (dir1, dir2) = (dir.join("1"), dir.join("2"));
+    std::fs::create_dir_all(&dir1).unwrap();
+    std::fs::create_dir_all(&dir2).unwrap();
+
+    let proof1 = e1.prove_with_checkpoint(Some(&dir1)).unwrap().to_bytes();
+    let proof2 = e2.prove_with_checkpoint(Some(&dir2)).unwrap().to_bytes();
+    let trace1 = std::fs::read(checkpoint::checkpoint_path(&dir1, e1.as_ref())).unwrap();
+    let trace2 = std::fs::read(checkpoint::checkpoint_path(&dir2, e2.as_ref())).unwrap();
+    assert_eq!(trace1[..32], trace2[..32]);
+    assert_eq!(proof1, proof2);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Makes sure that a claim created by the example can be serialized and verified by example name,
+/// and that it is rejected by a different example or when its contents are modified.
+pub fn test_claim_verification(example: &str, other_example: &str, e: Box<dyn Example>) {
+    let claim = e.claim(e.prove().unwrap()).with_created_at(42);
+    let bytes = claim.to_bytes();
+
+    // claims survive a serialization round trip, but trailing bytes are rejected
+    assert_eq!(claim, Claim::from_bytes(&bytes).unwrap());
+    let mut long_bytes = bytes.clone();
+    long_bytes.push(0);
+    assert!(Claim::from_bytes(&long_bytes).is_err());
+
+    assert_eq!(Ok(()), crate::verify_claim(example, &bytes));
+
+    // a claim is rejected by an example with a different AIR before the proof is verified
+    let err = crate::verify_claim(other_example, &bytes).unwrap_err();
+    assert!(err.starts_with(&format!("claim was created for AIR '{}'", e.air_id())));
+
+    // a claim with a modified AIR identifier is rejected
+    let mut wrong_claim = claim.clone();
+    wrong_claim.air_id.push('x');
+    let err = crate::verify_claim(example, &wrong_claim.to_bytes()).unwrap_err();
+    assert!(err.starts_with("claim was created for AIR"));
+
+    // a claim with modified public inputs is rejected
+    let mut wrong_claim = claim;
+    wrong_claim.pub_inputs[0] ^= 1;
+    assert!(crate::verify_claim(example, &wrong_claim.to_bytes()).is_err());
+}
+
+/// Makes sure that the description of the AIR of the example is deterministic and matches the
+/// expected JSON description, so that changes to the AIR are caught in review.
+pub fn test_air_description(e: Box<dyn Example>, expected: &str) {
+    let actual = crate::air_description_to_json(&e.describe_air());
+    assert_eq!(actual, crate::air_description_to_json(&e.describe_air()));
+    assert!(
+        actual == expected.trim_end(),
+        "AIR description changed; if the change is intentional, update the expected description to:\n{}",
+        actual
+    );
+}
+
+pub fn test_pub_inputs_serialization<P: Serializable + Deserializable>(pub_inputs: P) {
+    let bytes = pub_inputs.to_bytes();
+    let mut reader = SliceReader::new(&bytes);
+    let result = P::read_from(&mut reader).unwrap();
+    assert!(!reader.has_more_bytes());
+    assert_eq!(bytes, result.to_bytes());
+}
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn check_trace_size_guard() {
+    // the size of an execution trace is checked against the maximum number of trace cells
+    // specified by prover options, and the error message includes the estimated memory usage
+    let options = ExampleOptions::from_iter(&["winterfell", "fib"]);
+    let e = fibonacci::fib2::get_example(options, 64);
+    let trace_info = e.trace_info();
+    let cells = (trace_info.width() * trace_info.length()) as u64;
+
+    let prover_options = ProverOptions::new().with_max_trace_cells(cells);
+    assert!(check_trace_size(e.as_ref(), &prover_options).is_ok());
+
+    let prover_options = ProverOptions::new().with_max_trace_cells(cells - 1);
+    let err = check_trace_size(e.as_ref(), &prover_options).unwrap_err();
+    assert!(err.contains(&format!("execution trace has {} cells", cells)));
+    assert!(err.contains("GB of memory"));
+}
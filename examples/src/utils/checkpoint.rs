@@ -0,0 +1,118 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::Example;
+use log::{debug, warn};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use winterfell::{
+    math::StarkField, ByteReader, Deserializable, Serializable, SliceReader, TraceTable,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of bytes in a trace hash.
+const TRACE_HASH_LENGTH: usize = 32;
+
+// TRACE CHECKPOINTS
+// ================================================================================================
+
+/// Returns an execution trace for the specified example, loading it from a checkpoint if one
+/// exists in `checkpoint_dir`, and building it via `build` otherwise.
+///
+/// A newly built trace is saved into `checkpoint_dir` before it is returned, so that an
+/// interrupted proving job can be resumed without rebuilding the trace. Checkpoints which are
+/// corrupted or cannot be read are ignored, and the trace is rebuilt. When `checkpoint_dir` is
+/// `None`, the trace is always built.
+pub fn load_or_build_trace<B, F>(
+    checkpoint_dir: Option<&Path>,
+    example: &dyn Example,
+    build: F,
+) -> TraceTable<B>
+where
+    B: StarkField,
+    F: FnOnce() -> TraceTable<B>,
+{
+    let checkpoint_dir = match checkpoint_dir {
+        Some(dir) => dir,
+        None => return build(),
+    };
+
+    let path = checkpoint_path(checkpoint_dir, example);
+    if path.exists() {
+        match load_trace(&path) {
+            Ok(trace) => {
+                debug!("Loaded execution trace from {}", path.display());
+                return trace;
+            }
+            Err(err) => warn!("Ignoring checkpoint {}: {}", path.display(), err),
+        }
+    }
+
+    let trace = build();
+    match save_trace(&path, &trace) {
+        Ok(_) => debug!("Saved execution trace to {}", path.display()),
+        Err(err) => warn!("Failed to save checkpoint {}: {}", path.display(), err),
+    }
+    trace
+}
+
+/// Returns the path of the checkpoint file for the specified example in `checkpoint_dir`.
+///
+/// The name of the file is derived from the AIR identifier and public inputs of the example, and
+/// thus, traces for different computations or different inputs are saved into different files.
+pub fn checkpoint_path(checkpoint_dir: &Path, example: &dyn Example) -> PathBuf {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(example.air_id().as_bytes());
+    hasher.update(&example.pub_inputs_bytes());
+    let name = hasher.finalize().to_hex();
+    checkpoint_dir.join(format!("{}.trace", &name[..32]))
+}
+
+/// Saves the specified trace into a file at the specified path.
+///
+/// The serialized trace is prefixed with its hash so that corrupted checkpoints can be detected
+/// when the trace is loaded.
+pub fn save_trace<B: StarkField>(path: &Path, trace: &TraceTable<B>) -> Result<(), String> {
+    let trace_bytes = trace.to_bytes();
+    let mut bytes = trace_hash(&trace_bytes).to_vec();
+    bytes.extend_from_slice(&trace_bytes);
+    fs::write(path, bytes).map_err(|err| err.to_string())
+}
+
+/// Loads a trace saved via [save_trace()] from a file at the specified path.
+///
+/// # Errors
+/// Returns an error if the file could not be read, if the hash of the trace does not match the
+/// hash saved in the file, or if the trace could not be deserialized.
+pub fn load_trace<B: StarkField>(path: &Path) -> Result<TraceTable<B>, String> {
+    let bytes = fs::read(path).map_err(|err| err.to_string())?;
+    if bytes.len() < TRACE_HASH_LENGTH {
+        return Err("checkpoint is too short".to_string());
+    }
+
+    let (expected_hash, trace_bytes) = bytes.split_at(TRACE_HASH_LENGTH);
+    if trace_hash(trace_bytes) != expected_hash {
+        return Err("trace hash does not match the saved hash".to_string());
+    }
+
+    let mut reader = SliceReader::new(trace_bytes);
+    let trace = TraceTable::read_from(&mut reader).map_err(|err| err.to_string())?;
+    if reader.has_more_bytes() {
+        return Err("not all bytes of the checkpoint were consumed".to_string());
+    }
+    Ok(trace)
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns a hash of the specified serialized trace.
+fn trace_hash(trace_bytes: &[u8]) -> [u8; TRACE_HASH_LENGTH] {
+    *blake3::hash(trace_bytes).as_bytes()
+}
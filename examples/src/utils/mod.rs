@@ -3,12 +3,14 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use crate::Example;
 use core::ops::Range;
 use winterfell::{
     math::{fields::f128::BaseElement, FieldElement, StarkField},
-    Trace, TraceTable,
+    ProofOptions, ProverOptions, Trace, TraceInfo, TraceTable,
 };
 
+pub mod checkpoint;
 pub mod rescue;
 
 // CONSTRAINT EVALUATION HELPERS
@@ -54,6 +56,239 @@ impl<E: FieldElement> EvaluationResult<E> for Vec<E> {
     }
 }
 
+// EXAMPLE SIZES
+// ================================================================================================
+
+/// Upper bound on the number of cells in the low-degree extension of an execution trace of an
+/// example; with 128-bit field elements, such an extension takes up 16 GB of memory.
+const MAX_LDE_CELLS: usize = 1 << 30;
+
+/// Returns the largest power-of-two trace length for which the low-degree extension of a trace
+/// with the specified width and blowup factor does not exceed [MAX_LDE_CELLS].
+///
+/// Returns 0 if not even a single row of such a trace fits into [MAX_LDE_CELLS].
+pub const fn max_trace_length(trace_width: usize, blowup_factor: usize) -> usize {
+    let max_rows = match trace_width.checked_mul(blowup_factor) {
+        Some(row_cells) if row_cells > 0 => MAX_LDE_CELLS / row_cells,
+        _ => 0,
+    };
+    if max_rows == 0 {
+        return 0;
+    }
+    1 << (usize::BITS - 1 - max_rows.leading_zeros())
+}
+
+/// Describes the form which the size argument of an example must have.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SizeShape {
+    /// The size must be a power of two.
+    PowerOfTwo,
+    /// The size must be one less than a power of two.
+    PowerOfTwoMinusOne,
+    /// The size must be an even number.
+    Even,
+}
+
+impl SizeShape {
+    /// Returns a description of this shape for use in messages, e.g. "a power of two".
+    fn description(&self) -> &'static str {
+        match self {
+            SizeShape::PowerOfTwo => "a power of two",
+            SizeShape::PowerOfTwoMinusOne => "one less than a power of two",
+            SizeShape::Even => "an even number",
+        }
+    }
+}
+
+/// Describes the set of valid values of the size argument (`-n`) of an example.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SizeRange {
+    name: &'static str,
+    min: usize,
+    max: usize,
+    shape: SizeShape,
+}
+
+impl SizeRange {
+    /// Returns a range of powers of two between `min` and `max` (both inclusive); `name`
+    /// describes the size argument in error messages (e.g., "sequence length").
+    pub const fn power_of_two(name: &'static str, min: usize, max: usize) -> Self {
+        SizeRange {
+            name,
+            min,
+            max,
+            shape: SizeShape::PowerOfTwo,
+        }
+    }
+
+    /// Returns a range of values one less than a power of two between `min` and `max` (both
+    /// inclusive); `name` describes the size argument in error messages (e.g., "tree depth").
+    pub const fn power_of_two_minus_one(name: &'static str, min: usize, max: usize) -> Self {
+        SizeRange {
+            name,
+            min,
+            max,
+            shape: SizeShape::PowerOfTwoMinusOne,
+        }
+    }
+
+    /// Returns a range of even numbers between `min` and `max` (both inclusive); `name`
+    /// describes the size argument in error messages (e.g., "sequence length").
+    pub const fn even(name: &'static str, min: usize, max: usize) -> Self {
+        SizeRange {
+            name,
+            min,
+            max,
+            shape: SizeShape::Even,
+        }
+    }
+
+    /// Returns the smallest valid size.
+    pub fn min(&self) -> usize {
+        self.min
+    }
+
+    /// Returns the largest valid size.
+    pub fn max(&self) -> usize {
+        self.max
+    }
+
+    /// Returns the form which valid sizes must have.
+    pub fn shape(&self) -> SizeShape {
+        self.shape
+    }
+
+    /// Returns the help text of the size argument described by this range, e.g., "Sequence
+    /// length; must be a power of two between 16 and 2^27".
+    ///
+    /// Powers of two of at least 1024 are written as exponents. The text is leaked so that it can
+    /// be used as a help message of a command-line argument.
+    pub fn help(&self) -> &'static str {
+        fn format_size(n: usize) -> String {
+            if n >= 1024 && n.is_power_of_two() {
+                format!("2^{}", n.trailing_zeros())
+            } else {
+                n.to_string()
+            }
+        }
+
+        let mut name = self.name.to_string();
+        name[..1].make_ascii_uppercase();
+        let help = format!(
+            "{}; must be {} between {} and {}",
+            name,
+            self.shape.description(),
+            format_size(self.min),
+            format_size(self.max)
+        );
+        Box::leak(help.into_boxed_str())
+    }
+
+    /// Returns an error describing the valid sizes and suggesting the nearest valid value if
+    /// `n` is not a valid size.
+    pub fn check(&self, n: usize) -> Result<(), String> {
+        let shape_ok = match self.shape {
+            SizeShape::PowerOfTwo => n.is_power_of_two(),
+            SizeShape::PowerOfTwoMinusOne => {
+                n.checked_add(1).map_or(false, |v| v.is_power_of_two())
+            }
+            SizeShape::Even => n % 2 == 0,
+        };
+        if shape_ok && n >= self.min && n <= self.max {
+            return Ok(());
+        }
+
+        Err(format!(
+            "invalid {} {}: must be {} between {} and {}; nearest valid value is {}",
+            self.name,
+            n,
+            self.shape.description(),
+            self.min,
+            self.max,
+            self.nearest(n)
+        ))
+    }
+
+    /// Returns the valid size closest to `n`; if `n` is equally far from two valid sizes, the
+    /// larger one is returned.
+    pub fn nearest(&self, n: usize) -> usize {
+        let offset = match self.shape {
+            SizeShape::PowerOfTwo => 0,
+            SizeShape::PowerOfTwoMinusOne => 1,
+            SizeShape::Even => return n.saturating_add(n % 2).clamp(self.min, self.max),
+        };
+        let n = n.saturating_add(offset);
+        let (min, max) = (self.min + offset, self.max + offset);
+        let nearest = if n <= min {
+            min
+        } else if n >= max {
+            max
+        } else if n.is_power_of_two() {
+            n
+        } else {
+            let upper = n.next_power_of_two();
+            let lower = upper / 2;
+            if n - lower < upper - n {
+                lower
+            } else {
+                upper
+            }
+        };
+        nearest - offset
+    }
+}
+
+/// Returns an estimate of the amount of memory (in bytes) needed to generate a proof for an
+/// execution trace described by `trace_info` using the specified proof options.
+///
+/// The estimate accounts for the trace itself, its interpolated polynomials, and its low-degree
+/// extension; constraint evaluations and Merkle trees are not included, and thus, the actual
+/// memory usage is somewhat higher.
+pub fn estimate_prover_memory(trace_info: &TraceInfo, options: &ProofOptions) -> u64 {
+    let cells = trace_info.width() as u64 * trace_info.length() as u64;
+    cells * (2 + options.blowup_factor() as u64) * BaseElement::ELEMENT_BYTES as u64
+}
+
+/// Checks whether the execution trace of the specified example is small enough to be proven with
+/// the specified prover options.
+///
+/// The check is performed without building the trace; if the trace is too large, the returned
+/// error includes the estimated amount of memory needed to prove the example.
+pub fn check_trace_size(
+    example: &dyn Example,
+    prover_options: &ProverOptions,
+) -> Result<(), String> {
+    let trace_info = example.trace_info();
+    prover_options.check_trace_size(&trace_info).map_err(|err| {
+        let memory = estimate_prover_memory(&trace_info, example.options());
+        format!(
+            "{}\nproving this example would require at least {:.1} GB of memory",
+            err,
+            memory as f64 / (1u64 << 30) as f64
+        )
+    })
+}
+
+// AIR INSTANCE VALIDATION
+// ================================================================================================
+
+/// Makes sure that the width of the execution trace described by `trace_info` is equal to the
+/// width expected by an AIR.
+///
+/// This is meant to be used in [Air::validate_instance()](winterfell::Air::validate_instance)
+/// implementations of the examples, so that proofs with an unexpected trace width are rejected
+/// before the AIR is instantiated.
+pub fn validate_trace_width(trace_info: &TraceInfo, expected: usize) -> Result<(), String> {
+    if trace_info.width() != expected {
+        return Err(format!(
+            "expected trace width {}, but was {}",
+            expected,
+            trace_info.width()
+        ));
+    }
+    Ok(())
+}
+
 // OTHER FUNCTIONS
 // ================================================================================================
 
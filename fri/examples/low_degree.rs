@@ -0,0 +1,69 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Proves and verifies that evaluations of a random degree 1000 polynomial over a domain of
+//! 2^14 elements are evaluations of a polynomial of degree at most 1023.
+
+use crypto::hashers::Blake3_256;
+use math::{fft, fields::f128::BaseElement, log2, FieldElement};
+use rand_utils::rand_vector;
+use std::time::Instant;
+use winter_fri::{prove_low_degree, verify_low_degree, DefaultProverChannel, FriOptions};
+
+type Blake3 = Blake3_256<BaseElement>;
+
+const POLY_DEGREE: usize = 1000;
+const MAX_POLY_DEGREE: usize = 1023;
+const DOMAIN_SIZE: usize = 1 << 14;
+const NUM_QUERIES: usize = 32;
+
+fn main() {
+    let blowup_factor = DOMAIN_SIZE / (MAX_POLY_DEGREE + 1);
+    let options = FriOptions::new(blowup_factor, 4, 64);
+
+    // evaluate a random polynomial over the coset of the domain defined by FRI options
+    let mut poly: Vec<BaseElement> = rand_vector(POLY_DEGREE + 1);
+    poly.resize(MAX_POLY_DEGREE + 1, BaseElement::ZERO);
+    let twiddles = fft::get_twiddles::<BaseElement>(poly.len());
+    let evaluations =
+        fft::evaluate_poly_with_offset(&poly, &twiddles, options.domain_offset(), blowup_factor);
+
+    // generate the proof
+    let now = Instant::now();
+    let mut channel = DefaultProverChannel::<_, _, Blake3>::new(DOMAIN_SIZE, NUM_QUERIES);
+    let (proof, positions) =
+        prove_low_degree(&evaluations, MAX_POLY_DEGREE, &options, &mut channel);
+    let commitments = channel.layer_commitments().to_vec();
+    println!(
+        "Proved degree {} polynomial over domain of 2^{} elements in {} ms ({} bytes)",
+        POLY_DEGREE,
+        log2(DOMAIN_SIZE),
+        now.elapsed().as_millis(),
+        proof.size()
+    );
+
+    // verify the proof; in a real application, queried evaluations would be opened against an
+    // external commitment to the evaluations
+    let now = Instant::now();
+    let queried_evaluations = positions
+        .iter()
+        .map(|&p| evaluations[p])
+        .collect::<Vec<_>>();
+    match verify_low_degree::<_, _, Blake3>(
+        proof,
+        commitments,
+        &queried_evaluations,
+        &positions,
+        MAX_POLY_DEGREE,
+        NUM_QUERIES,
+        &options,
+    ) {
+        Ok(_) => println!(
+            "Proof verified in {:.1} ms",
+            now.elapsed().as_micros() as f64 / 1000.0
+        ),
+        Err(err) => println!("Failed to verify proof: {}", err),
+    }
+}
@@ -37,6 +37,12 @@ pub enum VerifierError {
     RemainderDegreeMismatch(usize),
     /// Polynomial degree at one of the FRI layers could not be divided evenly by the folding factor.
     DegreeTruncation(usize, usize, usize),
+    /// FRI proof could not be parsed into layer queries and remainder.
+    ProofDeserializationError(String),
+    /// Query positions did not match the positions drawn from the public coin.
+    QueryPositionsMismatch,
+    /// Number of query positions is not the number of queries expected by the verifier.
+    NumQueriesMismatch(usize, usize),
 }
 
 impl fmt::Display for VerifierError {
@@ -77,6 +83,15 @@ impl fmt::Display for VerifierError {
             Self::DegreeTruncation(degree, folding, layer) => {
                 write!(f, "degree reduction from {} by {} at layer {} results in degree truncation", degree, folding, layer)
             }
+            Self::ProofDeserializationError(err_msg) => {
+                write!(f, "FRI proof could not be parsed: {}", err_msg)
+            }
+            Self::QueryPositionsMismatch => {
+                write!(f, "query positions did not match the positions drawn from the public coin")
+            }
+            Self::NumQueriesMismatch(expected, actual) => {
+                write!(f, "expected {} query positions, but {} were provided", expected, actual)
+            }
         }
     }
 }
@@ -44,6 +44,14 @@
 //!    are provided to the [verify()](FriVerifier::verify()) function directly. The values at
 //!    remaining layers, the verifier reads from the specified verifier channel.
 //!
+//! # Standalone low-degree testing
+//! FRI protocol can also be used outside of the STARK protocol to prove that evaluations of some
+//! polynomial committed to via a different commitment scheme are of low degree. For this purpose,
+//! [prove_low_degree()] and [verify_low_degree()] functions run both phases of the protocol
+//! using [DefaultProverChannel] and [DefaultVerifierChannel] respectively. Applications which
+//! need a different way of drawing random values can implement [ProverChannel] and
+//! [VerifierChannel] traits and use [FriProver] and [FriVerifier] directly.
+//!
 //! # Protocol parameters
 //! The current implementation supports executing FRI protocol with dynamically configurable
 //! parameters including:
@@ -75,6 +83,9 @@ pub use prover::{DefaultProverChannel, FriProver, ProverChannel};
 mod verifier;
 pub use verifier::{DefaultVerifierChannel, FriVerifier, VerifierChannel};
 
+mod low_degree;
+pub use low_degree::{prove_low_degree, verify_low_degree};
+
 mod options;
 pub use options::FriOptions;
 
@@ -0,0 +1,292 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{
+    DefaultProverChannel, DefaultVerifierChannel, FriOptions, FriProof, FriProver, FriVerifier,
+    VerifierError,
+};
+use crypto::{ElementHasher, RandomCoin};
+use math::{FieldElement, StarkField};
+use utils::{collections::Vec, string::ToString};
+
+// STANDALONE LOW-DEGREE TESTING
+// ================================================================================================
+
+/// Generates a FRI proof that `evaluations` are evaluations of a polynomial of degree at most
+/// `max_poly_degree`.
+///
+/// The evaluations are expected to be taken over a multiplicative coset of a subgroup of size
+/// [options.domain_size(max_poly_degree)](FriOptions::domain_size); the coset is defined by
+/// `options.domain_offset()`. Layer commitments
+/// are written into the provided `channel`, and can be retrieved from it via
+/// [layer_commitments()](DefaultProverChannel::layer_commitments()) once this function returns.
+///
+/// Returns the proof together with the positions at which the evaluations were queried. The
+/// caller is responsible for opening their own commitment to `evaluations` at these positions,
+/// since FRI proof does not include the queried evaluations of the first layer.
+///
+/// # Panics
+/// Panics if:
+/// * The number of evaluations is not the domain size implied by `max_poly_degree`.
+/// * The number of evaluations is not the same as the domain size of the `channel`.
+pub fn prove_low_degree<B, E, H>(
+    evaluations: &[E],
+    max_poly_degree: usize,
+    options: &FriOptions,
+    channel: &mut DefaultProverChannel<B, E, H>,
+) -> (FriProof, Vec<usize>)
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+    H: ElementHasher<BaseField = B>,
+{
+    let domain_size = options.domain_size(max_poly_degree);
+    assert_eq!(
+        domain_size,
+        evaluations.len(),
+        "expected {} evaluations for a polynomial of degree {}, but was {}",
+        domain_size,
+        max_poly_degree,
+        evaluations.len()
+    );
+    assert_eq!(
+        evaluations.len(),
+        channel.domain_size(),
+        "number of evaluations must be equal to the domain size of the channel"
+    );
+
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(channel, evaluations.to_vec());
+    let positions = channel.draw_query_positions();
+    let proof = prover.build_proof(&positions);
+
+    (proof, positions)
+}
+
+/// Verifies a FRI proof generated via [prove_low_degree()] function.
+///
+/// Returns `Ok(())` if `layer_commitments` and `proof` attest to the fact that the committed
+/// evaluations are evaluations of a polynomial of degree at most `max_poly_degree`, and
+/// `queried_evaluations` are values of this polynomial at the specified `positions`.
+///
+/// The number of queries is set by the verifier via `num_queries` parameter, and `max_poly_degree`
+/// defines the evaluation domain in the same way as in [prove_low_degree()]. The query positions
+/// are re-derived from the layer commitments in the same way as [DefaultProverChannel] derives
+/// them, and the verification fails if they differ from the specified `positions`.
+///
+/// # Errors
+/// Returns an error if:
+/// * The number of specified `positions` is not `num_queries`.
+/// * The proof could not be parsed.
+/// * The specified `positions` are not the ones implied by the layer commitments.
+/// * Any of the checks performed by [FriVerifier] fails.
+pub fn verify_low_degree<B, E, H>(
+    proof: FriProof,
+    layer_commitments: Vec<H::Digest>,
+    queried_evaluations: &[E],
+    positions: &[usize],
+    max_poly_degree: usize,
+    num_queries: usize,
+    options: &FriOptions,
+) -> Result<(), VerifierError>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+    H: ElementHasher<BaseField = B>,
+{
+    // the number of queries defines soundness of the proof, and thus, must not be chosen by the
+    // prover
+    if positions.len() != num_queries {
+        return Err(VerifierError::NumQueriesMismatch(
+            num_queries,
+            positions.len(),
+        ));
+    }
+
+    let domain_size = options.domain_size(max_poly_degree);
+    let mut channel = DefaultVerifierChannel::<E, H>::new(
+        proof,
+        layer_commitments,
+        domain_size,
+        options.folding_factor(),
+    )
+    .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+
+    // the public coin must be seeded in the same way as in the default prover channel
+    let mut public_coin = RandomCoin::<B, H>::new(&[]);
+    let verifier = FriVerifier::new(
+        &mut channel,
+        &mut public_coin,
+        options.clone(),
+        max_poly_degree,
+    )?;
+
+    let expected_positions = public_coin
+        .label("query positions")
+        .draw_integers(num_queries, domain_size)
+        .map_err(VerifierError::PublicCoinError)?;
+    if expected_positions != positions {
+        return Err(VerifierError::QueryPositionsMismatch);
+    }
+
+    verifier.verify(&mut channel, queried_evaluations, positions)
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{prove_low_degree, verify_low_degree};
+    use crate::{DefaultProverChannel, FriOptions, FriProof, VerifierError};
+    use crypto::{hashers::Blake3_256, Hasher};
+    use math::{fft, fields::f128::BaseElement, FieldElement};
+    use rand_utils::rand_vector;
+    use utils::collections::Vec;
+
+    type Blake3 = Blake3_256<BaseElement>;
+
+    #[test]
+    fn prove_verify_low_degree() {
+        let options = FriOptions::new(8, 4, 32);
+        let evaluations = build_evaluations(1024, 1023, &options);
+        let (proof, commitments, positions, queried) = prove(&evaluations, 1023, 32, &options);
+
+        let result = verify_low_degree::<_, _, Blake3>(
+            proof.clone(),
+            commitments.clone(),
+            &queried,
+            &positions,
+            1023,
+            32,
+            &options,
+        );
+        assert!(result.is_ok(), "{}", result.unwrap_err());
+
+        // positions not derived from the commitments are rejected
+        let mut wrong_positions = positions.clone();
+        wrong_positions[0] = (wrong_positions[0] + 1) % 8192;
+        let result = verify_low_degree::<_, _, Blake3>(
+            proof,
+            commitments,
+            &queried,
+            &wrong_positions,
+            1023,
+            32,
+            &options,
+        );
+        assert_eq!(Err(VerifierError::QueryPositionsMismatch), result);
+    }
+
+    #[test]
+    fn prove_verify_low_degree_not_power_of_two() {
+        // a polynomial of degree 1000 is evaluated over the same domain as polynomials of degree
+        // 1023, and passes a degree 1023 check
+        let options = FriOptions::new(8, 4, 32);
+        let evaluations = build_evaluations(1001, 1023, &options);
+        assert_eq!(8192, evaluations.len());
+        let (proof, commitments, positions, queried) = prove(&evaluations, 1023, 32, &options);
+
+        let result = verify_low_degree::<_, _, Blake3>(
+            proof,
+            commitments,
+            &queried,
+            &positions,
+            1023,
+            32,
+            &options,
+        );
+        assert!(result.is_ok(), "{}", result.unwrap_err());
+    }
+
+    #[test]
+    fn prove_verify_low_degree_too_few_queries() {
+        // the number of queries is set by the verifier; a proof with fewer queries is rejected
+        let options = FriOptions::new(8, 4, 32);
+        let evaluations = build_evaluations(1024, 1023, &options);
+        let (proof, commitments, positions, queried) = prove(&evaluations, 1023, 1, &options);
+
+        let result = verify_low_degree::<_, _, Blake3>(
+            proof,
+            commitments,
+            &queried,
+            &positions,
+            1023,
+            32,
+            &options,
+        );
+        assert_eq!(Err(VerifierError::NumQueriesMismatch(32, 1)), result);
+    }
+
+    #[test]
+    fn prove_verify_low_degree_fail() {
+        // evaluations of a polynomial of degree 4095 do not pass a degree 1023 check
+        let options = FriOptions::new(2, 4, 32);
+        let evaluations = build_evaluations(4096, 4095, &options);
+        let (proof, commitments, positions, queried) = prove(&evaluations, 4095, 32, &options);
+
+        let options = FriOptions::new(8, 4, 32);
+        let result = verify_low_degree::<_, _, Blake3>(
+            proof,
+            commitments,
+            &queried,
+            &positions,
+            1023,
+            32,
+            &options,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "expected 16384 evaluations for a polynomial of degree 1024, but was 8192"
+    )]
+    fn prove_low_degree_wrong_domain_size() {
+        // a polynomial of degree 1024 requires a domain twice as large as a degree 1023 one
+        let options = FriOptions::new(8, 4, 32);
+        let evaluations = build_evaluations(1024, 1023, &options);
+        prove(&evaluations, 1024, 32, &options);
+    }
+
+    /// Generates a low-degree proof for the specified evaluations, and returns the proof, layer
+    /// commitments, query positions, and evaluations at these positions.
+    #[allow(clippy::type_complexity)]
+    fn prove(
+        evaluations: &[BaseElement],
+        max_poly_degree: usize,
+        num_queries: usize,
+        options: &FriOptions,
+    ) -> (
+        FriProof,
+        Vec<<Blake3 as Hasher>::Digest>,
+        Vec<usize>,
+        Vec<BaseElement>,
+    ) {
+        let mut channel = DefaultProverChannel::<_, _, Blake3>::new(evaluations.len(), num_queries);
+        let (proof, positions) =
+            prove_low_degree(evaluations, max_poly_degree, options, &mut channel);
+        let commitments = channel.layer_commitments().to_vec();
+        let queried = positions
+            .iter()
+            .map(|&p| evaluations[p])
+            .collect::<Vec<_>>();
+        (proof, commitments, positions, queried)
+    }
+
+    /// Evaluates a random polynomial with the specified number of coefficients over the domain
+    /// for polynomials of degree at most `max_poly_degree`.
+    fn build_evaluations(
+        num_coefficients: usize,
+        max_poly_degree: usize,
+        options: &FriOptions,
+    ) -> Vec<BaseElement> {
+        let mut poly: Vec<BaseElement> = rand_vector(num_coefficients);
+        poly.resize((max_poly_degree + 1).next_power_of_two(), BaseElement::ZERO);
+        let twiddles = fft::get_twiddles::<BaseElement>(poly.len());
+        let offset = options.domain_offset::<BaseElement>();
+        fft::evaluate_poly_with_offset(&poly, &twiddles, offset, options.blowup_factor())
+    }
+}
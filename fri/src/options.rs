@@ -14,6 +14,7 @@ pub struct FriOptions {
     folding_factor: usize,
     max_remainder_size: usize,
     blowup_factor: usize,
+    domain_offset: Option<u64>,
 }
 
 impl FriOptions {
@@ -46,17 +47,30 @@ impl FriOptions {
             folding_factor,
             max_remainder_size,
             blowup_factor,
+            domain_offset: None,
         }
     }
 
+    /// Returns these options with the offset of the evaluation domain set to `offset`.
+    ///
+    /// The offset is interpreted as an element of the field in which the protocol is executed;
+    /// see [domain_offset()](FriOptions::domain_offset).
+    pub fn with_domain_offset(mut self, offset: u64) -> Self {
+        self.domain_offset = Some(offset);
+        self
+    }
+
     /// Returns the offset by which the evaluation domain is shifted.
     ///
     /// The domain is shifted by multiplying every element in the domain by this offset.
     ///
-    /// Currently, the offset is hard-coded to be the primitive element in the field specified by
-    /// type parameter `B`.
+    /// By default, the offset is the primitive element in the field specified by type parameter
+    /// `B`; a different offset can be set via [with_domain_offset()](FriOptions::with_domain_offset).
     pub fn domain_offset<B: StarkField>(&self) -> B {
-        B::GENERATOR
+        match self.domain_offset {
+            Some(offset) => B::from(offset),
+            None => B::GENERATOR,
+        }
     }
 
     /// Returns the factor by which the degree of a polynomial is reduced with each FRI layer.
@@ -84,6 +98,15 @@ impl FriOptions {
         self.blowup_factor
     }
 
+    /// Returns the size of the domain over which polynomials of degree at most `max_poly_degree`
+    /// are evaluated.
+    ///
+    /// The domain size is computed by rounding `max_poly_degree + 1` up to the next power of two
+    /// and multiplying the result by the blowup factor.
+    pub fn domain_size(&self, max_poly_degree: usize) -> usize {
+        (max_poly_degree + 1).next_power_of_two() * self.blowup_factor
+    }
+
     /// Computes and return the number of FRI layers required for a domain of the specified size.
     ///
     /// The remainder layer (the last FRI layer) is not included in the returned value.
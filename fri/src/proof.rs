@@ -28,7 +28,7 @@ use utils::{
 /// All values in a proof are stored as vectors of bytes. Thus, the values must be parsed before
 /// they can be returned to the user. To do this, [parse_layers()](FriProof::parse_layers())
 /// and [parse_remainder()](FriProof::parse_remainder()) methods can be used.
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct FriProof {
     layers: Vec<FriProofLayer>,
     remainder: Vec<u8>,
@@ -230,6 +230,13 @@ impl Deserializable for FriProof {
 
         // read number of partitions
         let num_partitions = source.read_u8()?;
+        if num_partitions as u32 >= usize::BITS {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of partitions cannot be greater than 2^{}, but was 2^{}",
+                usize::BITS - 1,
+                num_partitions
+            )));
+        }
 
         Ok(FriProof {
             layers,
@@ -242,7 +249,7 @@ impl Deserializable for FriProof {
 // FRI PROOF LAYER
 // ================================================================================================
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct FriProofLayer {
     values: Vec<u8>,
     paths: Vec<u8>,
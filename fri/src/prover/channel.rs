@@ -4,7 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use core::marker::PhantomData;
-use crypto::{Hasher, RandomCoin};
+use crypto::{Hasher, PublicCoin, RandomCoin};
 use math::{FieldElement, StarkField};
 use utils::collections::Vec;
 
@@ -54,15 +54,30 @@ pub trait ProverChannel<E: FieldElement> {
 ///
 /// Though this implementation is intended primarily for testing purposes, it can be used in
 /// production use cases as well.
-pub struct DefaultProverChannel<B: StarkField, E: FieldElement<BaseField = B>, H: Hasher> {
-    public_coin: RandomCoin<B, H>,
+///
+/// Randomness is drawn from a public coin specified by the `R` type parameter, which defaults to
+/// [RandomCoin].
+pub struct DefaultProverChannel<B, E, H, R = RandomCoin<B, H>>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+    H: Hasher,
+    R: PublicCoin<BaseField = B, Hasher = H>,
+{
+    public_coin: R,
     commitments: Vec<H::Digest>,
     domain_size: usize,
     num_queries: usize,
     _field_element: PhantomData<E>,
 }
 
-impl<B: StarkField, E: FieldElement<BaseField = B>, H: Hasher> DefaultProverChannel<B, E, H> {
+impl<B, E, H, R> DefaultProverChannel<B, E, H, R>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+    H: Hasher,
+    R: PublicCoin<BaseField = B, Hasher = H>,
+{
     /// Returns a new prover channel instantiated from the specified parameters.
     ///
     /// # Panics
@@ -85,7 +100,7 @@ impl<B: StarkField, E: FieldElement<BaseField = B>, H: Hasher> DefaultProverChan
             "number of queries must be greater than zero"
         );
         DefaultProverChannel {
-            public_coin: RandomCoin::new(&[]),
+            public_coin: R::new(&[]),
             commitments: Vec::new(),
             domain_size,
             num_queries,
@@ -105,30 +120,42 @@ impl<B: StarkField, E: FieldElement<BaseField = B>, H: Hasher> DefaultProverChan
     /// construction of the channel.
     pub fn draw_query_positions(&mut self) -> Vec<usize> {
         self.public_coin
+            .label("query positions")
             .draw_integers(self.num_queries, self.domain_size)
             .expect("failed to draw query position")
     }
 
+    /// Returns the size of the domain from which query positions are drawn.
+    pub fn domain_size(&self) -> usize {
+        self.domain_size
+    }
+
     /// Returns a list of FRI layer commitments written by the prover into this channel.
     pub fn layer_commitments(&self) -> &[H::Digest] {
         &self.commitments
     }
 }
 
-impl<B, E, H> ProverChannel<E> for DefaultProverChannel<B, E, H>
+impl<B, E, H, R> ProverChannel<E> for DefaultProverChannel<B, E, H, R>
 where
     B: StarkField,
     E: FieldElement<BaseField = B>,
     H: Hasher,
+    R: PublicCoin<BaseField = B, Hasher = H>,
 {
     type Hasher = H;
 
     fn commit_fri_layer(&mut self, layer_root: H::Digest) {
         self.commitments.push(layer_root);
-        self.public_coin.reseed(layer_root);
+        self.public_coin
+            .label("fri layer commitment")
+            .reseed(layer_root);
     }
 
     fn draw_fri_alpha(&mut self) -> E {
-        self.public_coin.draw().expect("failed to draw FRI alpha")
+        self.public_coin
+            .label("fri alpha")
+            .draw()
+            .expect("failed to draw FRI alpha")
     }
 }
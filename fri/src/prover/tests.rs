@@ -59,6 +59,29 @@ fn fri_prove_verify() {
     assert!(result.is_err());
 }
 
+#[test]
+fn fri_proof_invalid_num_partitions() {
+    let trace_length = 1024;
+    let lde_blowup = 8;
+
+    let options = FriOptions::new(lde_blowup, 4, 256);
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    let mut prover = FriProver::new(options);
+    prover.build_layers(&mut channel, evaluations);
+    let positions = channel.draw_query_positions();
+    let proof = prover.build_proof(&positions);
+
+    // the number of partitions is the last byte of a serialized proof
+    let mut proof_bytes = proof.to_bytes();
+    let result = FriProof::read_from(&mut SliceReader::new(&proof_bytes)).unwrap();
+    assert_eq!(proof, result);
+
+    *proof_bytes.last_mut().unwrap() = usize::BITS as u8;
+    assert!(FriProof::read_from(&mut SliceReader::new(&proof_bytes)).is_err());
+}
+
 // TEST UTILS
 // ================================================================================================
 
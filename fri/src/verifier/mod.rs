@@ -7,9 +7,12 @@
 
 use crate::{folding::fold_positions, utils::map_positions_to_indexes, FriOptions, VerifierError};
 use core::{convert::TryInto, marker::PhantomData, mem};
-use crypto::{ElementHasher, RandomCoin};
+use crypto::{ElementHasher, PublicCoin};
 use math::{fft, log2, polynom, FieldElement, StarkField};
-use utils::collections::Vec;
+use utils::{collections::Vec, iter};
+
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
 
 mod channel;
 pub use channel::{DefaultVerifierChannel, VerifierChannel};
@@ -98,14 +101,17 @@ where
     /// * `max_poly_degree` is inconsistent with the number of FRI layers read from the channel
     ///   and `folding_factor` specified in the `options` parameter.
     /// * An error was encountered while drawing a random α value from the coin.
-    pub fn new(
+    pub fn new<R>(
         channel: &mut C,
-        public_coin: &mut RandomCoin<B, H>,
+        public_coin: &mut R,
         options: FriOptions,
         max_poly_degree: usize,
-    ) -> Result<Self, VerifierError> {
+    ) -> Result<Self, VerifierError>
+    where
+        R: PublicCoin<BaseField = B, Hasher = H>,
+    {
         // infer evaluation domain info
-        let domain_size = max_poly_degree.next_power_of_two() * options.blowup_factor();
+        let domain_size = options.domain_size(max_poly_degree);
         let domain_generator = B::get_root_of_unity(log2(domain_size));
 
         let num_partitions = channel.read_fri_num_partitions();
@@ -115,8 +121,13 @@ where
         let mut layer_alphas = Vec::with_capacity(layer_commitments.len());
         let mut max_degree_plus_1 = max_poly_degree + 1;
         for (depth, commitment) in layer_commitments.iter().enumerate() {
-            public_coin.reseed(*commitment);
-            let alpha = public_coin.draw().map_err(VerifierError::PublicCoinError)?;
+            public_coin
+                .label("fri layer commitment")
+                .reseed(*commitment);
+            let alpha = public_coin
+                .label("fri alpha")
+                .draw()
+                .map_err(VerifierError::PublicCoinError)?;
             layer_alphas.push(alpha);
 
             // make sure the degree can be reduced by the folding factor at all layers
@@ -156,8 +167,8 @@ where
     /// Returns size of the domain over which a polynomial commitment checked by this verifier
     /// has been evaluated.
     ///
-    /// The domain size can be computed by rounding `max_poly_degree` to the next power of two
-    /// and multiplying the result by the `blowup_factor` from the protocol options.
+    /// The domain size is computed from `max_poly_degree` via
+    /// [FriOptions::domain_size()](crate::FriOptions::domain_size).
     pub fn domain_size(&self) -> usize {
         self.domain_size
     }
@@ -244,6 +255,7 @@ where
         let mut max_degree_plus_1 = self.max_poly_degree + 1;
         let mut positions = positions.to_vec();
         let mut evaluations = evaluations.to_vec();
+        let domain_offset = self.options.domain_offset();
 
         for depth in 0..self.options.num_fri_layers(self.domain_size) {
             // determine which evaluations were queried in the folded layer
@@ -266,10 +278,11 @@ where
                 return Err(VerifierError::InvalidLayerFolding(depth));
             }
 
-            // build a set of x coordinates for each row polynomial
+            // build a set of x coordinates for each row polynomial; when `concurrent` feature is
+            // enabled, coordinates for different queries are computed in multiple threads
             #[rustfmt::skip]
-            let xs = folded_positions.iter().map(|&i| {
-                let xe = domain_generator.exp((i as u64).into()) * self.options.domain_offset();
+            let xs = iter!(folded_positions).map(|&i| {
+                let xe = domain_generator.exp((i as u64).into()) * domain_offset;
                 folding_roots.iter()
                     .map(|&r| E::from(xe * r))
                     .collect::<Vec<_>>().try_into().unwrap()
@@ -284,7 +297,7 @@ where
 
             // check that when the polynomials are evaluated at alpha, the result is equal to
             // the corresponding column value
-            evaluations = row_polys.iter().map(|p| polynom::eval(p, alpha)).collect();
+            evaluations = iter!(row_polys).map(|p| polynom::eval(p, alpha)).collect();
 
             // make sure next degree reduction does not result in degree truncation
             if max_degree_plus_1 % N != 0 {
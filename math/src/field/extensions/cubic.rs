@@ -87,6 +87,11 @@ impl<B: ExtensibleField<3>> FieldElement for CubeExtension<B> {
         )
     }
 
+    #[inline]
+    fn mul_base(self, other: B) -> Self {
+        Self(self.0 * other, self.1 * other, self.2 * other)
+    }
+
     #[inline]
     fn conjugate(&self) -> Self {
         let result = <B as ExtensibleField<3>>::frobenius([self.0, self.1, self.2]);
@@ -362,6 +367,18 @@ mod tests {
         assert_eq!(expected, r1 - r2);
     }
 
+    #[test]
+    fn mul_base() {
+        // identity
+        let r: CubeExtension<BaseElement> = rand_value();
+        assert_eq!(r, r.mul_base(BaseElement::ONE));
+
+        // multiplying by a base field element is the same as multiplying by its embedding
+        let r1: CubeExtension<BaseElement> = rand_value();
+        let r2: BaseElement = rand_value();
+        assert_eq!(r1 * CubeExtension::from(r2), r1.mul_base(r2));
+    }
+
     // INITIALIZATION
     // --------------------------------------------------------------------------------------------
 
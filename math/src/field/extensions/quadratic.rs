@@ -80,6 +80,11 @@ impl<B: ExtensibleField<2>> FieldElement for QuadExtension<B> {
         Self(numerator[0] * denom_inv, numerator[1] * denom_inv)
     }
 
+    #[inline]
+    fn mul_base(self, other: B) -> Self {
+        Self(self.0 * other, self.1 * other)
+    }
+
     #[inline]
     fn conjugate(&self) -> Self {
         let result = <B as ExtensibleField<2>>::frobenius([self.0, self.1]);
@@ -351,6 +356,18 @@ mod tests {
         assert_eq!(expected, r1 - r2);
     }
 
+    #[test]
+    fn mul_base() {
+        // identity
+        let r: QuadExtension<BaseElement> = rand_value();
+        assert_eq!(r, r.mul_base(BaseElement::ONE));
+
+        // multiplying by a base field element is the same as multiplying by its embedding
+        let r1: QuadExtension<BaseElement> = rand_value();
+        let r2: BaseElement = rand_value();
+        assert_eq!(r1 * QuadExtension::from(r2), r1.mul_base(r2));
+    }
+
     // INITIALIZATION
     // --------------------------------------------------------------------------------------------
 
@@ -159,6 +159,11 @@ impl StarkField for BaseElement {
     fn as_int(&self) -> Self::PositiveInteger {
         self.0
     }
+
+    #[inline]
+    fn is_canonical(&self) -> bool {
+        self.0 < M
+    }
 }
 
 impl Randomizable for BaseElement {
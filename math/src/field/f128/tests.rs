@@ -114,6 +114,17 @@ fn inv() {
     }
 }
 
+#[test]
+fn is_canonical() {
+    assert!(BaseElement::ZERO.is_canonical());
+    assert!(BaseElement::new(u128::MAX).is_canonical());
+    assert!(BaseElement(M - 1).is_canonical());
+
+    // a value read from raw memory can be outside of [0, M) range
+    assert!(!BaseElement(M).is_canonical());
+    assert!(!BaseElement(u128::MAX).is_canonical());
+}
+
 #[test]
 fn conjugate() {
     let a: BaseElement = rand_value();
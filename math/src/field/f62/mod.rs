@@ -189,6 +189,12 @@ impl StarkField for BaseElement {
         // since the result of multiplication can be in [0, 2M), we need to normalize it
         normalize(result)
     }
+
+    #[inline]
+    fn is_canonical(&self) -> bool {
+        // values in Montgomery representation are kept in [0, 2M) range
+        self.0 < 2 * M
+    }
 }
 
 impl Randomizable for BaseElement {
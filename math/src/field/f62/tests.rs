@@ -104,6 +104,17 @@ fn element_as_int() {
     assert_eq!(v % super::M, e.as_int());
 }
 
+#[test]
+fn is_canonical() {
+    assert!(BaseElement::ZERO.is_canonical());
+    assert!(BaseElement::new(u64::MAX).is_canonical());
+    assert!(BaseElement(2 * super::M - 1).is_canonical());
+
+    // a value read from raw memory can be outside of [0, 2M) range
+    assert!(!BaseElement(2 * super::M).is_canonical());
+    assert!(!BaseElement(u64::MAX).is_canonical());
+}
+
 #[test]
 fn equals() {
     let a = BaseElement::ONE;
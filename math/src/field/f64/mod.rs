@@ -216,6 +216,12 @@ impl StarkField for BaseElement {
             self.0
         }
     }
+
+    #[inline]
+    fn is_canonical(&self) -> bool {
+        // all values in [0, 2^64) range are valid internal representations of field elements
+        true
+    }
 }
 
 impl Randomizable for BaseElement {
@@ -120,6 +120,18 @@ fn element_as_int() {
     assert_eq!(v % super::M, e.as_int());
 }
 
+#[test]
+fn is_canonical() {
+    assert!(BaseElement::ZERO.is_canonical());
+    assert!(BaseElement::new(super::M - 1).is_canonical());
+
+    // a value read from raw memory is always a valid element, even if it is not reduced
+    let value = BaseElement(u64::MAX);
+    assert!(value.is_canonical());
+    assert_eq!(u64::MAX - super::M, value.as_int());
+    assert_eq!(BaseElement::new(u64::MAX - super::M), value);
+}
+
 #[test]
 fn equals() {
     let a = BaseElement::ONE;
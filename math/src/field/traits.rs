@@ -111,6 +111,17 @@ pub trait FieldElement:
         self * self * self
     }
 
+    /// Returns this field element multiplied by an element of the base field.
+    ///
+    /// For prime fields, this is the same as regular multiplication. For extension fields, this
+    /// is cheaper than converting `other` into an extension field element and multiplying by it
+    /// since each coordinate of this element needs to be multiplied by `other` only once.
+    #[inline]
+    #[must_use]
+    fn mul_base(self, other: Self::BaseField) -> Self {
+        self * Self::from(other)
+    }
+
     /// Exponentiates this field element by `power` parameter.
     #[must_use]
     fn exp(self, power: Self::PositiveInteger) -> Self {
@@ -237,6 +248,14 @@ pub trait StarkField: FieldElement<BaseField = Self> {
 
     /// Returns a canonical integer representation of this field element.
     fn as_int(&self) -> Self::PositiveInteger;
+
+    /// Returns true if the internal representation of this field element is within the range
+    /// maintained by the field arithmetic.
+    ///
+    /// Elements obtained by reinterpreting raw memory (e.g., via
+    /// [FieldElement::bytes_as_elements()]) may have internal representations outside of this
+    /// range; arithmetic on such elements produces incorrect results.
+    fn is_canonical(&self) -> bool;
 }
 
 // EXTENSIBLE FIELD
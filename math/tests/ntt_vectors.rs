@@ -0,0 +1,328 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Conformance test vectors for the number-theoretic transform (NTT).
+//!
+//! The vectors pin the conventions used by the `fft` module, so that independent
+//! implementations (e.g., verifier ports in other languages) can check that they compute
+//! byte-identical results. Test vectors for a field are stored in a single JSON object with the
+//! following fields:
+//! * `field` - name of the field (e.g., `f128`).
+//! * `modulus` - field modulus.
+//! * `vectors` - an array of test vectors, one for each size in `NTT_VECTOR_SIZES`.
+//!
+//! Each test vector contains the following fields:
+//! * `size` - number of values $n$ in the input vector.
+//! * `root_of_unity` - generator $g$ of the multiplicative subgroup of size $n$, as returned by
+//!   `StarkField::get_root_of_unity()`.
+//! * `input` - input vector $v$.
+//! * `forward` - result of the forward NTT of $v$, i.e., $\sum_j v_j \cdot g^{i \cdot j}$ for each
+//!   $i$ in natural order; this is the same as evaluating a polynomial with coefficients $v$ over
+//!   the subgroup via `fft::evaluate_poly()`.
+//! * `backward` - result of the backward NTT of $v$ (including division by $n$); this is the same
+//!   as interpolating a polynomial from its evaluations $v$ over the subgroup via
+//!   `fft::interpolate_poly()`.
+//! * `lde_blowup` - blowup factor $b$ of the low-degree extension; this is always `LDE_BLOWUP`.
+//! * `lde_offset` - offset $s$ of the low-degree extension domain; this is the default domain
+//!   offset used by STARK protocol (i.e., `StarkField::GENERATOR`).
+//! * `lde` - evaluations of a polynomial with coefficients $v$ over the coset
+//!   $s \cdot \langle h \rangle$, where $h$ is the generator of the subgroup of size $n \cdot b$,
+//!   in natural order; this is the same as the result of `fft::evaluate_poly_with_offset()`.
+//!
+//! All field elements (including the modulus) are encoded as hex strings of their canonical
+//! little-endian byte representation.
+//!
+//! Input vectors are generated deterministically, and thus, generated test vectors are always the
+//! same. To regenerate the checked-in test vectors (e.g., after adding a new field), run these
+//! tests with `NTT_VECTORS=overwrite` environment variable.
+
+use serde_json::{json, Value};
+use std::{env, fs, path::Path};
+use utils::SliceReader;
+use winter_math::{
+    fft,
+    fields::{f128, f64},
+    log2, StarkField,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Sizes of input vectors for which NTT test vectors are generated.
+const NTT_VECTOR_SIZES: [usize; 3] = [8, 64, 1024];
+
+/// Blowup factor of the low-degree extensions included in NTT test vectors.
+const LDE_BLOWUP: usize = 8;
+
+// TESTS
+// ================================================================================================
+
+const F64_VECTORS: &str = include_str!("../testvectors/ntt_f64.json");
+const F128_VECTORS: &str = include_str!("../testvectors/ntt_f128.json");
+
+#[test]
+fn ntt_vectors_f64() {
+    let vectors = generate_ntt_vectors::<f64::BaseElement>("f64");
+    overwrite_if_requested("ntt_f64.json", &vectors);
+    check_ntt_vectors::<f64::BaseElement>(F64_VECTORS).unwrap();
+    assert_eq!(F64_VECTORS, vectors);
+}
+
+#[test]
+fn ntt_vectors_f128() {
+    let vectors = generate_ntt_vectors::<f128::BaseElement>("f128");
+    overwrite_if_requested("ntt_f128.json", &vectors);
+    check_ntt_vectors::<f128::BaseElement>(F128_VECTORS).unwrap();
+    assert_eq!(F128_VECTORS, vectors);
+}
+
+#[test]
+fn ntt_vectors_mismatch() {
+    // vectors for one field do not pass the check for another field
+    let err = check_ntt_vectors::<f64::BaseElement>(F128_VECTORS).unwrap_err();
+    assert!(err.starts_with("f128: expected modulus"), "{}", err);
+
+    // a modified output value is reported
+    let forward = F64_VECTORS.find("\"forward\": [").unwrap() + 12;
+    let value = forward + F64_VECTORS[forward..].find('"').unwrap() + 1;
+    let mut vectors = F64_VECTORS.to_string();
+    let original = &F64_VECTORS[value..value + 2];
+    vectors.replace_range(value..value + 2, if original == "00" { "01" } else { "00" });
+    let err = check_ntt_vectors::<f64::BaseElement>(&vectors).unwrap_err();
+    assert!(
+        err.starts_with("f64 size 8: forward value 0 expected"),
+        "{}",
+        err
+    );
+}
+
+// GENERATION
+// ================================================================================================
+
+/// Returns NTT test vectors for field `B` serialized into a JSON string.
+///
+/// `field_name` is recorded in the `field` property of the returned JSON object.
+fn generate_ntt_vectors<B: StarkField>(field_name: &str) -> String {
+    let vectors = NTT_VECTOR_SIZES
+        .iter()
+        .map(|&size| build_ntt_vector(&build_input::<B>(size)))
+        .collect::<Vec<_>>();
+
+    let result = json!({
+        "field": field_name,
+        "modulus": encode_bytes(&B::get_modulus_le_bytes()),
+        "vectors": vectors,
+    });
+    let mut result = serde_json::to_string_pretty(&result).expect("failed to serialize JSON");
+    result.push('\n');
+    result
+}
+
+// VALIDATION
+// ================================================================================================
+
+/// Checks NTT test vectors serialized into the specified JSON string against the NTT
+/// implementation of field `B`.
+///
+/// Outputs are re-computed from the input vectors contained in the JSON string; thus, the check
+/// does not depend on how the input vectors were generated.
+///
+/// # Errors
+/// Returns an error describing the first mismatch if:
+/// * The JSON string could not be parsed or does not have the expected structure.
+/// * The modulus does not match the modulus of field `B`.
+/// * Sizes of test vectors are different from [NTT_VECTOR_SIZES].
+/// * Any of the values in the test vectors does not match the re-computed value.
+fn check_ntt_vectors<B: StarkField>(json: &str) -> Result<(), String> {
+    let source: Value =
+        serde_json::from_str(json).map_err(|err| format!("invalid JSON: {}", err))?;
+    let field = source["field"]
+        .as_str()
+        .ok_or("missing field name")?
+        .to_string();
+
+    let modulus = encode_bytes(&B::get_modulus_le_bytes());
+    if source["modulus"] != modulus.as_str() {
+        return Err(format!(
+            "{}: expected modulus {}, but was {}",
+            field, modulus, source["modulus"]
+        ));
+    }
+
+    let vectors = source["vectors"]
+        .as_array()
+        .ok_or_else(|| format!("{}: missing test vectors", field))?;
+    let sizes = vectors
+        .iter()
+        .map(|vector| vector["size"].as_u64().unwrap_or(0) as usize)
+        .collect::<Vec<_>>();
+    if sizes != NTT_VECTOR_SIZES {
+        return Err(format!(
+            "{}: expected test vectors of sizes {:?}, but were {:?}",
+            field, NTT_VECTOR_SIZES, sizes
+        ));
+    }
+
+    for (vector, &size) in vectors.iter().zip(sizes.iter()) {
+        let input = decode_elements::<B>(&vector["input"])
+            .map_err(|err| format!("{} size {}: invalid input: {}", field, size, err))?;
+        if input.len() != size {
+            return Err(format!(
+                "{} size {}: expected {} input values, but was {}",
+                field,
+                size,
+                size,
+                input.len()
+            ));
+        }
+
+        let expected = build_ntt_vector(&input);
+        for key in [
+            "root_of_unity",
+            "forward",
+            "backward",
+            "lde_blowup",
+            "lde_offset",
+            "lde",
+        ] {
+            check_value(&expected[key], &vector[key])
+                .map_err(|err| format!("{} size {}: {} {}", field, size, key, err))?;
+        }
+    }
+
+    Ok(())
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Builds a test vector for the specified input.
+fn build_ntt_vector<B: StarkField>(input: &[B]) -> Value {
+    let size = input.len();
+
+    let mut forward = input.to_vec();
+    fft::evaluate_poly(&mut forward, &fft::get_twiddles::<B>(size));
+
+    let mut backward = input.to_vec();
+    fft::interpolate_poly(&mut backward, &fft::get_inv_twiddles::<B>(size));
+
+    let twiddles = fft::get_twiddles::<B>(size);
+    let lde = fft::evaluate_poly_with_offset(input, &twiddles, B::GENERATOR, LDE_BLOWUP);
+
+    json!({
+        "size": size,
+        "root_of_unity": encode_element(B::get_root_of_unity(log2(size))),
+        "input": encode_elements(input),
+        "forward": encode_elements(&forward),
+        "backward": encode_elements(&backward),
+        "lde_blowup": LDE_BLOWUP,
+        "lde_offset": encode_element(B::GENERATOR),
+        "lde": encode_elements(&lde),
+    })
+}
+
+/// Returns a deterministic pseudo-random vector of the specified size.
+///
+/// Values are generated by a SplitMix64 generator seeded with the size of the vector; each value
+/// is built from two consecutive 64-bit outputs and is reduced modulo the field modulus.
+fn build_input<B: StarkField>(size: usize) -> Vec<B> {
+    let mut state = size as u64;
+    let mut next = move || {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    };
+    (0..size)
+        .map(|_| {
+            let low = next() as u128;
+            let high = next() as u128;
+            B::from(low | (high << 64))
+        })
+        .collect()
+}
+
+/// Compares an expected value against an actual value and describes the first difference.
+fn check_value(expected: &Value, actual: &Value) -> Result<(), String> {
+    match (expected.as_array(), actual.as_array()) {
+        (Some(expected), Some(actual)) => {
+            if expected.len() != actual.len() {
+                return Err(format!(
+                    "expected {} values, but was {}",
+                    expected.len(),
+                    actual.len()
+                ));
+            }
+            match expected.iter().zip(actual).position(|(e, a)| e != a) {
+                Some(i) => Err(format!(
+                    "value {} expected {}, but was {}",
+                    i, expected[i], actual[i]
+                )),
+                None => Ok(()),
+            }
+        }
+        _ if expected == actual => Ok(()),
+        _ => Err(format!("expected {}, but was {}", expected, actual)),
+    }
+}
+
+fn encode_element<B: StarkField>(value: B) -> String {
+    encode_bytes(&value.to_bytes())
+}
+
+fn encode_elements<B: StarkField>(values: &[B]) -> Vec<String> {
+    values.iter().map(|&value| encode_element(value)).collect()
+}
+
+fn encode_bytes(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn decode_elements<B: StarkField>(values: &Value) -> Result<Vec<B>, String> {
+    let values = values.as_array().ok_or("expected an array")?;
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| {
+            let bytes = value
+                .as_str()
+                .and_then(decode_bytes)
+                .ok_or_else(|| format!("value {} is not a hex string", i))?;
+            if bytes.len() != B::ELEMENT_BYTES {
+                return Err(format!(
+                    "value {} must be {} bytes long, but was {}",
+                    i,
+                    B::ELEMENT_BYTES,
+                    bytes.len()
+                ));
+            }
+            B::read_from(&mut SliceReader::new(&bytes))
+                .map_err(|err| format!("value {} is not a valid field element: {}", i, err))
+        })
+        .collect()
+}
+
+fn decode_bytes(value: &str) -> Option<Vec<u8>> {
+    if value.len() % 2 != 0 {
+        return None;
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(value.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Writes the specified test vectors into the file with the specified name in the test vectors
+/// directory if `NTT_VECTORS` environment variable is set to `overwrite`.
+fn overwrite_if_requested(file_name: &str, vectors: &str) {
+    if env::var("NTT_VECTORS").map_or(false, |value| value == "overwrite") {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("testvectors")
+            .join(file_name);
+        fs::write(&path, vectors)
+            .unwrap_or_else(|err| panic!("failed to write {}: {}", path.display(), err));
+    }
+}
@@ -0,0 +1,121 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use math::{
+    fields::{f128::BaseElement, QuadExtension},
+    get_power_series, log2, FieldElement, StarkField,
+};
+use rand_utils::{rand_value, rand_vector};
+use std::time::Duration;
+use winter_prover::{crypto::hashers::Blake3_256, EvaluationFrame, Matrix, TraceCommitment};
+
+type QuadElement = QuadExtension<BaseElement>;
+
+const TRACE_WIDTHS: [usize; 3] = [8, 32, 128];
+const FILL_NUM_ROWS: usize = 1 << 14;
+const BLOWUP_FACTOR: usize = 8;
+
+const OOD_TRACE_WIDTH: usize = 8;
+const OOD_POLY_SIZES: [usize; 3] = [1 << 16, 1 << 18, 1 << 20];
+
+// FRAME FILL
+// ================================================================================================
+
+/// Reads current and next rows of an extended trace into an evaluation frame for every step of
+/// the extended trace, in the same way as the constraint evaluator does.
+fn frame_fill(c: &mut Criterion) {
+    let mut group = c.benchmark_group("frame_fill");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(10));
+
+    for &width in TRACE_WIDTHS.iter() {
+        let trace_lde = build_matrix(width, FILL_NUM_ROWS);
+        let mut last_row = vec![BaseElement::ZERO; width];
+        trace_lde.read_row_into(FILL_NUM_ROWS - 1, &mut last_row);
+        let mut wrapped_row = vec![BaseElement::ZERO; width];
+        trace_lde.read_row_into(BLOWUP_FACTOR - 1, &mut wrapped_row);
+
+        let trace_tree = trace_lde.commit_to_rows::<Blake3_256<BaseElement>>();
+        let trace = TraceCommitment::new(trace_lde, trace_tree, BLOWUP_FACTOR);
+        let mut frame = EvaluationFrame::<BaseElement>::new(width);
+
+        // make sure the frame is filled with the expected rows; at the end of the trace, the
+        // next row wraps around to the beginning of the trace
+        trace.read_frame_into(FILL_NUM_ROWS - 1, &mut frame);
+        assert_eq!(last_row, frame.current());
+        assert_eq!(wrapped_row, frame.next());
+
+        group.bench_function(BenchmarkId::from_parameter(width), |bench| {
+            bench.iter(|| {
+                for step in 0..FILL_NUM_ROWS {
+                    trace.read_frame_into(step, &mut frame);
+                    black_box(&frame);
+                }
+            });
+        });
+    }
+    group.finish();
+}
+
+// OOD FRAME
+// ================================================================================================
+
+/// Evaluates trace polynomials at an out-of-domain point z and at z * g, in the same way as the
+/// prover builds the out-of-domain frame.
+fn ood_frame(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ood_frame");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(10));
+
+    for &poly_size in OOD_POLY_SIZES.iter() {
+        let polys = build_matrix(OOD_TRACE_WIDTH, poly_size);
+        let z = rand_value::<QuadElement>();
+        let g = QuadElement::from(BaseElement::get_root_of_unity(log2(poly_size)));
+
+        // make sure the frame matches evaluations computed via powers of z
+        let frame = get_ood_frame(&polys, z, g);
+        let z_powers = get_power_series(z, poly_size);
+        let zg_powers = get_power_series(z * g, poly_size);
+        for (col_idx, poly) in polys.columns().enumerate() {
+            assert_eq!(inner_product(poly, &z_powers), frame.current()[col_idx]);
+            assert_eq!(inner_product(poly, &zg_powers), frame.next()[col_idx]);
+        }
+
+        group.bench_function(BenchmarkId::from_parameter(poly_size), |bench| {
+            bench.iter(|| get_ood_frame(&polys, black_box(z), g));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(frames_group, frame_fill, ood_frame);
+criterion_main!(frames_group);
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn build_matrix(num_cols: usize, num_rows: usize) -> Matrix<BaseElement> {
+    Matrix::new((0..num_cols).map(|_| rand_vector(num_rows)).collect())
+}
+
+fn get_ood_frame(
+    polys: &Matrix<BaseElement>,
+    z: QuadElement,
+    g: QuadElement,
+) -> EvaluationFrame<QuadElement> {
+    EvaluationFrame::from_rows(
+        polys.evaluate_columns_at(z),
+        polys.evaluate_columns_at(z * g),
+    )
+}
+
+fn inner_product(poly: &[BaseElement], powers: &[QuadElement]) -> QuadElement {
+    poly.iter()
+        .zip(powers)
+        .fold(QuadElement::ZERO, |acc, (&c, &p)| {
+            acc + p * QuadElement::from(c)
+        })
+}
@@ -0,0 +1,147 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use math::{fields::f128::BaseElement, FieldElement, StarkField};
+use std::time::Duration;
+use winter_prover::{
+    Air, AirContext, Assertion, EvaluationFrame, FieldExtension, HashFunction, ProofOptions,
+    Prover, TraceInfo, TraceTable, TransitionConstraintDegree,
+};
+
+const TRACE_LENGTH: usize = 65_536;
+const TRACE_WIDTH: usize = 32;
+const NUM_CONSTANT_COLUMNS: [usize; 2] = [0, 16];
+
+fn prove_with_constant_columns(c: &mut Criterion) {
+    let mut group = c.benchmark_group("constant_columns");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(20));
+
+    for &num_constant_columns in NUM_CONSTANT_COLUMNS.iter() {
+        let prover = CounterProver::new(num_constant_columns);
+        group.bench_function(BenchmarkId::from_parameter(num_constant_columns), |bench| {
+            bench.iter(|| prover.prove(build_trace(num_constant_columns)).unwrap());
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(lde_group, prove_with_constant_columns);
+criterion_main!(lde_group);
+
+// COUNTER TRACE
+// ================================================================================================
+
+/// Builds a trace in which the first `num_constant_columns` columns are constant, and all other
+/// columns are incremented by one on each step.
+fn build_trace(num_constant_columns: usize) -> TraceTable<BaseElement> {
+    let mut trace = TraceTable::new(TRACE_WIDTH, TRACE_LENGTH);
+    trace.fill(
+        |state| {
+            for (i, value) in state.iter_mut().enumerate() {
+                *value = BaseElement::from(i as u64);
+            }
+        },
+        |_, state| {
+            for value in state.iter_mut().skip(num_constant_columns) {
+                *value += BaseElement::ONE;
+            }
+        },
+    );
+    trace
+}
+
+// COUNTER AIR
+// ================================================================================================
+
+struct CounterAir {
+    context: AirContext<BaseElement>,
+    num_constant_columns: usize,
+}
+
+impl Air for CounterAir {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+
+    fn new(
+        trace_info: TraceInfo,
+        num_constant_columns: BaseElement,
+        options: ProofOptions,
+    ) -> Self {
+        let degrees = vec![TransitionConstraintDegree::new(1); TRACE_WIDTH];
+        CounterAir {
+            context: AirContext::new(trace_info, degrees, options),
+            num_constant_columns: num_constant_columns.as_int() as usize,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        for (i, result) in result.iter_mut().enumerate() {
+            let increment = if i < self.num_constant_columns {
+                E::ZERO
+            } else {
+                E::ONE
+            };
+            *result = next[i] - (current[i] + increment);
+        }
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        (0..TRACE_WIDTH)
+            .map(|i| Assertion::single(i, 0, BaseElement::from(i as u64)))
+            .collect()
+    }
+}
+
+// COUNTER PROVER
+// ================================================================================================
+
+struct CounterProver {
+    options: ProofOptions,
+    num_constant_columns: usize,
+}
+
+impl CounterProver {
+    fn new(num_constant_columns: usize) -> Self {
+        CounterProver {
+            options: ProofOptions::new(
+                32,
+                8,
+                0,
+                HashFunction::Blake3_256,
+                FieldExtension::None,
+                4,
+                256,
+            ),
+            num_constant_columns,
+        }
+    }
+}
+
+impl Prover for CounterProver {
+    type BaseField = BaseElement;
+    type Air = CounterAir;
+    type Trace = TraceTable<BaseElement>;
+
+    fn get_pub_inputs(&self, _trace: &Self::Trace) -> BaseElement {
+        BaseElement::from(self.num_constant_columns as u64)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
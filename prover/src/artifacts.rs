@@ -0,0 +1,35 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use air::proof::OodFrame;
+use utils::collections::Vec;
+
+// PROOF ARTIFACTS
+// ================================================================================================
+/// Intermediate values of proof generation which are also committed to in the generated proof.
+///
+/// Proof artifacts are returned by [Prover::prove_with_artifacts()](crate::Prover::prove_with_artifacts)
+/// alongside the proof, so that integration layers can build auxiliary attestations (e.g., sign
+/// the composition commitment) without re-deriving these values from proof bytes. The artifacts
+/// are read-only copies of the values in the proof:
+/// * Each composition root is serialized in the same way as it is serialized within
+///   [StarkProof::commitments](air::proof::StarkProof::commitments). The protocol commits to the
+///   evaluations of the constraint composition polynomial once, and thus, there is currently a
+///   single composition root.
+/// * Each out-of-domain frame is identical to
+///   [StarkProof::ood_frame](air::proof::StarkProof::ood_frame); there is currently a single
+///   out-of-domain frame.
+/// * Query positions are the positions in the LDE domain drawn from the public coin, in the
+///   order in which they were drawn. These positions are not included in the proof explicitly,
+///   but are the positions against which trace and constraint queries in the proof were made.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofArtifacts {
+    /// Serialized roots of the commitments to the constraint composition polynomial evaluations.
+    pub composition_roots: Vec<Vec<u8>>,
+    /// Out-of-domain evaluation frames sent to the verifier.
+    pub ood_frames: Vec<OodFrame>,
+    /// Positions in the LDE domain at which the proof was queried.
+    pub query_positions: Vec<usize>,
+}
@@ -3,12 +3,13 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use crate::ProverError;
 use air::{
     proof::{Commitments, Context, OodFrame, Queries, StarkProof},
     Air, ConstraintCompositionCoefficients, DeepCompositionCoefficients, EvaluationFrame,
 };
 use core::marker::PhantomData;
-use crypto::{ElementHasher, RandomCoin};
+use crypto::{ElementHasher, PublicCoin, RandomCoin};
 use fri::{self, FriProof};
 use math::FieldElement;
 use utils::{collections::Vec, Serializable};
@@ -19,14 +20,19 @@ use utils::iterators::*;
 // TYPES AND INTERFACES
 // ================================================================================================
 
-pub struct ProverChannel<'a, A, E, H>
+/// Channel over which the prover communicates with the verifier.
+///
+/// Randomness is drawn from a public coin specified by the `R` type parameter, which defaults to
+/// [RandomCoin]; the verifier must use the same kind of coin.
+pub struct ProverChannel<'a, A, E, H, R = RandomCoin<<A as Air>::BaseField, H>>
 where
     A: Air,
     E: FieldElement<BaseField = A::BaseField>,
     H: ElementHasher<BaseField = A::BaseField>,
+    R: PublicCoin<BaseField = A::BaseField, Hasher = H> + Sync,
 {
     air: &'a A,
-    public_coin: RandomCoin<A::BaseField, H>,
+    public_coin: R,
     context: Context,
     commitments: Commitments,
     ood_frame: OodFrame,
@@ -37,11 +43,12 @@ where
 // PROVER CHANNEL IMPLEMENTATION
 // ================================================================================================
 
-impl<'a, A, E, H> ProverChannel<'a, A, E, H>
+impl<'a, A, E, H, R> ProverChannel<'a, A, E, H, R>
 where
     A: Air,
     E: FieldElement<BaseField = A::BaseField>,
     H: ElementHasher<BaseField = A::BaseField>,
+    R: PublicCoin<BaseField = A::BaseField, Hasher = H> + Sync,
 {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
@@ -49,15 +56,16 @@ where
     pub fn new(air: &'a A, pub_inputs_bytes: Vec<u8>) -> Self {
         let context = Context::new::<A::BaseField>(air.trace_info(), air.options().clone());
 
-        // build a seed for the public coin; the initial seed is the hash of public inputs and proof
-        // context, but as the protocol progresses, the coin will be reseeded with the info sent to
-        // the verifier
-        let mut coin_seed = pub_inputs_bytes;
+        // build a seed for the public coin; the initial seed is the hash of transcript domain tag,
+        // public inputs and proof context, but as the protocol progresses, the coin will be
+        // reseeded with the info sent to the verifier
+        let mut coin_seed = air.options().transcript_domain().to_vec();
+        coin_seed.extend_from_slice(&pub_inputs_bytes);
         context.write_into(&mut coin_seed);
 
         ProverChannel {
             air,
-            public_coin: RandomCoin::new(&coin_seed),
+            public_coin: R::new(&coin_seed),
             context,
             commitments: Commitments::default(),
             ood_frame: OodFrame::default(),
@@ -72,20 +80,26 @@ where
     /// Commits the prover the extended execution trace.
     pub fn commit_trace(&mut self, trace_root: H::Digest) {
         self.commitments.add::<H>(&trace_root);
-        self.public_coin.reseed(trace_root);
+        self.public_coin
+            .label("trace commitment")
+            .reseed(trace_root);
     }
 
     /// Commits the prover to the evaluations of the constraint composition polynomial.
     pub fn commit_constraints(&mut self, constraint_root: H::Digest) {
         self.commitments.add::<H>(&constraint_root);
-        self.public_coin.reseed(constraint_root);
+        self.public_coin
+            .label("constraint commitment")
+            .reseed(constraint_root);
     }
 
     /// Saves the out-of-domain evaluation frame. This also reseeds the public coin with the
     /// hashes of the evaluation frame states.
     pub fn send_ood_evaluation_frame(&mut self, frame: &EvaluationFrame<E>) {
         self.ood_frame.set_evaluation_frame(frame);
-        self.public_coin.reseed(H::hash_elements(frame.current()));
+        self.public_coin
+            .label("ood trace frame")
+            .reseed(H::hash_elements(frame.current()));
         self.public_coin.reseed(H::hash_elements(frame.next()));
     }
 
@@ -93,7 +107,9 @@ where
     /// point. This also reseeds the public coin wit the hash of the evaluations.
     pub fn send_ood_constraint_evaluations(&mut self, evaluations: &[E]) {
         self.ood_frame.set_constraint_evaluations(evaluations);
-        self.public_coin.reseed(H::hash_elements(evaluations));
+        self.public_coin
+            .label("ood constraint evaluations")
+            .reseed(H::hash_elements(evaluations));
     }
 
     // PUBLIC COIN METHODS
@@ -102,6 +118,8 @@ where
     /// Returns a set of coefficients for constructing a constraint composition polynomial drawn
     /// from the public coin.
     pub fn get_constraint_composition_coeffs(&mut self) -> ConstraintCompositionCoefficients<E> {
+        self.public_coin
+            .label("constraint composition coefficients");
         self.air
             .get_constraint_composition_coefficients(&mut self.public_coin)
             .expect("failed to draw composition coefficients")
@@ -109,12 +127,16 @@ where
 
     /// Returns an out-of-domain point drawn from the public coin.
     pub fn get_ood_point(&mut self) -> E {
-        self.public_coin.draw().expect("failed to draw OOD point")
+        self.public_coin
+            .label("ood point")
+            .draw()
+            .expect("failed to draw OOD point")
     }
 
     /// Returns a set of coefficients for constructing a DEEP composition polynomial drawn from
     /// the public coin.
     pub fn get_deep_composition_coeffs(&mut self) -> DeepCompositionCoefficients<E> {
+        self.public_coin.label("deep composition coefficients");
         self.air
             .get_deep_composition_coefficients(&mut self.public_coin)
             .expect("failed to draw DEEP composition coefficients")
@@ -126,6 +148,7 @@ where
         let num_queries = self.context.options().num_queries();
         let lde_domain_size = self.context.lde_domain_size();
         self.public_coin
+            .label("query positions")
             .draw_integers(num_queries, lde_domain_size)
             .expect("failed to draw query position")
     }
@@ -133,22 +156,29 @@ where
     /// Determines a nonce, which when hashed with the current seed of the public coin results
     /// in a new seed with the number of leading zeros equal to the grinding_factor specified
     /// in the proof options.
-    pub fn grind_query_seed(&mut self) {
+    ///
+    /// Nonces are tried starting with 1; if `max_attempts` is specified, at most `max_attempts`
+    /// nonces are tried, and an error is returned if none of them satisfies the grinding factor.
+    pub fn grind_query_seed(&mut self, max_attempts: Option<u64>) -> Result<(), ProverError> {
         let grinding_factor = self.context.options().grinding_factor();
+        let end = max_attempts.map_or(u64::MAX, |attempts| attempts.saturating_add(1));
 
         #[cfg(not(feature = "concurrent"))]
-        let nonce = (1..u64::MAX)
-            .find(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor)
-            .expect("nonce not found");
+        let nonce =
+            (1..end).find(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor);
 
         #[cfg(feature = "concurrent")]
-        let nonce = (1..u64::MAX)
+        let nonce = (1..end)
             .into_par_iter()
-            .find_any(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor)
-            .expect("nonce not found");
+            .find_any(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor);
 
+        let nonce = nonce.ok_or(ProverError::GrindingFailed {
+            grinding_factor,
+            attempts: end - 1,
+        })?;
         self.pow_nonce = nonce;
-        self.public_coin.reseed_with_int(nonce);
+        self.public_coin.label("pow nonce").reseed_with_int(nonce);
+        Ok(())
     }
 
     // PROOF BUILDER
@@ -176,22 +206,28 @@ where
 // FRI PROVER CHANNEL IMPLEMENTATION
 // ================================================================================================
 
-impl<'a, A, E, H> fri::ProverChannel<E> for ProverChannel<'a, A, E, H>
+impl<'a, A, E, H, R> fri::ProverChannel<E> for ProverChannel<'a, A, E, H, R>
 where
     A: Air,
     E: FieldElement<BaseField = A::BaseField>,
     H: ElementHasher<BaseField = A::BaseField>,
+    R: PublicCoin<BaseField = A::BaseField, Hasher = H> + Sync,
 {
     type Hasher = H;
 
     /// Commits the prover to a FRI layer.
     fn commit_fri_layer(&mut self, layer_root: H::Digest) {
         self.commitments.add::<H>(&layer_root);
-        self.public_coin.reseed(layer_root);
+        self.public_coin
+            .label("fri layer commitment")
+            .reseed(layer_root);
     }
 
     /// Returns a new alpha drawn from the public coin.
     fn draw_fri_alpha(&mut self) -> E {
-        self.public_coin.draw().expect("failed to draw FRI alpha")
+        self.public_coin
+            .label("fri alpha")
+            .draw()
+            .expect("failed to draw FRI alpha")
     }
 }
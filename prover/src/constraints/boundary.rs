@@ -84,7 +84,7 @@ impl<B: StarkField, E: FieldElement<BaseField = B>> BoundaryConstraintGroup<B, E
 
     /// Evaluates the constraints contained in this group at the specified step of the
     /// execution trace.
-    pub fn evaluate(&self, state: &[B], ce_step: usize, x: B, xp: E) -> E {
+    pub fn evaluate(&self, state: &[B], ce_step: usize, x: B, xp: B) -> E {
         let mut result = E::ZERO;
 
         // evaluate all single-value constraints
@@ -118,9 +118,9 @@ struct SingleValueConstraint<B: StarkField, E: FieldElement<BaseField = B>> {
 }
 
 impl<B: StarkField, E: FieldElement<BaseField = B>> SingleValueConstraint<B, E> {
-    pub fn evaluate(&self, state: &[B], xp: E) -> E {
-        let evaluation = E::from(state[self.register] - self.value);
-        evaluation * (self.coefficients.0 + self.coefficients.1 * xp)
+    pub fn evaluate(&self, state: &[B], xp: B) -> E {
+        let evaluation = state[self.register] - self.value;
+        (self.coefficients.0 + self.coefficients.1.mul_base(xp)).mul_base(evaluation)
     }
 }
 
@@ -135,12 +135,12 @@ struct SmallPolyConstraint<B: StarkField, E: FieldElement<BaseField = B>> {
 }
 
 impl<B: StarkField, E: FieldElement<BaseField = B>> SmallPolyConstraint<B, E> {
-    pub fn evaluate(&self, state: &[B], x: B, xp: E) -> E {
+    pub fn evaluate(&self, state: &[B], x: B, xp: B) -> E {
         let x = x * self.x_offset;
         // evaluate constraint polynomial as x * offset
         let assertion_value = polynom::eval(&self.poly, x);
-        let evaluation = E::from(state[self.register] - assertion_value);
-        evaluation * (self.coefficients.0 + self.coefficients.1 * xp)
+        let evaluation = state[self.register] - assertion_value;
+        (self.coefficients.0 + self.coefficients.1.mul_base(xp)).mul_base(evaluation)
     }
 }
 
@@ -155,7 +155,7 @@ struct LargePolyConstraint<B: StarkField, E: FieldElement<BaseField = B>> {
 }
 
 impl<B: StarkField, E: FieldElement<BaseField = B>> LargePolyConstraint<B, E> {
-    pub fn evaluate(&self, state: &[B], ce_step: usize, xp: E) -> E {
+    pub fn evaluate(&self, state: &[B], ce_step: usize, xp: B) -> E {
         let value_index = if self.step_offset > 0 {
             // if the assertion happens on steps which are not a power of 2, we need to offset the
             // evaluation; the below basically computes (ce_step - step_offset) % values.len();
@@ -168,7 +168,7 @@ impl<B: StarkField, E: FieldElement<BaseField = B>> LargePolyConstraint<B, E> {
         } else {
             ce_step
         };
-        let evaluation = E::from(state[self.register] - self.values[value_index]);
-        evaluation * (self.coefficients.0 + self.coefficients.1 * xp)
+        let evaluation = state[self.register] - self.values[value_index];
+        (self.coefficients.0 + self.coefficients.1.mul_base(xp)).mul_base(evaluation)
     }
 }
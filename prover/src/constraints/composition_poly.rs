@@ -95,6 +95,16 @@ impl<E: FieldElement> CompositionPoly<E> {
         self.data.evaluate_columns_at(z_m)
     }
 
+    /// Returns coefficients of the composition polynomial re-assembled from its columns; this
+    /// reverses the split performed in [CompositionPoly::new()].
+    #[cfg(test)]
+    pub fn coefficients(&self) -> Vec<E> {
+        let num_columns = self.num_columns();
+        (0..num_columns * self.column_len())
+            .map(|i| self.data.get(i % num_columns, i / num_columns))
+            .collect()
+    }
+
     /// Transforms this composition polynomial into a vector of individual column polynomials.
     pub fn into_columns(self) -> Vec<Vec<E>> {
         self.data.into_columns()
@@ -97,6 +97,11 @@ impl<B: StarkField, E: FieldElement<BaseField = B>> ConstraintEvaluationTable<B,
     // TABLE FRAGMENTS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns the largest number of fragments into which this table can be broken.
+    pub fn max_fragments(&self) -> usize {
+        (self.num_rows() / MIN_FRAGMENT_SIZE).max(1)
+    }
+
     /// Break the table into the number of specified fragments. All fragments can be updated
     /// independently - e.g. in different threads.
     pub fn fragments(&mut self, num_fragments: usize) -> Vec<EvaluationTableFragment<B, E>> {
@@ -208,20 +213,33 @@ impl<B: StarkField, E: FieldElement<BaseField = B>> ConstraintEvaluationTable<B,
             max_degree = core::cmp::max(max_degree, degree);
         }
 
-        // make sure expected and actual degrees are equal
-        assert_eq!(
-            self.t_expected_degrees, actual_degrees,
-            "transition constraint degrees didn't match\nexpected: {:>3?}\nactual:   {:>3?}",
-            self.t_expected_degrees, actual_degrees
-        );
+        // make sure expected and actual degrees are equal; if they are not, report the first
+        // constraint with a mismatched degree
+        if let Some(index) = self
+            .t_expected_degrees
+            .iter()
+            .zip(actual_degrees.iter())
+            .position(|(expected, actual)| expected != actual)
+        {
+            panic!(
+                "transition constraint degrees didn't match; constraint {} was declared with \
+                evaluation degree {}, but its actual degree is {}\nexpected: {:>3?}\nactual:   {:>3?}",
+                index,
+                self.t_expected_degrees[index],
+                actual_degrees[index],
+                self.t_expected_degrees,
+                actual_degrees
+            );
+        }
 
-        // make sure evaluation domain size does not exceed the size required by max degree
+        // make sure evaluation domain size is sufficient for the max degree; the domain may be
+        // larger than the size required by max degree if the composition polynomial is split
+        // into more columns than required by constraint degrees
         let expected_domain_size =
             core::cmp::max(max_degree, self.trace_length + 1).next_power_of_two();
-        assert_eq!(
-            expected_domain_size,
-            self.num_rows(),
-            "incorrect constraint evaluation domain size; expected {}, actual: {}",
+        assert!(
+            expected_domain_size <= self.num_rows(),
+            "incorrect constraint evaluation domain size; expected at least {}, actual: {}",
             expected_domain_size,
             self.num_rows()
         );
@@ -287,8 +305,8 @@ fn acc_column<B: StarkField, E: FieldElement<BaseField = B>>(
     let numerator = divisor.numerator();
     assert_eq!(numerator.len(), 1, "complex divisors are not yet supported");
     assert!(
-        divisor.exclude().len() <= 1,
-        "multiple exclusion points are not yet supported"
+        divisor.exemptions().len() <= 1,
+        "multiple periodic exemptions are not yet supported"
     );
 
     // compute inverse evaluations of the divisor's numerator, which has the form (x^a - b)
@@ -298,8 +316,8 @@ fn acc_column<B: StarkField, E: FieldElement<BaseField = B>>(
     // divide column values by the divisor; for boundary constraints this computed simply as
     // multiplication of column value by the inverse of divisor numerator; for transition
     // constraints, it is computed similarly, but the result is also multiplied by the divisor's
-    // denominator (exclusion point).
-    if divisor.exclude().is_empty() {
+    // denominator (exclusion points and periodic exemption).
+    if divisor.exclude().is_empty() && divisor.exemptions().is_empty() {
         // the column represents merged evaluations of boundary constraints, and divisor has the
         // form of (x^a - b); thus to divide the column by the divisor, we compute: value * z,
         // where z = 1 / (x^a - 1) and has already been computed above.
@@ -314,12 +332,15 @@ fn acc_column<B: StarkField, E: FieldElement<BaseField = B>>(
             });
     } else {
         // the column represents merged evaluations of transition constraints, and divisor has the
-        // form of (x^a - 1) / (x - b); thus, to divide the column by the divisor, we compute:
-        // value * (x - b) * z, where z = 1 / (x^a - 1) and has already been computed above.
+        // form of (x^a - 1) / ((x - b_0) * ... * (x - b_k) * (x^c - d)), where any of the terms in
+        // the denominator may be missing; thus, to divide the column by the divisor, we compute:
+        // value * (x - b_0) * ... * (x - b_k) * (x^c - d) * z, where z = 1 / (x^a - 1) and has
+        // already been computed above.
 
         // set up variables for computing x at every point in the domain
         let g = B::get_root_of_unity(domain_size.trailing_zeros());
-        let b = divisor.exclude()[0];
+        let exclude = divisor.exclude();
+        let exemptions = get_exemption_evaluations(divisor, domain_size, domain_offset);
 
         batch_iter_mut!(
             result,
@@ -327,12 +348,14 @@ fn acc_column<B: StarkField, E: FieldElement<BaseField = B>>(
             |batch: &mut [E], batch_offset: usize| {
                 let mut x = domain_offset * g.exp((batch_offset as u64).into());
                 for (i, acc_value) in batch.iter_mut().enumerate() {
-                    // compute value of (x - b) and compute next value of x
-                    let e = x - b;
+                    // compute value of (x - b_0) * ... * (x - b_k) * (x^c - d) and compute next
+                    // value of x
+                    let e = exclude.iter().fold(B::ONE, |e, &b| e * (x - b));
+                    let e = e * exemptions[(batch_offset + i) % exemptions.len()];
                     x *= g;
                     // determine which value of z corresponds to the current domain point
-                    let z = z[i % z.len()];
-                    // compute value * (x - b) * z and add it to the result
+                    let z = z[(batch_offset + i) % z.len()];
+                    // compute value * e * z and add it to the result
                     *acc_value += column[batch_offset + i] * E::from(z * e);
                 }
             }
@@ -372,6 +395,35 @@ fn get_inv_evaluation<B: StarkField>(
     batch_inversion(&evaluations)
 }
 
+/// Computes evaluations of the divisor's periodic exemption, which has the form (x^c - d), over
+/// the domain of the specified size and offset.
+///
+/// The evaluations repeat with a period of domain_size / c, and thus, only the first period is
+/// returned. If the divisor has no periodic exemptions, a single ONE is returned.
+fn get_exemption_evaluations<B: StarkField>(
+    divisor: &ConstraintDivisor<B>,
+    domain_size: usize,
+    domain_offset: B,
+) -> Vec<B> {
+    let (c, d) = match divisor.exemptions().first() {
+        Some(&(c, d)) => (c as u64, d),
+        None => return vec![B::ONE],
+    };
+
+    let n = domain_size / c as usize;
+    let g = B::get_root_of_unity(domain_size.trailing_zeros()).exp(c.into());
+
+    // compute x^c - d for all x in the first period
+    let mut x = domain_offset.exp(c.into());
+    (0..n)
+        .map(|_| {
+            let evaluation = x - d;
+            x *= g;
+            evaluation
+        })
+        .collect()
+}
+
 // DEBUG HELPERS
 // ================================================================================================
 
@@ -8,11 +8,11 @@ use super::{
     PeriodicValueTable, StarkDomain, TraceCommitment,
 };
 use air::{
-    Air, ConstraintCompositionCoefficients, ConstraintDivisor, EvaluationFrame,
-    TransitionConstraintGroup,
+    Air, ConstraintCompositionCoefficients, ConstraintDivisor, CustomBoundaryConstraint,
+    EvaluationFrame, TransitionConstraintGroup,
 };
 use crypto::ElementHasher;
-use math::FieldElement;
+use math::{FieldElement, StarkField};
 use utils::{
     collections::{BTreeMap, Vec},
     iter_mut,
@@ -33,9 +33,12 @@ const MIN_CONCURRENT_DOMAIN_SIZE: usize = 8192;
 pub struct ConstraintEvaluator<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> {
     air: &'a A,
     boundary_constraints: Vec<BoundaryConstraintGroup<A::BaseField, E>>,
+    custom_boundary_constraints: Vec<CustomBoundaryConstraint<'a, A::BaseField, E>>,
     transition_constraints: Vec<TransitionConstraintGroup<E>>,
     periodic_values: PeriodicValueTable<A::BaseField>,
     divisors: Vec<ConstraintDivisor<A::BaseField>>,
+    boundary_columns: Vec<usize>,
+    custom_boundary_columns: Vec<usize>,
 
     #[cfg(debug_assertions)]
     transition_constraint_degrees: Vec<usize>,
@@ -46,7 +49,33 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
     // --------------------------------------------------------------------------------------------
     /// Returns a new evaluator which can be used to evaluate transition and boundary constraints
     /// over extended execution trace.
+    ///
+    /// Evaluations of constraints with identical divisors are merged into the same column of the
+    /// constraint evaluation table, and thus, each distinct divisor is applied only once when
+    /// the composition polynomial is built.
     pub fn new(air: &'a A, coefficients: ConstraintCompositionCoefficients<E>) -> Self {
+        Self::build(air, coefficients, true)
+    }
+
+    /// Returns a new evaluator which puts evaluations of each boundary constraint group and each
+    /// custom boundary constraint into a separate column, even if some of these constraints have
+    /// identical divisors.
+    ///
+    /// The resulting composition polynomial is the same as for an evaluator returned from
+    /// [new()](ConstraintEvaluator::new); this evaluator is used as a reference in tests.
+    #[cfg(test)]
+    pub fn without_divisor_merging(
+        air: &'a A,
+        coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self {
+        Self::build(air, coefficients, false)
+    }
+
+    fn build(
+        air: &'a A,
+        coefficients: ConstraintCompositionCoefficients<E>,
+        merge_divisors: bool,
+    ) -> Self {
         // collect expected degrees for all transition constraints to compare them against actual
         // degrees; we do this in debug mode only because this comparison is expensive
         #[cfg(debug_assertions)]
@@ -63,41 +92,69 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
         // build periodic value table
         let periodic_values = PeriodicValueTable::new(air);
 
-        // set divisor for transition constraints; all transition constraints have the same divisor
+        // set divisor for transition constraints; all transition constraints have the same
+        // divisor, and their evaluations always go into the first column
         let mut divisors = vec![air.transition_constraint_divisor()];
 
-        // build boundary constraints and also append divisors for each group of boundary
-        // constraints to the divisor list
+        // build boundary constraints and determine the column for each group of boundary
+        // constraints; a new column is added only for a divisor which is not in the list yet
         let mut twiddle_map = BTreeMap::new();
+        let mut boundary_columns = Vec::new();
         let boundary_constraints = air
             .get_boundary_constraints(&coefficients.boundary)
             .into_iter()
             .map(|group| {
-                divisors.push(group.divisor().clone());
+                let column = get_divisor_column(&mut divisors, group.divisor(), merge_divisors);
+                boundary_columns.push(column);
                 BoundaryConstraintGroup::new(group, air, &mut twiddle_map)
             })
             .collect();
 
+        // build custom boundary constraints (if any); each of these constraints has its own
+        // divisor, but the divisor may be the same as the divisor of another constraint
+        let custom_boundary_constraints =
+            air.get_custom_boundary_constraints(&coefficients.custom_boundary);
+        let custom_boundary_columns = custom_boundary_constraints
+            .iter()
+            .map(|constraint| {
+                get_divisor_column(&mut divisors, constraint.divisor(), merge_divisors)
+            })
+            .collect();
+
         ConstraintEvaluator {
             air,
             boundary_constraints,
+            custom_boundary_constraints,
             transition_constraints,
             periodic_values,
             divisors,
+            boundary_columns,
+            custom_boundary_columns,
             #[cfg(debug_assertions)]
             transition_constraint_degrees,
         }
     }
 
+    /// Returns the number of columns in the constraint evaluation table built by this evaluator.
+    #[cfg(test)]
+    pub fn num_columns(&self) -> usize {
+        self.divisors.len()
+    }
+
     // EVALUATOR
     // --------------------------------------------------------------------------------------------
     /// Evaluates constraints against the provided extended execution trace. Constraints are
     /// evaluated over a constraint evaluation domain. This is an optimization because constraint
     /// evaluation domain can be many times smaller than the full LDE domain.
+    ///
+    /// If `num_fragments` is provided, the evaluation table is split into the specified number
+    /// of fragments (capped by the minimum fragment size); otherwise, the number of fragments is
+    /// chosen automatically.
     pub fn evaluate<H: ElementHasher<BaseField = A::BaseField>>(
         &self,
         trace: &TraceCommitment<A::BaseField, H>,
         domain: &StarkDomain<A::BaseField>,
+        num_fragments: Option<usize>,
     ) -> ConstraintEvaluationTable<A::BaseField, E> {
         assert_eq!(
             trace.trace_len(),
@@ -122,15 +179,22 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
         // then don't bother with concurrent evaluation
 
         #[cfg(not(feature = "concurrent"))]
-        let num_fragments = 1;
+        let default_num_fragments = 1;
 
         #[cfg(feature = "concurrent")]
-        let num_fragments = if domain.ce_domain_size() >= MIN_CONCURRENT_DOMAIN_SIZE {
+        let default_num_fragments = if domain.ce_domain_size() >= MIN_CONCURRENT_DOMAIN_SIZE {
             rayon::current_num_threads().next_power_of_two()
         } else {
             1
         };
 
+        // if the number of fragments was specified explicitly, make sure the fragments are not
+        // too small; both values are powers of two, and so the result is a power of two as well
+        let num_fragments = match num_fragments {
+            Some(num_fragments) => num_fragments.min(evaluation_table.max_fragments()),
+            None => default_num_fragments,
+        };
+
         let mut fragments = evaluation_table.fragments(num_fragments);
         iter_mut!(fragments).for_each(|fragment| self.evaluate_fragment(trace, domain, fragment));
 
@@ -156,6 +220,7 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
         let mut ev_frame = EvaluationFrame::new(trace.trace_width());
         let mut evaluations = vec![E::ZERO; fragment.num_columns()];
         let mut t_evaluations = vec![A::BaseField::ZERO; self.air.num_transition_constraints()];
+        let mut periodic_values = vec![A::BaseField::ZERO; self.periodic_values.width()];
 
         // pre-compute values needed to determine x coordinates in the constraint evaluation domain
         let g = domain.ce_domain_generator();
@@ -176,16 +241,22 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
 
             // evaluate transition constraints and save the merged result the first slot of the
             // evaluations buffer
-            evaluations[0] =
-                self.evaluate_transition_constraints(&ev_frame, x, step, &mut t_evaluations);
+            evaluations[0] = self.evaluate_transition_constraints(
+                &ev_frame,
+                x,
+                step,
+                &mut periodic_values,
+                &mut t_evaluations,
+            );
 
             // when in debug mode, save transition constraint evaluations
             #[cfg(debug_assertions)]
-            fragment.update_transition_evaluations(step, &t_evaluations);
+            fragment.update_transition_evaluations(i, &t_evaluations);
 
-            // evaluate boundary constraints; the results go into remaining slots of the
-            // evaluations buffer
-            self.evaluate_boundary_constraints(ev_frame.current(), x, step, &mut evaluations[1..]);
+            // evaluate boundary constraints (including custom ones); the results are added to the
+            // slots of the evaluations buffer which correspond to their divisors
+            evaluations[1..].fill(E::ZERO);
+            self.evaluate_boundary_constraints(ev_frame.current(), x, step, &mut evaluations);
 
             // record the result in the evaluation table
             fragment.update_row(i, &evaluations);
@@ -198,19 +269,21 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
     /// Evaluates transition constraints at the specified step of the execution trace. `step` is
     /// the step in the constraint evaluation, and `x` is the corresponding domain value. That
     /// is, x = s * g^step, where g is the generator of the constraint evaluation domain, and s
-    /// is the domain offset.
+    /// is the domain offset. `periodic_values` is a buffer into which values of periodic columns
+    /// at the specified step are read.
     fn evaluate_transition_constraints(
         &self,
         frame: &EvaluationFrame<A::BaseField>,
         x: A::BaseField,
         step: usize,
+        periodic_values: &mut [A::BaseField],
         evaluations: &mut [A::BaseField],
     ) -> E {
         // TODO: use a more efficient way to zero out memory
         evaluations.fill(A::BaseField::ZERO);
 
         // get periodic values at the evaluation step
-        let periodic_values = self.periodic_values.get_row(step);
+        self.periodic_values.read_row_into(step, periodic_values);
 
         // evaluate transition constraints and save the results into evaluations buffer
         self.air
@@ -221,14 +294,17 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
         self.transition_constraints
             .iter()
             .fold(E::ZERO, |result, group| {
-                result + group.merge_evaluations(evaluations, x)
+                result + group.merge_base_evaluations(evaluations, x)
             })
     }
 
-    /// Evaluates all boundary constraint groups at a specific step of the execution trace.
-    /// `step` is the step in the constraint evaluation domain, and `x` is the corresponding
-    /// domain value. That is, x = s * g^step, where g is the generator of the constraint
-    /// evaluation domain, and s is the domain offset.
+    /// Evaluates all boundary constraint groups and custom boundary constraints at a specific
+    /// step of the execution trace. `step` is the step in the constraint evaluation domain, and
+    /// `x` is the corresponding domain value. That is, x = s * g^step, where g is the generator
+    /// of the constraint evaluation domain, and s is the domain offset.
+    ///
+    /// The result of each evaluation is added to the slot of `result` which corresponds to the
+    /// column of the constraint's divisor.
     fn evaluate_boundary_constraints(
         &self,
         state: &[A::BaseField],
@@ -237,18 +313,277 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
         result: &mut [E],
     ) {
         // compute the adjustment degree outside of the group so that we can re-use
-        // it for groups which have the same adjustment degree
-        let mut degree_adjustment = self.boundary_constraints[0].degree_adjustment;
-        let mut xp = E::from(x.exp(degree_adjustment.into()));
+        // it for groups which have the same adjustment degree; x^0 = 1, so we can start
+        // with the adjustment degree of 0
+        let mut degree_adjustment = 0;
+        let mut xp = A::BaseField::ONE;
 
-        for (group, result) in self.boundary_constraints.iter().zip(result.iter_mut()) {
+        for (group, &column) in self
+            .boundary_constraints
+            .iter()
+            .zip(self.boundary_columns.iter())
+        {
             // recompute adjustment degree only when it has changed
             if group.degree_adjustment != degree_adjustment {
                 degree_adjustment = group.degree_adjustment;
-                xp = E::from(x.exp(degree_adjustment.into()));
+                xp = x.exp(degree_adjustment.into());
+            }
+            // evaluate the group and add the result to the column of its divisor
+            result[column] += group.evaluate(state, step, x, xp);
+        }
+
+        for (constraint, &column) in self
+            .custom_boundary_constraints
+            .iter()
+            .zip(self.custom_boundary_columns.iter())
+        {
+            if constraint.degree_adjustment() != degree_adjustment {
+                degree_adjustment = constraint.degree_adjustment();
+                xp = x.exp(degree_adjustment.into());
+            }
+            result[column] += constraint.evaluate_numerator(state, x, xp);
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the index of the column into which evaluations of constraints with the specified
+/// divisor should be written.
+///
+/// If `merge_divisors` is true and the divisor is already in the list, the index of the existing
+/// divisor is returned; otherwise, the divisor is appended to the list.
+fn get_divisor_column<B: StarkField>(
+    divisors: &mut Vec<ConstraintDivisor<B>>,
+    divisor: &ConstraintDivisor<B>,
+    merge_divisors: bool,
+) -> usize {
+    if merge_divisors {
+        if let Some(column) = divisors.iter().position(|d| d == divisor) {
+            return column;
+        }
+    }
+    divisors.push(divisor.clone());
+    divisors.len() - 1
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::ConstraintEvaluator;
+    use crate::{tests::build_fib_trace, StarkDomain, Trace, TraceCommitment};
+    use air::{
+        Air, AirContext, Assertion, ConstraintCompositionCoefficients, ConstraintDivisor,
+        CustomBoundaryConstraint, EvaluationFrame, FieldExtension, HashFunction, ProofOptions,
+        TraceInfo, TransitionConstraintDegree,
+    };
+    use crypto::hashers::Blake3_256;
+    use math::{fields::f128::BaseElement, FieldElement};
+    use rand_utils::rand_vector;
+    use utils::collections::Vec;
+
+    #[test]
+    fn merge_constraints_with_identical_divisors() {
+        let trace = build_fib_trace(32);
+        let result = trace.get(1, trace.length() - 1);
+        let air = FibMergeAir::new(trace.get_info(), result, build_options());
+        let domain = StarkDomain::new(&air);
+
+        let trace_polys = trace.into_matrix().interpolate_columns_into();
+        let trace_lde = trace_polys.evaluate_columns_over(&domain);
+        let trace_tree = trace_lde.commit_to_rows::<Blake3_256<BaseElement>>();
+        let trace_comm = TraceCommitment::new(trace_lde, trace_tree, domain.trace_to_lde_blowup());
+
+        // two assertion groups and two custom boundary constraints share two distinct divisors;
+        // together with the transition constraint divisor, this results in three columns
+        let coefficients = build_coefficients(&air);
+        let evaluator = ConstraintEvaluator::new(&air, coefficients.clone());
+        assert_eq!(3, evaluator.num_columns());
+        let reference = ConstraintEvaluator::without_divisor_merging(&air, coefficients);
+        assert_eq!(5, reference.num_columns());
+
+        // the composition polynomial must be the same as without merging
+        let expected = reference
+            .evaluate(&trace_comm, &domain, None)
+            .into_poly()
+            .unwrap()
+            .into_columns();
+        for num_fragments in [None, Some(1), Some(4)] {
+            let actual = evaluator
+                .evaluate(&trace_comm, &domain, num_fragments)
+                .into_poly()
+                .unwrap()
+                .into_columns();
+            assert_eq!(expected, actual);
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(
+        expected = "constraint 1 was declared with evaluation degree 15, but its actual degree is 30"
+    )]
+    fn misdeclared_transition_constraint_degree() {
+        let trace = build_fib_trace(32);
+        let air = MisdeclaredAir::new(trace.get_info(), (), build_options());
+        let domain = StarkDomain::new(&air);
+
+        let trace_polys = trace.into_matrix().interpolate_columns_into();
+        let trace_lde = trace_polys.evaluate_columns_over(&domain);
+        let trace_tree = trace_lde.commit_to_rows::<Blake3_256<BaseElement>>();
+        let trace_comm = TraceCommitment::new(trace_lde, trace_tree, domain.trace_to_lde_blowup());
+
+        let coefficients = ConstraintCompositionCoefficients {
+            transition: vec![(BaseElement::ONE, BaseElement::ONE); 2],
+            boundary: vec![(BaseElement::ONE, BaseElement::ONE)],
+            custom_boundary: Vec::new(),
+        };
+        ConstraintEvaluator::new(&air, coefficients).evaluate(&trace_comm, &domain, None);
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    fn build_options() -> ProofOptions {
+        ProofOptions::new(
+            32,
+            8,
+            0,
+            HashFunction::Blake3_256,
+            FieldExtension::None,
+            4,
+            256,
+        )
+    }
+
+    fn build_coefficients(air: &FibMergeAir) -> ConstraintCompositionCoefficients<BaseElement> {
+        let random_pairs = |n: usize| {
+            let values = rand_vector::<BaseElement>(2 * n);
+            values.chunks(2).map(|c| (c[0], c[1])).collect::<Vec<_>>()
+        };
+        ConstraintCompositionCoefficients {
+            transition: random_pairs(air.num_transition_constraints()),
+            boundary: random_pairs(air.get_assertions().len()),
+            custom_boundary: random_pairs(air.num_custom_boundary_constraints()),
+        }
+    }
+
+    // TEST AIR
+    // --------------------------------------------------------------------------------------------
+
+    /// AIR for the trace built by [build_fib_trace()] which asserts the first and the last rows
+    /// both via assertions and via custom boundary constraints.
+    struct FibMergeAir {
+        context: AirContext<BaseElement>,
+        result: BaseElement,
+    }
+
+    impl Air for FibMergeAir {
+        type BaseField = BaseElement;
+        type PublicInputs = BaseElement;
+
+        fn new(trace_info: TraceInfo, result: BaseElement, options: ProofOptions) -> Self {
+            let degrees = vec![
+                TransitionConstraintDegree::new(1),
+                TransitionConstraintDegree::new(1),
+            ];
+            FibMergeAir {
+                context: AirContext::new(trace_info, degrees, options)
+                    .with_custom_boundary_constraints(2),
+                result,
             }
-            // evaluate the group and save the result
-            *result = group.evaluate(state, step, x, xp);
+        }
+
+        fn context(&self) -> &AirContext<Self::BaseField> {
+            &self.context
+        }
+
+        fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+            &self,
+            frame: &EvaluationFrame<E>,
+            _periodic_values: &[E],
+            result: &mut [E],
+        ) {
+            let current = frame.current();
+            let next = frame.next();
+            result[0] = next[0] - (current[0] + current[1]);
+            result[1] = next[1] - (current[0] + current[1].double());
+        }
+
+        fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+            let last_step = self.trace_length() - 1;
+            vec![
+                Assertion::single(0, 0, BaseElement::ONE),
+                Assertion::single(1, 0, BaseElement::ONE),
+                Assertion::single(1, last_step, self.result),
+            ]
+        }
+
+        fn custom_boundary_constraints<E: FieldElement<BaseField = Self::BaseField>>(
+            &self,
+            coefficients: &[(E, E)],
+        ) -> Option<Vec<CustomBoundaryConstraint<'_, Self::BaseField, E>>> {
+            let assertions = self.get_assertions();
+            let first_row = CustomBoundaryConstraint::new(
+                self,
+                ConstraintDivisor::from_assertion(&assertions[0], self.trace_length()),
+                coefficients[0],
+                |state: &[E], _| state[0] - state[1],
+                |state: &[BaseElement], _| E::from(state[0] - state[1]),
+            );
+            let result = self.result;
+            let last_row = CustomBoundaryConstraint::new(
+                self,
+                ConstraintDivisor::from_assertion(&assertions[2], self.trace_length()),
+                coefficients[1],
+                move |state: &[E], _| state[1] - E::from(result),
+                move |state: &[BaseElement], _| E::from(state[1] - result),
+            );
+            Some(vec![first_row, last_row])
+        }
+    }
+
+    /// AIR for the trace built by [build_fib_trace()] whose second transition constraint has
+    /// degree 2, but is declared with degree 1.
+    struct MisdeclaredAir {
+        context: AirContext<BaseElement>,
+    }
+
+    impl Air for MisdeclaredAir {
+        type BaseField = BaseElement;
+        type PublicInputs = ();
+
+        fn new(trace_info: TraceInfo, _pub_inputs: (), options: ProofOptions) -> Self {
+            let degrees = vec![
+                TransitionConstraintDegree::new(1),
+                TransitionConstraintDegree::new(1),
+            ];
+            MisdeclaredAir {
+                context: AirContext::new(trace_info, degrees, options),
+            }
+        }
+
+        fn context(&self) -> &AirContext<Self::BaseField> {
+            &self.context
+        }
+
+        fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+            &self,
+            frame: &EvaluationFrame<E>,
+            _periodic_values: &[E],
+            result: &mut [E],
+        ) {
+            let current = frame.current();
+            let next = frame.next();
+            result[0] = next[0] - (current[0] + current[1]);
+            result[1] = next[1] - current[0] * current[1];
+        }
+
+        fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+            vec![Assertion::single(0, 0, BaseElement::ONE)]
         }
     }
 }
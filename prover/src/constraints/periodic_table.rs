@@ -5,44 +5,40 @@
 
 use air::Air;
 use math::{fft, StarkField};
-use utils::{
-    collections::{BTreeMap, Vec},
-    uninit_vector,
-};
+use utils::collections::{BTreeMap, Vec};
 
+// PERIODIC VALUE TABLE
+// ================================================================================================
+/// Values of periodic columns over the constraint evaluation domain.
+///
+/// Values of a periodic column with cycle length `n` repeat every `n * ce_blowup` steps of the
+/// constraint evaluation domain. Thus, the table holds only a single cycle of evaluations for each
+/// column, and values at a given step are looked up modulo the length of the column's cycle. The
+/// memory needed by the table is proportional to the sum of cycle lengths of all columns rather
+/// than to the size of the constraint evaluation domain.
 pub struct PeriodicValueTable<B: StarkField> {
-    values: Vec<B>,
-    length: usize,
-    width: usize,
+    columns: Vec<Vec<B>>,
 }
 
 impl<B: StarkField> PeriodicValueTable<B> {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
-    /// Builds a table of periodic column values for the specified AIR. The table contains expanded
-    /// values of all periodic columns normalized to the same length. This enables simple lookup
-    /// into the able using step index of the constraint evaluation domain.
+    /// Builds a table of periodic column values for the specified AIR. For each periodic column,
+    /// the table contains evaluations of the column's polynomial over a single cycle of the
+    /// constraint evaluation domain.
     pub fn new<A: Air<BaseField = B>>(air: &A) -> PeriodicValueTable<B> {
         // get a list of polynomials describing periodic columns from AIR. if there are no
-        // periodic columns return an empty table
+        // periodic columns the table will be empty
         let polys = air.get_periodic_column_polys();
-        if polys.is_empty() {
-            return PeriodicValueTable {
-                values: Vec::new(),
-                length: 0,
-                width: 0,
-            };
-        }
-
-        // determine the size of the biggest polynomial in the set. unwrap is OK here
-        // because if we get here, there must be at least one polynomial in the set.
-        let max_poly_size = polys.iter().max_by_key(|p| p.len()).unwrap().len();
 
         // cache twiddles used for polynomial evaluation here so that we don't have to re-build
         // them for polynomials of the same size
         let mut twiddle_map = BTreeMap::new();
 
-        let evaluations = polys
+        // a column with cycle length n is evaluated over the coset of the subgroup of size
+        // n * ce_blowup defined by offset^(trace_length / n); this is the same as evaluating the
+        // column at x^(trace_length / n) for the first n * ce_blowup points x of the domain
+        let columns = polys
             .iter()
             .map(|poly| {
                 let poly_size = poly.len();
@@ -54,39 +50,26 @@ impl<B: StarkField> PeriodicValueTable<B> {
 
                 fft::evaluate_poly_with_offset(poly, twiddles, offset, air.ce_blowup_factor())
             })
-            .collect::<Vec<_>>();
+            .collect();
 
-        // allocate memory to hold all expanded values and copy polynomial evaluations into the
-        // table in such a way that values for the same row are adjacent to each other.
-        let row_width = polys.len();
-        let column_length = max_poly_size * air.ce_blowup_factor();
-        let mut values = unsafe { uninit_vector(row_width * column_length) };
-        for i in 0..column_length {
-            for (j, column) in evaluations.iter().enumerate() {
-                values[i * row_width + j] = column[i % column.len()];
-            }
-        }
-
-        PeriodicValueTable {
-            values,
-            length: column_length,
-            width: row_width,
-        }
+        PeriodicValueTable { columns }
     }
 
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
-    pub fn is_empty(&self) -> bool {
-        self.width == 0
+    /// Returns the number of periodic columns in this table.
+    pub fn width(&self) -> usize {
+        self.columns.len()
     }
 
-    pub fn get_row(&self, ce_step: usize) -> &[B] {
-        if self.is_empty() {
-            &[]
-        } else {
-            let start = (ce_step % self.length) * self.width;
-            &self.values[start..start + self.width]
+    /// Copies values of all periodic columns at the specified step of the constraint evaluation
+    /// domain into the provided row.
+    pub fn read_row_into(&self, ce_step: usize, row: &mut [B]) {
+        debug_assert_eq!(self.width(), row.len(), "invalid periodic row width");
+        for (value, column) in row.iter_mut().zip(self.columns.iter()) {
+            // cycle lengths are powers of two, and so the modulo can be computed via a mask
+            *value = column[ce_step & (column.len() - 1)];
         }
     }
 }
@@ -122,8 +105,10 @@ mod tests {
         // build a table of periodic values
         let table = super::PeriodicValueTable::new(&air);
 
-        assert_eq!(2, table.width);
-        assert_eq!(4 * air.ce_blowup_factor(), table.length);
+        // only a single cycle of each column is stored
+        assert_eq!(2, table.width());
+        assert_eq!(2 * air.ce_blowup_factor(), table.columns[0].len());
+        assert_eq!(4 * air.ce_blowup_factor(), table.columns[1].len());
 
         let polys = air.get_periodic_column_polys();
         let domain = build_ce_domain(air.ce_domain_size(), air.domain_offset());
@@ -145,8 +130,9 @@ mod tests {
 
         // build actual values by recording rows of the table at each step of ce_domain
         let mut actual = vec![Vec::new(), Vec::new()];
+        let mut row = vec![BaseElement::ZERO; table.width()];
         for i in 0..air.ce_domain_size() {
-            let row = table.get_row(i);
+            table.read_row_into(i, &mut row);
             actual[0].push(row[0]);
             actual[1].push(row[1]);
         }
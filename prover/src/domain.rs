@@ -90,6 +90,11 @@ impl<B: StarkField> StarkDomain<B> {
         self.ce_domain_size() * self.ce_to_lde_blowup()
     }
 
+    /// Returns the generator of the low-degree extension domain.
+    pub fn lde_domain_generator(&self) -> B {
+        B::get_root_of_unity(log2(self.lde_domain_size()))
+    }
+
     /// Returns LDE domain offset.
     pub fn offset(&self) -> B {
         self.domain_offset
@@ -21,6 +21,31 @@ pub enum ProverError {
     /// This error occurs when the base field specified by the AIR does not support field extension
     /// of degree specified by proof options.
     UnsupportedFieldExtension(usize),
+    /// This error occurs when the number of cells in an execution trace exceeds the maximum
+    /// specified by [ProverOptions](crate::ProverOptions).
+    TraceTooLarge {
+        /// Number of cells (width x length) in the execution trace.
+        cells: u64,
+        /// Maximum number of cells allowed by prover options.
+        max: u64,
+    },
+    /// This error occurs when no proof-of-work nonce satisfying the grinding factor was found
+    /// within the maximum number of attempts specified by [ProverOptions](crate::ProverOptions).
+    GrindingFailed {
+        /// Number of leading zeros required by the grinding factor.
+        grinding_factor: u32,
+        /// Number of nonces tried.
+        attempts: u64,
+    },
+    /// This error occurs when the trace LDE self-check enabled via
+    /// [ProverOptions](crate::ProverOptions) finds a value in the extended execution trace which
+    /// differs from the evaluation of the corresponding trace polynomial.
+    LdeSelfCheckFailed {
+        /// Index of the trace column containing the value.
+        column: usize,
+        /// Position of the value in the LDE domain.
+        position: usize,
+    },
 }
 
 impl fmt::Display for ProverError {
@@ -36,6 +61,58 @@ impl fmt::Display for ProverError {
             Self::UnsupportedFieldExtension(degree) => {
                 write!(f, "field extension of degree {} is not supported for the specified base field", degree)
             }
+            Self::TraceTooLarge { cells, max } => {
+                write!(f, "execution trace has {} cells, but at most {} cells are allowed; use a shorter trace or raise the limit via ProverOptions::with_max_trace_cells()", cells, max)
+            }
+            Self::GrindingFailed { grinding_factor, attempts } => {
+                write!(f, "failed to find a proof-of-work nonce with grinding factor {} in {} attempts", grinding_factor, attempts)
+            }
+            Self::LdeSelfCheckFailed { column, position } => {
+                write!(f, "trace LDE value in column {} at position {} does not match the trace polynomial", column, position)
+            }
+        }
+    }
+}
+
+// MATRIX ERROR
+// ================================================================================================
+/// Represents an error returned when a set of columns cannot be assembled into a
+/// [Matrix](crate::Matrix).
+#[derive(Debug, PartialEq, Eq)]
+pub enum MatrixError {
+    /// This error occurs when no columns were provided.
+    NoColumns,
+    /// This error occurs when the columns have fewer than two rows.
+    TooFewRows(usize),
+    /// This error occurs when the number of rows in the columns is not a power of two.
+    NumRowsNotPowerOfTwo(usize),
+    /// This error occurs when a column has a different length than the first column.
+    RaggedColumns {
+        /// Index of the offending column.
+        column: usize,
+        /// Length of the first column.
+        expected: usize,
+        /// Length of the offending column.
+        actual: usize,
+    },
+}
+
+impl fmt::Display for MatrixError {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoColumns => {
+                write!(f, "a matrix must contain at least one column")
+            }
+            Self::TooFewRows(_) => {
+                write!(f, "number of rows in a matrix must be greater than one")
+            }
+            Self::NumRowsNotPowerOfTwo(_) => {
+                write!(f, "number of rows in a matrix must be a power of 2")
+            }
+            Self::RaggedColumns { column, expected, actual } => {
+                write!(f, "all matrix columns must have the same length; column {} has {} elements, but {} were expected", column, actual, expected)
+            }
         }
     }
 }
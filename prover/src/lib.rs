@@ -44,15 +44,18 @@
 extern crate alloc;
 
 pub use air::{
-    proof::StarkProof, Air, AirContext, Assertion, BoundaryConstraint, BoundaryConstraintGroup,
-    ConstraintCompositionCoefficients, ConstraintDivisor, DeepCompositionCoefficients,
-    EvaluationFrame, FieldExtension, HashFunction, ProofOptions, TraceInfo,
-    TransitionConstraintDegree, TransitionConstraintGroup,
+    proof::StarkProof, Air, AirContext, AirDescription, Assertion, AssertionDescription,
+    BoundaryConstraint, BoundaryConstraintGroup, ConstraintCompositionCoefficients,
+    ConstraintDivisor, ConstraintSet, CustomBoundaryConstraint, DeepCompositionCoefficients,
+    EvaluationFrame, FieldExtension, HashFunction, ProofOptions, TraceInfo, TraceInfoError,
+    TransitionConstraintDegree, TransitionConstraintDescription, TransitionConstraintGroup,
 };
 pub use utils::{
-    iterators, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+    collections, iterators, ByteReader, ByteWriter, Deserializable, DeserializationError,
+    Serializable, SliceReader,
 };
 
+pub use fri;
 use fri::FriProver;
 use utils::collections::Vec;
 
@@ -75,6 +78,25 @@ use log::debug;
 use math::log2;
 #[cfg(feature = "std")]
 use std::time::Instant;
+#[cfg(feature = "tracing")]
+use tracing::info_span;
+
+/// Ends the current phase recorded in `$guard` (if any) and enters a new tracing span with the
+/// specified name and fields; expands to nothing when the `tracing` feature is disabled.
+///
+/// The previous span is exited before the new one is created, so that phases are recorded as
+/// siblings rather than as children of each other.
+macro_rules! enter_phase {
+    ($guard:ident, $($span:tt)+) => {
+        // the span entered by the last phase is only read when the guard is dropped
+        #[cfg(feature = "tracing")]
+        #[allow(unused_assignments)]
+        {
+            drop($guard.take());
+            $guard = Some(info_span!($($span)+).entered());
+        }
+    };
+}
 
 mod domain;
 use domain::StarkDomain;
@@ -90,13 +112,22 @@ use composer::DeepCompositionPoly;
 
 mod trace;
 use trace::TracePolyTable;
-pub use trace::{Trace, TraceCommitment, TraceTable, TraceTableFragment};
+pub use trace::{
+    CellDiff, PaddingStrategy, StreamingTrace, Trace, TraceCommitment, TraceDiff, TraceTable,
+    TraceTableFragment,
+};
 
 mod channel;
 use channel::ProverChannel;
 
+mod options;
+pub use options::ProverOptions;
+
+mod artifacts;
+pub use artifacts::ProofArtifacts;
+
 mod errors;
-pub use errors::ProverError;
+pub use errors::{MatrixError, ProverError};
 
 #[cfg(test)]
 pub mod tests;
@@ -155,24 +186,171 @@ pub trait Prover {
     /// the computation described by [Self::Air](Prover::Air) and generated using some set of
     /// secret and public inputs. Public inputs must match the value returned from
     /// [Self::get_pub_inputs()](Prover::get_pub_inputs) for the provided trace.
-    #[rustfmt::skip]
     fn prove(&self, trace: Self::Trace) -> Result<StarkProof, ProverError> {
+        self.prove_with(trace, &ProverOptions::default())
+    }
+
+    /// Returns a STARK proof attesting to a correct execution of a computation defined by the
+    /// provided trace, generated using the specified prover options.
+    ///
+    /// This is equivalent to [prove()](Prover::prove), but prover-local choices such as the
+    /// number of constraint evaluation fragments are taken from `prover_options`. These options
+    /// do not affect the generated proof.
+    ///
+    /// # Errors
+    /// Returns [ProverError::TraceTooLarge] if the number of cells in `trace` exceeds the maximum
+    /// specified by `prover_options`; this is checked before any trace-sized data is allocated.
+    fn prove_with(
+        &self,
+        trace: Self::Trace,
+        prover_options: &ProverOptions,
+    ) -> Result<StarkProof, ProverError> {
+        self.prove_with_artifacts(trace, prover_options)
+            .map(|(proof, _)| proof)
+    }
+
+    /// Returns a STARK proof attesting to a correct execution of a computation defined by the
+    /// provided trace together with [ProofArtifacts] of the proof generation.
+    ///
+    /// This is equivalent to [prove_with()](Prover::prove_with), and the generated proof is
+    /// identical to the one generated by it. The returned artifacts are copies of values
+    /// committed to in the proof (composition roots, out-of-domain frames, and query positions).
+    ///
+    /// # Errors
+    /// Returns [ProverError::TraceTooLarge] if the number of cells in `trace` exceeds the maximum
+    /// specified by `prover_options`; this is checked before any trace-sized data is allocated.
+    fn prove_with_artifacts(
+        &self,
+        trace: Self::Trace,
+        prover_options: &ProverOptions,
+    ) -> Result<(StarkProof, ProofArtifacts), ProverError> {
+        // make sure the trace is not too large before any trace-sized data is allocated
+        prover_options.check_trace_size(&trace.get_info())?;
+
+        // serialize public inputs; these will be included in the seed for the public coin
+        let pub_inputs = self.get_pub_inputs(&trace);
+        let mut pub_inputs_bytes = Vec::new();
+        pub_inputs.write_into(&mut pub_inputs_bytes);
+
+        // create an instance of AIR for the provided parameters. this takes a generic description
+        // of the computation (provided via AIR type), and creates a description of a specific
+        // execution of the computation for the provided public inputs.
+        let air = Self::Air::new(trace.get_info(), pub_inputs, self.options().clone());
+
+        // make sure the specified trace is valid against the AIR. This checks validity of both,
+        // assertions and state transitions. we do this in debug mode only because this is a very
+        // expensive operation.
+        #[cfg(debug_assertions)]
+        if prover_options.validate_trace() {
+            trace.validate(&air);
+        }
+
+        // interpolate the execution trace into polynomials; the trace is consumed in the process
+        let trace_polys = trace.into_matrix().interpolate_columns_into();
+
+        self.prove_with_trace_polys(air, pub_inputs_bytes, trace_polys, prover_options)
+    }
+
+    /// Returns a STARK proof attesting to a correct execution of a computation defined by the
+    /// provided streaming trace.
+    ///
+    /// This is equivalent to [prove()](Prover::prove), but the full execution trace is never
+    /// materialized. Instead, the columns of the `trace` are requested and interpolated one at a
+    /// time (and in debug mode, the trace is also validated in a single pass over its rows). For
+    /// the same execution trace, the generated proof is identical to the one generated by
+    /// [prove()](Prover::prove).
+    ///
+    /// Since public inputs cannot be derived from a streaming trace via
+    /// [get_pub_inputs()](Prover::get_pub_inputs), they must be provided explicitly.
+    ///
+    /// # Errors
+    /// Returns [ProverError::TraceTooLarge] if the number of cells in `trace` exceeds the maximum
+    /// specified by default prover options.
+    fn prove_streaming<T>(
+        &self,
+        trace: &T,
+        pub_inputs: <<Self as Prover>::Air as Air>::PublicInputs,
+    ) -> Result<StarkProof, ProverError>
+    where
+        T: StreamingTrace<BaseField = Self::BaseField>,
+    {
+        self.prove_streaming_with(trace, pub_inputs, &ProverOptions::default())
+    }
+
+    /// Returns a STARK proof attesting to a correct execution of a computation defined by the
+    /// provided streaming trace, generated using the specified prover options.
+    ///
+    /// This is equivalent to [prove_streaming()](Prover::prove_streaming), but prover-local
+    /// choices are taken from `prover_options`. These options do not affect the generated proof.
+    ///
+    /// # Errors
+    /// Returns [ProverError::TraceTooLarge] if the number of cells in `trace` exceeds the maximum
+    /// specified by `prover_options`; this is checked before any trace-sized data is allocated.
+    fn prove_streaming_with<T>(
+        &self,
+        trace: &T,
+        pub_inputs: <<Self as Prover>::Air as Air>::PublicInputs,
+        prover_options: &ProverOptions,
+    ) -> Result<StarkProof, ProverError>
+    where
+        T: StreamingTrace<BaseField = Self::BaseField>,
+    {
+        // make sure the trace is not too large before any trace-sized data is allocated
+        prover_options.check_trace_size(&trace.stream_info())?;
+
+        // serialize public inputs; these will be included in the seed for the public coin
+        let mut pub_inputs_bytes = Vec::new();
+        pub_inputs.write_into(&mut pub_inputs_bytes);
+
+        // create an instance of AIR for the provided parameters
+        let air = Self::Air::new(trace.stream_info(), pub_inputs, self.options().clone());
+
+        // make sure the specified trace is valid against the AIR; we do this in debug mode only
+        // because this is a very expensive operation.
+        #[cfg(debug_assertions)]
+        if prover_options.validate_trace() {
+            trace.validate_stream(&air);
+        }
+
+        // interpolate the execution trace into polynomials one column at a time
+        let trace_polys = trace.interpolate_columns();
+
+        self.prove_with_trace_polys(air, pub_inputs_bytes, trace_polys, prover_options)
+            .map(|(proof, _)| proof)
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Generates a proof from the interpolated execution trace polynomials.
+    ///
+    /// This selects the version of the generic proof generation procedure to run based on the
+    /// field extension and hash function specified in proof options.
+    #[doc(hidden)]
+    #[rustfmt::skip]
+    fn prove_with_trace_polys(
+        &self,
+        air: Self::Air,
+        pub_inputs_bytes: Vec<u8>,
+        trace_polys: Matrix<Self::BaseField>,
+        prover_options: &ProverOptions,
+    ) -> Result<(StarkProof, ProofArtifacts), ProverError> {
         // figure out which version of the generic proof generation procedure to run. this is a sort
         // of static dispatch for selecting two generic parameter: extension field and hash function.
         match self.options().field_extension() {
             FieldExtension::None => match self.options().hash_fn() {
-                HashFunction::Blake3_256 => self.generate_proof::<Self::BaseField, Blake3_256<Self::BaseField>>(trace),
-                HashFunction::Blake3_192 => self.generate_proof::<Self::BaseField, Blake3_192<Self::BaseField>>(trace),
-                HashFunction::Sha3_256 => self.generate_proof::<Self::BaseField, Sha3_256<Self::BaseField>>(trace),
+                HashFunction::Blake3_256 => self.generate_proof::<Self::BaseField, Blake3_256<Self::BaseField>>(air, pub_inputs_bytes, trace_polys, prover_options),
+                HashFunction::Blake3_192 => self.generate_proof::<Self::BaseField, Blake3_192<Self::BaseField>>(air, pub_inputs_bytes, trace_polys, prover_options),
+                HashFunction::Sha3_256 => self.generate_proof::<Self::BaseField, Sha3_256<Self::BaseField>>(air, pub_inputs_bytes, trace_polys, prover_options),
             },
             FieldExtension::Quadratic => {
                 if !<QuadExtension<Self::BaseField>>::is_supported() {
                     return Err(ProverError::UnsupportedFieldExtension(2));
                 }
                 match self.options().hash_fn() {
-                    HashFunction::Blake3_256 => self.generate_proof::<QuadExtension<Self::BaseField>, Blake3_256<Self::BaseField>>(trace),
-                    HashFunction::Blake3_192 => self.generate_proof::<QuadExtension<Self::BaseField>, Blake3_192<Self::BaseField>>(trace),
-                    HashFunction::Sha3_256 => self.generate_proof::<QuadExtension<Self::BaseField>, Sha3_256<Self::BaseField>>(trace),
+                    HashFunction::Blake3_256 => self.generate_proof::<QuadExtension<Self::BaseField>, Blake3_256<Self::BaseField>>(air, pub_inputs_bytes, trace_polys, prover_options),
+                    HashFunction::Blake3_192 => self.generate_proof::<QuadExtension<Self::BaseField>, Blake3_192<Self::BaseField>>(air, pub_inputs_bytes, trace_polys, prover_options),
+                    HashFunction::Sha3_256 => self.generate_proof::<QuadExtension<Self::BaseField>, Sha3_256<Self::BaseField>>(air, pub_inputs_bytes, trace_polys, prover_options),
                 }
             }
             FieldExtension::Cubic => {
@@ -180,43 +358,41 @@ pub trait Prover {
                     return Err(ProverError::UnsupportedFieldExtension(3));
                 }
                 match self.options().hash_fn() {
-                    HashFunction::Blake3_256 => self.generate_proof::<CubeExtension<Self::BaseField>, Blake3_256<Self::BaseField>>(trace),
-                    HashFunction::Blake3_192 => self.generate_proof::<CubeExtension<Self::BaseField>, Blake3_192<Self::BaseField>>(trace),
-                    HashFunction::Sha3_256 => self.generate_proof::<CubeExtension<Self::BaseField>, Sha3_256<Self::BaseField>>(trace),
+                    HashFunction::Blake3_256 => self.generate_proof::<CubeExtension<Self::BaseField>, Blake3_256<Self::BaseField>>(air, pub_inputs_bytes, trace_polys, prover_options),
+                    HashFunction::Blake3_192 => self.generate_proof::<CubeExtension<Self::BaseField>, Blake3_192<Self::BaseField>>(air, pub_inputs_bytes, trace_polys, prover_options),
+                    HashFunction::Sha3_256 => self.generate_proof::<CubeExtension<Self::BaseField>, Sha3_256<Self::BaseField>>(air, pub_inputs_bytes, trace_polys, prover_options),
                 }
             }
         }
     }
 
-    // HELPER METHODS
-    // --------------------------------------------------------------------------------------------
-
-    /// Performs the actual proof generation procedure, generating the proof that the provided
-    /// execution `trace` is valid against this prover's AIR.
+    /// Performs the actual proof generation procedure, generating the proof that the execution
+    /// trace described by `trace_polys` is valid against the provided `air`.
     /// TODO: make this function un-callable externally?
     #[doc(hidden)]
-    fn generate_proof<E, H>(&self, trace: Self::Trace) -> Result<StarkProof, ProverError>
+    fn generate_proof<E, H>(
+        &self,
+        air: Self::Air,
+        pub_inputs_bytes: Vec<u8>,
+        trace_polys: Matrix<Self::BaseField>,
+        prover_options: &ProverOptions,
+    ) -> Result<(StarkProof, ProofArtifacts), ProverError>
     where
         E: FieldElement<BaseField = Self::BaseField>,
         H: ElementHasher<BaseField = Self::BaseField>,
     {
-        // 0 ----- instantiate AIR and prover channel ---------------------------------------------
-
-        // serialize public inputs; these will be included in the seed for the public coin
-        let pub_inputs = self.get_pub_inputs(&trace);
-        let mut pub_inputs_bytes = Vec::new();
-        pub_inputs.write_into(&mut pub_inputs_bytes);
-
-        // create an instance of AIR for the provided parameters. this takes a generic description
-        // of the computation (provided via AIR type), and creates a description of a specific
-        // execution of the computation for the provided public inputs.
-        let air = Self::Air::new(trace.get_info(), pub_inputs, self.options().clone());
+        #[cfg(feature = "tracing")]
+        let _span = info_span!(
+            "prove",
+            trace_width = air.trace_width(),
+            trace_length = air.trace_length(),
+            lde_domain_size = air.lde_domain_size()
+        )
+        .entered();
+        #[cfg(feature = "tracing")]
+        let mut phase = None;
 
-        // make sure the specified trace is valid against the AIR. This checks validity of both,
-        // assertions and state transitions. we do this in debug mode only because this is a very
-        // expensive operation.
-        #[cfg(debug_assertions)]
-        trace.validate(&air);
+        // 0 ----- instantiate prover channel -----------------------------------------------------
 
         // create a channel which is used to simulate interaction between the prover and the
         // verifier; the channel will be used to commit to values and to draw randomness that
@@ -224,6 +400,13 @@ pub trait Prover {
         let mut channel = ProverChannel::<Self::Air, E, H>::new(&air, pub_inputs_bytes);
 
         // 1 ----- Commit to the execution trace --------------------------------------------------
+        enter_phase!(
+            phase,
+            "commit_trace",
+            trace_width = air.trace_width(),
+            trace_length = air.trace_length(),
+            blowup_factor = air.options().blowup_factor()
+        );
 
         // build computation domain; this is used later for polynomial evaluations
         #[cfg(feature = "std")]
@@ -238,7 +421,7 @@ pub trait Prover {
 
         // extend the execution trace and build a Merkle tree from the extended trace
         let (trace_commitment, trace_polys) =
-            self.build_trace_commitment::<H>(trace.into_matrix(), &domain);
+            self.build_trace_commitment::<H>(trace_polys, &domain, prover_options)?;
 
         // commit to the extended trace by writing the root of the Merkle tree into the channel
         channel.commit_trace(trace_commitment.root());
@@ -250,11 +433,21 @@ pub trait Prover {
         // identical denominators are merged together. the results are saved into a constraint
         // evaluation table where each column contains merged evaluations of constraints with
         // identical denominators.
+        enter_phase!(
+            phase,
+            "evaluate_constraints",
+            ce_domain_size = air.ce_domain_size(),
+            num_transition_constraints = air.num_transition_constraints()
+        );
         #[cfg(feature = "std")]
         let now = Instant::now();
         let constraint_coeffs = channel.get_constraint_composition_coeffs();
         let evaluator = ConstraintEvaluator::new(&air, constraint_coeffs);
-        let constraint_evaluations = evaluator.evaluate(&trace_commitment, &domain);
+        let constraint_evaluations = evaluator.evaluate(
+            &trace_commitment,
+            &domain,
+            prover_options.num_evaluation_fragments(),
+        );
         #[cfg(feature = "std")]
         debug!(
             "Evaluated constraints over domain of 2^{} elements in {} ms",
@@ -263,6 +456,11 @@ pub trait Prover {
         );
 
         // 3 ----- commit to constraint evaluations -----------------------------------------------
+        enter_phase!(
+            phase,
+            "commit_composition",
+            ce_domain_size = air.ce_domain_size()
+        );
 
         // first, build constraint composition polynomial from the constraint evaluation table:
         // - divide all constraint evaluation columns by their respective divisors
@@ -287,9 +485,15 @@ pub trait Prover {
 
         // then, commit to the evaluations of constraints by writing the root of the constraint
         // Merkle tree into the channel
-        channel.commit_constraints(constraint_commitment.root());
+        let composition_root = constraint_commitment.root();
+        channel.commit_constraints(composition_root);
 
         // 4 ----- build DEEP composition polynomial ----------------------------------------------
+        enter_phase!(
+            phase,
+            "build_deep_composition",
+            trace_width = air.trace_width()
+        );
         #[cfg(feature = "std")]
         let now = Instant::now();
 
@@ -339,6 +543,11 @@ pub trait Prover {
         assert_eq!(domain.trace_length() - 1, deep_composition_poly.degree());
 
         // 5 ----- evaluate DEEP composition polynomial over LDE domain ---------------------------
+        enter_phase!(
+            phase,
+            "evaluate_deep_composition",
+            lde_domain_size = air.lde_domain_size()
+        );
         #[cfg(feature = "std")]
         let now = Instant::now();
         let deep_evaluations = deep_composition_poly.evaluate(&domain);
@@ -356,6 +565,12 @@ pub trait Prover {
         );
 
         // 6 ----- compute FRI layers for the composition polynomial ------------------------------
+        enter_phase!(
+            phase,
+            "build_fri_layers",
+            lde_domain_size = air.lde_domain_size(),
+            folding_factor = air.options().to_fri_options().folding_factor()
+        );
         #[cfg(feature = "std")]
         let now = Instant::now();
         let mut fri_prover = FriProver::new(air.options().to_fri_options());
@@ -368,11 +583,17 @@ pub trait Prover {
         );
 
         // 7 ----- determine query positions ------------------------------------------------------
+        enter_phase!(
+            phase,
+            "determine_queries",
+            num_queries = air.options().num_queries(),
+            grinding_factor = air.options().grinding_factor()
+        );
         #[cfg(feature = "std")]
         let now = Instant::now();
 
         // apply proof-of-work to the query seed
-        channel.grind_query_seed();
+        channel.grind_query_seed(prover_options.max_grinding_attempts())?;
 
         // generate pseudo-random query positions
         let query_positions = channel.get_query_positions();
@@ -384,6 +605,7 @@ pub trait Prover {
         );
 
         // 8 ----- build proof object -------------------------------------------------------------
+        enter_phase!(phase, "build_proof", num_queries = query_positions.len());
         #[cfg(feature = "std")]
         let now = Instant::now();
 
@@ -404,33 +626,46 @@ pub trait Prover {
         #[cfg(feature = "std")]
         debug!("Built proof object in {} ms", now.elapsed().as_millis());
 
-        Ok(proof)
+        // collect copies of the values committed to in the proof
+        let artifacts = ProofArtifacts {
+            composition_roots: vec![composition_root.to_bytes()],
+            ood_frames: vec![proof.ood_frame.clone()],
+            query_positions,
+        };
+
+        Ok((proof, artifacts))
     }
 
-    /// Computes a low-degree extension (LDE) of the provided execution trace over the specified
-    /// domain and build a commitment to the extended trace.
+    /// Computes a low-degree extension (LDE) of the execution trace described by the provided
+    /// trace polynomials over the specified domain and build a commitment to the extended trace.
     ///
-    /// The extension is performed by interpolating each column of the execution trace into a
-    /// polynomial of degree = trace_length - 1, and then evaluating the polynomial over the LDE
-    /// domain.
+    /// The extension is performed by evaluating each trace polynomial (of degree =
+    /// trace_length - 1) over the LDE domain.
     ///
     /// Trace commitment is computed by hashing each row of the extended execution trace, and then
     /// building a Merkle tree from the resulting hashes.
+    ///
+    /// If the trace LDE self-check is enabled in `prover_options`, the extended trace is checked
+    /// against the trace polynomials before the commitment is returned.
+    #[allow(clippy::type_complexity)]
     fn build_trace_commitment<H>(
         &self,
-        trace: Matrix<Self::BaseField>,
+        trace_polys: Matrix<Self::BaseField>,
         domain: &StarkDomain<Self::BaseField>,
-    ) -> (
-        TraceCommitment<Self::BaseField, H>,
-        TracePolyTable<Self::BaseField>,
-    )
+        prover_options: &ProverOptions,
+    ) -> Result<
+        (
+            TraceCommitment<Self::BaseField, H>,
+            TracePolyTable<Self::BaseField>,
+        ),
+        ProverError,
+    >
     where
         H: ElementHasher<BaseField = Self::BaseField>,
     {
         // extend the execution trace
         #[cfg(feature = "std")]
         let now = Instant::now();
-        let trace_polys = trace.interpolate_columns_into();
         let trace_lde = trace_polys.evaluate_columns_over(domain);
         #[cfg(feature = "std")]
         debug!(
@@ -453,10 +688,23 @@ pub trait Prover {
             now.elapsed().as_millis()
         );
 
-        (
-            TraceCommitment::new(trace_lde, trace_tree, domain.trace_to_lde_blowup()),
-            TracePolyTable::new(trace_polys),
-        )
+        let trace_commitment =
+            TraceCommitment::new(trace_lde, trace_tree, domain.trace_to_lde_blowup());
+
+        // make sure the trace LDE is consistent with trace polynomials, if requested
+        if let Some(num_samples) = prover_options.lde_self_check() {
+            #[cfg(feature = "std")]
+            let now = Instant::now();
+            trace_commitment.check_lde(&trace_polys, domain, num_samples)?;
+            #[cfg(feature = "std")]
+            debug!(
+                "Checked trace LDE at {} positions in {} ms",
+                num_samples,
+                now.elapsed().as_millis()
+            );
+        }
+
+        Ok((trace_commitment, TracePolyTable::new(trace_polys)))
     }
 
     /// Evaluates constraint composition polynomial over the LDE domain and builds a commitment
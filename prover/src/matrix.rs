@@ -1,4 +1,4 @@
-use super::StarkDomain;
+use super::{MatrixError, StarkDomain};
 use core::{iter::FusedIterator, slice};
 use crypto::{ElementHasher, MerkleTree};
 use math::{fft, polynom, FieldElement};
@@ -38,28 +38,77 @@ impl<E: FieldElement> Matrix<E> {
     /// * Number of rows is smaller than or equal to 1.
     /// * Number of rows is not a power of two.
     pub fn new(columns: Vec<Vec<E>>) -> Self {
-        assert!(
-            !columns.is_empty(),
-            "a matrix must contain at least one column"
-        );
+        Self::try_from_columns(columns).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Returns a new [Matrix] instantiated with the data from the specified columns, or an error
+    /// if the columns do not form a valid matrix.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The provided vector of columns is empty.
+    /// * Not all of the columns have the same number of elements.
+    /// * Number of rows is smaller than or equal to 1.
+    /// * Number of rows is not a power of two.
+    pub fn try_from_columns(columns: Vec<Vec<E>>) -> Result<Self, MatrixError> {
+        if columns.is_empty() {
+            return Err(MatrixError::NoColumns);
+        }
         let num_rows = columns[0].len();
+        if num_rows <= 1 {
+            return Err(MatrixError::TooFewRows(num_rows));
+        }
+        if !num_rows.is_power_of_two() {
+            return Err(MatrixError::NumRowsNotPowerOfTwo(num_rows));
+        }
+        for (col_idx, column) in columns.iter().enumerate().skip(1) {
+            if column.len() != num_rows {
+                return Err(MatrixError::RaggedColumns {
+                    column: col_idx,
+                    expected: num_rows,
+                    actual: column.len(),
+                });
+            }
+        }
+
+        Ok(Self { columns })
+    }
+
+    /// Returns a new [Matrix] with the specified number of columns and rows filled with values
+    /// from the provided iterator in column-major order.
+    ///
+    /// That is, the first `num_rows` values yielded by the iterator become the first column,
+    /// the next `num_rows` values become the second column etc.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The iterator yields more or fewer than `num_cols * num_rows` values.
+    /// * `num_cols` and `num_rows` do not describe a valid matrix; see [Matrix::new()].
+    pub fn from_column_iter<I>(num_cols: usize, num_rows: usize, values: I) -> Self
+    where
+        I: IntoIterator<Item = E>,
+    {
+        let mut values = values.into_iter();
+        let columns = (0..num_cols)
+            .map(|col_idx| {
+                let column = values.by_ref().take(num_rows).collect::<Vec<_>>();
+                assert_eq!(
+                    column.len(),
+                    num_rows,
+                    "expected {} values, but the iterator yielded {}",
+                    num_cols * num_rows,
+                    col_idx * num_rows + column.len()
+                );
+                column
+            })
+            .collect::<Vec<_>>();
         assert!(
-            num_rows > 1,
-            "number of rows in a matrix must be greater than one"
-        );
-        assert!(
-            num_rows.is_power_of_two(),
-            "number of rows in a matrix must be a power of 2"
+            values.next().is_none(),
+            "expected {} values, but the iterator yielded more",
+            num_cols * num_rows
         );
-        for column in columns.iter().skip(1) {
-            assert_eq!(
-                column.len(),
-                num_rows,
-                "all matrix columns must have the same length"
-            );
-        }
 
-        Self { columns }
+        Self::new(columns)
     }
 
     // PUBLIC ACCESSORS
@@ -121,6 +170,27 @@ impl<E: FieldElement> Matrix<E> {
         }
     }
 
+    /// Changes the number of rows in this matrix to `num_rows`.
+    ///
+    /// If `num_rows` is greater than the current number of rows, all columns are extended with
+    /// ZERO elements; otherwise, all columns are truncated to `num_rows` elements.
+    ///
+    /// # Panics
+    /// Panics if `num_rows` is smaller than or equal to 1, or is not a power of two.
+    pub(crate) fn resize_rows(&mut self, num_rows: usize) {
+        assert!(
+            num_rows > 1,
+            "number of rows in a matrix must be greater than one"
+        );
+        assert!(
+            num_rows.is_power_of_two(),
+            "number of rows in a matrix must be a power of 2"
+        );
+        for column in self.columns.iter_mut() {
+            column.resize(num_rows, E::ZERO);
+        }
+    }
+
     // ITERATION
     // --------------------------------------------------------------------------------------------
 
@@ -134,6 +204,37 @@ impl<E: FieldElement> Matrix<E> {
         ColumnIterMut::new(self)
     }
 
+    /// Applies `f` to each column of this matrix and returns a new matrix built from the results.
+    ///
+    /// The columns are processed sequentially; see [Matrix::par_map_columns()] for a version
+    /// which processes the columns in multiple threads.
+    ///
+    /// # Panics
+    /// Panics if the columns returned by `f` do not form a valid matrix; see [Matrix::new()].
+    pub fn map_columns<F, M>(&self, f: M) -> Matrix<F>
+    where
+        F: FieldElement,
+        M: Fn(&[E]) -> Vec<F>,
+    {
+        Matrix::new(self.columns.iter().map(|column| f(column)).collect())
+    }
+
+    /// Applies `f` to each column of this matrix in multiple threads and returns a new matrix
+    /// built from the results.
+    ///
+    /// The order of columns in the resulting matrix is the same as for [Matrix::map_columns()].
+    ///
+    /// # Panics
+    /// Panics if the columns returned by `f` do not form a valid matrix; see [Matrix::new()].
+    #[cfg(feature = "concurrent")]
+    pub fn par_map_columns<F, M>(&self, f: M) -> Matrix<F>
+    where
+        F: FieldElement,
+        M: Fn(&[E]) -> Vec<F> + Send + Sync,
+    {
+        Matrix::new(self.columns.par_iter().map(|column| f(column)).collect())
+    }
+
     // POLYNOMIAL METHODS
     // --------------------------------------------------------------------------------------------
 
@@ -163,15 +264,21 @@ impl<E: FieldElement> Matrix<E> {
     ///   [StarkDomain] using FFT algorithm. The domain specification includes the size of the
     ///   subgroup as well as the domain offset (to define a coset).
     /// * The resulting evaluations are returned in a new Matrix.
+    ///
+    /// Polynomials of degree at most one (e.g., polynomials of constant or padding columns) are
+    /// evaluated directly rather than via FFT; the results are the same, but computing them
+    /// requires at most one multiplication per point of the domain.
     pub fn evaluate_columns_over(&self, domain: &StarkDomain<E::BaseField>) -> Self {
         let columns = iter!(self.columns)
-            .map(|poly| {
-                fft::evaluate_poly_with_offset(
+            .map(|poly| match polynom::degree_of(poly) {
+                0 => vec![poly[0]; domain.lde_domain_size()],
+                1 => evaluate_linear_poly(poly[0], poly[1], domain),
+                _ => fft::evaluate_poly_with_offset(
                     poly,
                     domain.trace_twiddles(),
                     domain.offset(),
                     domain.trace_to_lde_blowup(),
-                )
+                ),
             })
             .collect();
         Self { columns }
@@ -185,6 +292,39 @@ impl<E: FieldElement> Matrix<E> {
         iter!(self.columns).map(|p| polynom::eval(p, x)).collect()
     }
 
+    /// Evaluates polynomials contained in the columns of this matrix for which `mask` returns
+    /// true at a single point `x`, and writes the results into `result`.
+    ///
+    /// The `result` vector is cleared before the evaluations are written into it. Thus, if the
+    /// capacity of `result` is sufficient to hold evaluations of all columns, no memory is
+    /// allocated.
+    pub fn evaluate_columns_at_into<F>(
+        &self,
+        x: F,
+        mask: impl Fn(usize) -> bool + Sync,
+        result: &mut Vec<F>,
+    ) where
+        F: FieldElement + From<E>,
+    {
+        result.clear();
+        result.resize(self.num_cols(), F::ZERO);
+        iter_mut!(result)
+            .zip(iter!(self.columns))
+            .enumerate()
+            .for_each(|(col_idx, (value, p))| {
+                if mask(col_idx) {
+                    *value = polynom::eval(p, x);
+                }
+            });
+
+        // remove slots of the columns which were not evaluated
+        let mut col_idx = 0;
+        result.retain(|_| {
+            col_idx += 1;
+            mask(col_idx - 1)
+        });
+    }
+
     // COMMITMENTS
     // --------------------------------------------------------------------------------------------
 
@@ -311,3 +451,179 @@ impl<'a, E: FieldElement> ExactSizeIterator for ColumnIterMut<'a, E> {
 }
 
 impl<'a, E: FieldElement> FusedIterator for ColumnIterMut<'a, E> {}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Evaluates polynomial c0 + c1 * x over the LDE domain defined by the specified [StarkDomain].
+///
+/// The evaluations are in the same order as the evaluations computed via FFT algorithm; that is,
+/// the i-th evaluation is at point offset * g^i, where g is the generator of the LDE domain.
+fn evaluate_linear_poly<E: FieldElement>(
+    c0: E,
+    c1: E,
+    domain: &StarkDomain<E::BaseField>,
+) -> Vec<E> {
+    let g = domain.lde_domain_generator();
+    let mut x = domain.offset();
+    let mut result = Vec::with_capacity(domain.lde_domain_size());
+    for _ in 0..domain.lde_domain_size() {
+        result.push(c0 + c1.mul_base(x));
+        x *= g;
+    }
+    result
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{Matrix, MatrixError, StarkDomain};
+    use crate::tests::MockAir;
+    use math::{fft, fields::f128::BaseElement, polynom, FieldElement};
+    use rand_utils::rand_vector;
+    use utils::collections::Vec;
+
+    #[test]
+    fn evaluate_columns_over() {
+        let trace_length = 32;
+        let domain = StarkDomain::new(&MockAir::with_trace_length(trace_length));
+
+        // build polynomials of degree 0 (including the zero polynomial), degree 1, and full
+        // degree
+        let mut constant = vec![BaseElement::ZERO; trace_length];
+        constant[0] = BaseElement::new(42);
+        let mut linear = vec![BaseElement::ZERO; trace_length];
+        linear[0] = BaseElement::new(3);
+        linear[1] = BaseElement::new(5);
+        let polys = vec![
+            constant,
+            vec![BaseElement::ZERO; trace_length],
+            linear,
+            rand_vector(trace_length),
+        ];
+
+        // all evaluations must be identical to FFT-based evaluations
+        let actual = Matrix::new(polys.clone()).evaluate_columns_over(&domain);
+        assert_eq!(domain.lde_domain_size(), actual.num_rows());
+        for (col_idx, poly) in polys.iter().enumerate() {
+            let expected = fft::evaluate_poly_with_offset(
+                poly,
+                domain.trace_twiddles(),
+                domain.offset(),
+                domain.trace_to_lde_blowup(),
+            );
+            assert_eq!(expected, actual.get_column(col_idx));
+        }
+    }
+
+    #[test]
+    fn evaluate_columns_at_into() {
+        let columns = (0..4u128)
+            .map(|i| {
+                (0..8)
+                    .map(|j| BaseElement::new(i * 8 + j))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let matrix = Matrix::new(columns.clone());
+        let x = BaseElement::new(11);
+
+        // evaluating all columns gives the same result as evaluate_columns_at()
+        let mut result = Vec::with_capacity(matrix.num_cols());
+        matrix.evaluate_columns_at_into(x, |_| true, &mut result);
+        assert_eq!(matrix.evaluate_columns_at(x), result);
+
+        // evaluating a subset of columns re-uses the provided buffer
+        let ptr = result.as_ptr();
+        matrix.evaluate_columns_at_into(x, |col_idx| col_idx % 2 == 1, &mut result);
+        let expected = vec![polynom::eval(&columns[1], x), polynom::eval(&columns[3], x)];
+        assert_eq!(expected, result);
+        assert_eq!(ptr, result.as_ptr());
+
+        // evaluating no columns leaves the buffer empty
+        matrix.evaluate_columns_at_into(x, |_| false, &mut result);
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn try_from_columns() {
+        let column = |len: usize| (0..len as u128).map(BaseElement::new).collect::<Vec<_>>();
+
+        let matrix = Matrix::try_from_columns(vec![column(4), column(4)]).unwrap();
+        assert_eq!(2, matrix.num_cols());
+        assert_eq!(4, matrix.num_rows());
+
+        assert_eq!(
+            Err(MatrixError::NoColumns),
+            Matrix::<BaseElement>::try_from_columns(Vec::new()).map(|_| ())
+        );
+        assert_eq!(
+            Err(MatrixError::TooFewRows(1)),
+            Matrix::try_from_columns(vec![column(1)]).map(|_| ())
+        );
+        assert_eq!(
+            Err(MatrixError::NumRowsNotPowerOfTwo(6)),
+            Matrix::try_from_columns(vec![column(6)]).map(|_| ())
+        );
+        assert_eq!(
+            Err(MatrixError::RaggedColumns {
+                column: 2,
+                expected: 4,
+                actual: 8
+            }),
+            Matrix::try_from_columns(vec![column(4), column(4), column(8)]).map(|_| ())
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "all matrix columns must have the same length; column 1 has 2 elements, but 4 were expected"
+    )]
+    fn new_ragged_columns() {
+        Matrix::new(vec![vec![BaseElement::ONE; 4], vec![BaseElement::ONE; 2]]);
+    }
+
+    #[test]
+    fn from_column_iter() {
+        let matrix = Matrix::from_column_iter(3, 4, (0..12u128).map(BaseElement::new));
+        assert_eq!(3, matrix.num_cols());
+        assert_eq!(4, matrix.num_rows());
+        for (col_idx, column) in matrix.columns().enumerate() {
+            let expected = (0..4u128)
+                .map(|row_idx| BaseElement::new(col_idx as u128 * 4 + row_idx))
+                .collect::<Vec<_>>();
+            assert_eq!(expected, column);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "expected 12 values, but the iterator yielded 11")]
+    fn from_column_iter_too_few_values() {
+        Matrix::from_column_iter(3, 4, (0..11u128).map(BaseElement::new));
+    }
+
+    #[test]
+    #[should_panic(expected = "expected 12 values, but the iterator yielded more")]
+    fn from_column_iter_too_many_values() {
+        Matrix::from_column_iter(3, 4, (0..13u128).map(BaseElement::new));
+    }
+
+    #[test]
+    fn map_columns() {
+        let matrix = Matrix::from_column_iter(2, 4, (0..8u128).map(BaseElement::new));
+        let doubled = matrix.map_columns(|column| column.iter().map(|&v| v.double()).collect());
+        for (column, doubled) in matrix.columns().zip(doubled.columns()) {
+            let expected = column.iter().map(|&v| v.double()).collect::<Vec<_>>();
+            assert_eq!(expected, doubled);
+        }
+
+        #[cfg(feature = "concurrent")]
+        {
+            let par_doubled =
+                matrix.par_map_columns(|column| column.iter().map(|&v| v.double()).collect());
+            assert_eq!(doubled.into_columns(), par_doubled.into_columns());
+        }
+    }
+}
@@ -0,0 +1,197 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::ProverError;
+use air::TraceInfo;
+
+// PROVER OPTIONS
+// ================================================================================================
+/// Prover-local options which affect how a proof is generated, but not the proof itself.
+///
+/// Unlike [ProofOptions](crate::ProofOptions), these options are not included in the proof
+/// context and are not known to the verifier. For the same execution trace and the same proof
+/// options, a proof generated with any combination of prover options is identical to the proof
+/// generated with the default prover options.
+///
+/// Prover options can be passed to the prover via
+/// [Prover::prove_with()](crate::Prover::prove_with) method.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProverOptions {
+    num_evaluation_fragments: Option<usize>,
+    validate_trace: bool,
+    max_trace_cells: u64,
+    max_grinding_attempts: Option<u64>,
+    lde_self_check: Option<usize>,
+}
+
+impl ProverOptions {
+    /// Default maximum number of cells (width x length) in an execution trace; with 128-bit field
+    /// elements, such a trace takes up 64 GB of memory before it is extended.
+    pub const DEFAULT_MAX_TRACE_CELLS: u64 = 1 << 32;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new instance of [ProverOptions] with default values.
+    ///
+    /// By default, the number of constraint evaluation fragments is chosen automatically,
+    /// execution traces are validated against the AIR in debug mode, execution traces may
+    /// contain at most [DEFAULT_MAX_TRACE_CELLS](ProverOptions::DEFAULT_MAX_TRACE_CELLS) cells,
+    /// the number of grinding attempts is not limited, and the trace LDE self-check is disabled.
+    pub fn new() -> Self {
+        ProverOptions {
+            num_evaluation_fragments: None,
+            validate_trace: true,
+            max_trace_cells: Self::DEFAULT_MAX_TRACE_CELLS,
+            max_grinding_attempts: None,
+            lde_self_check: None,
+        }
+    }
+
+    /// Sets the number of fragments into which the constraint evaluation table is split, and
+    /// returns the updated options.
+    ///
+    /// When `concurrent` feature is enabled, fragments are evaluated in parallel. The number of
+    /// fragments is capped so that each fragment contains at least the minimum number of rows
+    /// supported by the evaluation table.
+    ///
+    /// # Panics
+    /// Panics if `num_fragments` is zero or is not a power of two.
+    pub fn with_num_evaluation_fragments(mut self, num_fragments: usize) -> Self {
+        assert!(
+            num_fragments.is_power_of_two(),
+            "number of evaluation fragments must be a power of two, but was {}",
+            num_fragments
+        );
+        self.num_evaluation_fragments = Some(num_fragments);
+        self
+    }
+
+    /// Sets whether execution traces should be validated against the AIR before a proof is
+    /// generated, and returns the updated options.
+    ///
+    /// Validation is performed in debug mode only; in release mode, this option has no effect.
+    pub fn with_trace_validation(mut self, validate_trace: bool) -> Self {
+        self.validate_trace = validate_trace;
+        self
+    }
+
+    /// Sets the maximum number of cells (width x length) in an execution trace for which a proof
+    /// can be generated, and returns the updated options.
+    ///
+    /// The prover rejects larger traces with [ProverError::TraceTooLarge] before any of the
+    /// trace-sized data structures (e.g., the low-degree extension of the trace) are allocated.
+    ///
+    /// # Panics
+    /// Panics if `max_trace_cells` is zero.
+    pub fn with_max_trace_cells(mut self, max_trace_cells: u64) -> Self {
+        assert!(
+            max_trace_cells > 0,
+            "maximum number of trace cells must be greater than zero"
+        );
+        self.max_trace_cells = max_trace_cells;
+        self
+    }
+
+    /// Sets the maximum number of nonces tried while searching for a proof-of-work nonce for the
+    /// query seed, and returns the updated options.
+    ///
+    /// If none of the nonces results in a seed with the number of leading zeros required by the
+    /// grinding factor, the prover returns [ProverError::GrindingFailed] instead of continuing the
+    /// search. Nonces are tried in the same order regardless of this option; thus, if grinding
+    /// succeeds, the generated proof is the same as with an unlimited number of attempts.
+    ///
+    /// # Panics
+    /// Panics if `max_attempts` is zero.
+    pub fn with_max_grinding_attempts(mut self, max_attempts: u64) -> Self {
+        assert!(
+            max_attempts > 0,
+            "maximum number of grinding attempts must be greater than zero"
+        );
+        self.max_grinding_attempts = Some(max_attempts);
+        self
+    }
+
+    /// Enables the self-check of the trace low-degree extension (LDE), and returns the updated
+    /// options.
+    ///
+    /// After the trace LDE is built, each trace polynomial is evaluated directly at `num_samples`
+    /// pseudo-random points of the LDE domain, and the results are compared to the values stored
+    /// in the LDE. On a mismatch, the prover returns [ProverError::LdeSelfCheckFailed] before
+    /// committing to the trace. Each sample costs one polynomial evaluation per trace column,
+    /// and sampled positions do not depend on the public coin; thus, the generated proof is the
+    /// same as with the self-check disabled.
+    ///
+    /// # Panics
+    /// Panics if `num_samples` is zero.
+    pub fn with_lde_self_check(mut self, num_samples: usize) -> Self {
+        assert!(
+            num_samples > 0,
+            "number of LDE self-check samples must be greater than zero"
+        );
+        self.lde_self_check = Some(num_samples);
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of fragments into which the constraint evaluation table should be
+    /// split, or `None` if the number should be chosen automatically.
+    pub fn num_evaluation_fragments(&self) -> Option<usize> {
+        self.num_evaluation_fragments
+    }
+
+    /// Returns true if execution traces should be validated against the AIR in debug mode.
+    pub fn validate_trace(&self) -> bool {
+        self.validate_trace
+    }
+
+    /// Returns the maximum number of cells in an execution trace for which a proof can be
+    /// generated.
+    pub fn max_trace_cells(&self) -> u64 {
+        self.max_trace_cells
+    }
+
+    /// Returns the maximum number of nonces tried while grinding the query seed, or `None` if
+    /// the number of attempts is not limited.
+    pub fn max_grinding_attempts(&self) -> Option<u64> {
+        self.max_grinding_attempts
+    }
+
+    /// Returns the number of points at which the trace LDE is checked against trace polynomials,
+    /// or `None` if the self-check is disabled.
+    pub fn lde_self_check(&self) -> Option<usize> {
+        self.lde_self_check
+    }
+
+    // TRACE SIZE CHECK
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks whether a proof can be generated for an execution trace described by the specified
+    /// trace info.
+    ///
+    /// This check does not require the trace to be built, and thus, can be used to reject traces
+    /// which are too large before any memory is allocated for them.
+    ///
+    /// # Errors
+    /// Returns [ProverError::TraceTooLarge] if the number of cells in the trace is greater than
+    /// the maximum number of trace cells specified by these options.
+    pub fn check_trace_size(&self, trace_info: &TraceInfo) -> Result<(), ProverError> {
+        let cells = trace_info.width() as u64 * trace_info.length() as u64;
+        if cells > self.max_trace_cells {
+            return Err(ProverError::TraceTooLarge {
+                cells,
+                max: self.max_trace_cells,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Default for ProverOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,587 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Slow but exact cross-check of constraint composition.
+//!
+//! The prover builds the constraint composition polynomial by evaluating constraints over the
+//! constraint evaluation domain, dividing the evaluations by constraint divisors point-wise, and
+//! interpolating the result. The harness in this module computes the same polynomial directly:
+//! numerators of all constraints are evaluated over a domain large enough to contain them, and
+//! are interpolated into polynomials which are then divided by divisor polynomials exactly.
+//! The two polynomials must be identical coefficient-by-coefficient.
+//!
+//! Most of the composition tests are expensive and are ignored by default; they can be run via
+//! `cargo test -p winter-prover -- --ignored`.
+
+use super::{build_fib_trace, TestProver};
+use crate::{
+    channel::ProverChannel, constraints::ConstraintEvaluator, Air, AirContext, Assertion,
+    ConstraintCompositionCoefficients, ConstraintDivisor, CustomBoundaryConstraint,
+    EvaluationFrame, ProofOptions, Prover, ProverOptions, Serializable, StarkDomain, Trace,
+    TraceInfo, TraceTable, TransitionConstraintDegree,
+};
+use crypto::hashers::Blake3_256;
+use math::{fft, fields::f128::BaseElement, get_power_series, polynom, FieldElement, StarkField};
+use utils::collections::Vec;
+
+// TESTS
+// ================================================================================================
+
+#[test]
+#[ignore]
+fn composition_accuracy_fibonacci() {
+    let trace = build_fib_trace(32);
+    let prover = TestProver::<FibAir>::new(|trace| trace.get(1, trace.length() - 1));
+    check_composition(&prover, trace);
+}
+
+#[test]
+#[ignore]
+fn composition_accuracy_periodic_exemptions() {
+    let trace = build_periodic_trace(64);
+    let prover = TestProver::<PeriodicAir>::new(|trace| trace.get(1, trace.length() - 1));
+    check_composition(&prover, trace);
+}
+
+#[test]
+#[ignore]
+fn composition_accuracy_strided_transitions() {
+    let trace = build_stride_trace(64, 4, 0);
+    let prover = TestProver::<StrideAir<4, 0>>::new(|trace| trace.get(0, 1));
+    check_composition(&prover, trace);
+}
+
+#[test]
+fn composition_accuracy_strided_transitions_at_last_step() {
+    // with the last step enforced, the divisor has an exclusion point; with a large stride, the
+    // inverse evaluations of its numerator span more than one batch when constraint evaluations
+    // are divided in parallel
+    let trace = build_stride_trace(256, 128, 127);
+    let prover = TestProver::<StrideAir<128, 127>>::new(|trace| trace.get(0, 1));
+
+    #[cfg(not(feature = "concurrent"))]
+    check_composition(&prover, trace);
+
+    #[cfg(feature = "concurrent")]
+    utils::rayon::ThreadPoolBuilder::new()
+        .num_threads(4)
+        .build()
+        .unwrap()
+        .install(|| check_composition(&prover, trace));
+}
+
+#[test]
+fn validate_strided_transitions() {
+    let trace = build_stride_trace(64, 4, 0);
+    let prover = TestProver::<StrideAir<4, 0>>::new(|trace| trace.get(0, 1));
+    let air = StrideAir::<4, 0>::new(
+        trace.get_info(),
+        prover.get_pub_inputs(&trace),
+        prover.options().clone(),
+    );
+    trace.validate(&air);
+}
+
+#[test]
+#[should_panic(expected = "did not evaluate to ZERO at step 1")]
+fn validate_strided_transitions_with_default_divisor() {
+    // without a strided divisor, the transition constraint must hold on every step
+    let trace = build_stride_trace(64, 4, 0);
+    let prover = TestProver::<StrideAir<1, 0>>::new(|trace| trace.get(0, 1));
+    let air = StrideAir::<1, 0>::new(
+        trace.get_info(),
+        prover.get_pub_inputs(&trace),
+        prover.options().clone(),
+    );
+    trace.validate(&air);
+}
+
+#[test]
+#[should_panic(expected = "did not evaluate to ZERO at step 1")]
+fn validate_streaming_strided_transitions_with_default_divisor() {
+    // a streaming trace is validated by the same checks as a materialized trace
+    let trace = build_stride_trace(64, 4, 0);
+    let prover = TestProver::<StrideAir<1, 0>>::new(|trace| trace.get(0, 1));
+    let air = StrideAir::<1, 0>::new(
+        trace.get_info(),
+        prover.get_pub_inputs(&trace),
+        prover.options().clone(),
+    );
+    crate::StreamingTrace::validate_stream(&trace, &air);
+}
+
+// HARNESS
+// ================================================================================================
+
+/// Checks that the composition polynomial built by the prover for the specified trace is
+/// identical to the composition polynomial computed directly from trace polynomials and
+/// constraint coefficients drawn by the prover.
+///
+/// # Panics
+/// Panics if a constraint numerator is not divisible by its divisor, or if the polynomials
+/// differ; in the latter case, the index of the first differing coefficient is reported.
+fn check_composition<A>(prover: &TestProver<A>, trace: TraceTable<BaseElement>)
+where
+    A: Air<BaseField = BaseElement, PublicInputs = BaseElement>,
+{
+    let air = A::new(
+        trace.get_info(),
+        prover.get_pub_inputs(&trace),
+        prover.options().clone(),
+    );
+    let inv_twiddles = fft::get_inv_twiddles::<BaseElement>(trace.length());
+    let trace_polys = (0..trace.width())
+        .map(|col_idx| {
+            let mut poly = trace.get_register(col_idx).to_vec();
+            fft::interpolate_poly(&mut poly, &inv_twiddles);
+            poly
+        })
+        .collect::<Vec<_>>();
+
+    let (coefficients, actual) = build_composition_poly(prover, trace);
+
+    let expected = compute_composition_poly(&air, &trace_polys, &coefficients);
+    assert_eq!(air.ce_domain_size(), actual.len());
+    for (i, (&e, &a)) in expected.iter().zip(actual.iter()).enumerate() {
+        assert!(
+            e == a,
+            "composition polynomials differ at coefficient {}: expected {}, but was {}",
+            i,
+            e,
+            a
+        );
+    }
+    for (i, &e) in expected.iter().enumerate().skip(actual.len()) {
+        assert!(
+            e == BaseElement::ZERO,
+            "composition polynomials differ at coefficient {}: expected {}, but was 0",
+            i,
+            e
+        );
+    }
+}
+
+/// Builds the constraint composition polynomial for the specified trace in the same way as
+/// the prover does, and returns coefficients of the polynomial together with the constraint
+/// composition coefficients drawn from the prover channel.
+fn build_composition_poly<A>(
+    prover: &TestProver<A>,
+    trace: TraceTable<BaseElement>,
+) -> (
+    ConstraintCompositionCoefficients<BaseElement>,
+    Vec<BaseElement>,
+)
+where
+    A: Air<BaseField = BaseElement, PublicInputs = BaseElement>,
+{
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    let air = A::new(trace.get_info(), pub_inputs, prover.options().clone());
+    #[cfg(debug_assertions)]
+    trace.validate(&air);
+
+    // commit to the extended trace
+    let domain = StarkDomain::new(&air);
+    let trace_polys = trace.into_matrix().interpolate_columns_into();
+    let (trace_commitment, _) = prover
+        .build_trace_commitment::<Blake3_256<BaseElement>>(
+            trace_polys,
+            &domain,
+            &ProverOptions::default(),
+        )
+        .unwrap();
+
+    // draw constraint composition coefficients and evaluate constraints
+    let mut channel =
+        ProverChannel::<A, BaseElement, Blake3_256<BaseElement>>::new(&air, pub_inputs.to_bytes());
+    channel.commit_trace(trace_commitment.root());
+    let coefficients = channel.get_constraint_composition_coeffs();
+    let evaluator = ConstraintEvaluator::new(&air, coefficients.clone());
+    let composition_poly = evaluator
+        .evaluate(&trace_commitment, &domain, None)
+        .into_poly()
+        .unwrap();
+
+    (coefficients, composition_poly.coefficients())
+}
+
+/// Computes the constraint composition polynomial for the specified trace polynomials directly
+/// via polynomial arithmetic.
+fn compute_composition_poly<A>(
+    air: &A,
+    trace_polys: &[Vec<BaseElement>],
+    coefficients: &ConstraintCompositionCoefficients<BaseElement>,
+) -> Vec<BaseElement>
+where
+    A: Air<BaseField = BaseElement>,
+{
+    // numerators of all constraints multiplied by their composition coefficients have degree
+    // smaller than deg(composition) + deg(divisor) + 1 <= 2 * ce_domain_size; so, they are
+    // fully described by their evaluations over a domain of this size
+    let domain_size = air.ce_domain_size() * 2;
+    let domain = get_power_series(
+        BaseElement::get_root_of_unity(log2(domain_size)),
+        domain_size,
+    );
+    let twiddles = fft::get_twiddles::<BaseElement>(domain_size);
+    let trace_evaluations = trace_polys
+        .iter()
+        .map(|poly| {
+            let mut evaluations = poly.clone();
+            evaluations.resize(domain_size, BaseElement::ZERO);
+            fft::evaluate_poly(&mut evaluations, &twiddles);
+            evaluations
+        })
+        .collect::<Vec<_>>();
+    let row = |i: usize| {
+        trace_evaluations
+            .iter()
+            .map(|column| column[i % domain_size])
+            .collect::<Vec<_>>()
+    };
+
+    // the next row of the frame is at x * g, where g is the generator of the trace domain
+    let next_row_offset = domain_size / air.trace_length();
+    let mut result = Vec::new();
+
+    // transition constraints
+    let t_groups = air.get_transition_constraints(&coefficients.transition);
+    let periodic_polys = air.get_periodic_column_polys();
+    let numerator = evaluate_numerator(&domain, |i, x| {
+        let frame = EvaluationFrame::from_rows(row(i), row(i + next_row_offset));
+        let periodic_values = periodic_polys
+            .iter()
+            .map(|poly| {
+                let num_cycles = air.trace_length() / poly.len();
+                polynom::eval(poly, x.exp((num_cycles as u64).into()))
+            })
+            .collect::<Vec<_>>();
+        let mut evaluations = vec![BaseElement::ZERO; air.num_transition_constraints()];
+        air.evaluate_transition(&frame, &periodic_values, &mut evaluations);
+        t_groups
+            .iter()
+            .map(|group| group.merge_evaluations(&evaluations, x))
+            .fold(BaseElement::ZERO, |acc, value| acc + value)
+    });
+    let quotient = divide_exactly(&numerator, &air.transition_constraint_divisor());
+    result = polynom::add(&result, &quotient);
+
+    // boundary constraints derived from assertions
+    for group in air.get_boundary_constraints(&coefficients.boundary) {
+        let numerator = evaluate_numerator(&domain, |i, x| {
+            let xp = x.exp(group.degree_adjustment().into());
+            group
+                .constraints()
+                .iter()
+                .map(|c| c.evaluate_at(x, row(i)[c.register()]) * (c.cc().0 + c.cc().1 * xp))
+                .fold(BaseElement::ZERO, |acc, value| acc + value)
+        });
+        let quotient = divide_exactly(&numerator, group.divisor());
+        result = polynom::add(&result, &quotient);
+    }
+
+    // custom boundary constraints
+    let custom_constraints = air
+        .custom_boundary_constraints(&coefficients.custom_boundary)
+        .unwrap_or_default();
+    for constraint in custom_constraints {
+        let numerator = evaluate_numerator(&domain, |i, x| {
+            let xp = x.exp(constraint.degree_adjustment().into());
+            constraint.evaluate_numerator(&row(i), x, xp)
+        });
+        let quotient = divide_exactly(&numerator, constraint.divisor());
+        result = polynom::add(&result, &quotient);
+    }
+
+    result
+}
+
+/// Evaluates a numerator at all points of the domain and interpolates the evaluations into a
+/// polynomial; `evaluator` receives the index of a point in the domain and the point itself.
+fn evaluate_numerator<F>(domain: &[BaseElement], evaluator: F) -> Vec<BaseElement>
+where
+    F: Fn(usize, BaseElement) -> BaseElement,
+{
+    let mut evaluations = domain
+        .iter()
+        .enumerate()
+        .map(|(i, &x)| evaluator(i, x))
+        .collect::<Vec<_>>();
+    let inv_twiddles = fft::get_inv_twiddles::<BaseElement>(domain.len());
+    fft::interpolate_poly(&mut evaluations, &inv_twiddles);
+    evaluations
+}
+
+/// Divides the numerator polynomial by the polynomial of the specified divisor, and panics if
+/// the division leaves a remainder.
+fn divide_exactly(
+    numerator: &[BaseElement],
+    divisor: &ConstraintDivisor<BaseElement>,
+) -> Vec<BaseElement> {
+    // the terms in the denominator of the divisor are moved into the numerator
+    let mut dividend = numerator.to_vec();
+    for &x in divisor.exclude() {
+        dividend = polynom::mul(&dividend, &sparse_poly(1, x));
+    }
+    for &(degree, constant) in divisor.exemptions() {
+        dividend = polynom::mul(&dividend, &sparse_poly(degree, constant));
+    }
+    let divisor_poly = divisor
+        .numerator()
+        .iter()
+        .fold(vec![BaseElement::ONE], |acc, &(degree, constant)| {
+            polynom::mul(&acc, &sparse_poly(degree, constant))
+        });
+
+    let quotient = polynom::div(&dividend, &divisor_poly);
+    let remainder = polynom::sub(&dividend, &polynom::mul(&quotient, &divisor_poly));
+    assert!(
+        remainder.iter().all(|&c| c == BaseElement::ZERO),
+        "constraint numerator is not divisible by {}",
+        divisor
+    );
+    quotient
+}
+
+/// Returns coefficients of polynomial x^degree - constant.
+fn sparse_poly(degree: usize, constant: BaseElement) -> Vec<BaseElement> {
+    let mut result = vec![BaseElement::ZERO; degree + 1];
+    result[0] = -constant;
+    result[degree] = BaseElement::ONE;
+    result
+}
+
+fn log2(n: usize) -> u32 {
+    n.trailing_zeros()
+}
+
+// FIBONACCI AIR
+// ================================================================================================
+
+/// AIR for the trace built by [build_fib_trace()]; transition constraints are applied to
+/// consecutive rows of the trace.
+pub(super) struct FibAir {
+    context: AirContext<BaseElement>,
+    result: BaseElement,
+}
+
+impl Air for FibAir {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+
+    fn new(trace_info: TraceInfo, result: BaseElement, options: ProofOptions) -> Self {
+        let degrees = vec![
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(1),
+        ];
+        FibAir {
+            context: AirContext::new(trace_info, degrees, options),
+            result,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        result[0] = next[0] - (current[0] + current[1]);
+        result[1] = next[1] - (current[0] + current[1].double());
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(0, 0, BaseElement::ONE),
+            Assertion::single(1, 0, BaseElement::ONE),
+            Assertion::single(1, last_step, self.result),
+        ]
+    }
+}
+
+// PERIODIC AIR
+// ================================================================================================
+
+/// Length of the cycle over which the first register of [PeriodicAir] accumulates values.
+const CYCLE_LENGTH: usize = 8;
+
+/// Step within each cycle on which transition constraints of [PeriodicAir] are switched off.
+const EXEMPT_STEP: usize = 3;
+
+/// Builds a trace for [PeriodicAir]: the first register accumulates values of a periodic column
+/// and is reset to zero after step 3 of every 8-step cycle, while the second register
+/// accumulates squares of the first register.
+fn build_periodic_trace(length: usize) -> TraceTable<BaseElement> {
+    let cycle = periodic_column();
+    let mut reg0 = vec![BaseElement::ZERO];
+    let mut reg1 = vec![BaseElement::ONE];
+    for i in 0..length - 1 {
+        let next0 = if i % CYCLE_LENGTH == EXEMPT_STEP {
+            BaseElement::ZERO
+        } else {
+            reg0[i] + cycle[i % CYCLE_LENGTH]
+        };
+        reg0.push(next0);
+        reg1.push(reg1[i] + reg0[i].square());
+    }
+    TraceTable::init(vec![reg0, reg1])
+}
+
+fn periodic_column() -> Vec<BaseElement> {
+    (1..=CYCLE_LENGTH as u64).map(BaseElement::from).collect()
+}
+
+/// AIR for the trace built by [build_periodic_trace()]; this AIR uses a periodic column, a
+/// transition divisor with periodic exemptions, a cyclic assertion, and a custom boundary
+/// constraint.
+struct PeriodicAir {
+    context: AirContext<BaseElement>,
+    result: BaseElement,
+}
+
+impl Air for PeriodicAir {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+
+    fn new(trace_info: TraceInfo, result: BaseElement, options: ProofOptions) -> Self {
+        let degrees = vec![
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(2),
+        ];
+        PeriodicAir {
+            context: AirContext::new(trace_info, degrees, options)
+                .with_custom_boundary_constraints(1),
+            result,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        result[0] = next[0] - (current[0] + periodic_values[0]);
+        result[1] = next[1] - (current[1] + current[0].square());
+    }
+
+    fn transition_constraint_divisor(&self) -> ConstraintDivisor<Self::BaseField> {
+        ConstraintDivisor::from_transition_with_periodic_exemptions(
+            self.trace_length(),
+            CYCLE_LENGTH,
+            EXEMPT_STEP,
+        )
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(0, 0, BaseElement::ZERO),
+            Assertion::periodic(0, EXEMPT_STEP + 1, CYCLE_LENGTH, BaseElement::ZERO),
+            Assertion::single(1, last_step, self.result),
+        ]
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        vec![periodic_column()]
+    }
+
+    fn custom_boundary_constraints<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        coefficients: &[(E, E)],
+    ) -> Option<Vec<CustomBoundaryConstraint<'_, Self::BaseField, E>>> {
+        // the second register starts one above the first register
+        let first_row = CustomBoundaryConstraint::new(
+            self,
+            ConstraintDivisor::from_assertion(
+                &Assertion::single(1, 0, BaseElement::ONE),
+                self.trace_length(),
+            ),
+            coefficients[0],
+            |state: &[E], _| state[1] - state[0] - E::ONE,
+            |state: &[BaseElement], _| E::from(state[1] - state[0] - BaseElement::ONE),
+        );
+        Some(vec![first_row])
+    }
+}
+
+// STRIDE AIR
+// ================================================================================================
+
+/// Builds a trace for [StrideAir]: the first register holds the step number and the second
+/// register holds 3 times the step number plus 2, except that on the steps which follow steps
+/// `offset`, `offset + stride` etc., the first register holds the product of both registers at
+/// the previous step. The last step of the trace has no following step, and thus, is skipped.
+fn build_stride_trace(length: usize, stride: usize, offset: usize) -> TraceTable<BaseElement> {
+    let mut reg0 = (0..length as u128)
+        .map(BaseElement::new)
+        .collect::<Vec<_>>();
+    let reg1 = (0..length as u128)
+        .map(|i| BaseElement::new(3 * i + 2))
+        .collect::<Vec<_>>();
+    for step in (offset..length - 1).step_by(stride) {
+        reg0[step + 1] = reg0[step] * reg1[step];
+    }
+    TraceTable::init(vec![reg0, reg1])
+}
+
+/// AIR for the trace built by [build_stride_trace()]; the transition constraint is enforced only
+/// on steps `OFFSET`, `OFFSET + STRIDE` etc. via a strided transition divisor. With `STRIDE` set
+/// to 1, the default transition divisor is used instead.
+struct StrideAir<const STRIDE: usize, const OFFSET: usize> {
+    context: AirContext<BaseElement>,
+    result: BaseElement,
+}
+
+impl<const STRIDE: usize, const OFFSET: usize> Air for StrideAir<STRIDE, OFFSET> {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+
+    fn new(trace_info: TraceInfo, result: BaseElement, options: ProofOptions) -> Self {
+        let degrees = vec![TransitionConstraintDegree::new(2)];
+        StrideAir {
+            context: AirContext::new(trace_info, degrees, options),
+            result,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        result[0] = frame.next()[0] - current[0] * current[1];
+    }
+
+    fn transition_constraint_divisor(&self) -> ConstraintDivisor<Self::BaseField> {
+        if STRIDE == 1 {
+            ConstraintDivisor::from_transition(self.trace_length())
+        } else {
+            ConstraintDivisor::from_transition_with_stride(self.trace_length(), STRIDE, OFFSET)
+        }
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        vec![Assertion::single(0, 1, self.result)]
+    }
+}
@@ -0,0 +1,350 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! End-to-end proof generation and verification tests for AIRs which deviate from the most
+//! common AIR structure.
+//!
+//! All tests in this module use [FibTestAir]; variations of the AIR (e.g., custom boundary
+//! constraints instead of assertions) are selected via trace metadata, and thus, a verifier
+//! instantiates the same variation of the AIR as the prover did.
+
+use super::TestProver;
+use crate::{
+    Air, AirContext, Assertion, ConstraintDivisor, CustomBoundaryConstraint, EvaluationFrame,
+    ProofOptions, Prover, StarkProof, Trace, TraceInfo, TraceTable, TransitionConstraintDegree,
+};
+use math::{fields::f128::BaseElement, FieldElement};
+use utils::{collections::Vec, string::String};
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn fib_test_air_proof_verification() {
+    let config = FibTestConfig::default();
+    let (proof, result) = prove(1024, config);
+    assert!(verify(proof.clone(), result).is_ok());
+    assert!(verify(proof, result + BaseElement::ONE).is_err());
+}
+
+#[test]
+fn fib_test_air_wide_composition() {
+    // split the composition polynomial into twice as many columns as required by constraint
+    // degrees
+    let config = FibTestConfig {
+        wide_composition: true,
+        ..Default::default()
+    };
+    let (proof, result) = prove(1024, config);
+
+    // without this, the AIR requires 2 composition columns, and so the proof contains
+    // evaluations of 4 columns
+    assert!(proof.ood_frame.clone().parse::<BaseElement>(3, 4).is_ok());
+    assert!(proof.ood_frame.clone().parse::<BaseElement>(3, 2).is_err());
+    assert!(verify(proof.clone(), result).is_ok());
+    assert!(verify(proof, result + BaseElement::ONE).is_err());
+}
+
+#[test]
+fn fib_test_air_custom_boundary_constraints() {
+    // custom boundary constraints alongside the result assertion, and instead of all assertions
+    for custom_result in [false, true] {
+        let config = FibTestConfig {
+            custom_first_row: true,
+            custom_result,
+            ..Default::default()
+        };
+        let (proof, result) = prove(64, config);
+        assert!(verify(proof.clone(), result).is_ok());
+        assert!(verify(proof.clone(), result + BaseElement::ONE).is_err());
+
+        // a proof against custom boundary constraints is not accepted by the verifier which
+        // uses assertions instead
+        let proof = flip_meta_bits(proof, config.to_byte() ^ FibTestConfig::default().to_byte());
+        assert!(verify(proof, result).is_err());
+    }
+}
+
+#[test]
+fn fib_test_air_meta_dependent_constraints() {
+    // proofs can be generated and verified with the counter constraints either enabled or
+    // disabled via trace metadata
+    for counter in [true, false] {
+        let config = FibTestConfig {
+            counter,
+            ..Default::default()
+        };
+        let (proof, result) = prove(1024, config);
+        assert!(verify(proof.clone(), result).is_ok());
+        assert!(verify(proof.clone(), result + BaseElement::ONE).is_err());
+
+        // a proof must be rejected when the verifier instantiates the AIR with a different set
+        // of constraints than the prover did
+        let proof = flip_meta_bits(proof, FibTestConfig::COUNTER);
+        let flipped = FibTestConfig::from_meta(proof.context.get_trace_info().meta());
+        assert_eq!(Some(!counter), flipped.map(|config| config.counter));
+        assert!(verify(proof, result).is_err());
+    }
+}
+
+// TEST AIR CONFIGURATION
+// ================================================================================================
+
+/// Variation of [FibTestAir]; the variation is encoded into the first byte of trace metadata.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FibTestConfig {
+    /// Constrain the third column of the trace to be a step counter starting at zero; otherwise,
+    /// values in this column are unconstrained.
+    pub counter: bool,
+    /// Split the composition polynomial into twice as many columns as required by constraint
+    /// degrees.
+    pub wide_composition: bool,
+    /// Replace the two assertions against the first row of the trace with a single custom
+    /// boundary constraint.
+    pub custom_first_row: bool,
+    /// Replace the assertion against the result with a custom boundary constraint.
+    pub custom_result: bool,
+}
+
+impl FibTestConfig {
+    const COUNTER: u8 = 1;
+    const WIDE_COMPOSITION: u8 = 2;
+    const CUSTOM_FIRST_ROW: u8 = 4;
+    const CUSTOM_RESULT: u8 = 8;
+
+    /// Returns the config encoded in the specified trace metadata, or `None` if the metadata is
+    /// not a valid encoding of a config.
+    pub fn from_meta(meta: &[u8]) -> Option<Self> {
+        match meta {
+            [byte] if byte >> 4 == 0 => Some(FibTestConfig {
+                counter: byte & Self::COUNTER != 0,
+                wide_composition: byte & Self::WIDE_COMPOSITION != 0,
+                custom_first_row: byte & Self::CUSTOM_FIRST_ROW != 0,
+                custom_result: byte & Self::CUSTOM_RESULT != 0,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Encodes this config into a single byte of trace metadata.
+    pub fn to_byte(self) -> u8 {
+        let mut byte = 0;
+        if self.counter {
+            byte |= Self::COUNTER;
+        }
+        if self.wide_composition {
+            byte |= Self::WIDE_COMPOSITION;
+        }
+        if self.custom_first_row {
+            byte |= Self::CUSTOM_FIRST_ROW;
+        }
+        if self.custom_result {
+            byte |= Self::CUSTOM_RESULT;
+        }
+        byte
+    }
+
+    /// Returns the number of custom boundary constraints defined by this config.
+    fn num_custom_boundary_constraints(&self) -> usize {
+        self.custom_first_row as usize + self.custom_result as usize
+    }
+}
+
+// TEST AIR
+// ================================================================================================
+
+/// Number of columns in the execution trace of [FibTestAir].
+const TRACE_WIDTH: usize = 3;
+
+/// AIR for the trace built by [build_fib_test_trace()]; the first two columns are constrained in
+/// the same way as in [build_fib_trace()](super::build_fib_trace), and the constraints on the
+/// third column depend on the [FibTestConfig] encoded in trace metadata.
+pub struct FibTestAir {
+    context: AirContext<BaseElement>,
+    config: FibTestConfig,
+    result: BaseElement,
+}
+
+impl Air for FibTestAir {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+
+    fn new(trace_info: TraceInfo, result: BaseElement, options: ProofOptions) -> Self {
+        assert_eq!(TRACE_WIDTH, trace_info.width());
+        let config = FibTestConfig::from_meta(trace_info.meta())
+            .unwrap_or_else(|| panic!("invalid trace metadata: {:?}", trace_info.meta()));
+
+        let mut degrees = vec![
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(1),
+        ];
+        if config.counter {
+            degrees.push(TransitionConstraintDegree::new(1));
+        }
+        let context = AirContext::new(trace_info, degrees, options)
+            .with_custom_boundary_constraints(config.num_custom_boundary_constraints());
+
+        let mut air = FibTestAir {
+            context,
+            config,
+            result,
+        };
+        if config.wide_composition {
+            let num_columns = air.num_composition_columns() * 2;
+            air.context = air.context.with_num_composition_columns(num_columns);
+        }
+        air
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        result[0] = next[0] - (current[0] + current[1]);
+        result[1] = next[1] - (current[0] + current[1].double());
+
+        if self.config.counter {
+            result[2] = next[2] - current[2] - E::ONE;
+        }
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let mut assertions = Vec::new();
+        if !self.config.custom_first_row {
+            assertions.push(Assertion::single(0, 0, BaseElement::ONE));
+            assertions.push(Assertion::single(1, 0, BaseElement::ONE));
+        }
+        if !self.config.custom_result {
+            let last_step = self.trace_length() - 1;
+            assertions.push(Assertion::single(1, last_step, self.result));
+        }
+        if self.config.counter {
+            assertions.push(Assertion::single(2, 0, BaseElement::ZERO));
+        }
+        assertions
+    }
+
+    fn custom_boundary_constraints<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        coefficients: &[(E, E)],
+    ) -> Option<Vec<CustomBoundaryConstraint<'_, Self::BaseField, E>>> {
+        let mut constraints = Vec::new();
+        let mut coefficients = coefficients.iter();
+
+        if self.config.custom_first_row {
+            // both registers must be equal to ONE in the first row; the two conditions are
+            // folded into a single numerator using a random coefficient
+            let one = BaseElement::ONE;
+            let cc = *coefficients.next().expect("not enough coefficients");
+            let gamma = cc.0;
+            let assertion = Assertion::single(0, 0, one);
+            constraints.push(CustomBoundaryConstraint::new(
+                self,
+                ConstraintDivisor::from_assertion(&assertion, self.trace_length()),
+                cc,
+                move |state: &[E], _| (state[0] - E::ONE) + gamma * (state[1] - E::ONE),
+                move |state: &[BaseElement], _| {
+                    E::from(state[0] - one) + gamma * E::from(state[1] - one)
+                },
+            ));
+        }
+
+        if self.config.custom_result {
+            // register 1 must be equal to the result in the last row
+            let result = self.result;
+            let cc = *coefficients.next().expect("not enough coefficients");
+            let assertion = Assertion::single(1, self.trace_length() - 1, result);
+            constraints.push(CustomBoundaryConstraint::new(
+                self,
+                ConstraintDivisor::from_assertion(&assertion, self.trace_length()),
+                cc,
+                move |state: &[E], _| state[1] - E::from(result),
+                move |state: &[BaseElement], _| E::from(state[1] - result),
+            ));
+        }
+
+        Some(constraints)
+    }
+
+    fn validate_instance(trace_info: &TraceInfo, _result: &BaseElement) -> Result<(), String> {
+        if trace_info.width() != TRACE_WIDTH {
+            return Err(format!(
+                "expected trace width {}, but was {}",
+                TRACE_WIDTH,
+                trace_info.width()
+            ));
+        }
+        if FibTestConfig::from_meta(trace_info.meta()).is_none() {
+            return Err(format!("invalid trace metadata: {:?}", trace_info.meta()));
+        }
+        Ok(())
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Builds a trace for [FibTestAir] with the specified config encoded in trace metadata; when
+/// counter constraints are disabled, the third column contains values which do not form a step
+/// counter.
+pub fn build_fib_test_trace(length: usize, config: FibTestConfig) -> TraceTable<BaseElement> {
+    let mut trace = TraceTable::with_meta(TRACE_WIDTH, length, vec![config.to_byte()]);
+    trace.fill(
+        |state| {
+            state[0] = BaseElement::ONE;
+            state[1] = BaseElement::ONE;
+            state[2] = if config.counter {
+                BaseElement::ZERO
+            } else {
+                BaseElement::new(7)
+            };
+        },
+        |_, state| {
+            let s0 = state[0] + state[1];
+            state[1] = state[0] + state[1].double();
+            state[0] = s0;
+            state[2] = if config.counter {
+                state[2] + BaseElement::ONE
+            } else {
+                state[2] * state[2]
+            };
+        },
+    );
+    trace
+}
+
+/// Generates a proof for [FibTestAir] with the specified config, and returns the proof together
+/// with the result of the computation.
+pub fn prove(length: usize, config: FibTestConfig) -> (StarkProof, BaseElement) {
+    let trace = build_fib_test_trace(length, config);
+    let result = trace.get(1, trace.length() - 1);
+    let prover = TestProver::<FibTestAir>::new(|trace| trace.get(1, trace.length() - 1));
+    (prover.prove(trace).unwrap(), result)
+}
+
+/// Verifies a proof generated for [FibTestAir] against the specified result.
+pub fn verify(proof: StarkProof, result: BaseElement) -> Result<(), verifier::VerifierError> {
+    verifier::verify::<FibTestAir>(proof, result)
+}
+
+/// Flips the specified bits of the trace metadata byte in a serialized proof, and returns the
+/// deserialized result.
+fn flip_meta_bits(proof: StarkProof, bits: u8) -> StarkProof {
+    // the first byte of trace metadata follows trace width (1 byte), trace length (1 byte), and
+    // the number of metadata bytes (2 bytes) in a serialized proof
+    const META_POSITION: usize = 4;
+
+    let mut proof_bytes = proof.to_bytes();
+    proof_bytes[META_POSITION] ^= bits;
+    StarkProof::from_bytes(&proof_bytes).unwrap()
+}
@@ -0,0 +1,51 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Tests for proving execution traces which were built incrementally.
+
+use super::{
+    fib::{build_fib_test_trace, verify, FibTestAir, FibTestConfig},
+    TestProver,
+};
+use crate::{Prover, Trace, TraceTable};
+use math::{fields::f128::BaseElement, FieldElement};
+use utils::collections::Vec;
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn finalized_trace_proof() {
+    // a trace which was truncated, extended by whole steps of 8 rows, and then finalized results
+    // in the same proof as the trace built at once
+    let config = FibTestConfig::default();
+    let full_trace = build_fib_test_trace(64, config);
+    let result = full_trace.get(1, full_trace.length() - 1);
+
+    let mut trace = build_fib_test_trace(64, config);
+    trace.set_rows_per_step(8);
+    trace.truncate(24);
+    let rows = (24..64)
+        .map(|step| read_row(&full_trace, step))
+        .collect::<Vec<_>>();
+    trace.append_rows(&rows);
+    let trace = trace.finalize(&read_row(&full_trace, 63));
+    assert!(trace.is_finalized());
+
+    let prover = TestProver::<FibTestAir>::new(|trace| trace.get(1, trace.length() - 1));
+    let proof = prover.prove(trace).unwrap();
+    let expected = prover.prove(full_trace).unwrap();
+    assert_eq!(expected.to_bytes(), proof.to_bytes());
+    assert!(verify(proof, result).is_ok());
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn read_row(trace: &TraceTable<BaseElement>, step: usize) -> Vec<BaseElement> {
+    let mut row = vec![BaseElement::ZERO; trace.width()];
+    trace.read_row_into(step, &mut row);
+    row
+}
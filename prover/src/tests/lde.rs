@@ -0,0 +1,79 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Tests for the trace LDE self-check enabled via [ProverOptions::with_lde_self_check()].
+
+use super::{build_fib_trace, composition::FibAir, TestProver};
+use crate::{Air, Matrix, Prover, ProverError, ProverOptions, StarkDomain, Trace, TraceCommitment};
+use crypto::hashers::Blake3_256;
+use math::{fields::f128::BaseElement, FieldElement};
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn lde_self_check_passes() {
+    let prover = build_prover();
+    let options = ProverOptions::new().with_lde_self_check(16);
+
+    let expected = prover.prove(build_fib_trace(32)).unwrap();
+    let proof = prover.prove_with(build_fib_trace(32), &options).unwrap();
+    assert_eq!(expected.to_bytes(), proof.to_bytes());
+}
+
+#[test]
+fn lde_self_check_detects_corruption() {
+    let prover = build_prover();
+    let trace = build_fib_trace(32);
+    let air = FibAir::new(
+        trace.get_info(),
+        prover.get_pub_inputs(&trace),
+        prover.options().clone(),
+    );
+    let domain = StarkDomain::new(&air);
+    let trace_polys = trace.into_matrix().interpolate_columns_into();
+
+    // an LDE which is consistent with the trace polynomials passes the check
+    let trace_lde = trace_polys.evaluate_columns_over(&domain);
+    assert!(build_commitment(trace_lde, &domain)
+        .check_lde(&trace_polys, &domain, 32)
+        .is_ok());
+
+    // corrupt the second half of the second column, as if a strip of the LDE was written at
+    // a wrong offset
+    let mut trace_lde = trace_polys.evaluate_columns_over(&domain);
+    let lde_domain_size = domain.lde_domain_size();
+    for value in trace_lde.get_column_mut(1)[lde_domain_size / 2..].iter_mut() {
+        *value += BaseElement::ONE;
+    }
+    match build_commitment(trace_lde, &domain).check_lde(&trace_polys, &domain, 32) {
+        Err(ProverError::LdeSelfCheckFailed { column, position }) => {
+            assert_eq!(1, column);
+            assert!(position >= lde_domain_size / 2);
+        }
+        result => panic!("expected LDE self-check to fail, but was {:?}", result),
+    }
+}
+
+#[test]
+#[should_panic(expected = "number of LDE self-check samples must be greater than zero")]
+fn lde_self_check_zero_samples() {
+    ProverOptions::new().with_lde_self_check(0);
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn build_prover() -> TestProver<FibAir> {
+    TestProver::new(|trace| trace.get(1, trace.length() - 1))
+}
+
+fn build_commitment(
+    trace_lde: Matrix<BaseElement>,
+    domain: &StarkDomain<BaseElement>,
+) -> TraceCommitment<BaseElement, Blake3_256<BaseElement>> {
+    let trace_tree = trace_lde.commit_to_rows();
+    TraceCommitment::new(trace_lde, trace_tree, domain.trace_to_lde_blowup())
+}
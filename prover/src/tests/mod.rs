@@ -3,7 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::TraceTable;
+use crate::{Prover, TraceTable};
 use air::{
     Air, AirContext, Assertion, EvaluationFrame, FieldExtension, HashFunction, ProofOptions,
     TraceInfo, TransitionConstraintDegree,
@@ -11,6 +11,17 @@ use air::{
 use math::{fields::f128::BaseElement, FieldElement, StarkField};
 use utils::collections::Vec;
 
+pub mod composition;
+mod fib;
+mod incremental;
+mod lde;
+mod options;
+mod serialization;
+#[cfg(feature = "tracing")]
+mod spans;
+mod transcript;
+mod verification;
+
 // FIBONACCI TRACE BUILDER
 // ================================================================================================
 
@@ -28,6 +39,58 @@ pub fn build_fib_trace(length: usize) -> TraceTable<BaseElement> {
     TraceTable::init(vec![reg1, reg2])
 }
 
+// TEST PROVER
+// ================================================================================================
+
+/// Prover for AIRs whose public input is a single value derived from the trace.
+pub struct TestProver<A> {
+    options: ProofOptions,
+    pub_inputs: fn(&TraceTable<BaseElement>) -> BaseElement,
+    _air: core::marker::PhantomData<A>,
+}
+
+impl<A> TestProver<A> {
+    pub fn new(pub_inputs: fn(&TraceTable<BaseElement>) -> BaseElement) -> Self {
+        Self::with_grinding_factor(0, pub_inputs)
+    }
+
+    pub fn with_grinding_factor(
+        grinding_factor: u32,
+        pub_inputs: fn(&TraceTable<BaseElement>) -> BaseElement,
+    ) -> Self {
+        TestProver {
+            options: ProofOptions::new(
+                32,
+                8,
+                grinding_factor,
+                HashFunction::Blake3_256,
+                FieldExtension::None,
+                4,
+                256,
+            ),
+            pub_inputs,
+            _air: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<A> Prover for TestProver<A>
+where
+    A: Air<BaseField = BaseElement, PublicInputs = BaseElement>,
+{
+    type BaseField = BaseElement;
+    type Air = A;
+    type Trace = TraceTable<BaseElement>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+        (self.pub_inputs)(trace)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
+
 // MOCK AIR
 // ================================================================================================
 
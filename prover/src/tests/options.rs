@@ -0,0 +1,145 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Tests for prover-local options.
+
+use super::{
+    fib::{build_fib_test_trace, verify, FibTestAir, FibTestConfig},
+    TestProver,
+};
+use crate::{Prover, ProverError, ProverOptions, Trace, TraceTable};
+use crypto::{hashers::Blake3_256, Digest, Hasher};
+use math::fields::f128::BaseElement;
+use utils::{collections::Vec, string::String};
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn trace_size_guard() {
+    // a trace with 3 columns and 512 rows has 1536 cells
+    let prover = build_prover();
+    let trace = build_trace();
+    let result = get_result(&trace);
+
+    // the prover rejects a trace with more cells than allowed before generating a proof
+    let prover_options = ProverOptions::new().with_max_trace_cells(1535);
+    assert_eq!(
+        Err(ProverError::TraceTooLarge {
+            cells: 1536,
+            max: 1535
+        }),
+        prover
+            .prove_with(build_trace(), &prover_options)
+            .map(|_| ())
+    );
+
+    let prover_options = ProverOptions::new().with_max_trace_cells(1536);
+    assert!(prover.prove_with(build_trace(), &prover_options).is_ok());
+
+    // the same limit applies to streaming traces
+    let prover_options = ProverOptions::new().with_max_trace_cells(1535);
+    assert_eq!(
+        Err(ProverError::TraceTooLarge {
+            cells: 1536,
+            max: 1535
+        }),
+        prover
+            .prove_streaming_with(&trace, result, &prover_options)
+            .map(|_| ())
+    );
+
+    let prover_options = ProverOptions::new().with_max_trace_cells(1536);
+    assert!(prover
+        .prove_streaming_with(&trace, result, &prover_options)
+        .is_ok());
+}
+
+#[test]
+fn prover_options_do_not_affect_proofs() {
+    let options_matrix = [None, Some(1), Some(2), Some(8), Some(1024)]
+        .into_iter()
+        .flat_map(|num_fragments| {
+            [true, false].into_iter().map(move |validate_trace| {
+                let options = ProverOptions::new().with_trace_validation(validate_trace);
+                match num_fragments {
+                    Some(num_fragments) => options.with_num_evaluation_fragments(num_fragments),
+                    None => options,
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let prover = build_prover();
+    let config = FibTestConfig::default();
+    let small_proof = prover.prove(build_fib_test_trace(16, config)).unwrap();
+    let large_proof = prover.prove(build_fib_test_trace(2048, config)).unwrap();
+    for prover_options in options_matrix.iter() {
+        let proof = prover.prove_with(build_fib_test_trace(16, config), prover_options);
+        assert_eq!(small_proof.to_bytes(), proof.unwrap().to_bytes());
+        let proof = prover.prove_with(build_fib_test_trace(2048, config), prover_options);
+        assert_eq!(large_proof.to_bytes(), proof.unwrap().to_bytes());
+    }
+
+    // the proof is pinned so that changes to prover-local behavior which alter proof bytes are
+    // caught
+    let digest = Blake3_256::<BaseElement>::hash(&small_proof.to_bytes());
+    let digest = digest
+        .as_bytes()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<String>();
+    assert_eq!(
+        "f9c0cf57dc7c498379769400d77a43ba149c313531fed556d44453a561c952ad",
+        digest
+    );
+}
+
+#[test]
+fn grinding_attempts() {
+    // a grinding factor which cannot be met within the attempt budget results in an error
+    // rather than an unbounded nonce search
+    let prover = TestProver::<FibTestAir>::with_grinding_factor(32, get_result);
+    let prover_options = ProverOptions::new().with_max_grinding_attempts(64);
+    assert_eq!(
+        Err(ProverError::GrindingFailed {
+            grinding_factor: 32,
+            attempts: 64
+        }),
+        prover
+            .prove_with(
+                build_fib_test_trace(16, FibTestConfig::default()),
+                &prover_options
+            )
+            .map(|_| ())
+    );
+
+    // if grinding succeeds within the budget, the proof is the same as without the budget
+    let prover = TestProver::<FibTestAir>::with_grinding_factor(4, get_result);
+    let prover_options = ProverOptions::new().with_max_grinding_attempts(1024);
+    let trace = build_fib_test_trace(16, FibTestConfig::default());
+    let result = get_result(&trace);
+    let proof = prover.prove_with(trace, &prover_options).unwrap();
+    let expected = prover
+        .prove(build_fib_test_trace(16, FibTestConfig::default()))
+        .unwrap();
+    assert_eq!(expected.to_bytes(), proof.to_bytes());
+    assert!(verify(proof, result).is_ok());
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn build_trace() -> TraceTable<BaseElement> {
+    build_fib_test_trace(512, FibTestConfig::default())
+}
+
+fn build_prover() -> TestProver<FibTestAir> {
+    TestProver::new(get_result)
+}
+
+fn get_result(trace: &TraceTable<BaseElement>) -> BaseElement {
+    trace.get(1, trace.length() - 1)
+}
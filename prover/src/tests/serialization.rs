@@ -0,0 +1,56 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Tests for serialization of generated proofs.
+
+use super::fib::{prove, FibTestConfig};
+use crate::StarkProof;
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn proof_canonical_form() {
+    // deserializing and re-serializing a proof yields identical bytes
+    let (proof, _) = prove(64, FibTestConfig::default());
+    let proof_bytes = proof.to_bytes();
+
+    let parsed = StarkProof::from_bytes(&proof_bytes).unwrap();
+    assert_eq!(proof_bytes, parsed.to_bytes());
+    assert_eq!(proof, parsed);
+    assert_eq!(fingerprint(&proof), fingerprint(&parsed));
+
+    // proofs generated independently for the same trace and options are identical
+    let (other, _) = prove(64, FibTestConfig::default());
+    assert_eq!(proof_bytes, other.to_bytes());
+    assert_eq!(proof, other);
+    assert_eq!(fingerprint(&proof), fingerprint(&other));
+
+    // proofs which differ in a single byte are different
+    let mut other = proof.clone();
+    other.pow_nonce += 1;
+    assert_ne!(proof_bytes, other.to_bytes());
+    assert_ne!(proof, other);
+    assert_ne!(fingerprint(&proof), fingerprint(&other));
+
+    // trailing bytes are rejected
+    let mut bytes = proof_bytes;
+    bytes.push(0);
+    assert!(StarkProof::from_bytes(&bytes).is_err());
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns a hash of the specified proof computed via its [Hash] implementation.
+fn fingerprint(proof: &StarkProof) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    proof.hash(&mut hasher);
+    hasher.finish()
+}
@@ -0,0 +1,113 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Tests for tracing spans emitted by the prover and the verifier.
+
+use super::fib::{prove, verify, FibTestConfig};
+use std::sync::{Arc, Mutex};
+use utils::{collections::Vec, string::String};
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn tracing_spans() {
+    // proving and verifying emits spans for all major prover and verifier phases, and the spans
+    // carry the expected fields
+    let recorder = SpanRecorder::default();
+    let num_queries = tracing::subscriber::with_default(recorder.clone(), || {
+        let (proof, result) = prove(16, FibTestConfig::default());
+        let num_queries = proof.options().num_queries().to_string();
+        assert!(verify(proof, result).is_ok());
+        num_queries
+    });
+    let spans = recorder.spans.lock().unwrap();
+
+    let prover_spans = [
+        "prove",
+        "commit_trace",
+        "evaluate_constraints",
+        "commit_composition",
+        "build_deep_composition",
+        "evaluate_deep_composition",
+        "build_fri_layers",
+        "determine_queries",
+        "build_proof",
+    ];
+    let verifier_spans = [
+        "verify",
+        "read_commitments",
+        "check_ood_consistency",
+        "read_fri_commitments",
+        "read_queries",
+        "compose_deep",
+        "verify_fri",
+    ];
+    let names = spans.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+    let expected = prover_spans.iter().chain(verifier_spans.iter());
+    assert_eq!(expected.copied().collect::<Vec<_>>(), names);
+
+    let fields = |name: &str| {
+        spans
+            .iter()
+            .find(|(span_name, _)| *span_name == name)
+            .map(|(_, fields)| fields.clone())
+            .unwrap()
+    };
+    for name in ["prove", "commit_trace", "verify"] {
+        let fields = fields(name);
+        assert!(fields.iter().any(|(field, _)| *field == "trace_width"));
+        assert!(fields.iter().any(|(field, _)| *field == "trace_length"));
+    }
+    assert!(fields("verify").contains(&("num_queries", num_queries.clone())));
+    assert!(fields("determine_queries").contains(&("num_queries", num_queries)));
+}
+
+// SPAN RECORDER
+// ================================================================================================
+
+/// Name of a span together with names and values of its fields.
+type RecordedSpan = (&'static str, Vec<(&'static str, String)>);
+
+/// Subscriber which records names and fields of all created spans.
+#[derive(Clone, Default)]
+struct SpanRecorder {
+    spans: Arc<Mutex<Vec<RecordedSpan>>>,
+}
+
+impl tracing::Subscriber for SpanRecorder {
+    fn enabled(&self, _metadata: &tracing::Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+        struct FieldVisitor(Vec<(&'static str, String)>);
+        impl tracing::field::Visit for FieldVisitor {
+            fn record_debug(
+                &mut self,
+                field: &tracing::field::Field,
+                value: &dyn core::fmt::Debug,
+            ) {
+                self.0.push((field.name(), format!("{:?}", value)));
+            }
+        }
+
+        let mut visitor = FieldVisitor(Vec::new());
+        span.record(&mut visitor);
+        let mut spans = self.spans.lock().unwrap();
+        spans.push((span.metadata().name(), visitor.0));
+        tracing::span::Id::from_u64(spans.len() as u64)
+    }
+
+    fn record(&self, _span: &tracing::span::Id, _values: &tracing::span::Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &tracing::span::Id, _follows: &tracing::span::Id) {}
+
+    fn event(&self, _event: &tracing::Event<'_>) {}
+
+    fn enter(&self, _span: &tracing::span::Id) {}
+
+    fn exit(&self, _span: &tracing::span::Id) {}
+}
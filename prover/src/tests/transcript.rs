@@ -0,0 +1,31 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Tests for consistency of the Fiat-Shamir transcripts of the prover and the verifier.
+
+use super::fib::{prove, verify, FibTestConfig};
+use crypto::transcript::capture_transcript;
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn transcript_consistency() {
+    // the public coin events recorded while generating a proof are identical (event-for-event)
+    // to the events recorded while verifying it
+    let ((proof, result), prover_transcript) =
+        capture_transcript(|| prove(16, FibTestConfig::default()));
+    let (verification, verifier_transcript) = capture_transcript(|| verify(proof, result));
+    assert!(verification.is_ok());
+
+    if let Some(diff) = prover_transcript.diff(&verifier_transcript) {
+        panic!("prover and verifier transcripts differ; {}", diff);
+    }
+    assert!(!prover_transcript.events().is_empty());
+    assert!(prover_transcript
+        .events()
+        .iter()
+        .all(|event| !event.label.is_empty()));
+}
@@ -0,0 +1,128 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Tests which make sure that the verifier rejects tampered proofs with the expected errors, and
+//! that the cost of verification stays bounded as traces grow.
+
+use super::fib::{prove, verify, FibTestConfig};
+use crate::StarkProof;
+use crypto::transcript::capture_transcript;
+use rand_utils::prng_vector;
+use utils::{collections::Vec, Serializable};
+use verifier::VerifierError;
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn tampered_context_verification() {
+    // proofs with randomly modified contexts must be rejected without panicking
+    let (proof, result) = prove(64, FibTestConfig::default());
+    let context_len = proof.context.to_bytes().len();
+    let proof_bytes = proof.to_bytes();
+
+    let noise = prng_vector::<u64>([1; 32], 512);
+    for (i, &value) in noise.iter().enumerate() {
+        let mut bytes = proof_bytes.clone();
+        bytes[i % context_len] = value as u8;
+        if bytes == proof_bytes {
+            continue;
+        }
+        if let Ok(proof) = StarkProof::from_bytes(&bytes) {
+            assert!(verify(proof, result).is_err());
+        }
+    }
+}
+
+#[test]
+fn tampered_queries_verification() {
+    // a modified trace query, constraint query, or FRI remainder results in the same error on
+    // every verification attempt
+    let (proof, result) = prove(64, FibTestConfig::default());
+    let proof_bytes = proof.to_bytes();
+
+    // value bytes of trace and constraint queries come right after their length prefixes, and
+    // the FRI remainder is followed only by the number of partitions and the proof-of-work nonce
+    let trace_offset = proof.context.to_bytes().len() + proof.commitments.to_bytes().len();
+    let constraint_offset = trace_offset + proof.trace_queries.to_bytes().len();
+    let positions = [
+        trace_offset + 4,
+        constraint_offset + 4,
+        proof_bytes.len() - 10,
+    ];
+
+    let mut errors = Vec::new();
+    for &position in positions.iter() {
+        let mut bytes = proof_bytes.clone();
+        bytes[position] ^= 1;
+        let error = verify(StarkProof::from_bytes(&bytes).unwrap(), result).unwrap_err();
+        for _ in 0..4 {
+            let proof = StarkProof::from_bytes(&bytes).unwrap();
+            assert_eq!(error, verify(proof, result).unwrap_err());
+        }
+        errors.push(error);
+    }
+
+    assert_eq!(VerifierError::TraceQueryDoesNotMatchCommitment, errors[0]);
+    assert_eq!(
+        VerifierError::ConstraintQueryDoesNotMatchCommitment,
+        errors[1]
+    );
+    assert!(matches!(errors[2], VerifierError::FriVerificationFailed(_)));
+}
+
+#[test]
+fn inconsistent_fri_parameters() {
+    // with LDE domain of 4096, folding factor of 4, and max remainder size of 256, the proof has
+    // 2 FRI layers and the remainder of size 256; changing the folding factor to 8 in the context
+    // implies the remainder of size 64
+    let (proof, result) = prove(512, FibTestConfig::default());
+    let context_len = proof.context.to_bytes().len();
+    let mut bytes = proof.to_bytes();
+    assert_eq!(4, bytes[context_len - 2]);
+    bytes[context_len - 2] = 8;
+
+    let proof = StarkProof::from_bytes(&bytes).unwrap();
+    assert_eq!(
+        Err(VerifierError::InconsistentFriParameters),
+        verify(proof, result)
+    );
+}
+
+#[test]
+fn verification_cost_is_bounded() {
+    // instead of timing verification, measure the amount of work the verifier does as the number
+    // of bytes it needs to process and the number of public coin operations it performs; for a
+    // trace which is 8 times longer, both must grow only logarithmically
+    let (small_size, small_ops) = measure_verification(1024);
+    let (large_size, large_ops) = measure_verification(8192);
+
+    assert!(
+        large_size < 2 * small_size,
+        "proof size grew from {} to {} bytes",
+        small_size,
+        large_size
+    );
+    assert!(
+        large_ops <= small_ops + 4,
+        "number of public coin operations grew from {} to {}",
+        small_ops,
+        large_ops
+    );
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Generates and verifies a proof for a trace of the specified length, and returns the size of
+/// the serialized proof together with the number of public coin operations performed by the
+/// verifier.
+fn measure_verification(trace_length: usize) -> (usize, usize) {
+    let (proof, result) = prove(trace_length, FibTestConfig::default());
+    let proof_size = proof.to_bytes().len();
+    let (verification, transcript) = capture_transcript(|| verify(proof, result));
+    assert!(verification.is_ok());
+    (proof_size, transcript.events().len())
+}
@@ -3,10 +3,10 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::Matrix;
+use crate::{Matrix, ProverError, StarkDomain};
 use air::{proof::Queries, EvaluationFrame};
-use crypto::{ElementHasher, MerkleTree};
-use math::StarkField;
+use crypto::{Digest, ElementHasher, MerkleTree};
+use math::{polynom, StarkField};
 use utils::collections::Vec;
 
 // TRACE COMMITMENT
@@ -74,6 +74,39 @@ impl<B: StarkField, H: ElementHasher<BaseField = B>> TraceCommitment<B, H> {
             .read_row_into(next_lde_step, frame.next_mut());
     }
 
+    // SELF-CHECK
+    // --------------------------------------------------------------------------------------------
+    /// Checks the trace LDE against the specified trace polynomials at `num_samples` positions of
+    /// the LDE domain.
+    ///
+    /// Positions are derived from the root of the commitment rather than drawn from the public
+    /// coin, and thus, the check does not affect the rest of the protocol.
+    ///
+    /// # Errors
+    /// Returns [ProverError::LdeSelfCheckFailed] for the first sampled value in the trace LDE
+    /// which differs from the evaluation of the corresponding trace polynomial.
+    pub fn check_lde(
+        &self,
+        trace_polys: &Matrix<B>,
+        domain: &StarkDomain<B>,
+        num_samples: usize,
+    ) -> Result<(), ProverError> {
+        let root = self.root();
+        for i in 0..num_samples {
+            let seed = H::merge_with_int(root, i as u64).as_bytes();
+            let seed = u64::from_le_bytes(seed[..8].try_into().unwrap());
+            let position = (seed % self.trace_len() as u64) as usize;
+
+            let x = domain.offset() * domain.lde_domain_generator().exp((position as u64).into());
+            for (column, poly) in trace_polys.columns().enumerate() {
+                if polynom::eval(poly, x) != self.trace_lde.get(column, position) {
+                    return Err(ProverError::LdeSelfCheckFailed { column, position });
+                }
+            }
+        }
+        Ok(())
+    }
+
     // QUERY TRACE
     // --------------------------------------------------------------------------------------------
     /// Returns trace table rows at the specified positions along with Merkle authentication paths
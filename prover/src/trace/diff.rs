@@ -0,0 +1,157 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::fmt;
+use math::StarkField;
+use utils::collections::Vec;
+
+// TRACE DIFF
+// ================================================================================================
+/// Differences between two execution traces.
+///
+/// A trace diff is created via [TraceTable::diff()](super::TraceTable::diff) method, and reports:
+/// * Widths and lengths of both traces, so that dimension mismatches can be detected.
+/// * Metadata of both traces, so that metadata mismatches can be detected.
+/// * The first differing cells of the traces (in row-major order) within the region covered by
+///   both traces; at most [TraceDiff::MAX_REPORTED_CELLS] cells are reported.
+/// * The total number of differing cells within that region.
+///
+/// When displayed, a trace diff lists dimension and metadata mismatches followed by differing
+/// cells, one per line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceDiff<B: StarkField> {
+    /// Widths of the left and the right traces.
+    pub width: (usize, usize),
+    /// Lengths of the left and the right traces.
+    pub length: (usize, usize),
+    /// Metadata of the left and the right traces.
+    pub meta: (Vec<u8>, Vec<u8>),
+    /// The first differing cells within the region covered by both traces.
+    pub cells: Vec<CellDiff<B>>,
+    /// Total number of differing cells within the region covered by both traces.
+    pub num_differing_cells: usize,
+}
+
+impl<B: StarkField> TraceDiff<B> {
+    /// Maximum number of differing cells reported by a trace diff.
+    pub const MAX_REPORTED_CELLS: usize = 10;
+
+    /// Returns a diff of two traces described by the specified columns and metadata.
+    pub(super) fn new(left: &[&[B]], right: &[&[B]], meta: (&[u8], &[u8])) -> Self {
+        let width = (left.len(), right.len());
+        let length = (
+            left.first().map_or(0, |column| column.len()),
+            right.first().map_or(0, |column| column.len()),
+        );
+
+        let mut cells = Vec::new();
+        let mut num_differing_cells = 0;
+        for step in 0..length.0.min(length.1) {
+            for (column, (l, r)) in left.iter().zip(right.iter()).enumerate() {
+                if l[step] != r[step] {
+                    if cells.len() < Self::MAX_REPORTED_CELLS {
+                        cells.push(CellDiff {
+                            step,
+                            column,
+                            left: l[step],
+                            right: r[step],
+                        });
+                    }
+                    num_differing_cells += 1;
+                }
+            }
+        }
+
+        TraceDiff {
+            width,
+            length,
+            meta: (meta.0.to_vec(), meta.1.to_vec()),
+            cells,
+            num_differing_cells,
+        }
+    }
+
+    /// Returns true if the traces have the same dimensions and metadata, and contain the same
+    /// values.
+    pub fn is_empty(&self) -> bool {
+        self.width.0 == self.width.1
+            && self.length.0 == self.length.1
+            && self.meta.0 == self.meta.1
+            && self.cells.is_empty()
+    }
+
+    /// Returns the first differing cell of the traces, if any.
+    pub fn first_difference(&self) -> Option<&CellDiff<B>> {
+        self.cells.first()
+    }
+}
+
+impl<B: StarkField> fmt::Display for TraceDiff<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "traces are identical");
+        }
+        if self.width.0 != self.width.1 {
+            writeln!(
+                f,
+                "trace widths differ: {} vs. {}",
+                self.width.0, self.width.1
+            )?;
+        }
+        if self.length.0 != self.length.1 {
+            writeln!(
+                f,
+                "trace lengths differ: {} vs. {}",
+                self.length.0, self.length.1
+            )?;
+        }
+        if self.meta.0 != self.meta.1 {
+            writeln!(
+                f,
+                "trace metadata differs: {:?} vs. {:?}",
+                self.meta.0, self.meta.1
+            )?;
+        }
+        if self.num_differing_cells > 0 {
+            writeln!(f, "{} cells differ", self.num_differing_cells)?;
+        }
+        for cell in self.cells.iter() {
+            writeln!(f, "{}", cell)?;
+        }
+        if self.num_differing_cells > self.cells.len() {
+            writeln!(
+                f,
+                "... and {} more",
+                self.num_differing_cells - self.cells.len()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// CELL DIFF
+// ================================================================================================
+/// A cell which holds different values in two execution traces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellDiff<B: StarkField> {
+    /// Step (row index) of the cell.
+    pub step: usize,
+    /// Column index of the cell.
+    pub column: usize,
+    /// Value of the cell in the left trace.
+    pub left: B,
+    /// Value of the cell in the right trace.
+    pub right: B,
+}
+
+impl<B: StarkField> fmt::Display for CellDiff<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "step {}, column {}: {} vs. {}",
+            self.step, self.column, self.left, self.right
+        )
+    }
+}
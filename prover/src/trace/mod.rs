@@ -6,16 +6,26 @@
 use super::Matrix;
 use air::{Air, EvaluationFrame, TraceInfo};
 use math::{polynom, FieldElement, StarkField};
+use utils::{
+    collections::{BTreeMap, Vec},
+    string::String,
+};
 
 mod poly_table;
 pub use poly_table::TracePolyTable;
 
 mod trace_table;
-pub use trace_table::{TraceTable, TraceTableFragment};
+pub use trace_table::{PaddingStrategy, TraceTable, TraceTableFragment};
 
 mod commitment;
 pub use commitment::TraceCommitment;
 
+mod streaming;
+pub use streaming::StreamingTrace;
+
+mod diff;
+pub use diff::{CellDiff, TraceDiff};
+
 #[cfg(test)]
 mod tests;
 
@@ -77,68 +87,115 @@ pub trait Trace: Sized {
     /// NOTE: this is a very expensive operation and is intended for use only in debug mode.
     fn validate<A: Air<BaseField = Self::BaseField>>(&self, air: &A) {
         // TODO: eventually, this should return errors instead of panicking
+        validate_rows(air, self.width(), self.length(), |step, row| {
+            self.read_row_into(step, row)
+        });
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
 
-        // make sure the width align; if they don't something went terribly wrong
-        assert_eq!(
-            self.width(),
-            air.trace_width(),
-            "inconsistent trace width: expected {}, but was {}",
-            self.width(),
-            air.trace_width()
-        );
-
-        // --- 1. make sure the assertions are valid ----------------------------------------------
-        for assertion in air.get_assertions() {
-            assertion.apply(self.length(), |step, value| {
+/// Checks if a trace with the specified dimensions is valid against the specified AIR, and panics
+/// if not.
+///
+/// Rows of the trace are requested via `read_row` in order starting with row 0, and each row is
+/// requested exactly once; at most two rows are held in memory at any time. This makes the check
+/// suitable both for materialized traces and for traces which are produced on demand.
+pub(crate) fn validate_rows<A, R>(air: &A, width: usize, length: usize, mut read_row: R)
+where
+    A: Air,
+    R: FnMut(usize, &mut [A::BaseField]),
+{
+    // make sure the width align; if they don't something went terribly wrong
+    assert_eq!(
+        width,
+        air.trace_width(),
+        "inconsistent trace width: expected {}, but was {}",
+        width,
+        air.trace_width()
+    );
+
+    // group asserted values by step so that they can be checked as the rows are read
+    let mut assertions = BTreeMap::<usize, Vec<(usize, A::BaseField)>>::new();
+    for assertion in air.get_assertions() {
+        assertion.apply(length, |step, value| {
+            assertions
+                .entry(step)
+                .or_default()
+                .push((assertion.register(), value));
+        });
+    }
+    let mut read_and_check_row = |step: usize, row: &mut [A::BaseField]| {
+        read_row(step, row);
+        if let Some(asserted_values) = assertions.get(&step) {
+            for &(register, value) in asserted_values.iter() {
                 assert!(
-                    value == self.get(assertion.register(), step),
+                    value == row[register],
                     "trace does not satisfy assertion trace({}, {}) == {}",
-                    assertion.register(),
+                    register,
                     step,
                     value
                 );
-            });
-        }
-
-        // --- 2. make sure this trace satisfies all transition constraints -----------------------
-
-        // collect the info needed to build periodic values for a specific step
-        let g = air.trace_domain_generator();
-        let periodic_values_polys = air.get_periodic_column_polys();
-        let mut periodic_values = vec![Self::BaseField::ZERO; periodic_values_polys.len()];
-
-        // initialize buffers to hold evaluation frames and results of constraint evaluations
-        let mut x = Self::BaseField::ONE;
-        let mut ev_frame = EvaluationFrame::new(self.width());
-        let mut evaluations = vec![Self::BaseField::ZERO; air.num_transition_constraints()];
-
-        for step in 0..self.length() - 1 {
-            // build periodic values
-            for (p, v) in periodic_values_polys.iter().zip(periodic_values.iter_mut()) {
-                let num_cycles = air.trace_length() / p.len();
-                let x = x.exp((num_cycles as u32).into());
-                *v = polynom::eval(p, x);
             }
+        }
+    };
+
+    // collect the info needed to build periodic values for a specific step, and the divisor
+    // which determines the steps on which transition constraints must hold
+    let g = air.trace_domain_generator();
+    let divisor = air.transition_constraint_divisor();
+    let periodic_values_polys = air.get_periodic_column_polys();
+    let mut periodic_values = vec![A::BaseField::ZERO; periodic_values_polys.len()];
+
+    // initialize buffers to hold evaluation frames and results of constraint evaluations
+    let mut x = A::BaseField::ONE;
+    let mut ev_frame = EvaluationFrame::new(width);
+    let mut evaluations = vec![A::BaseField::ZERO; air.num_transition_constraints()];
+
+    let mut current = vec![A::BaseField::ZERO; width];
+    let mut next = vec![A::BaseField::ZERO; width];
+    read_and_check_row(0, &mut next);
+    for step in 0..length - 1 {
+        // build periodic values
+        for (p, v) in periodic_values_polys.iter().zip(periodic_values.iter_mut()) {
+            let num_cycles = air.trace_length() / p.len();
+            let x = x.exp((num_cycles as u32).into());
+            *v = polynom::eval(p, x);
+        }
 
-            // build evaluation frame
-            self.read_row_into(step, ev_frame.current_mut());
-            self.read_row_into(step + 1, ev_frame.next_mut());
+        // build evaluation frame; the next row of the previous frame becomes the current row
+        core::mem::swap(&mut current, &mut next);
+        read_and_check_row(step + 1, &mut next);
+        ev_frame.current_mut().copy_from_slice(&current);
+        ev_frame.next_mut().copy_from_slice(&next);
 
-            // evaluate transition constraints
-            air.evaluate_transition(&ev_frame, &periodic_values, &mut evaluations);
+        // evaluate transition constraints
+        air.evaluate_transition(&ev_frame, &periodic_values, &mut evaluations);
 
-            // make sure all constraints evaluated to ZERO
+        // make sure all constraints evaluated to ZERO on the steps enforced by the transition
+        // constraint divisor
+        if divisor.enforces(x) {
             for (i, &evaluation) in evaluations.iter().enumerate() {
                 assert!(
-                    evaluation == Self::BaseField::ZERO,
+                    evaluation == A::BaseField::ZERO,
                     "transition constraint {} did not evaluate to ZERO at step {}",
-                    i,
+                    describe_transition_constraint(air, i),
                     step
                 );
             }
-
-            // update x coordinate of the domain
-            x *= g;
         }
+
+        // update x coordinate of the domain
+        x *= g;
+    }
+}
+
+/// Returns a description of the transition constraint at the specified index for use in error
+/// messages; the description includes the label of the constraint if the AIR defines one.
+fn describe_transition_constraint<A: Air>(air: &A, index: usize) -> String {
+    match air.transition_constraint_label(index) {
+        Some(label) => format!("{} ({})", index, label),
+        None => format!("{}", index),
     }
 }
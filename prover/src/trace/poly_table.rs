@@ -30,9 +30,18 @@ impl<B: StarkField> TracePolyTable<B> {
 
     /// Returns an out-of-domain evaluation frame constructed by evaluating trace polynomials
     /// for all registers at points z and z * g, where g is the generator of the trace domain.
+    ///
+    /// Each row of the frame is evaluated directly into a vector which is then moved into the
+    /// frame, and thus, only a single allocation is performed per frame row.
     pub fn get_ood_frame<E: FieldElement<BaseField = B>>(&self, z: E) -> EvaluationFrame<E> {
         let g = E::from(B::get_root_of_unity(log2(self.poly_size())));
-        EvaluationFrame::from_rows(self.evaluate_at(z), self.evaluate_at(z * g))
+
+        let mut current = Vec::with_capacity(self.num_polys());
+        self.0.evaluate_columns_at_into(z, |_| true, &mut current);
+        let mut next = Vec::with_capacity(self.num_polys());
+        self.0.evaluate_columns_at_into(z * g, |_| true, &mut next);
+
+        EvaluationFrame::from_rows(current, next)
     }
 
     /// Returns the number of trace polynomials in the table.
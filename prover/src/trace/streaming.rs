@@ -0,0 +1,142 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Matrix, Trace, TraceTable};
+use air::{Air, TraceInfo};
+use math::{fft, StarkField};
+use utils::collections::Vec;
+
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+
+// STREAMING TRACE TRAIT
+// ================================================================================================
+/// Defines an execution trace which is produced column by column on demand.
+///
+/// Unlike [Trace](super::Trace), a streaming trace is never required to hold all of its cells in
+/// memory at the same time. Instead, the prover requests an iterator over rows of each column
+/// when it needs the column's values: once to interpolate the column into a polynomial, and, in
+/// debug mode, once more to validate the trace against the AIR. Thus, a streaming trace is useful
+/// for computations whose rows can be re-generated cheaply, but for which materializing the full
+/// trace would be too expensive.
+///
+/// A streaming trace can be used to generate a proof via
+/// [Prover::prove_streaming()](super::Prover::prove_streaming) method.
+///
+/// Accessors of this trait are prefixed with `stream_` so that they do not clash with the methods
+/// of [Trace](super::Trace) on types which implement both traits, such as [TraceTable].
+pub trait StreamingTrace {
+    /// Base field for this execution trace.
+    type BaseField: StarkField;
+
+    // REQUIRED METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns number of columns in this trace.
+    fn stream_width(&self) -> usize;
+
+    /// Returns the number of rows in this trace.
+    fn stream_length(&self) -> usize;
+
+    /// Returns metadata associated with this trace.
+    fn stream_meta(&self) -> &[u8];
+
+    /// Returns an iterator over values of the specified column of this trace.
+    ///
+    /// The iterator must yield exactly [stream_length()](StreamingTrace::stream_length) values, and may be
+    /// requested multiple times for the same column.
+    fn column(&self, col_idx: usize) -> Box<dyn Iterator<Item = Self::BaseField> + '_>;
+
+    // PROVIDED METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns trace info for this trace.
+    fn stream_info(&self) -> TraceInfo {
+        TraceInfo::with_meta(
+            self.stream_width(),
+            self.stream_length(),
+            self.stream_meta().to_vec(),
+        )
+    }
+
+    /// Interpolates columns of this trace into polynomials in coefficient form and returns the
+    /// result as a [Matrix] where each column contains coefficients of a degree `length - 1`
+    /// polynomial.
+    ///
+    /// Columns are interpolated one at a time, and so values of at most one column of the trace
+    /// are held in memory in addition to the already interpolated polynomials.
+    ///
+    /// # Panics
+    /// Panics if a column iterator yields a number of values different from the trace length.
+    fn interpolate_columns(&self) -> Matrix<Self::BaseField> {
+        let inv_twiddles = fft::get_inv_twiddles::<Self::BaseField>(self.stream_length());
+        let columns = (0..self.stream_width())
+            .map(|col_idx| {
+                let mut column = self.column(col_idx).collect::<Vec<_>>();
+                assert_eq!(
+                    self.stream_length(),
+                    column.len(),
+                    "column {} must contain {} values, but contained {}",
+                    col_idx,
+                    self.stream_length(),
+                    column.len()
+                );
+                fft::interpolate_poly(&mut column, &inv_twiddles);
+                column
+            })
+            .collect();
+        Matrix::new(columns)
+    }
+
+    // VALIDATION
+    // --------------------------------------------------------------------------------------------
+    /// Checks if this trace is valid against the specified AIR, and panics if not.
+    ///
+    /// Rows of the trace are read from column iterators in a single pass, and at most two rows
+    /// are held in memory at any time.
+    ///
+    /// NOTE: this is a very expensive operation and is intended for use only in debug mode.
+    fn validate_stream<A: Air<BaseField = Self::BaseField>>(&self, air: &A) {
+        let mut columns = (0..self.stream_width())
+            .map(|col_idx| self.column(col_idx))
+            .collect::<Vec<_>>();
+        super::validate_rows(
+            air,
+            self.stream_width(),
+            self.stream_length(),
+            |step, row| {
+                for (col_idx, (column, value)) in columns.iter_mut().zip(row.iter_mut()).enumerate()
+                {
+                    *value = column
+                        .next()
+                        .unwrap_or_else(|| panic!("column {} ended before step {}", col_idx, step));
+                }
+            },
+        );
+    }
+}
+
+// TRACE TABLE IMPLEMENTATION
+// ================================================================================================
+
+impl<B: StarkField> StreamingTrace for TraceTable<B> {
+    type BaseField = B;
+
+    fn stream_width(&self) -> usize {
+        Trace::width(self)
+    }
+
+    fn stream_length(&self) -> usize {
+        Trace::length(self)
+    }
+
+    fn stream_meta(&self) -> &[u8] {
+        Trace::meta(self)
+    }
+
+    fn column(&self, col_idx: usize) -> Box<dyn Iterator<Item = B> + '_> {
+        Box::new(self.get_register(col_idx).iter().copied())
+    }
+}
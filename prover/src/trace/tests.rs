@@ -6,14 +6,18 @@
 use crate::{
     tests::{build_fib_trace, MockAir},
     trace::TracePolyTable,
-    StarkDomain, Trace, TraceCommitment,
+    CellDiff, PaddingStrategy, StarkDomain, Trace, TraceCommitment, TraceDiff, TraceTable,
 };
+use air::{EvaluationFrame, TraceInfo};
+use core::slice;
 use crypto::{hashers::Blake3_256, ElementHasher, MerkleTree};
 use math::{
     fields::f128::BaseElement, get_power_series, get_power_series_with_offset, log2, polynom,
     FieldElement, StarkField,
 };
+use rand_utils::prng_vector;
 use utils::collections::Vec;
+use utils::{ByteReader, Deserializable, Serializable, SliceReader};
 
 type Blake3 = Blake3_256<BaseElement>;
 
@@ -38,6 +42,243 @@ fn new_trace_table() {
     assert_eq!(expected, trace.get_register(1));
 }
 
+#[test]
+fn append_rows_and_finalize() {
+    // build a full trace of 16 steps and a partial trace containing only its first 8 steps
+    let full_trace = build_fib_trace(32);
+    let mut trace = build_fib_trace(16);
+
+    // append 5 more steps taken from the full trace
+    let rows = (8..13)
+        .map(|i| read_row(&full_trace, i))
+        .collect::<Vec<_>>();
+    trace.append_rows(&rows);
+    assert_eq!(13, trace.length());
+    assert!(!trace.is_finalized());
+
+    // pad the trace with the last row of the full trace
+    let padding_row = read_row(&full_trace, 15);
+    let trace = trace.finalize(&padding_row);
+    assert!(trace.is_finalized());
+    assert_eq!(16, trace.length());
+
+    for i in 0..13 {
+        assert_eq!(read_row(&full_trace, i), read_row(&trace, i));
+    }
+    for i in 13..16 {
+        assert_eq!(padding_row, read_row(&trace, i));
+    }
+}
+
+#[test]
+fn truncate_append_and_finalize() {
+    // truncating a trace and then appending the removed rows back should result in a trace
+    // identical to the original one
+    let full_trace = build_fib_trace(32);
+    let mut trace = build_fib_trace(32);
+
+    trace.truncate(5);
+    assert_eq!(5, trace.length());
+    assert_eq!(&full_trace.get_register(0)[..5], trace.get_register(0));
+
+    let rows = (5..16)
+        .map(|i| read_row(&full_trace, i))
+        .collect::<Vec<_>>();
+    trace.append_rows(&rows);
+    let trace = trace.finalize(&read_row(&full_trace, 15));
+
+    assert_eq!(full_trace.length(), trace.length());
+    assert_eq!(full_trace.get_register(0), trace.get_register(0));
+    assert_eq!(full_trace.get_register(1), trace.get_register(1));
+    assert_eq!(
+        full_trace.into_matrix().into_columns(),
+        trace.into_matrix().into_columns()
+    );
+}
+
+#[test]
+fn finalize_pads_to_min_trace_length() {
+    let full_trace = build_fib_trace(16);
+    let mut trace = build_fib_trace(16);
+    trace.truncate(3);
+
+    let trace = trace.finalize(&read_row(&full_trace, 2));
+    assert_eq!(8, trace.length());
+    for i in 3..8 {
+        assert_eq!(read_row(&full_trace, 2), read_row(&trace, i));
+    }
+}
+
+#[test]
+fn pad_records_unpadded_length() {
+    let full_trace = build_fib_trace(32);
+    let mut trace = build_fib_trace(32);
+    trace.truncate(11);
+
+    let trace = trace.pad(PaddingStrategy::RepeatLastRow);
+    assert_eq!(16, trace.length());
+    for i in 11..16 {
+        assert_eq!(read_row(&full_trace, 10), read_row(&trace, i));
+    }
+    let info = TraceInfo::with_meta(trace.width(), trace.length(), trace.meta().to_vec());
+    assert_eq!(TraceInfo::with_padding(2, 11), info);
+    assert_eq!(Some(11), info.unpadded_length());
+
+    let mut trace = build_fib_trace(32);
+    trace.truncate(11);
+    let trace = trace.pad(PaddingStrategy::Zeros);
+    assert_eq!(vec![BaseElement::ZERO; 2], read_row(&trace, 11));
+
+    // a trace which does not need to be padded is returned unchanged
+    let trace = build_fib_trace(32).pad(PaddingStrategy::Zeros);
+    assert!(trace.meta().is_empty());
+    assert_eq!(full_trace.get_register(0), trace.get_register(0));
+}
+
+#[test]
+#[should_panic(expected = "trace metadata is already set")]
+fn pad_with_meta() {
+    let mut trace = build_fib_trace(32);
+    trace.truncate(11);
+    trace.set_meta(vec![1, 2, 3]);
+    trace.pad(PaddingStrategy::Row(vec![BaseElement::ONE; 2]));
+}
+
+#[test]
+#[should_panic(expected = "row 1 must contain 2 values, but contained 3")]
+fn append_rows_wrong_width() {
+    let mut trace = build_fib_trace(16);
+    let rows = vec![vec![BaseElement::ONE; 2], vec![BaseElement::ONE; 3]];
+    trace.append_rows(&rows);
+}
+
+#[test]
+fn append_rows_in_whole_steps() {
+    let full_trace = build_fib_trace(32);
+    let mut trace = build_fib_trace(16);
+    trace.set_rows_per_step(4);
+    assert_eq!(4, trace.rows_per_step());
+
+    let rows = (8..12)
+        .map(|i| read_row(&full_trace, i))
+        .collect::<Vec<_>>();
+    trace.append_rows(&rows);
+    let trace = trace.finalize(&read_row(&full_trace, 15));
+    assert_eq!(16, trace.length());
+    assert_eq!(
+        &full_trace.get_register(0)[..12],
+        &trace.get_register(0)[..12]
+    );
+}
+
+#[test]
+#[should_panic(expected = "number of rows per step must be a power of two, but was 3")]
+fn set_rows_per_step_not_power_of_two() {
+    let mut trace = build_fib_trace(16);
+    trace.set_rows_per_step(3);
+}
+
+#[test]
+#[should_panic(
+    expected = "number of appended rows must be a multiple of 4 rows per step, but was 3"
+)]
+fn append_rows_partial_step() {
+    let mut trace = build_fib_trace(16);
+    trace.set_rows_per_step(4);
+    trace.append_rows(&vec![vec![BaseElement::ONE; 2]; 3]);
+}
+
+#[test]
+#[should_panic(
+    expected = "execution trace length must be a multiple of 4 rows per step, but was 6"
+)]
+fn finalize_partial_step() {
+    let mut trace = build_fib_trace(16);
+    trace.set_rows_per_step(4);
+    trace.truncate(6);
+    trace.finalize(&[BaseElement::ONE; 2]);
+}
+
+#[test]
+#[should_panic(expected = "padding row must contain 2 values, but contained 1")]
+fn finalize_wrong_padding_width() {
+    let mut trace = build_fib_trace(16);
+    trace.append_rows(&[vec![BaseElement::ONE; 2]]);
+    trace.finalize(&[BaseElement::ONE]);
+}
+
+#[test]
+#[should_panic(expected = "execution trace must be finalized")]
+fn into_matrix_not_finalized() {
+    let mut trace = build_fib_trace(16);
+    trace.append_rows(&[vec![BaseElement::ONE; 2]]);
+    trace.into_matrix();
+}
+
+#[test]
+fn update_cells_by_mask() {
+    // build a random execution of 16 steps where each step updates a random subset of 6 columns
+    let (width, length) = (6, 16);
+    let columns = (0..width)
+        .map(|i| prng_vector::<BaseElement>([i as u8; 32], length))
+        .collect::<Vec<_>>();
+    let values = prng_vector::<BaseElement>([7; 32], width * length);
+    let mask = prng_vector::<u64>([8; 32], width * length)
+        .into_iter()
+        .map(|v| v & 1 == 1)
+        .collect::<Vec<_>>();
+
+    // apply the updates via full-row updates
+    let mut expected = TraceTable::init(columns.clone());
+    for step in 0..length {
+        let mut row = read_row(&expected, step);
+        for column in 0..width {
+            if mask[step * width + column] {
+                row[column] = values[step * width + column];
+            }
+        }
+        expected.update_row(step, &row);
+    }
+
+    // apply the same updates via masked rows, individual cells, and windows of 4 rows
+    let mut masked = TraceTable::init(columns.clone());
+    let mut cells = TraceTable::init(columns.clone());
+    let mut windows = TraceTable::init(columns);
+    for step in 0..length {
+        let row_range = step * width..(step + 1) * width;
+        masked.update_row_masked(step, &mask[row_range.clone()], &values[row_range]);
+
+        let updates = (0..width)
+            .filter(|&column| mask[step * width + column])
+            .map(|column| (column, values[step * width + column]))
+            .collect::<Vec<_>>();
+        cells.update_cells(step, &updates);
+    }
+    for base_step in (0..length).step_by(4) {
+        let updates = (0..4 * width)
+            .filter(|&i| mask[base_step * width + i])
+            .map(|i| (i / width, i % width, values[base_step * width + i]))
+            .collect::<Vec<_>>();
+        windows.update_rows_cells(base_step, &updates);
+    }
+
+    for step in 0..length {
+        let expected_row = read_row(&expected, step);
+        assert_eq!(expected_row, read_row(&masked, step));
+        assert_eq!(expected_row, read_row(&cells, step));
+        assert_eq!(expected_row, read_row(&windows, step));
+    }
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "step 12 is out of bounds for trace of length 12")]
+fn update_cells_out_of_bounds() {
+    let mut trace = build_fib_trace(32);
+    trace.truncate(12);
+    trace.update_cells(12, &[(0, BaseElement::ONE)]);
+}
+
 #[test]
 fn extend_trace_table() {
     // build the trace and the domain
@@ -87,6 +328,181 @@ fn extend_trace_table() {
     );
 }
 
+#[test]
+fn trace_table_serialization() {
+    let mut trace = build_fib_trace(32);
+    trace.set_meta(vec![1, 2, 3]);
+
+    let bytes = trace.to_bytes();
+    let mut reader = SliceReader::new(&bytes);
+    let result = TraceTable::<BaseElement>::read_from(&mut reader).unwrap();
+    assert!(!reader.has_more_bytes());
+    assert_eq!(trace.meta(), result.meta());
+    let diff = trace.diff(&result);
+    assert!(diff.is_empty(), "deserialized trace differs:\n{}", diff);
+
+    // a trace serialized with a different format version is rejected
+    let mut bytes = bytes;
+    bytes[0] += 1;
+    assert!(TraceTable::<BaseElement>::read_from(&mut SliceReader::new(&bytes)).is_err());
+
+    // a truncated trace is rejected
+    bytes[0] -= 1;
+    bytes.pop();
+    assert!(TraceTable::<BaseElement>::read_from(&mut SliceReader::new(&bytes)).is_err());
+}
+
+#[test]
+#[should_panic(expected = "execution trace must be finalized before it can be serialized")]
+fn trace_table_serialization_not_finalized() {
+    let mut trace = build_fib_trace(32);
+    trace.truncate(5);
+    trace.to_bytes();
+}
+
+#[test]
+fn interpolate_streaming_trace() {
+    let trace_length = 8;
+    let trace = build_fib_trace(trace_length * 2);
+
+    // interpolating columns one at a time yields the same polynomials as the materialized trace
+    let trace_polys = crate::StreamingTrace::interpolate_columns(&trace);
+    let expected = trace.into_matrix().interpolate_columns_into();
+    assert_eq!(expected.get_column(0), trace_polys.get_column(0));
+    assert_eq!(expected.get_column(1), trace_polys.get_column(1));
+}
+
+#[test]
+fn get_ood_frame() {
+    let trace_length = 8;
+    let trace = build_fib_trace(trace_length * 2);
+    let trace_polys = TracePolyTable::new(trace.into_matrix().interpolate_columns_into());
+
+    let z = BaseElement::new(123);
+    let g = BaseElement::get_root_of_unity(log2(trace_length));
+    let frame = trace_polys.get_ood_frame(z);
+
+    // the frame rows must be evaluations of all trace polynomials at z and z * g
+    assert_eq!(trace_polys.evaluate_at(z), frame.current());
+    assert_eq!(trace_polys.evaluate_at(z * g), frame.next());
+    assert_eq!(
+        polynom::eval(trace_polys.get_poly(1), z * g),
+        frame.next()[1]
+    );
+
+    // the rows can be moved out of the frame and back into it
+    let (current, next) = frame.clone().into_rows();
+    assert_eq!(frame.current(), current);
+    assert_eq!(frame.next(), next);
+    let frame = EvaluationFrame::from_rows(current, next);
+    assert_eq!(trace_polys.evaluate_at(z), frame.current());
+}
+
+#[test]
+fn validate_canonical_trace() {
+    let mut trace = build_fib_trace(32);
+    assert_eq!(Ok(()), trace.validate_canonical());
+
+    // a non-canonical element is reported at its exact position, even when a later column
+    // contains a non-canonical element at an earlier step
+    trace.set(0, 11, non_canonical_element());
+    trace.set(1, 5, non_canonical_element());
+    assert_eq!(Err((5, 1)), trace.validate_canonical());
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "non-canonical field element at step 3, column 1")]
+fn update_row_non_canonical() {
+    let mut trace = build_fib_trace(32);
+    trace.update_row(3, &[BaseElement::ONE, non_canonical_element()]);
+}
+
+#[test]
+fn diff_identical_traces() {
+    let trace = build_fib_trace(16);
+    let diff = trace.diff(&build_fib_trace(16));
+    assert!(diff.is_empty());
+    assert_eq!(0, diff.num_differing_cells);
+    assert_eq!(None, diff.first_difference());
+    assert_eq!("traces are identical", diff.to_string());
+}
+
+#[test]
+fn diff_traces_with_different_cell() {
+    let left = build_fib_trace(16);
+    let mut right = build_fib_trace(16);
+    right.set(1, 5, BaseElement::new(42));
+
+    let diff = left.diff(&right);
+    assert!(!diff.is_empty());
+    assert_eq!((2, 2), diff.width);
+    assert_eq!((8, 8), diff.length);
+    assert_eq!(1, diff.num_differing_cells);
+
+    let expected = CellDiff {
+        step: 5,
+        column: 1,
+        left: left.get(1, 5),
+        right: BaseElement::new(42),
+    };
+    assert_eq!(Some(&expected), diff.first_difference());
+    assert!(diff.to_string().contains("step 5, column 1"));
+}
+
+#[test]
+fn diff_traces_with_different_dimensions() {
+    let left = build_fib_trace(16);
+    let mut right = build_fib_trace(32);
+
+    // overlapping cells are the same, but lengths differ
+    let diff = left.diff(&right);
+    assert!(!diff.is_empty());
+    assert_eq!((8, 16), diff.length);
+    assert_eq!(0, diff.num_differing_cells);
+    assert!(diff.to_string().contains("trace lengths differ: 8 vs. 16"));
+
+    // only the first differences are reported, but all differences are counted
+    for step in 0..8 {
+        right.set(0, step, BaseElement::ZERO);
+        right.set(1, step, BaseElement::ZERO);
+    }
+    let diff = left.diff(&right);
+    assert_eq!(16, diff.num_differing_cells);
+    assert_eq!(
+        TraceDiff::<BaseElement>::MAX_REPORTED_CELLS,
+        diff.cells.len()
+    );
+    assert_eq!(0, diff.first_difference().unwrap().step);
+
+    // widths differ
+    let other = TraceTable::init(vec![left.get_register(0).to_vec()]);
+    let diff = left.diff(&other);
+    assert!(!diff.is_empty());
+    assert_eq!((2, 1), diff.width);
+    assert_eq!(0, diff.num_differing_cells);
+    assert!(diff.to_string().contains("trace widths differ: 2 vs. 1"));
+}
+
+#[test]
+fn diff_traces_with_different_meta() {
+    let left = build_fib_trace(16);
+    let mut right = build_fib_trace(16);
+    right.set_meta(vec![1, 2]);
+
+    // all cells are the same, but metadata differs
+    let diff = left.diff(&right);
+    assert!(!diff.is_empty());
+    assert_eq!((vec![], vec![1, 2]), diff.meta);
+    assert_eq!(0, diff.num_differing_cells);
+    assert!(diff
+        .to_string()
+        .contains("trace metadata differs: [] vs. [1, 2]"));
+
+    right.set_meta(vec![]);
+    assert!(left.diff(&right).is_empty());
+}
+
 #[test]
 fn commit_trace_table() {
     // build the trade and the domain
@@ -118,10 +534,52 @@ fn commit_trace_table() {
     assert_eq!(*expected_tree.root(), trace_comm.root())
 }
 
+#[test]
+fn read_frames_from_trace_commitment() {
+    // with blowup of 1 the committed rows are the rows of the raw trace; with larger blowup
+    // factors, the next row of a frame is `blowup` rows away; in both cases frames read at the
+    // end of the table wrap around to its beginning
+    let trace = build_fib_trace(32);
+    let rows = (0..trace.length())
+        .map(|i| read_row(&trace, i))
+        .collect::<Vec<_>>();
+    let num_rows = rows.len();
+
+    for blowup in [1, 2, 4] {
+        let table = build_fib_trace(32).into_matrix();
+        let tree = table.commit_to_rows::<Blake3>();
+        let trace_comm = TraceCommitment::new(table, tree, blowup);
+
+        let mut frame = EvaluationFrame::new(trace_comm.trace_width());
+        for step in 0..num_rows {
+            trace_comm.read_frame_into(step, &mut frame);
+            assert_eq!(rows[step], frame.current());
+            assert_eq!(rows[(step + blowup) % num_rows], frame.next());
+        }
+    }
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
+fn read_row<T: Trace>(trace: &T, step: usize) -> Vec<T::BaseField> {
+    let mut row = vec![T::BaseField::ZERO; trace.width()];
+    trace.read_row_into(step, &mut row);
+    row
+}
+
 fn build_lde_domain<B: StarkField>(domain_size: usize) -> Vec<B> {
     let g = B::get_root_of_unity(log2(domain_size));
     get_power_series_with_offset(g, B::GENERATOR, domain_size)
 }
+
+/// Returns a field element with an internal representation which is greater than the field
+/// modulus; such elements cannot be created via safe constructors.
+fn non_canonical_element() -> BaseElement {
+    let raw = [u128::MAX];
+    let bytes =
+        unsafe { slice::from_raw_parts(raw.as_ptr() as *const u8, BaseElement::ELEMENT_BYTES) };
+    let element = unsafe { BaseElement::bytes_as_elements(bytes) }.unwrap()[0];
+    assert!(element.as_int() >= BaseElement::MODULUS);
+    element
+}
@@ -3,14 +3,21 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{Matrix, Trace};
+use super::{Matrix, Trace, TraceDiff};
 use air::TraceInfo;
+use core::cmp;
 use math::{log2, StarkField};
-use utils::{collections::Vec, uninit_vector};
+use utils::{
+    collections::Vec, uninit_vector, ByteReader, ByteWriter, Deserializable, DeserializationError,
+    Serializable,
+};
 
 #[cfg(not(feature = "concurrent"))]
 use utils::collections::vec;
 
+#[cfg(not(feature = "std"))]
+use alloc::string::ToString;
+
 #[cfg(feature = "concurrent")]
 use utils::{iterators::*, rayon};
 
@@ -19,6 +26,10 @@ use utils::{iterators::*, rayon};
 
 const MIN_FRAGMENT_LENGTH: usize = 2;
 
+/// Version of the serialized trace table format; serialized traces with a different version are
+/// rejected during deserialization.
+const SERIALIZATION_VERSION: u8 = 1;
+
 // TRACE TABLE
 // ================================================================================================
 /// A concrete implementation of the [Trace] trait.
@@ -59,9 +70,28 @@ const MIN_FRAGMENT_LENGTH: usize = 2;
 /// [fill()](TraceTableFragment::fill) method to fill all fragments with data in parallel.
 /// The semantics of the fragment's [TraceTableFragment::fill()] method are identical to the
 /// semantics of the [TraceTable::fill()] method.
+///
+/// # Incremental trace construction
+/// An existing trace table can also be extended with additional rows via the
+/// [append_rows()](TraceTable::append_rows) method, or shortened via the
+/// [truncate()](TraceTable::truncate) method. After either of these methods is called, the length
+/// of the table may no longer be a power of two. Such a table cannot be used to generate a proof
+/// until it is padded to the next power of two via the [finalize()](TraceTable::finalize) method.
+///
+/// If a single step of the computation spans several rows of the trace, the number of rows per
+/// step can be declared via [set_rows_per_step()](TraceTable::set_rows_per_step). In such cases,
+/// rows can be appended only in whole steps, and the trace can be finalized only if it contains a
+/// whole number of steps.
+///
+/// # Serialization
+/// A finalized trace table can be serialized into a sequence of bytes, and later deserialized to
+/// generate a proof without rebuilding the trace (e.g., to resume an interrupted proving job).
+/// The serialized trace includes its metadata, and is prefixed with a format version.
 pub struct TraceTable<B: StarkField> {
     trace: Matrix<B>,
     meta: Vec<u8>,
+    num_rows: usize,
+    rows_per_step: usize,
 }
 
 impl<B: StarkField> TraceTable<B> {
@@ -132,6 +162,8 @@ impl<B: StarkField> TraceTable<B> {
         Self {
             trace: Matrix::new(columns),
             meta,
+            num_rows: length,
+            rows_per_step: 1,
         }
     }
 
@@ -178,10 +210,19 @@ impl<B: StarkField> TraceTable<B> {
                 "all columns traces must have the same length"
             );
         }
+        if cfg!(debug_assertions) {
+            for (column, values) in columns.iter().enumerate() {
+                for (step, &value) in values.iter().enumerate() {
+                    Self::debug_check_canonical(step, column, value);
+                }
+            }
+        }
 
         Self {
             trace: Matrix::new(columns),
             meta: vec![],
+            num_rows: trace_length,
+            rows_per_step: 1,
         }
     }
 
@@ -213,6 +254,23 @@ impl<B: StarkField> TraceTable<B> {
         self.meta = meta
     }
 
+    /// Declares the number of rows occupied by a single step of the computation.
+    ///
+    /// This is used to validate incremental trace construction: rows passed to
+    /// [append_rows()](TraceTable::append_rows) and the length of the trace passed to
+    /// [finalize()](TraceTable::finalize) must be multiples of `rows_per_step`.
+    ///
+    /// # Panics
+    /// Panics if `rows_per_step` is zero or is not a power of two.
+    pub fn set_rows_per_step(&mut self, rows_per_step: usize) {
+        assert!(
+            rows_per_step.is_power_of_two(),
+            "number of rows per step must be a power of two, but was {}",
+            rows_per_step
+        );
+        self.rows_per_step = rows_per_step;
+    }
+
     /// Fill all rows in the execution trace.
     ///
     /// The rows are filled by executing the provided closures as follows:
@@ -240,10 +298,247 @@ impl<B: StarkField> TraceTable<B> {
     }
 
     /// Updates a single row in the execution trace with provided data.
+    ///
+    /// # Panics
+    /// Panics in debug mode if any of the values in `state` is not a canonical field element.
     pub fn update_row(&mut self, step: usize, state: &[B]) {
+        for (column, &value) in state.iter().enumerate() {
+            Self::debug_check_canonical(step, column, value);
+        }
         self.trace.update_row(step, state);
     }
 
+    /// Updates the specified cells in a single row of the execution trace.
+    ///
+    /// `cells` is a list of `(column, value)` tuples; cells of the row which are not in the list
+    /// are left unchanged.
+    ///
+    /// # Panics
+    /// Panics if either `step` or any of the columns are out of bounds for this execution trace.
+    /// Bounds of `step` are checked against the current length of the trace in debug mode only.
+    pub fn update_cells(&mut self, step: usize, cells: &[(usize, B)]) {
+        self.debug_check_step(step);
+        for &(column, value) in cells {
+            self.debug_check_column(column);
+            Self::debug_check_canonical(step, column, value);
+            self.trace.set(column, step, value);
+        }
+    }
+
+    /// Updates cells of a single row in the execution trace for which `mask` is set to true.
+    ///
+    /// Values for the updated cells are taken from the same positions in `values`; cells for
+    /// which `mask` is false are left unchanged.
+    ///
+    /// # Panics
+    /// Panics if `step` is out of bounds for this execution trace, or if, in debug mode, either
+    /// `mask` or `values` do not have the same length as the width of this trace.
+    pub fn update_row_masked(&mut self, step: usize, mask: &[bool], values: &[B]) {
+        self.debug_check_step(step);
+        debug_assert_eq!(
+            self.trace.num_cols(),
+            mask.len(),
+            "mask length must be equal to trace width {}, but was {}",
+            self.trace.num_cols(),
+            mask.len()
+        );
+        debug_assert_eq!(
+            self.trace.num_cols(),
+            values.len(),
+            "number of values must be equal to trace width {}, but was {}",
+            self.trace.num_cols(),
+            values.len()
+        );
+        for (column, (&update, &value)) in mask.iter().zip(values).enumerate() {
+            if update {
+                Self::debug_check_canonical(step, column, value);
+                self.trace.set(column, step, value);
+            }
+        }
+    }
+
+    /// Updates the specified cells in a window of rows starting at `base_step`.
+    ///
+    /// `updates` is a list of `(row_offset, column, value)` tuples where `row_offset` is relative
+    /// to `base_step`. This is useful for filling all cells touched by a single step of a
+    /// computation which spans several rows of the trace.
+    ///
+    /// # Panics
+    /// Panics if any of the updated cells is out of bounds for this execution trace. Bounds of
+    /// updated steps are checked against the current length of the trace in debug mode only.
+    pub fn update_rows_cells(&mut self, base_step: usize, updates: &[(usize, usize, B)]) {
+        for &(row_offset, column, value) in updates {
+            let step = base_step + row_offset;
+            self.debug_check_step(step);
+            self.debug_check_column(column);
+            Self::debug_check_canonical(step, column, value);
+            self.trace.set(column, step, value);
+        }
+    }
+
+    // INCREMENTAL CONSTRUCTION
+    // --------------------------------------------------------------------------------------------
+
+    /// Appends the provided rows to the end of this execution trace.
+    ///
+    /// After this method is called, the length of the trace may no longer be a power of two. In
+    /// such cases, [finalize()](TraceTable::finalize) must be called before the trace can be used
+    /// to generate a proof.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * Number of `rows` is not a multiple of the number of rows per step declared for this
+    ///   trace.
+    /// * Number of values in any of the `rows` is not equal to the width of this trace.
+    /// * The length of the resulting trace would exceed the biggest multiplicative subgroup in
+    ///   the field `B`.
+    pub fn append_rows(&mut self, rows: &[Vec<B>]) {
+        assert!(
+            rows.len() % self.rows_per_step == 0,
+            "number of appended rows must be a multiple of {} rows per step, but was {}",
+            self.rows_per_step,
+            rows.len()
+        );
+        for (i, row) in rows.iter().enumerate() {
+            assert_eq!(
+                row.len(),
+                self.width(),
+                "row {} must contain {} values, but contained {}",
+                i,
+                self.width(),
+                row.len()
+            );
+        }
+
+        let new_length = self.num_rows + rows.len();
+        if new_length > self.trace.num_rows() {
+            let capacity = new_length.next_power_of_two();
+            assert!(
+                log2(capacity) as u32 <= B::TWO_ADICITY,
+                "execution trace length cannot exceed 2^{} steps, but was 2^{}",
+                B::TWO_ADICITY,
+                log2(capacity)
+            );
+            self.trace.resize_rows(capacity);
+        }
+
+        for (i, row) in rows.iter().enumerate() {
+            for (column, &value) in row.iter().enumerate() {
+                Self::debug_check_canonical(self.num_rows + i, column, value);
+            }
+            self.trace.update_row(self.num_rows + i, row);
+        }
+        self.num_rows = new_length;
+    }
+
+    /// Shortens this execution trace to the first `length` rows.
+    ///
+    /// After this method is called, the length of the trace may no longer be a power of two. In
+    /// such cases, [finalize()](TraceTable::finalize) must be called before the trace can be used
+    /// to generate a proof.
+    ///
+    /// # Panics
+    /// Panics if `length` is zero or is greater than the current length of this trace.
+    pub fn truncate(&mut self, length: usize) {
+        assert!(
+            length > 0,
+            "execution trace cannot be truncated to zero rows"
+        );
+        assert!(
+            length <= self.num_rows,
+            "execution trace cannot be truncated to {} rows as it contains only {} rows",
+            length,
+            self.num_rows
+        );
+        self.num_rows = length;
+    }
+
+    /// Pads this execution trace with copies of `padding_row` until its length becomes the
+    /// smallest power of two (but no less than 8) which is greater than or equal to the current
+    /// length, and returns the result.
+    ///
+    /// If the length of the trace is already a power of two greater than or equal to 8, the
+    /// trace is returned unchanged.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The length of this trace is not a multiple of the number of rows per step declared for
+    ///   this trace.
+    /// * The number of values in `padding_row` is not equal to the width of this trace.
+    pub fn finalize(mut self, padding_row: &[B]) -> Self {
+        assert!(
+            self.num_rows % self.rows_per_step == 0,
+            "execution trace length must be a multiple of {} rows per step, but was {}",
+            self.rows_per_step,
+            self.num_rows
+        );
+        assert_eq!(
+            padding_row.len(),
+            self.width(),
+            "padding row must contain {} values, but contained {}",
+            self.width(),
+            padding_row.len()
+        );
+
+        let padded_length = cmp::max(
+            self.num_rows.next_power_of_two(),
+            TraceInfo::MIN_TRACE_LENGTH,
+        );
+        if padded_length != self.trace.num_rows() {
+            self.trace.resize_rows(padded_length);
+        }
+        for step in self.num_rows..padded_length {
+            self.trace.update_row(step, padding_row);
+        }
+        self.num_rows = padded_length;
+
+        self
+    }
+
+    /// Pads this execution trace to the smallest power of two (but no less than 8) which is greater
+    /// than or equal to the current length using the specified padding strategy, records the
+    /// unpadded length in trace metadata, and returns the result.
+    ///
+    /// The unpadded length is recorded in the same way as it is done by
+    /// [TraceInfo::with_padding()]; thus, an AIR can retrieve it via
+    /// [TraceInfo::unpadded_length()] and adjust its constraints accordingly (e.g., by using
+    /// [ConstraintDivisor::from_transition_with_padding()](air::ConstraintDivisor::from_transition_with_padding)
+    /// as the transition constraint divisor).
+    ///
+    /// If the length of the trace is already a power of two greater than or equal to 8, the
+    /// trace is returned unchanged, and its metadata remains empty.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The trace needs to be padded, but its metadata is not empty.
+    /// * The trace needs to be padded, but its length is not a multiple of the number of rows
+    ///   per step declared for this trace.
+    /// * The padding row specified via [PaddingStrategy::Row] contains a number of values
+    ///   different from the width of this trace.
+    pub fn pad(mut self, strategy: PaddingStrategy<B>) -> Self {
+        if self.is_finalized() {
+            return self;
+        }
+        assert!(
+            self.meta.is_empty(),
+            "cannot record unpadded length of the trace because trace metadata is already set"
+        );
+
+        let padding_row = match strategy {
+            PaddingStrategy::RepeatLastRow => {
+                let mut row = vec![B::ZERO; self.width()];
+                self.read_row_into(self.num_rows - 1, &mut row);
+                row
+            }
+            PaddingStrategy::Zeros => vec![B::ZERO; self.width()],
+            PaddingStrategy::Row(row) => row,
+        };
+
+        let info = TraceInfo::with_padding(self.width(), self.num_rows);
+        self.meta = info.meta().to_vec();
+        self.finalize(&padding_row)
+    }
+
     // FRAGMENTS
     // --------------------------------------------------------------------------------------------
 
@@ -279,6 +574,10 @@ impl<B: StarkField> TraceTable<B> {
     /// Returns a vector of trace fragments each covering the number of steps specified by the
     /// `fragment_length` parameter.
     fn build_fragments(&mut self, fragment_length: usize) -> Vec<TraceTableFragment<B>> {
+        assert!(
+            self.is_finalized(),
+            "execution trace must be finalized before it can be broken into fragments"
+        );
         assert!(
             fragment_length >= MIN_FRAGMENT_LENGTH,
             "fragment length must be at least {}, but was {}",
@@ -320,7 +619,95 @@ impl<B: StarkField> TraceTable<B> {
 
     /// Returns the entire register trace for the register at the specified index.
     pub fn get_register(&self, idx: usize) -> &[B] {
-        self.trace.get_column(idx)
+        &self.trace.get_column(idx)[..self.num_rows]
+    }
+
+    /// Returns the number of rows occupied by a single step of the computation; this is 1 unless
+    /// declared otherwise via [set_rows_per_step()](TraceTable::set_rows_per_step).
+    pub fn rows_per_step(&self) -> usize {
+        self.rows_per_step
+    }
+
+    /// Returns true if the length of this execution trace is a valid trace length; this is
+    /// false only for traces which were extended or truncated but not yet finalized.
+    pub fn is_finalized(&self) -> bool {
+        self.num_rows == self.trace.num_rows() && self.num_rows >= TraceInfo::MIN_TRACE_LENGTH
+    }
+
+    /// Checks whether all cells of this execution trace contain canonical field elements.
+    ///
+    /// Field elements created via safe constructors are always canonical; however, elements
+    /// obtained by reinterpreting raw memory (e.g., via `FieldElement::bytes_as_elements()`) may
+    /// not be. Such elements propagate silently and result in proofs which fail verification.
+    /// Mutators of the trace check their inputs in debug mode only; this method can be used to
+    /// check the entire trace in release mode as well.
+    ///
+    /// # Errors
+    /// Returns `(step, column)` of the first cell (in row-major order) which contains a
+    /// non-canonical field element.
+    pub fn validate_canonical(&self) -> Result<(), (usize, usize)> {
+        (0..self.width())
+            .filter_map(|column| {
+                self.get_register(column)
+                    .iter()
+                    .position(|&value| !value.is_canonical())
+                    .map(|step| (step, column))
+            })
+            .min()
+            .map_or(Ok(()), Err)
+    }
+
+    // COMPARISON
+    // --------------------------------------------------------------------------------------------
+
+    /// Compares this execution trace with the `other` trace, and returns a description of their
+    /// differences.
+    ///
+    /// This is intended for debugging: for example, to find the first step at which two traces
+    /// of the same program diverge. Traces of different dimensions can be compared as well; in
+    /// such a case, cells are compared only within the region covered by both traces. Metadata of
+    /// the traces is compared as well.
+    pub fn diff(&self, other: &TraceTable<B>) -> TraceDiff<B> {
+        let left = (0..self.width())
+            .map(|i| self.get_register(i))
+            .collect::<Vec<_>>();
+        let right = (0..other.width())
+            .map(|i| other.get_register(i))
+            .collect::<Vec<_>>();
+        TraceDiff::new(&left, &right, (&self.meta, &other.meta))
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Makes sure the specified step is within the current length of this execution trace.
+    fn debug_check_step(&self, step: usize) {
+        debug_assert!(
+            step < self.num_rows,
+            "step {} is out of bounds for trace of length {}",
+            step,
+            self.num_rows
+        );
+    }
+
+    /// Makes sure the specified column is within the width of this execution trace.
+    fn debug_check_column(&self, column: usize) {
+        debug_assert!(
+            column < self.trace.num_cols(),
+            "column {} is out of bounds for trace of width {}",
+            column,
+            self.trace.num_cols()
+        );
+    }
+
+    /// Makes sure the value written into the specified cell is a canonical field element.
+    fn debug_check_canonical(step: usize, column: usize, value: B) {
+        debug_assert!(
+            value.is_canonical(),
+            "non-canonical field element at step {}, column {}",
+            step,
+            column
+        );
     }
 }
 
@@ -335,7 +722,7 @@ impl<B: StarkField> Trace for TraceTable<B> {
     }
 
     fn length(&self) -> usize {
-        self.trace.num_rows()
+        self.num_rows
     }
 
     fn meta(&self) -> &[u8] {
@@ -351,10 +738,95 @@ impl<B: StarkField> Trace for TraceTable<B> {
     }
 
     fn into_matrix(self) -> Matrix<B> {
+        assert!(
+            self.is_finalized(),
+            "execution trace must be finalized before it can be converted into a matrix"
+        );
         self.trace
     }
 }
 
+// SERIALIZATION / DESERIALIZATION
+// ================================================================================================
+
+impl<B: StarkField> Serializable for TraceTable<B> {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    ///
+    /// # Panics
+    /// Panics if this trace table has not been finalized.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        assert!(
+            self.is_finalized(),
+            "execution trace must be finalized before it can be serialized"
+        );
+        target.write_u8(SERIALIZATION_VERSION);
+        target.write_u8(self.width() as u8);
+        target.write_u8(log2(self.length()) as u8);
+        target.write_u16(self.meta.len() as u16);
+        target.write_u8_slice(&self.meta);
+        for col_idx in 0..self.width() {
+            B::write_batch_into(self.get_register(col_idx), target);
+        }
+    }
+}
+
+impl<B: StarkField> Deserializable for TraceTable<B> {
+    /// Reads a trace table from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid trace table could not be read from the specified `source`,
+    /// or if the trace was serialized using a different format version.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let version = source.read_u8()?;
+        if version != SERIALIZATION_VERSION {
+            return Err(DeserializationError::InvalidValue(format!(
+                "unsupported execution trace version {}; expected version {}",
+                version, SERIALIZATION_VERSION
+            )));
+        }
+
+        let width = source.read_u8()? as usize;
+        if width == 0 {
+            return Err(DeserializationError::InvalidValue(
+                "execution trace must consist of at least one column".to_string(),
+            ));
+        }
+        let length_log2 = source.read_u8()? as u32;
+        if length_log2 > B::TWO_ADICITY || (1 << length_log2) < TraceInfo::MIN_TRACE_LENGTH {
+            return Err(DeserializationError::InvalidValue(format!(
+                "invalid execution trace length 2^{}",
+                length_log2
+            )));
+        }
+        let length = 1 << length_log2;
+
+        let num_meta_bytes = source.read_u16()? as usize;
+        let meta = source.read_u8_vec(num_meta_bytes)?;
+
+        let mut columns = Vec::with_capacity(width);
+        for _ in 0..width {
+            columns.push(B::read_batch_from(source, length)?);
+        }
+
+        let mut trace = TraceTable::init(columns);
+        trace.set_meta(meta);
+        Ok(trace)
+    }
+}
+
+// PADDING STRATEGY
+// ================================================================================================
+/// Describes how rows appended by [TraceTable::pad()] are filled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaddingStrategy<B: StarkField> {
+    /// Each padding row is a copy of the last row of the unpadded trace.
+    RepeatLastRow,
+    /// All values in padding rows are set to zero.
+    Zeros,
+    /// Each padding row is a copy of the specified row.
+    Row(Vec<B>),
+}
+
 // TRACE FRAGMENTS
 // ================================================================================================
 /// A set of consecutive rows of an execution trace.
@@ -428,8 +900,12 @@ impl<'a, B: StarkField> TraceTableFragment<'a, B> {
     }
 
     /// Updates a single row in the fragment with provided data.
+    ///
+    /// # Panics
+    /// Panics in debug mode if any of the values in `row_data` is not a canonical field element.
     pub fn update_row(&mut self, row_idx: usize, row_data: &[B]) {
-        for (column, &value) in self.data.iter_mut().zip(row_data) {
+        for (i, (column, &value)) in self.data.iter_mut().zip(row_data).enumerate() {
+            TraceTable::debug_check_canonical(self.offset + row_idx, i, value);
             column[row_idx] = value;
         }
     }
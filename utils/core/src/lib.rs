@@ -25,6 +25,8 @@ pub mod iterators;
 mod errors;
 pub use errors::DeserializationError;
 
+pub use derive::{Deserializable, Serializable};
+
 #[cfg(test)]
 mod tests;
 
@@ -0,0 +1,322 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! This crate contains derive macros for `Serializable` and `Deserializable` traits defined in
+//! the `winter-utils` crate.
+//!
+//! The macros are re-exported from `winter-utils` and should be used via that crate (or via any
+//! other crate which re-exports the traits from `winter-utils`, e.g., `winterfell`). The macros
+//! can be applied only to structs, and all fields of a struct must be of the following types:
+//!
+//! * Types which implement `Serializable` and `Deserializable` traits (e.g., field elements).
+//! * Fixed-size arrays of supported types; array lengths must be integer literals.
+//! * `Vec` of supported types; vectors are serialized with a `u64` length prefix.
+//! * `u8`, `u16`, `u32`, `u64` integers, and `usize` integers which are serialized as `u64`.
+//!
+//! Fields are serialized and deserialized in the order in which they are declared.
+//!
+//! Generated code refers to the serialization traits via `winter_utils` crate. If the traits
+//! are imported via a different crate, path to that crate can be specified using
+//! `#[winter_utils(crate = "...")]` attribute.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, Data, DeriveInput, Error, Expr, ExprLit, Fields, GenericArgument, Lit, Meta,
+    NestedMeta, Path, PathArguments, Type,
+};
+
+// DERIVE MACROS
+// ================================================================================================
+
+/// Derives `Serializable` trait for a struct.
+///
+/// Fields of the struct are written into the target in the order of their declaration.
+#[proc_macro_derive(Serializable, attributes(winter_utils))]
+pub fn derive_serializable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_serializable(&input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+/// Derives `Deserializable` trait for a struct.
+///
+/// Fields of the struct are read from the source in the order of their declaration.
+#[proc_macro_derive(Deserializable, attributes(winter_utils))]
+pub fn derive_deserializable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_deserializable(&input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+// EXPANSION
+// ================================================================================================
+
+fn expand_serializable(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let krate = get_crate_path(input)?;
+    let fields = get_struct_fields(input, "Serializable")?;
+
+    let mut writes = Vec::with_capacity(fields.len());
+    for (i, field) in fields.iter().enumerate() {
+        let field_type = FieldType::parse(&field.ty)?;
+        let value = match &field.ident {
+            Some(ident) => quote!(&self.#ident),
+            None => {
+                let index = syn::Index::from(i);
+                quote!(&self.#index)
+            }
+        };
+        writes.push(field_type.write(&krate, value));
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #krate::Serializable for #name #ty_generics #where_clause {
+            fn write_into<W: #krate::ByteWriter>(&self, target: &mut W) {
+                #(#writes)*
+            }
+        }
+    })
+}
+
+fn expand_deserializable(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let krate = get_crate_path(input)?;
+    let fields = get_struct_fields(input, "Deserializable")?;
+
+    let mut reads = Vec::with_capacity(fields.len());
+    for field in fields.iter() {
+        let field_type = FieldType::parse(&field.ty)?;
+        let value = field_type.read(&krate);
+        reads.push(match &field.ident {
+            Some(ident) => quote!(#ident: #value),
+            None => value,
+        });
+    }
+
+    let result = match fields {
+        Fields::Named(_) => quote!(Self { #(#reads),* }),
+        Fields::Unnamed(_) => quote!(Self(#(#reads),*)),
+        Fields::Unit => quote!(Self),
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #krate::Deserializable for #name #ty_generics #where_clause {
+            fn read_from<R: #krate::ByteReader>(
+                source: &mut R,
+            ) -> ::core::result::Result<Self, #krate::DeserializationError> {
+                Ok(#result)
+            }
+        }
+    })
+}
+
+// FIELD TYPES
+// ================================================================================================
+
+/// Describes how a single field of a struct is serialized.
+enum FieldType<'a> {
+    /// A type which implements serialization traits on its own.
+    Element(&'a Type),
+    /// A primitive unsigned integer.
+    Integer(IntegerType),
+    /// A fixed-size array of the specified number of values of the inner type.
+    Array(Box<FieldType<'a>>, usize),
+    /// A vector of values of the inner type; the vector is serialized with a length prefix.
+    Vec(Box<FieldType<'a>>),
+}
+
+#[derive(Clone, Copy)]
+enum IntegerType {
+    U8,
+    U16,
+    U32,
+    U64,
+    Usize,
+}
+
+impl<'a> FieldType<'a> {
+    /// Determines how a field of the specified type should be serialized.
+    fn parse(ty: &'a Type) -> Result<Self, Error> {
+        match ty {
+            Type::Array(array) => {
+                let inner = Self::parse(&array.elem)?;
+                let len = match &array.len {
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Int(len), ..
+                    }) => len.base10_parse()?,
+                    len => {
+                        return Err(Error::new_spanned(
+                            len,
+                            "array length must be an integer literal",
+                        ))
+                    }
+                };
+                Ok(Self::Array(Box::new(inner), len))
+            }
+            Type::Group(group) => Self::parse(&group.elem),
+            Type::Paren(paren) => Self::parse(&paren.elem),
+            Type::Path(type_path) if type_path.qself.is_none() => {
+                let path = &type_path.path;
+                if let Some(ident) = path.get_ident() {
+                    match ident.to_string().as_str() {
+                        "u8" => return Ok(Self::Integer(IntegerType::U8)),
+                        "u16" => return Ok(Self::Integer(IntegerType::U16)),
+                        "u32" => return Ok(Self::Integer(IntegerType::U32)),
+                        "u64" => return Ok(Self::Integer(IntegerType::U64)),
+                        "usize" => return Ok(Self::Integer(IntegerType::Usize)),
+                        "u128" | "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "bool"
+                        | "char" | "f32" | "f64" | "str" | "String" => {
+                            return Err(unsupported_type_error(ty))
+                        }
+                        _ => (),
+                    }
+                }
+
+                let last = path.segments.last().expect("empty type path");
+                if last.ident == "Vec" {
+                    if let PathArguments::AngleBracketed(args) = &last.arguments {
+                        if let Some(GenericArgument::Type(inner)) = args.args.first() {
+                            if args.args.len() == 1 {
+                                let inner = Self::parse(inner)?;
+                                return Ok(Self::Vec(Box::new(inner)));
+                            }
+                        }
+                    }
+                    return Err(unsupported_type_error(ty));
+                }
+
+                Ok(Self::Element(ty))
+            }
+            _ => Err(unsupported_type_error(ty)),
+        }
+    }
+
+    /// Returns code which writes the value referenced by `value` into `target`.
+    fn write(&self, krate: &Path, value: TokenStream2) -> TokenStream2 {
+        match self {
+            Self::Element(_) => quote!(#krate::Serializable::write_into(#value, target);),
+            Self::Integer(IntegerType::U8) => quote!(target.write_u8(*#value);),
+            Self::Integer(IntegerType::U16) => quote!(target.write_u16(*#value);),
+            Self::Integer(IntegerType::U32) => quote!(target.write_u32(*#value);),
+            Self::Integer(IntegerType::U64) => quote!(target.write_u64(*#value);),
+            Self::Integer(IntegerType::Usize) => quote!(target.write_u64(*#value as u64);),
+            Self::Array(inner, _) => {
+                let write_item = inner.write(krate, quote!(item));
+                quote! {
+                    for item in (#value).iter() {
+                        #write_item
+                    }
+                }
+            }
+            Self::Vec(inner) => {
+                let write_item = inner.write(krate, quote!(item));
+                quote! {
+                    target.write_u64((#value).len() as u64);
+                    for item in (#value).iter() {
+                        #write_item
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns an expression which reads a value of this type from `source`.
+    fn read(&self, krate: &Path) -> TokenStream2 {
+        match self {
+            Self::Element(ty) => quote!(<#ty as #krate::Deserializable>::read_from(source)?),
+            Self::Integer(IntegerType::U8) => quote!(source.read_u8()?),
+            Self::Integer(IntegerType::U16) => quote!(source.read_u16()?),
+            Self::Integer(IntegerType::U32) => quote!(source.read_u32()?),
+            Self::Integer(IntegerType::U64) => quote!(source.read_u64()?),
+            Self::Integer(IntegerType::Usize) => read_usize(krate),
+            Self::Array(inner, len) => {
+                // array elements are evaluated in order, and so items are read in order as well
+                let read_items = (0..*len).map(|_| inner.read(krate));
+                quote!([#(#read_items),*])
+            }
+            Self::Vec(inner) => {
+                let read_item = inner.read(krate);
+                let read_num_items = read_usize(krate);
+                quote! {{
+                    let num_items = #read_num_items;
+                    let mut items = #krate::collections::Vec::new();
+                    for _ in 0..num_items {
+                        items.push(#read_item);
+                    }
+                    items
+                }}
+            }
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns an expression which reads a `usize` value serialized as `u64` from `source`.
+fn read_usize(krate: &Path) -> TokenStream2 {
+    quote! {{
+        let value = source.read_u64()?;
+        match <usize as ::core::convert::TryFrom<u64>>::try_from(value) {
+            Ok(value) => value,
+            Err(_) => {
+                return Err(#krate::DeserializationError::InvalidValue(
+                    ::core::convert::From::from("usize value is too large for this platform"),
+                ))
+            }
+        }
+    }}
+}
+
+/// Returns fields of the struct described by the input, or an error if the input is not a struct.
+fn get_struct_fields<'a>(input: &'a DeriveInput, trait_name: &str) -> Result<&'a Fields, Error> {
+    match &input.data {
+        Data::Struct(data) => Ok(&data.fields),
+        _ => Err(Error::new_spanned(
+            &input.ident,
+            format!("{} can be derived only for structs", trait_name),
+        )),
+    }
+}
+
+/// Returns the path to the crate which defines serialization traits; this defaults to
+/// `winter_utils` but can be overridden via `#[winter_utils(crate = "...")]` attribute.
+fn get_crate_path(input: &DeriveInput) -> Result<Path, Error> {
+    for attr in input.attrs.iter() {
+        if !attr.path.is_ident("winter_utils") {
+            continue;
+        }
+        if let Meta::List(list) = attr.parse_meta()? {
+            for nested in list.nested.iter() {
+                if let NestedMeta::Meta(Meta::NameValue(value)) = nested {
+                    if value.path.is_ident("crate") {
+                        if let Lit::Str(path) = &value.lit {
+                            return path.parse();
+                        }
+                    }
+                }
+            }
+        }
+        return Err(Error::new_spanned(
+            attr,
+            "expected attribute of the form #[winter_utils(crate = \"...\")]",
+        ));
+    }
+    Ok(syn::parse_quote!(::winter_utils))
+}
+
+fn unsupported_type_error(ty: &Type) -> Error {
+    Error::new_spanned(
+        ty,
+        "unsupported field type; fields must be serializable types, arrays or vectors of such \
+        types, or unsigned integers",
+    )
+}
@@ -0,0 +1,93 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use math::fields::f128::BaseElement;
+use utils::{Deserializable, Serializable, SliceReader};
+
+// TEST STRUCTS
+// ================================================================================================
+
+#[derive(Debug, PartialEq, Serializable, Deserializable)]
+#[winter_utils(crate = "utils")]
+struct Inputs {
+    value: BaseElement,
+    array: [BaseElement; 2],
+    vector: Vec<BaseElement>,
+    array_vector: Vec<[BaseElement; 2]>,
+    small: u8,
+    count: usize,
+}
+
+#[derive(Debug, PartialEq, Serializable, Deserializable)]
+#[winter_utils(crate = "utils")]
+struct TupleInputs(u32, [BaseElement; 3]);
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn serialization_round_trip() {
+    let inputs = Inputs {
+        value: BaseElement::new(1),
+        array: [BaseElement::new(2), BaseElement::new(3)],
+        vector: vec![
+            BaseElement::new(4),
+            BaseElement::new(5),
+            BaseElement::new(6),
+        ],
+        array_vector: vec![[BaseElement::new(7), BaseElement::new(8)]],
+        small: 9,
+        count: 10,
+    };
+
+    let bytes = inputs.to_bytes();
+    let expected_len = 16 + 2 * 16 + (8 + 3 * 16) + (8 + 2 * 16) + 1 + 8;
+    assert_eq!(expected_len, bytes.len());
+
+    let mut reader = SliceReader::new(&bytes);
+    assert_eq!(inputs, Inputs::read_from(&mut reader).unwrap());
+}
+
+#[test]
+fn serialization_field_order() {
+    let inputs = TupleInputs(
+        1,
+        [
+            BaseElement::new(2),
+            BaseElement::new(3),
+            BaseElement::new(4),
+        ],
+    );
+
+    // fields are written in declaration order without any extra metadata
+    let mut expected = vec![1, 0, 0, 0];
+    BaseElement::write_batch_into(&inputs.1, &mut expected);
+    assert_eq!(expected, inputs.to_bytes());
+
+    let mut reader = SliceReader::new(&expected);
+    assert_eq!(inputs, TupleInputs::read_from(&mut reader).unwrap());
+}
+
+#[test]
+fn deserialization_not_enough_bytes() {
+    let inputs = TupleInputs(
+        1,
+        [
+            BaseElement::new(2),
+            BaseElement::new(3),
+            BaseElement::new(4),
+        ],
+    );
+    let bytes = inputs.to_bytes();
+
+    let mut reader = SliceReader::new(&bytes[..bytes.len() - 1]);
+    assert!(TupleInputs::read_from(&mut reader).is_err());
+}
+
+#[test]
+fn unsupported_types() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,12 @@
+use math::fields::f128::BaseElement;
+use utils::Deserializable;
+
+const NUM_VALUES: usize = 2;
+
+#[derive(Deserializable)]
+#[winter_utils(crate = "utils")]
+struct Inputs {
+    values: [BaseElement; NUM_VALUES],
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use utils::Serializable;
+
+#[derive(Serializable)]
+#[winter_utils(crate = "utils")]
+enum Inputs {
+    A,
+    B,
+}
+
+fn main() {}
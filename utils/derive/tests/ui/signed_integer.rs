@@ -0,0 +1,9 @@
+use utils::Deserializable;
+
+#[derive(Deserializable)]
+#[winter_utils(crate = "utils")]
+struct Inputs {
+    value: i32,
+}
+
+fn main() {}
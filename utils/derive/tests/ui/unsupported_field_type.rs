@@ -0,0 +1,10 @@
+use math::fields::f128::BaseElement;
+use utils::Serializable;
+
+#[derive(Serializable)]
+#[winter_utils(crate = "utils")]
+struct Inputs {
+    values: (BaseElement, BaseElement),
+}
+
+fn main() {}
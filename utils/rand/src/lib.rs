@@ -14,6 +14,7 @@ pub use internal::*;
 mod internal {
     use core::{convert::TryInto, fmt::Debug};
     use rand::prelude::*;
+    use rand_chacha::ChaCha20Rng;
     use utils::Randomizable;
 
     // RANDOM VALUE GENERATION
@@ -26,14 +27,7 @@ mod internal {
     /// * A valid value requires over 32 bytes.
     /// * A valid value could not be generated after 1000 tries.
     pub fn rand_value<R: Randomizable>() -> R {
-        for _ in 0..1000 {
-            let bytes = rand::thread_rng().gen::<[u8; 32]>();
-            if let Some(value) = R::from_random_bytes(&bytes[..R::VALUE_SIZE]) {
-                return value;
-            }
-        }
-
-        panic!("failed generate a random field element");
+        value_from_rng(&mut rand::thread_rng())
     }
 
     /// Returns a vector of random value of the specified type and the specified length.
@@ -116,6 +110,83 @@ mod internal {
     pub fn shuffle<T>(values: &mut [T]) {
         values.shuffle(&mut thread_rng());
     }
+
+    // SEEDED GENERATION
+    // ============================================================================================
+
+    /// A pseudo-random generator of values initialized from a `u64` seed.
+    ///
+    /// Two generators initialized from the same seed produce the same sequence of values; this
+    /// makes it possible to reproduce inputs which were generated randomly. The generator is
+    /// backed by ChaCha20, and thus, unlike [StdRng], the sequence of values produced from a given
+    /// seed does not change between versions of the `rand` crate.
+    pub struct SeededRng(ChaCha20Rng);
+
+    impl SeededRng {
+        /// Returns a new generator initialized from the specified `seed`.
+        pub fn new(seed: u64) -> Self {
+            SeededRng(ChaCha20Rng::seed_from_u64(seed))
+        }
+
+        /// Returns the next pseudo-random value of the specified type.
+        ///
+        /// # Panics
+        /// Panics if:
+        /// * A valid value requires over 32 bytes.
+        /// * A valid value could not be generated after 1000 tries.
+        pub fn value<R: Randomizable>(&mut self) -> R {
+            value_from_rng(&mut self.0)
+        }
+
+        /// Returns a vector of the specified length filled with the next pseudo-random values of
+        /// the specified type.
+        ///
+        /// # Panics
+        /// Panics if a valid value could not be generated.
+        pub fn vector<R: Randomizable>(&mut self, n: usize) -> Vec<R> {
+            (0..n).map(|_| self.value()).collect()
+        }
+
+        /// Returns the next pseudo-random 32-byte seed; this seed can be used to initialize
+        /// other seeded generators (e.g., via [prng_vector()]).
+        pub fn seed(&mut self) -> [u8; 32] {
+            self.0.gen()
+        }
+
+        /// Returns the next pseudo-random index in the range [0, n).
+        ///
+        /// # Panics
+        /// Panics if `n` is zero.
+        pub fn index(&mut self, n: usize) -> usize {
+            self.0.gen_range(0..n)
+        }
+
+        /// Pseudo-randomly shuffles slice elements.
+        pub fn shuffle<T>(&mut self, values: &mut [T]) {
+            values.shuffle(&mut self.0);
+        }
+    }
+
+    // HELPER FUNCTIONS
+    // ============================================================================================
+
+    /// Returns a single value of the specified type generated from the specified random number
+    /// generator.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * A valid value requires over 32 bytes.
+    /// * A valid value could not be generated after 1000 tries.
+    fn value_from_rng<R: Randomizable, G: Rng>(rng: &mut G) -> R {
+        for _ in 0..1000 {
+            let bytes = rng.gen::<[u8; 32]>();
+            if let Some(value) = R::from_random_bytes(&bytes[..R::VALUE_SIZE]) {
+                return value;
+            }
+        }
+
+        panic!("failed to generate a random value");
+    }
 }
 
 #[cfg(target_family = "wasm")]
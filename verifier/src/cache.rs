@@ -0,0 +1,153 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::evaluator::AirArtifacts;
+use air::Air;
+use std::{
+    any::{Any, TypeId},
+    collections::{BTreeMap, VecDeque},
+    sync::{Arc, RwLock},
+};
+use utils::collections::Vec;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Default maximum number of entries kept in a [VerifierCache].
+pub const DEFAULT_CACHE_CAPACITY: usize = 64;
+
+// VERIFIER CACHE
+// ================================================================================================
+
+type CacheKey = (TypeId, Vec<u8>);
+type CacheEntry = Arc<dyn Any + Send + Sync>;
+
+/// A cache of AIR-derived data which can be shared between verifications of proofs for the same
+/// computation.
+///
+/// Entries in the cache are keyed by the type of the AIR and the serialized proof context (trace
+/// info and proof options). Public inputs are not a part of the key, and thus, proofs for the
+/// same computation against different public inputs share a cache entry. For this reason, cached
+/// data must be fully determined by the trace info and proof options of the AIR.
+///
+/// Proof contexts are read from untrusted proofs, and so the number of entries in the cache is
+/// bounded; once the cache is full, the oldest entry is evicted to make room for a new one.
+///
+/// The cache is meant to be used together with the [verify_cached()](crate::verify_cached)
+/// function, and can be safely shared between multiple threads.
+pub struct VerifierCache {
+    state: RwLock<CacheState>,
+    capacity: usize,
+}
+
+#[derive(Default)]
+struct CacheState {
+    entries: BTreeMap<CacheKey, CacheEntry>,
+    insertion_order: VecDeque<CacheKey>,
+}
+
+impl VerifierCache {
+    /// Returns a new empty cache which can hold up to [DEFAULT_CACHE_CAPACITY] entries.
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_CACHE_CAPACITY)
+    }
+
+    /// Returns a new empty cache which can hold up to `capacity` entries.
+    ///
+    /// # Panics
+    /// Panics if `capacity` is zero.
+    pub fn with_capacity(capacity: usize) -> Self {
+        assert!(
+            capacity > 0,
+            "verifier cache capacity must be greater than zero"
+        );
+        VerifierCache {
+            state: RwLock::new(CacheState::default()),
+            capacity,
+        }
+    }
+
+    /// Returns the maximum number of entries this cache can hold.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of entries in this cache.
+    pub fn len(&self) -> usize {
+        self.state
+            .read()
+            .expect("verifier cache lock poisoned")
+            .entries
+            .len()
+    }
+
+    /// Returns true if this cache has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all entries from this cache.
+    pub fn clear(&self) {
+        let mut state = self.state.write().expect("verifier cache lock poisoned");
+        state.entries.clear();
+        state.insertion_order.clear();
+    }
+
+    /// Returns artifacts for the specified AIR, building and inserting them into the cache if an
+    /// entry for the AIR type and the specified serialized proof context is not yet present.
+    ///
+    /// If inserting a new entry would exceed the capacity of the cache, the oldest entry is
+    /// evicted first.
+    pub(crate) fn get_or_insert<A>(
+        &self,
+        air: &A,
+        context: &[u8],
+    ) -> Arc<AirArtifacts<A::BaseField>>
+    where
+        A: Air + 'static,
+        A::BaseField: 'static,
+    {
+        let key = (TypeId::of::<A>(), context.to_vec());
+
+        let cached = self
+            .state
+            .read()
+            .expect("verifier cache lock poisoned")
+            .entries
+            .get(&key)
+            .cloned();
+        if let Some(entry) = cached {
+            let artifacts = entry
+                .downcast::<AirArtifacts<A::BaseField>>()
+                .expect("verifier cache entry has unexpected type");
+            debug_assert!(
+                *artifacts == AirArtifacts::new(air),
+                "AIR artifacts depend on more than the proof context"
+            );
+            return artifacts;
+        }
+
+        let artifacts = Arc::new(AirArtifacts::new(air));
+        let mut state = self.state.write().expect("verifier cache lock poisoned");
+        if !state.entries.contains_key(&key) {
+            if state.entries.len() == self.capacity {
+                let oldest = state
+                    .insertion_order
+                    .pop_front()
+                    .expect("verifier cache insertion order is empty");
+                state.entries.remove(&oldest);
+            }
+            state.insertion_order.push_back(key.clone());
+            state.entries.insert(key, artifacts.clone());
+        }
+        artifacts
+    }
+}
+
+impl Default for VerifierCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -82,7 +82,7 @@ where
         // --- parse constraint evaluation queries ------------------------------------------------
         let (constraint_proof, constraint_evaluations) = proof
             .constraint_queries
-            .parse::<H, E>(lde_domain_size, num_queries, air.ce_blowup_factor())
+            .parse::<H, E>(lde_domain_size, num_queries, air.num_composition_columns())
             .map_err(|err| {
                 VerifierError::ProofDeserializationError(format!(
                     "constraint evaluation query deserialization failed: {}",
@@ -104,7 +104,7 @@ where
         // --- parse out-of-domain evaluation frame -----------------------------------------------
         let (ood_frame, ood_evaluations) = proof
             .ood_frame
-            .parse(air.trace_width(), air.ce_blowup_factor())
+            .parse(air.trace_width(), air.num_composition_columns())
             .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
 
         Ok(VerifierChannel {
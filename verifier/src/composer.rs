@@ -5,7 +5,10 @@
 
 use air::{Air, DeepCompositionCoefficients, EvaluationFrame, FieldExtension};
 use math::FieldElement;
-use utils::collections::Vec;
+use utils::{collections::Vec, iter};
+
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
 
 // DEEP COMPOSER
 // ================================================================================================
@@ -29,8 +32,7 @@ impl<A: Air, E: FieldElement + From<A::BaseField>> DeepComposer<A, E> {
         // compute LDE domain coordinates for all query positions
         let g_lde = air.lde_domain_generator();
         let domain_offset = air.domain_offset();
-        let x_coordinates: Vec<A::BaseField> = query_positions
-            .iter()
+        let x_coordinates: Vec<A::BaseField> = iter!(query_positions)
             .map(|&p| g_lde.exp((p as u64).into()) * domain_offset)
             .collect();
 
@@ -72,34 +74,33 @@ impl<A: Air, E: FieldElement + From<A::BaseField>> DeepComposer<A, E> {
         // z as well as conjugate of z itself
         let conjugate_values = get_conjugate_values(self.field_extension, trace_at_z1, self.z);
 
-        let mut result = Vec::with_capacity(queried_trace_states.len());
-        for (registers, &x) in queried_trace_states.iter().zip(&self.x_coordinates) {
-            let x = E::from(x);
-            let mut composition = E::ZERO;
-            for (i, &value) in registers.iter().enumerate() {
-                let value = E::from(value);
-                // compute T'_i(x) = (T_i(x) - T_i(z)) / (x - z)
-                let t1 = (value - trace_at_z1[i]) / (x - self.z);
-                // multiply it by a pseudo-random coefficient, and add the result to T(x)
-                composition += t1 * self.cc.trace[i].0;
-
-                // compute T''_i(x) = (T_i(x) - T_i(z * g)) / (x - z * g)
-                let t2 = (value - trace_at_z2[i]) / (x - self.next_z);
-                // multiply it by a pseudo-random coefficient, and add the result to T(x)
-                composition += t2 * self.cc.trace[i].1;
-
-                // when extension field is enabled compute
-                // T'''_i(x) = (T_i(x) - T_i(z_conjugate)) / (x - z_conjugate)
-                if let Some((z_conjugate, ref trace_at_z1_conjugates)) = conjugate_values {
-                    let t3 = (value - trace_at_z1_conjugates[i]) / (x - z_conjugate);
-                    composition += t3 * self.cc.trace[i].2;
+        iter!(queried_trace_states)
+            .zip(&self.x_coordinates)
+            .map(|(registers, &x)| {
+                let x = E::from(x);
+                let mut composition = E::ZERO;
+                for (i, &value) in registers.iter().enumerate() {
+                    let value = E::from(value);
+                    // compute T'_i(x) = (T_i(x) - T_i(z)) / (x - z)
+                    let t1 = (value - trace_at_z1[i]) / (x - self.z);
+                    // multiply it by a pseudo-random coefficient, and add the result to T(x)
+                    composition += t1 * self.cc.trace[i].0;
+
+                    // compute T''_i(x) = (T_i(x) - T_i(z * g)) / (x - z * g)
+                    let t2 = (value - trace_at_z2[i]) / (x - self.next_z);
+                    // multiply it by a pseudo-random coefficient, and add the result to T(x)
+                    composition += t2 * self.cc.trace[i].1;
+
+                    // when extension field is enabled compute
+                    // T'''_i(x) = (T_i(x) - T_i(z_conjugate)) / (x - z_conjugate)
+                    if let Some((z_conjugate, ref trace_at_z1_conjugates)) = conjugate_values {
+                        let t3 = (value - trace_at_z1_conjugates[i]) / (x - z_conjugate);
+                        composition += t3 * self.cc.trace[i].2;
+                    }
                 }
-            }
-
-            result.push(composition);
-        }
-
-        result
+                composition
+            })
+            .collect()
     }
 
     /// For each queried set of composition polynomial column evaluations, combine evaluations
@@ -121,24 +122,23 @@ impl<A: Air, E: FieldElement + From<A::BaseField>> DeepComposer<A, E> {
     ) -> Vec<E> {
         assert_eq!(queried_evaluations.len(), self.x_coordinates.len());
 
-        let mut result = Vec::with_capacity(queried_evaluations.len());
-
         // compute z^m
         let num_evaluation_columns = ood_evaluations.len() as u32;
         let z_m = self.z.exp(num_evaluation_columns.into());
 
-        for (query_values, &x) in queried_evaluations.iter().zip(&self.x_coordinates) {
-            let mut composition = E::ZERO;
-            for (i, &evaluation) in query_values.iter().enumerate() {
-                // compute H'_i(x) = (H_i(x) - H(z^m)) / (x - z^m)
-                let h_i = (evaluation - ood_evaluations[i]) / (E::from(x) - z_m);
-                // multiply it by a pseudo-random coefficient, and add the result to H(x)
-                composition += h_i * self.cc.constraints[i];
-            }
-            result.push(composition);
-        }
-
-        result
+        iter!(queried_evaluations)
+            .zip(&self.x_coordinates)
+            .map(|(query_values, &x)| {
+                let mut composition = E::ZERO;
+                for (i, &evaluation) in query_values.iter().enumerate() {
+                    // compute H'_i(x) = (H_i(x) - H(z^m)) / (x - z^m)
+                    let h_i = (evaluation - ood_evaluations[i]) / (E::from(x) - z_m);
+                    // multiply it by a pseudo-random coefficient, and add the result to H(x)
+                    composition += h_i * self.cc.constraints[i];
+                }
+                composition
+            })
+            .collect()
     }
 
     /// Combines trace and constraint compositions together, and also rases the degree of the
@@ -150,18 +150,16 @@ impl<A: Air, E: FieldElement + From<A::BaseField>> DeepComposer<A, E> {
         assert_eq!(t_composition.len(), self.x_coordinates.len());
         assert_eq!(c_composition.len(), self.x_coordinates.len());
 
-        let mut result = Vec::with_capacity(self.x_coordinates.len());
-        for ((&x, t), c) in self.x_coordinates.iter().zip(t_composition).zip(c_composition) {
+        iter!(self.x_coordinates).zip(t_composition).zip(c_composition).map(|((&x, t), c)| {
             // compute C(x) by adding the two compositions together
             let composition = t + c;
 
             // raise the degree of C(x) by computing C'(x) = C(x) * (cc_0 + x * cc_1), where
             // cc_0 and cc_1 are the coefficients for the random linear combination drawn from
             // the public coin.
-            result.push(composition * (self.cc.degree.0 + E::from(x) * self.cc.degree.1));
-        }
-
-        result
+            composition * (self.cc.degree.0 + E::from(x) * self.cc.degree.1)
+        })
+        .collect()
     }
 }
 
@@ -21,6 +21,17 @@ pub enum VerifierError {
     UnsupportedFieldExtension(usize),
     /// This error occurs when a verifier cannot deserialize the specified proof.
     ProofDeserializationError(String),
+    /// This error occurs when trace info specified in the proof context or the public inputs are
+    /// rejected by [Air::validate_instance()](air::Air::validate_instance) of the AIR with which
+    /// the verifier was instantiated.
+    InvalidAirInstance(String),
+    /// This error occurs when the transcript domain separation tag specified in the proof context
+    /// differs from the tag required by the verification policy.
+    TranscriptDomainMismatch,
+    /// This error occurs when the number of FRI layers or the size of the FRI remainder in the
+    /// proof does not match the values implied by the trace length and proof options specified
+    /// in the proof context.
+    InconsistentFriParameters,
     /// This error occurs when a verifier fails to draw a random value from a random coin
     /// within a specified number of tries.
     RandomCoinError,
@@ -56,6 +67,15 @@ impl fmt::Display for VerifierError {
             Self::ProofDeserializationError(msg) => {
                 write!(f, "proof deserialization failed: {}", msg)
             }
+            Self::InvalidAirInstance(msg) => {
+                write!(f, "proof context or public inputs are not valid for the specified AIR: {}", msg)
+            }
+            Self::TranscriptDomainMismatch => {
+                write!(f, "transcript domain of the proof does not match the domain required by the verification policy")
+            }
+            Self::InconsistentFriParameters => {
+                write!(f, "FRI proof parameters are inconsistent with the proof context")
+            }
             Self::RandomCoinError => {
                 write!(f, "failed to draw a random value from a random coin")
             }
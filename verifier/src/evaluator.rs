@@ -3,16 +3,51 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use air::{Air, ConstraintCompositionCoefficients, EvaluationFrame};
-use math::{polynom, FieldElement};
+use air::{Air, ConstraintCompositionCoefficients, ConstraintDivisor, EvaluationFrame};
+use math::{polynom, FieldElement, StarkField};
 use utils::collections::Vec;
 
+// AIR ARTIFACTS
+// ================================================================================================
+
+/// Data derived from an AIR instance which the verifier needs during constraint evaluation.
+///
+/// When used with a [VerifierCache](crate::VerifierCache), the artifacts are shared between all
+/// AIR instances with the same trace info and proof options; for this to be sound, periodic
+/// columns of the AIR must not depend on its public inputs.
+#[derive(PartialEq)]
+pub(crate) struct AirArtifacts<B: StarkField> {
+    periodic_column_polys: Vec<Vec<B>>,
+    transition_divisor: ConstraintDivisor<B>,
+}
+
+impl<B: StarkField> AirArtifacts<B> {
+    /// Builds the artifacts from the specified AIR.
+    pub fn new<A: Air<BaseField = B>>(air: &A) -> Self {
+        AirArtifacts {
+            periodic_column_polys: air.get_periodic_column_polys(),
+            transition_divisor: air.transition_constraint_divisor(),
+        }
+    }
+
+    /// Returns polynomials describing periodic columns of the AIR.
+    pub fn periodic_column_polys(&self) -> &[Vec<B>] {
+        &self.periodic_column_polys
+    }
+
+    /// Returns the divisor for transition constraints of the AIR.
+    pub fn transition_divisor(&self) -> &ConstraintDivisor<B> {
+        &self.transition_divisor
+    }
+}
+
 // CONSTRAINT EVALUATION
 // ================================================================================================
 
 /// Evaluates constraints for the specified evaluation frame.
 pub fn evaluate_constraints<A: Air, E: FieldElement<BaseField = A::BaseField>>(
     air: &A,
+    artifacts: &AirArtifacts<A::BaseField>,
     coefficients: ConstraintCompositionCoefficients<E>,
     ood_frame: &EvaluationFrame<E>,
     x: E,
@@ -23,8 +58,8 @@ pub fn evaluate_constraints<A: Air, E: FieldElement<BaseField = A::BaseField>>(
     let mut t_evaluations = E::zeroed_vector(air.num_transition_constraints());
 
     // compute values of periodic columns at x
-    let periodic_values = air
-        .get_periodic_column_polys()
+    let periodic_values = artifacts
+        .periodic_column_polys()
         .iter()
         .map(|poly| {
             let num_cycles = air.trace_length() / poly.len();
@@ -44,7 +79,7 @@ pub fn evaluate_constraints<A: Air, E: FieldElement<BaseField = A::BaseField>>(
     });
 
     // divide out the evaluation of divisor at x
-    let z = air.transition_constraint_divisor().evaluate_at(x);
+    let z = artifacts.transition_divisor().evaluate_at(x);
     let mut result = t_evaluation / z;
 
     // 2 ----- evaluate boundary constraints ------------------------------------------------------
@@ -55,9 +90,10 @@ pub fn evaluate_constraints<A: Air, E: FieldElement<BaseField = A::BaseField>>(
     // iterate over boundary constraint groups (each group has a distinct divisor), evaluate
     // constraints in each group and add them to the evaluations vector
 
-    // cache power of x here so that we only re-compute it when degree_adjustment changes
-    let mut degree_adjustment = b_constraints[0].degree_adjustment();
-    let mut xp = x.exp(degree_adjustment.into());
+    // cache power of x here so that we only re-compute it when degree_adjustment changes;
+    // x^0 = 1, so we can start with the adjustment degree of 0
+    let mut degree_adjustment = 0;
+    let mut xp = E::ONE;
 
     for group in b_constraints.iter() {
         // if adjustment degree hasn't changed, no need to recompute `xp` - so just reuse the
@@ -70,5 +106,18 @@ pub fn evaluate_constraints<A: Air, E: FieldElement<BaseField = A::BaseField>>(
         result += group.evaluate_at(ood_frame.current(), x, xp);
     }
 
+    // 3 ----- evaluate custom boundary constraints -----------------------------------------------
+
+    // custom boundary constraints (if any) are evaluated one at a time since each of them has
+    // its own divisor
+    let c_constraints = air.get_custom_boundary_constraints(&coefficients.custom_boundary);
+    for constraint in c_constraints.iter() {
+        if constraint.degree_adjustment() != degree_adjustment {
+            degree_adjustment = constraint.degree_adjustment();
+            xp = x.exp(degree_adjustment.into());
+        }
+        result += constraint.evaluate_at(ood_frame.current(), x, xp);
+    }
+
     result
 }
@@ -25,6 +25,11 @@
 //! asserted values. But for the impact to be noticeable, the number of asserted values would
 //! need to be in tens of thousands. And even for hundreds of thousands of asserted values, the
 //! verification time should not exceed 50 ms.
+//!
+//! When the crate is compiled with `concurrent` feature enabled, the work done independently for
+//! each query (e.g., computing DEEP composition evaluations) will be performed in multiple
+//! threads. Steps which depend on the public coin are always executed sequentially, and thus,
+//! verification results are the same as for single-threaded verification.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -34,15 +39,15 @@ extern crate alloc;
 
 pub use air::{
     proof::StarkProof, Air, AirContext, Assertion, BoundaryConstraint, BoundaryConstraintGroup,
-    ConstraintCompositionCoefficients, ConstraintDivisor, DeepCompositionCoefficients,
-    EvaluationFrame, FieldExtension, HashFunction, ProofOptions, TraceInfo,
-    TransitionConstraintDegree, TransitionConstraintGroup,
+    ConstraintCompositionCoefficients, ConstraintDivisor, ConstraintSet, CustomBoundaryConstraint,
+    DeepCompositionCoefficients, EvaluationFrame, FieldExtension, HashFunction, ProofOptions,
+    TraceInfo, TransitionConstraintDegree, TransitionConstraintGroup,
 };
 
 pub use math;
 use math::{
     fields::{CubeExtension, QuadExtension},
-    FieldElement,
+    FieldElement, StarkField,
 };
 
 use utils::collections::Vec;
@@ -51,16 +56,44 @@ pub use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Se
 pub use crypto;
 use crypto::{
     hashers::{Blake3_192, Blake3_256, Sha3_256},
-    ElementHasher, RandomCoin,
+    ElementHasher, PublicCoin, RandomCoin,
 };
 
 use fri::FriVerifier;
 
+#[cfg(feature = "tracing")]
+use tracing::info_span;
+
+/// Ends the current phase recorded in `$guard` (if any) and enters a new tracing span with the
+/// specified name and fields; expands to nothing when the `tracing` feature is disabled.
+///
+/// The previous span is exited before the new one is created, so that phases are recorded as
+/// siblings rather than as children of each other.
+macro_rules! enter_phase {
+    ($guard:ident, $($span:tt)+) => {
+        // the span entered by the last phase is only read when the guard is dropped
+        #[cfg(feature = "tracing")]
+        #[allow(unused_assignments)]
+        {
+            drop($guard.take());
+            $guard = Some(info_span!($($span)+).entered());
+        }
+    };
+}
+
 mod channel;
 use channel::VerifierChannel;
 
 mod evaluator;
-use evaluator::evaluate_constraints;
+use evaluator::{evaluate_constraints, AirArtifacts};
+
+#[cfg(feature = "std")]
+mod cache;
+#[cfg(feature = "std")]
+pub use cache::{VerifierCache, DEFAULT_CACHE_CAPACITY};
+
+mod policy;
+pub use policy::VerificationPolicy;
 
 mod composer;
 use composer::DeepComposer;
@@ -81,21 +114,147 @@ pub use errors::VerifierError;
 /// a correct execution of the computation. This could happen for many various reasons, including:
 /// - The specified proof was generated for a different computation.
 /// - The specified proof was generated for this computation but for different public inputs.
-#[rustfmt::skip]
 pub fn verify<AIR: Air>(
     proof: StarkProof,
     pub_inputs: AIR::PublicInputs,
 ) -> Result<(), VerifierError> {
-    // build a seed for the public coin; the initial seed is the hash of public inputs and proof
-    // context, but as the protocol progresses, the coin will be reseeded with the info received
-    // from the prover
-    let mut public_coin_seed = Vec::new();
+    // make sure the proof context is consistent with the structure of the proof before doing
+    // anything else with the proof
+    validate_context::<AIR::BaseField>(&proof)?;
+    AIR::validate_instance(&proof.get_trace_info(), &pub_inputs)
+        .map_err(VerifierError::InvalidAirInstance)?;
+
+    // build a seed for the public coin; the initial seed is the hash of transcript domain tag,
+    // public inputs and proof context, but as the protocol progresses, the coin will be reseeded
+    // with the info received from the prover; the tag is taken from the proof context
+    let mut public_coin_seed = proof.options().transcript_domain().to_vec();
     pub_inputs.write_into(&mut public_coin_seed);
     proof.context.write_into(&mut public_coin_seed);
 
     // create AIR instance for the computation specified in the proof
     let air = AIR::new(proof.get_trace_info(), pub_inputs, proof.options().clone());
+    let artifacts = AirArtifacts::new(&air);
+
+    dispatch_verification(air, &artifacts, proof, public_coin_seed)
+}
+
+/// Verifies that the specified computation was executed correctly against the specified inputs,
+/// and that the proof satisfies the specified `policy`.
+///
+/// This function is equivalent to [verify()], but before the proof is verified, it is checked
+/// against the `policy`. For example, a policy can require the proof to be generated under a
+/// specific transcript domain separation tag, while [verify()] accepts the tag specified in the
+/// proof context.
+///
+/// # Errors
+/// Returns an error if the proof does not satisfy the `policy`, or under the same conditions as
+/// [verify()].
+pub fn verify_with_policy<AIR: Air>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    policy: &VerificationPolicy,
+) -> Result<(), VerifierError> {
+    policy.check(&proof)?;
+    verify::<AIR>(proof, pub_inputs)
+}
 
+/// Verifies that the specified computation was executed correctly against the specified inputs
+/// using the provided `cache` for data derived from the AIR.
+///
+/// This function is equivalent to [verify()], but data derived from the AIR (e.g., periodic
+/// column polynomials) is built only once per AIR type and proof context, and is then re-used for
+/// all subsequent verifications with the same parameters, regardless of their public inputs. This
+/// makes verifying a stream of proofs for the same computation cheaper. An AIR instance is still
+/// built for every proof because it holds the public inputs of the computation.
+///
+/// Periodic columns of the AIR must be fully determined by the trace info and proof options;
+/// in debug builds, this is checked every time a cached entry is used.
+///
+/// # Errors
+/// Returns an error under the same conditions as [verify()].
+#[cfg(feature = "std")]
+pub fn verify_cached<AIR>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    cache: &VerifierCache,
+) -> Result<(), VerifierError>
+where
+    AIR: Air + 'static,
+    AIR::BaseField: 'static,
+{
+    validate_context::<AIR::BaseField>(&proof)?;
+    AIR::validate_instance(&proof.get_trace_info(), &pub_inputs)
+        .map_err(VerifierError::InvalidAirInstance)?;
+
+    // build a seed for the public coin in the same way as in verify(); serialized proof context
+    // is also used as a part of the cache key
+    let context = proof.context.to_bytes();
+    let mut public_coin_seed = proof.options().transcript_domain().to_vec();
+    pub_inputs.write_into(&mut public_coin_seed);
+    public_coin_seed.extend_from_slice(&context);
+
+    // create AIR instance for the computation specified in the proof, and get AIR artifacts
+    // either from the cache or by building them from the AIR
+    let air = AIR::new(proof.get_trace_info(), pub_inputs, proof.options().clone());
+    let artifacts = cache.get_or_insert(&air, &context);
+
+    dispatch_verification(air, &artifacts, proof, public_coin_seed)
+}
+
+// PROOF CONTEXT VALIDATION
+// ================================================================================================
+/// Makes sure that the proof context is consistent with the structure of the specified proof.
+///
+/// The number of FRI layers and the size of the FRI remainder are fully determined by the trace
+/// length, blowup factor, field extension, FRI folding factor, and FRI max remainder size
+/// specified in the context. Checking them before any commitments are parsed ensures that proofs
+/// with a tampered context are rejected with a descriptive error rather than failing somewhere
+/// deep inside FRI verification.
+fn validate_context<B: StarkField>(proof: &StarkProof) -> Result<(), VerifierError> {
+    // make sure the LDE domain is small enough to be defined over the base field
+    let options = proof.options();
+    let lde_domain_size_log2 =
+        proof.trace_length().trailing_zeros() + options.blowup_factor().trailing_zeros();
+    if lde_domain_size_log2 > B::TWO_ADICITY {
+        return Err(VerifierError::ProofDeserializationError(format!(
+            "LDE domain of size 2^{} cannot be defined over a field with two-adicity {}",
+            lde_domain_size_log2,
+            B::TWO_ADICITY
+        )));
+    }
+
+    // make sure the evaluation domain offset declared by the context is valid for the field
+    options
+        .validate_domain_offset::<B>()
+        .map_err(VerifierError::ProofDeserializationError)?;
+
+    // make sure the number of FRI layers and the size of the FRI remainder are the ones implied
+    // by the context; remainder elements are in the extension field, and thus, the number of
+    // remainder elements in the base field is scaled by the extension degree
+    let lde_domain_size = proof.context.lde_domain_size();
+    let fri_options = options.to_fri_options();
+    let extension_degree = options.field_extension().degree() as usize;
+    if proof.fri_proof.num_layers() != fri_options.num_fri_layers(lde_domain_size)
+        || proof.fri_proof.num_remainder_elements::<B>()
+            != fri_options.fri_remainder_size(lde_domain_size) * extension_degree
+    {
+        return Err(VerifierError::InconsistentFriParameters);
+    }
+
+    Ok(())
+}
+
+// VERIFICATION DISPATCH
+// ================================================================================================
+/// Selects the version of the generic proof verification procedure to run based on the options
+/// specified in the AIR.
+#[rustfmt::skip]
+fn dispatch_verification<AIR: Air>(
+    air: AIR,
+    artifacts: &AirArtifacts<AIR::BaseField>,
+    proof: StarkProof,
+    public_coin_seed: Vec<u8>,
+) -> Result<(), VerifierError> {
     // figure out which version of the generic proof verification procedure to run. this is a sort
     // of static dispatch for selecting two generic parameter: extension field and hash function.
     match air.options().field_extension() {
@@ -103,17 +262,17 @@ pub fn verify<AIR: Air>(
             HashFunction::Blake3_256 => {
                 let public_coin = RandomCoin::new(&public_coin_seed);
                 let channel = VerifierChannel::new(&air, proof)?;
-                perform_verification::<AIR, AIR::BaseField, Blake3_256<AIR::BaseField>>(air, channel, public_coin)
+                perform_verification::<AIR, AIR::BaseField, Blake3_256<AIR::BaseField>, _>(air, artifacts, channel, public_coin)
             }
             HashFunction::Blake3_192 => {
                 let public_coin = RandomCoin::new(&public_coin_seed);
                 let channel = VerifierChannel::new(&air, proof)?;
-                perform_verification::<AIR, AIR::BaseField, Blake3_192<AIR::BaseField>>(air, channel, public_coin)
+                perform_verification::<AIR, AIR::BaseField, Blake3_192<AIR::BaseField>, _>(air, artifacts, channel, public_coin)
             }
             HashFunction::Sha3_256 => {
                 let public_coin = RandomCoin::new(&public_coin_seed);
                 let channel = VerifierChannel::new(&air, proof)?;
-                perform_verification::<AIR, AIR::BaseField, Sha3_256<AIR::BaseField>>(air, channel, public_coin)
+                perform_verification::<AIR, AIR::BaseField, Sha3_256<AIR::BaseField>, _>(air, artifacts, channel, public_coin)
             }
         },
         FieldExtension::Quadratic => {
@@ -124,17 +283,17 @@ pub fn verify<AIR: Air>(
                 HashFunction::Blake3_256 => {
                     let public_coin = RandomCoin::new(&public_coin_seed);
                     let channel = VerifierChannel::new(&air, proof)?;
-                    perform_verification::<AIR, QuadExtension<AIR::BaseField>, Blake3_256<AIR::BaseField>>(air, channel, public_coin)
+                    perform_verification::<AIR, QuadExtension<AIR::BaseField>, Blake3_256<AIR::BaseField>, _>(air, artifacts, channel, public_coin)
                 }
                 HashFunction::Blake3_192 => {
                     let public_coin = RandomCoin::new(&public_coin_seed);
                     let channel = VerifierChannel::new(&air, proof)?;
-                    perform_verification::<AIR, QuadExtension<AIR::BaseField>, Blake3_192<AIR::BaseField>>(air, channel, public_coin)
+                    perform_verification::<AIR, QuadExtension<AIR::BaseField>, Blake3_192<AIR::BaseField>, _>(air, artifacts, channel, public_coin)
                 }
                 HashFunction::Sha3_256 => {
                     let public_coin = RandomCoin::new(&public_coin_seed);
                     let channel = VerifierChannel::new(&air, proof)?;
-                    perform_verification::<AIR, QuadExtension<AIR::BaseField>, Sha3_256<AIR::BaseField>>(air, channel, public_coin)
+                    perform_verification::<AIR, QuadExtension<AIR::BaseField>, Sha3_256<AIR::BaseField>, _>(air, artifacts, channel, public_coin)
                 }
             }
         },
@@ -146,17 +305,17 @@ pub fn verify<AIR: Air>(
                 HashFunction::Blake3_256 => {
                     let public_coin = RandomCoin::new(&public_coin_seed);
                     let channel = VerifierChannel::new(&air, proof)?;
-                    perform_verification::<AIR, CubeExtension<AIR::BaseField>, Blake3_256<AIR::BaseField>>(air, channel, public_coin)
+                    perform_verification::<AIR, CubeExtension<AIR::BaseField>, Blake3_256<AIR::BaseField>, _>(air, artifacts, channel, public_coin)
                 }
                 HashFunction::Blake3_192 => {
                     let public_coin = RandomCoin::new(&public_coin_seed);
                     let channel = VerifierChannel::new(&air, proof)?;
-                    perform_verification::<AIR, CubeExtension<AIR::BaseField>, Blake3_192<AIR::BaseField>>(air, channel, public_coin)
+                    perform_verification::<AIR, CubeExtension<AIR::BaseField>, Blake3_192<AIR::BaseField>, _>(air, artifacts, channel, public_coin)
                 }
                 HashFunction::Sha3_256 => {
                     let public_coin = RandomCoin::new(&public_coin_seed);
                     let channel = VerifierChannel::new(&air, proof)?;
-                    perform_verification::<AIR, CubeExtension<AIR::BaseField>, Sha3_256<AIR::BaseField>>(air, channel, public_coin)
+                    perform_verification::<AIR, CubeExtension<AIR::BaseField>, Sha3_256<AIR::BaseField>, _>(air, artifacts, channel, public_coin)
                 }
             }
         },
@@ -167,23 +326,43 @@ pub fn verify<AIR: Air>(
 // ================================================================================================
 /// Performs the actual verification by reading the data from the `channel` and making sure it
 /// attests to a correct execution of the computation specified by the provided `air`.
-fn perform_verification<A, E, H>(
+///
+/// The `public_coin` must be seeded in the same way, and be of the same kind, as the public coin
+/// used by the prover.
+fn perform_verification<A, E, H, R>(
     air: A,
+    artifacts: &AirArtifacts<A::BaseField>,
     mut channel: VerifierChannel<A::BaseField, E, H>,
-    mut public_coin: RandomCoin<A::BaseField, H>,
+    mut public_coin: R,
 ) -> Result<(), VerifierError>
 where
     A: Air,
     E: FieldElement<BaseField = A::BaseField>,
     H: ElementHasher<BaseField = A::BaseField>,
+    R: PublicCoin<BaseField = A::BaseField, Hasher = H>,
 {
+    #[cfg(feature = "tracing")]
+    let _span = info_span!(
+        "verify",
+        trace_width = air.trace_width(),
+        trace_length = air.trace_length(),
+        num_queries = air.options().num_queries()
+    )
+    .entered();
+    #[cfg(feature = "tracing")]
+    let mut phase = None;
+
     // 1 ----- trace commitment -------------------------------------------------------------------
+    enter_phase!(phase, "read_commitments");
     // read the commitment to evaluations of the trace polynomials over the LDE domain sent by the
     // prover, use it to update the public coin, and draw a set of random coefficients from the
     // coin; in the interactive version of the protocol, the verifier sends these coefficients to
     // the prover, and prover uses them to compute constraint composition polynomial.
     let trace_commitment = channel.read_trace_commitment();
-    public_coin.reseed(trace_commitment);
+    public_coin
+        .label("trace commitment")
+        .reseed(trace_commitment);
+    public_coin.label("constraint composition coefficients");
     let constraint_coeffs = air
         .get_constraint_composition_coefficients(&mut public_coin)
         .map_err(|_| VerifierError::RandomCoinError)?;
@@ -195,20 +374,31 @@ where
     // to the prover, and the prover evaluates trace and constraint composition polynomials at z,
     // and send the results back to the verifier.
     let constraint_commitment = channel.read_constraint_commitment();
-    public_coin.reseed(constraint_commitment);
+    public_coin
+        .label("constraint commitment")
+        .reseed(constraint_commitment);
     let z = public_coin
+        .label("ood point")
         .draw::<E>()
         .map_err(|_| VerifierError::RandomCoinError)?;
 
     // 3 ----- OOD consistency check --------------------------------------------------------------
+    enter_phase!(
+        phase,
+        "check_ood_consistency",
+        num_transition_constraints = air.num_transition_constraints()
+    );
     // make sure that evaluations obtained by evaluating constraints over the out-of-domain frame
     // are consistent with the evaluations of composition polynomial columns sent by the prover
 
     // read the out-of-domain evaluation frame sent by the prover and evaluate constraints over it;
     // also, reseed the public coin with the OOD frame received from the prover
     let ood_frame = channel.read_ood_evaluation_frame();
-    let ood_constraint_evaluation_1 = evaluate_constraints(&air, constraint_coeffs, &ood_frame, z);
-    public_coin.reseed(H::hash_elements(ood_frame.current()));
+    let ood_constraint_evaluation_1 =
+        evaluate_constraints(&air, artifacts, constraint_coeffs, &ood_frame, z);
+    public_coin
+        .label("ood trace frame")
+        .reseed(H::hash_elements(ood_frame.current()));
     public_coin.reseed(H::hash_elements(ood_frame.next()));
 
     // read evaluations of composition polynomial columns sent by the prover, and reduce them into
@@ -222,7 +412,9 @@ where
         .fold(E::ZERO, |result, (i, &value)| {
             result + z.exp((i as u32).into()) * value
         });
-    public_coin.reseed(H::hash_elements(&ood_evaluations));
+    public_coin
+        .label("ood constraint evaluations")
+        .reseed(H::hash_elements(&ood_evaluations));
 
     // finally, make sure the values are the same
     if ood_constraint_evaluation_1 != ood_constraint_evaluation_2 {
@@ -230,12 +422,14 @@ where
     }
 
     // 4 ----- FRI commitments --------------------------------------------------------------------
+    enter_phase!(phase, "read_fri_commitments");
     // draw coefficients for computing DEEP composition polynomial from the public coin; in the
     // interactive version of the protocol, the verifier sends these coefficients to the prover
     // and the prover uses them to compute the DEEP composition polynomial. the prover, then
     // applies FRI protocol to the evaluations of the DEEP composition polynomial.
+    public_coin.label("deep composition coefficients");
     let deep_coefficients = air
-        .get_deep_composition_coefficients::<E, H>(&mut public_coin)
+        .get_deep_composition_coefficients::<E, R>(&mut public_coin)
         .map_err(|_| VerifierError::RandomCoinError)?;
 
     // instantiates a FRI verifier with the FRI layer commitments read from the channel. From the
@@ -253,9 +447,14 @@ where
     // TODO: make sure air.lde_domain_size() == fri_verifier.domain_size()
 
     // 5 ----- trace and constraint queries -------------------------------------------------------
+    enter_phase!(
+        phase,
+        "read_queries",
+        num_queries = air.options().num_queries()
+    );
     // read proof-of-work nonce sent by the prover and update the public coin with it
     let pow_nonce = channel.read_pow_nonce();
-    public_coin.reseed_with_int(pow_nonce);
+    public_coin.label("pow nonce").reseed_with_int(pow_nonce);
 
     // make sure the proof-of-work specified by the grinding factor is satisfied
     if public_coin.leading_zeros() < air.options().grinding_factor() {
@@ -267,6 +466,7 @@ where
     // and the prover responds with decommitments against these positions for trace and constraint
     // composition polynomial evaluations.
     let query_positions = public_coin
+        .label("query positions")
         .draw_integers(air.options().num_queries(), air.lde_domain_size())
         .map_err(|_| VerifierError::RandomCoinError)?;
 
@@ -277,6 +477,7 @@ where
         channel.read_constraint_evaluations(&query_positions, &constraint_commitment)?;
 
     // 6 ----- DEEP composition -------------------------------------------------------------------
+    enter_phase!(phase, "compose_deep", num_queries = query_positions.len());
     // compute evaluations of the DEEP composition polynomial at the queried positions
     let composer = DeepComposer::new(&air, &query_positions, z, deep_coefficients);
     let t_composition = composer.compose_registers(queried_trace_states, ood_frame);
@@ -284,6 +485,7 @@ where
     let deep_evaluations = composer.combine_compositions(t_composition, c_composition);
 
     // 7 ----- Verify low-degree proof -------------------------------------------------------------
+    enter_phase!(phase, "verify_fri", num_queries = query_positions.len());
     // make sure that evaluations of the DEEP composition polynomial we computed in the previous
     // step are in fact evaluations of a polynomial of degree equal to trace polynomial degree
     fri_verifier
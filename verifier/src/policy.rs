@@ -0,0 +1,55 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::VerifierError;
+use air::proof::StarkProof;
+use utils::collections::Vec;
+
+// VERIFICATION POLICY
+// ================================================================================================
+
+/// Requirements which a proof must satisfy in addition to being valid.
+///
+/// A policy is checked by [verify_with_policy()](crate::verify_with_policy) before the proof is
+/// verified. A default policy places no additional requirements on proofs.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VerificationPolicy {
+    transcript_domain: Option<Vec<u8>>,
+}
+
+impl VerificationPolicy {
+    /// Returns a new policy which places no additional requirements on proofs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requires proofs to be generated under the specified transcript domain separation tag, and
+    /// returns the updated policy.
+    ///
+    /// Without this requirement, the verifier accepts the tag specified in the proof context.
+    pub fn with_transcript_domain(mut self, tag: &[u8]) -> Self {
+        self.transcript_domain = Some(tag.to_vec());
+        self
+    }
+
+    /// Returns the transcript domain separation tag required by this policy, if any.
+    pub fn transcript_domain(&self) -> Option<&[u8]> {
+        self.transcript_domain.as_deref()
+    }
+
+    /// Checks the specified proof against this policy.
+    ///
+    /// # Errors
+    /// Returns an error if the proof was generated under a transcript domain separation tag
+    /// different from the one required by this policy.
+    pub fn check(&self, proof: &StarkProof) -> Result<(), VerifierError> {
+        if let Some(tag) = self.transcript_domain() {
+            if proof.options().transcript_domain() != tag {
+                return Err(VerifierError::TranscriptDomainMismatch);
+            }
+        }
+        Ok(())
+    }
+}
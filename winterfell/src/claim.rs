@@ -0,0 +1,227 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{
+    verify, Air, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+    SliceReader, StarkProof, VerifierError,
+};
+use core::fmt;
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+// CLAIM
+// ================================================================================================
+/// A proof bundled together with everything needed to verify it.
+///
+/// A claim consists of:
+/// * An identifier of the AIR for which the proof was generated (see [Air::air_id()]).
+/// * The proof itself; proof options and trace info are a part of the proof context.
+/// * Serialized public inputs against which the proof should be verified.
+/// * A timestamp of when the claim was created; this is not interpreted by the verifier.
+///
+/// A claim can be verified via [Claim::verify()] method which makes sure that the claim was
+/// created for the expected AIR before verifying the proof.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Claim {
+    pub air_id: String,
+    pub proof: StarkProof,
+    pub pub_inputs: Vec<u8>,
+    pub created_at: u64,
+}
+
+impl Claim {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new claim for a proof of the computation described by AIR `A`.
+    ///
+    /// Public inputs are serialized into the claim, and the timestamp of the claim is set to 0;
+    /// it can be set via [with_created_at()](Claim::with_created_at) method.
+    ///
+    /// # Panics
+    /// Panics if the identifier of `A` is longer than 2^16 - 1 bytes.
+    pub fn new<A: Air>(proof: StarkProof, pub_inputs: &A::PublicInputs) -> Self {
+        assert!(
+            A::air_id().len() <= u16::MAX as usize,
+            "AIR identifier cannot be longer than {} bytes, but was {} bytes",
+            u16::MAX,
+            A::air_id().len()
+        );
+        Claim {
+            air_id: A::air_id().to_string(),
+            proof,
+            pub_inputs: pub_inputs.to_bytes(),
+            created_at: 0,
+        }
+    }
+
+    /// Sets the timestamp of this claim and returns the updated claim.
+    pub fn with_created_at(mut self, created_at: u64) -> Self {
+        self.created_at = created_at;
+        self
+    }
+
+    // VERIFICATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Verifies this claim against the computation described by AIR `A`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The claim was created for an AIR with a different identifier than `A`.
+    /// * Public inputs of the claim could not be deserialized into public inputs of `A`.
+    /// * The proof is not valid for the public inputs of the claim.
+    pub fn verify<A>(self) -> Result<(), ClaimError>
+    where
+        A: Air,
+        A::PublicInputs: Deserializable,
+    {
+        if self.air_id != A::air_id() {
+            return Err(ClaimError::AirMismatch {
+                expected: A::air_id().to_string(),
+                actual: self.air_id,
+            });
+        }
+
+        let mut reader = SliceReader::new(&self.pub_inputs);
+        let pub_inputs = A::PublicInputs::read_from(&mut reader)
+            .map_err(|err| ClaimError::PublicInputsDeserializationError(err.to_string()))?;
+        if reader.has_more_bytes() {
+            return Err(ClaimError::PublicInputsDeserializationError(
+                "not all bytes were consumed".to_string(),
+            ));
+        }
+
+        verify::<A>(self.proof, pub_inputs).map_err(ClaimError::VerificationFailed)
+    }
+
+    // SERIALIZATION / DESERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Serializes this claim into a vector of bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut result = Vec::new();
+        self.write_into(&mut result);
+        result
+    }
+
+    /// Returns a claim read from the specified `source`.
+    ///
+    /// # Errors
+    /// Returns an error if a valid claim could not be read from the source bytes, or if not all
+    /// of the source bytes were consumed.
+    pub fn from_bytes(source: &[u8]) -> Result<Self, DeserializationError> {
+        let mut reader = SliceReader::new(source);
+        let claim = Self::read_from(&mut reader)?;
+        if reader.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+        Ok(claim)
+    }
+}
+
+impl Serializable for Claim {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    ///
+    /// # Panics
+    /// Panics if the AIR identifier is longer than 2^16 - 1 bytes, or if the serialized proof or
+    /// public inputs are longer than 2^32 - 1 bytes.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        let num_id_bytes = u16::try_from(self.air_id.len()).unwrap_or_else(|_| {
+            panic!(
+                "AIR identifier cannot be longer than {} bytes, but was {} bytes",
+                u16::MAX,
+                self.air_id.len()
+            )
+        });
+        target.write_u16(num_id_bytes);
+        target.write_u8_slice(self.air_id.as_bytes());
+        let proof = self.proof.to_bytes();
+        target.write_u32(to_u32_len(proof.len(), "serialized proof"));
+        target.write_u8_slice(&proof);
+        target.write_u32(to_u32_len(
+            self.pub_inputs.len(),
+            "serialized public inputs",
+        ));
+        target.write_u8_slice(&self.pub_inputs);
+        target.write_u64(self.created_at);
+    }
+}
+
+impl Deserializable for Claim {
+    /// Reads a claim from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid claim could not be read from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let num_id_bytes = source.read_u16()? as usize;
+        let air_id = String::from_utf8(source.read_u8_vec(num_id_bytes)?).map_err(|err| {
+            DeserializationError::InvalidValue(format!("invalid AIR identifier: {}", err))
+        })?;
+        let num_proof_bytes = source.read_u32()? as usize;
+        let proof = StarkProof::from_bytes(&source.read_u8_vec(num_proof_bytes)?)?;
+        let num_input_bytes = source.read_u32()? as usize;
+        let pub_inputs = source.read_u8_vec(num_input_bytes)?;
+        let created_at = source.read_u64()?;
+
+        Ok(Claim {
+            air_id,
+            proof,
+            pub_inputs,
+            created_at,
+        })
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Converts the length of a serialized part of a claim into a u32 length prefix.
+fn to_u32_len(len: usize, what: &str) -> u32 {
+    u32::try_from(len).unwrap_or_else(|_| {
+        panic!(
+            "{} cannot be longer than {} bytes, but was {} bytes",
+            what,
+            u32::MAX,
+            len
+        )
+    })
+}
+
+// CLAIM ERROR
+// ================================================================================================
+/// Represents an error returned when verifying a [Claim].
+#[derive(Debug, PartialEq)]
+pub enum ClaimError {
+    /// This error occurs when a claim was created for an AIR other than the one against which
+    /// it is being verified.
+    AirMismatch { expected: String, actual: String },
+    /// This error occurs when public inputs of a claim cannot be deserialized into public inputs
+    /// of the AIR against which the claim is being verified.
+    PublicInputsDeserializationError(String),
+    /// This error occurs when the proof of a claim is not valid.
+    VerificationFailed(VerifierError),
+}
+
+impl fmt::Display for ClaimError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AirMismatch { expected, actual } => {
+                write!(
+                    f,
+                    "claim was created for AIR '{}', but AIR '{}' was expected",
+                    actual, expected
+                )
+            }
+            Self::PublicInputsDeserializationError(msg) => {
+                write!(f, "public inputs deserialization failed: {}", msg)
+            }
+            Self::VerificationFailed(err) => write!(f, "proof verification failed: {}", err),
+        }
+    }
+}
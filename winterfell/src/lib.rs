@@ -58,6 +58,9 @@
 //! need to be in tens of thousands. And even for hundreds of thousands of asserted values, the
 //! verification time should not exceed 50 ms.
 //!
+//! When the crate is compiled with `concurrent-verify` feature enabled, the work done
+//! independently for each query during proof verification will be performed in multiple threads.
+//!
 //! # Examples
 //! The best way to understand the STARK proof generation and verification process is to go
 //! through a trivial example from start to finish. First, we'll need to pick a computation for
@@ -520,12 +523,24 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(not(feature = "std"))]
+#[macro_use]
+extern crate alloc;
+
 pub use prover::{
-    crypto, iterators, math, Air, AirContext, Assertion, BoundaryConstraint,
-    BoundaryConstraintGroup, ByteReader, ByteWriter, ConstraintCompositionCoefficients,
-    ConstraintDivisor, DeepCompositionCoefficients, Deserializable, DeserializationError,
-    EvaluationFrame, FieldExtension, HashFunction, ProofOptions, Prover, ProverError, Serializable,
-    StarkProof, Trace, TraceInfo, TraceTable, TraceTableFragment, TransitionConstraintDegree,
-    TransitionConstraintGroup,
+    collections, crypto, fri, iterators, math, Air, AirContext, AirDescription, Assertion,
+    AssertionDescription, BoundaryConstraint, BoundaryConstraintGroup, ByteReader, ByteWriter,
+    CellDiff, ConstraintCompositionCoefficients, ConstraintDivisor, ConstraintSet,
+    CustomBoundaryConstraint, DeepCompositionCoefficients, Deserializable, DeserializationError,
+    EvaluationFrame, FieldExtension, HashFunction, PaddingStrategy, ProofArtifacts, ProofOptions,
+    Prover, ProverError, ProverOptions, Serializable, SliceReader, StarkProof, StreamingTrace,
+    Trace, TraceDiff, TraceInfo, TraceInfoError, TraceTable, TraceTableFragment,
+    TransitionConstraintDegree, TransitionConstraintDescription, TransitionConstraintGroup,
 };
-pub use verifier::{verify, VerifierError};
+pub use verifier::{verify, verify_with_policy, VerificationPolicy, VerifierError};
+
+mod claim;
+pub use claim::{Claim, ClaimError};
+
+#[cfg(feature = "std")]
+pub use verifier::{verify_cached, VerifierCache, DEFAULT_CACHE_CAPACITY};